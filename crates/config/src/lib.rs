@@ -2,6 +2,7 @@ use core::time::Duration;
 
 use calimero_context::config::ContextConfig;
 use calimero_network_primitives::config::{BootstrapConfig, DiscoveryConfig, SwarmConfig};
+use calimero_primitives::utils::serde_duration;
 use calimero_server::admin::service::AdminConfig;
 use calimero_server::jsonrpc::JsonRpcConfig;
 use calimero_server::ws::WsConfig;
@@ -42,6 +43,26 @@ pub struct SyncConfig {
     pub interval: Duration,
     #[serde(rename = "frequency_ms", with = "serde_duration")]
     pub frequency: Duration,
+    #[serde(
+        rename = "delta_timeout_ms",
+        with = "serde_duration",
+        default = "default_delta_timeout"
+    )]
+    pub delta_timeout: Duration,
+    #[serde(
+        rename = "tombstone_retention_ms",
+        with = "serde_duration",
+        default = "default_tombstone_retention"
+    )]
+    pub tombstone_retention: Duration,
+}
+
+const fn default_delta_timeout() -> Duration {
+    Duration::from_secs(30)
+}
+
+const fn default_tombstone_retention() -> Duration {
+    Duration::from_secs(7 * 24 * 60 * 60)
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -185,26 +206,6 @@ impl ConfigFile {
     }
 }
 
-mod serde_duration {
-    use core::time::Duration;
-
-    use serde::{Deserialize, Deserializer, Serializer};
-
-    pub fn serialize<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_u64(duration.as_millis() as u64)
-    }
-
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        u64::deserialize(deserializer).map(Duration::from_millis)
-    }
-}
-
 pub mod serde_identity {
     use core::fmt::{self, Formatter};
 
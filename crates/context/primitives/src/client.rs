@@ -1,21 +1,28 @@
 #![allow(clippy::multiple_inherent_impl, reason = "better readability")]
 
+use std::collections::BTreeMap;
+
 use async_stream::try_stream;
 use calimero_context_config::client::{AnyTransport, Client as ExternalClient};
 use calimero_node_primitives::client::NodeClient;
 use calimero_primitives::alias::Alias;
 use calimero_primitives::application::ApplicationId;
-use calimero_primitives::context::{Context, ContextId, ContextInvitationPayload};
+use calimero_primitives::context::{
+    Context, ContextId, ContextInvitationPayload, ContextSyncRecord, SyncStatus,
+};
 use calimero_primitives::identity::{PrivateKey, PublicKey};
-use calimero_store::{key, Store};
+use calimero_store::{key, types, Store};
 use calimero_utils_actix::LazyRecipient;
 use futures_util::Stream;
 use tokio::sync::oneshot;
 
 use crate::messages::create_context::{CreateContextRequest, CreateContextResponse};
 use crate::messages::delete_context::{DeleteContextRequest, DeleteContextResponse};
-use crate::messages::execute::{ExecuteError, ExecuteRequest, ExecuteResponse};
+use crate::messages::execute::{
+    ExecuteBudget, ExecuteError, ExecuteReplay, ExecuteRequest, ExecuteResponse,
+};
 use crate::messages::join_context::{JoinContextRequest, JoinContextResponse};
+use crate::messages::simulate::{SimulateRequest, SimulateResponse};
 use crate::messages::update_application::UpdateApplicationRequest;
 use crate::messages::ContextMessage;
 use crate::ContextAtomic;
@@ -24,6 +31,10 @@ pub mod crypto;
 pub mod external;
 mod sync;
 
+#[cfg(test)]
+#[path = "tests/client.rs"]
+mod tests;
+
 #[derive(Clone, Debug)]
 pub struct ContextClient {
     datastore: Store,
@@ -149,6 +160,94 @@ impl ContextClient {
         Ok(Some(context))
     }
 
+    /// Returns the bytes of keys and values held by the context's state.
+    pub fn get_context_usage(&self, context_id: &ContextId) -> eyre::Result<u64> {
+        let handle = self.datastore.handle();
+
+        let key = key::ContextUsage::new(*context_id);
+
+        let usage = handle.get(&key)?;
+
+        Ok(usage.map_or(0, |usage| usage.storage_bytes))
+    }
+
+    /// Counts the storage held by contexts created before usage was tracked,
+    /// returning how many were counted.
+    pub fn backfill_context_usage(&self) -> eyre::Result<usize> {
+        let mut handle = self.datastore.handle();
+
+        let mut uncounted = BTreeMap::new();
+
+        {
+            let mut iter = handle.iter::<key::ContextMeta>()?;
+
+            for key in iter.keys() {
+                let context_id = key?.context_id();
+
+                if !handle.has(&key::ContextUsage::new(context_id))? {
+                    let _ignored = uncounted.insert(context_id, 0_u64);
+                }
+            }
+        }
+
+        if uncounted.is_empty() {
+            return Ok(0);
+        }
+
+        {
+            let mut iter = handle.iter::<key::ContextState>()?;
+
+            for (key, value) in iter.entries() {
+                let Some(usage) = uncounted.get_mut(&key?.context_id()) else {
+                    continue;
+                };
+
+                *usage = usage
+                    .saturating_add(types::ContextUsage::entry_size(value?.value.len() as u64));
+            }
+        }
+
+        for (context_id, usage) in &uncounted {
+            handle.put(
+                &key::ContextUsage::new(*context_id),
+                &types::ContextUsage::new(*usage),
+            )?;
+        }
+
+        Ok(uncounted.len())
+    }
+
+    /// Returns the history of the context's syncs with its peers, if one has
+    /// ever been attempted.
+    pub fn get_context_sync(
+        &self,
+        context_id: &ContextId,
+    ) -> eyre::Result<Option<ContextSyncRecord>> {
+        let handle = self.datastore.handle();
+
+        let key = key::ContextSync::new(*context_id);
+
+        let record = handle.get(&key)?;
+
+        Ok(record.map(from_stored_sync))
+    }
+
+    /// Replaces the history of the context's syncs with its peers.
+    pub fn put_context_sync(
+        &self,
+        context_id: &ContextId,
+        record: &ContextSyncRecord,
+    ) -> eyre::Result<()> {
+        let mut handle = self.datastore.handle();
+
+        handle.put(
+            &key::ContextSync::new(*context_id),
+            &into_stored_sync(record),
+        )?;
+
+        Ok(())
+    }
+
     pub fn get_contexts(
         &self,
         start: Option<ContextId>,
@@ -231,6 +330,141 @@ impl ContextClient {
                     payload,
                     aliases,
                     atomic,
+                    view: false,
+                    budget: ExecuteBudget::default(),
+                    replay: None,
+                },
+                outcome: sender,
+            })
+            .await
+            .expect("Mailbox not to be dropped");
+
+        receiver.await.expect("Mailbox not to be dropped")
+    }
+
+    /// Executes a method again with the inputs an earlier execution
+    /// returned, failing the execution if it diverges from them. The
+    /// context's state is left as it was, and nothing is broadcast.
+    pub async fn replay(
+        &self,
+        context: &ContextId,
+        executor: &PublicKey,
+        method: String,
+        payload: Vec<u8>,
+        aliases: Vec<Alias<PublicKey>>,
+        replay: ExecuteReplay,
+    ) -> Result<ExecuteResponse, ExecuteError> {
+        let (sender, receiver) = oneshot::channel();
+
+        self.context_manager
+            .send(ContextMessage::Execute {
+                request: ExecuteRequest {
+                    context: *context,
+                    executor: *executor,
+                    method,
+                    payload,
+                    aliases,
+                    atomic: None,
+                    view: false,
+                    budget: ExecuteBudget::default(),
+                    replay: Some(replay),
+                },
+                outcome: sender,
+            })
+            .await
+            .expect("Mailbox not to be dropped");
+
+        receiver.await.expect("Mailbox not to be dropped")
+    }
+
+    /// Runs a method without changing the context's state.
+    pub async fn query(
+        &self,
+        context: &ContextId,
+        executor: &PublicKey,
+        method: String,
+        payload: Vec<u8>,
+        aliases: Vec<Alias<PublicKey>>,
+    ) -> Result<ExecuteResponse, ExecuteError> {
+        let (sender, receiver) = oneshot::channel();
+
+        self.context_manager
+            .send(ContextMessage::Execute {
+                request: ExecuteRequest {
+                    context: *context,
+                    executor: *executor,
+                    method,
+                    payload,
+                    aliases,
+                    atomic: None,
+                    view: true,
+                    budget: ExecuteBudget::default(),
+                    replay: None,
+                },
+                outcome: sender,
+            })
+            .await
+            .expect("Mailbox not to be dropped");
+
+        receiver.await.expect("Mailbox not to be dropped")
+    }
+
+    /// Runs a method of a context on behalf of another context's guest,
+    /// without changing the called context's state.
+    ///
+    /// The executor must be a member of the called context, exactly as it
+    /// would have to be to query it directly.
+    pub async fn call(
+        &self,
+        context: &ContextId,
+        executor: &PublicKey,
+        method: String,
+        payload: Vec<u8>,
+        budget: ExecuteBudget,
+    ) -> Result<ExecuteResponse, ExecuteError> {
+        let (sender, receiver) = oneshot::channel();
+
+        self.context_manager
+            .send(ContextMessage::Execute {
+                request: ExecuteRequest {
+                    context: *context,
+                    executor: *executor,
+                    method,
+                    payload,
+                    aliases: vec![],
+                    atomic: None,
+                    view: true,
+                    budget,
+                    replay: None,
+                },
+                outcome: sender,
+            })
+            .await
+            .expect("Mailbox not to be dropped");
+
+        receiver.await.expect("Mailbox not to be dropped")
+    }
+
+    /// Runs a method as it would be executed, without committing any of its
+    /// effects, reporting the changes it would have made.
+    pub async fn simulate(
+        &self,
+        context: &ContextId,
+        executor: &PublicKey,
+        method: String,
+        payload: Vec<u8>,
+        aliases: Vec<Alias<PublicKey>>,
+    ) -> Result<SimulateResponse, ExecuteError> {
+        let (sender, receiver) = oneshot::channel();
+
+        self.context_manager
+            .send(ContextMessage::Simulate {
+                request: SimulateRequest {
+                    context: *context,
+                    executor: *executor,
+                    method,
+                    payload,
+                    aliases,
                 },
                 outcome: sender,
             })
@@ -282,3 +516,45 @@ impl ContextClient {
         receiver.await.expect("Mailbox not to be dropped")
     }
 }
+
+fn from_stored_sync(record: types::ContextSync) -> ContextSyncRecord {
+    let status = match record.status {
+        types::ContextSyncStatus::Succeeded => SyncStatus::Succeeded,
+        types::ContextSyncStatus::Failed { reason } => SyncStatus::Failed {
+            reason: reason.into_string(),
+        },
+        types::ContextSyncStatus::TimedOut => SyncStatus::TimedOut,
+    };
+
+    ContextSyncRecord::new(
+        record.last_attempt,
+        record.last_success,
+        record.peer_id.map(Into::into),
+        status,
+        record.bytes_transferred,
+        record.root_hash_before.into(),
+        record.root_hash_after.into(),
+        record.consecutive_failures,
+    )
+}
+
+fn into_stored_sync(record: &ContextSyncRecord) -> types::ContextSync {
+    let status = match &record.status {
+        SyncStatus::Succeeded => types::ContextSyncStatus::Succeeded,
+        SyncStatus::Failed { reason } => types::ContextSyncStatus::Failed {
+            reason: reason.as_str().into(),
+        },
+        SyncStatus::TimedOut => types::ContextSyncStatus::TimedOut,
+    };
+
+    types::ContextSync::new(
+        record.last_attempt,
+        record.last_success,
+        record.peer_id.as_deref().map(Into::into),
+        status,
+        record.bytes_transferred,
+        *record.root_hash_before,
+        *record.root_hash_after,
+        record.consecutive_failures,
+    )
+}
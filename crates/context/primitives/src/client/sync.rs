@@ -157,11 +157,29 @@ impl ContextClient {
         );
 
         if should_save {
+            let changed =
+                !context.is_some_and(|meta| meta.application.application_id() == application_id);
+
+            let bytecode = if changed {
+                self.node_client
+                    .retain_application_bytecode(&application_id)?
+            } else {
+                context.and_then(|meta| meta.bytecode)
+            };
+
             // todo! if the application_id changed, we need to notify ContextManager
             handle.put(
                 &key::ContextMeta::new(context_id),
-                &types::ContextMeta::new(key::ApplicationMeta::new(application_id), *root_hash),
+                &types::ContextMeta::new(
+                    key::ApplicationMeta::new(application_id),
+                    *root_hash,
+                    bytecode,
+                ),
             )?;
+
+            if let Some(previous) = context.filter(|_| changed) {
+                self.node_client.release_context_bytecode(&previous).await?;
+            }
         }
 
         let context = Context::new(context_id, application_id, root_hash);
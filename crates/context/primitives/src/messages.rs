@@ -5,12 +5,14 @@ pub mod create_context;
 pub mod delete_context;
 pub mod execute;
 pub mod join_context;
+pub mod simulate;
 pub mod update_application;
 
 use create_context::CreateContextRequest;
 use delete_context::DeleteContextRequest;
 use execute::ExecuteRequest;
 use join_context::JoinContextRequest;
+use simulate::SimulateRequest;
 use update_application::UpdateApplicationRequest;
 
 #[derive(Debug, Message)]
@@ -36,4 +38,8 @@ pub enum ContextMessage {
         request: UpdateApplicationRequest,
         outcome: oneshot::Sender<<UpdateApplicationRequest as Message>::Result>,
     },
+    Simulate {
+        request: SimulateRequest,
+        outcome: oneshot::Sender<<SimulateRequest as Message>::Result>,
+    },
 }
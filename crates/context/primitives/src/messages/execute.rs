@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use actix::Message;
 use calimero_primitives::alias::Alias;
 use calimero_primitives::application::ApplicationId;
@@ -17,6 +19,52 @@ pub struct ExecuteRequest {
     pub payload: Vec<u8>,
     pub aliases: Vec<Alias<PublicKey>>,
     pub atomic: Option<ContextAtomic>,
+    /// Runs the method concurrently with other requests, against state it
+    /// may not change, ignoring `atomic`. Nothing is committed or broadcast.
+    pub view: bool,
+    /// What the request may use when it's made by another context, which is
+    /// whatever the caller has left. Only honoured for views.
+    pub budget: ExecuteBudget,
+    /// Runs the method with the inputs recorded by an earlier execution
+    /// rather than fresh ones. Not honoured for views.
+    pub replay: Option<ExecuteReplay>,
+}
+
+/// What a request may use on top of the node's limits.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExecuteBudget {
+    /// How many cross-context calls deep the request is made, which is zero
+    /// unless it was made by another context.
+    pub depth: u32,
+    /// The most gas the request may use.
+    pub gas: Option<u64>,
+    /// The instant by which the request must finish.
+    pub deadline: Option<Instant>,
+}
+
+/// The inputs to an execution which come from the node rather than from the
+/// caller or the state, which reproduce it when given back.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ExecuteReplay {
+    /// Seeds the random bytes and proposal IDs given to the guest.
+    pub seed: [u8; 32],
+    /// The time given to the guest, in nanoseconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The responses to the requests made by the guest, in order.
+    #[serde(default)]
+    pub fetches: Vec<Result<Vec<u8>, String>>,
+    /// What the guest found in the blob store, in order.
+    #[serde(default)]
+    pub blob_reads: Vec<ExecuteBlobRead>,
+}
+
+/// A lookup in the blob store made by the guest.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ExecuteBlobRead {
+    /// A blob was opened, if it was found.
+    Opened(bool),
+    /// A chunk of an open blob was read, if it could be.
+    Read(Option<Vec<u8>>),
 }
 
 #[derive(Debug)]
@@ -29,6 +77,8 @@ pub struct ExecuteResponse {
     pub root_hash: Hash,
     pub artifact: Vec<u8>,
     pub atomic: Option<ContextAtomicKey>,
+    pub gas_used: u64,
+    pub replay: ExecuteReplay,
 }
 
 #[derive(Debug)]
@@ -64,4 +114,6 @@ pub enum ExecuteError {
     InternalError,
     #[error("error resolving identity alias '{alias}'")]
     AliasResolutionFailed { alias: Alias<PublicKey> },
+    #[error("execution timed out")]
+    TimedOut,
 }
@@ -0,0 +1,52 @@
+use actix::Message;
+use calimero_primitives::alias::Alias;
+use calimero_primitives::context::ContextId;
+use calimero_primitives::hash::Hash;
+use calimero_primitives::identity::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use super::execute::{ExecuteError, ExecuteEvent};
+
+#[derive(Debug)]
+pub struct SimulateRequest {
+    pub context: ContextId,
+    pub executor: PublicKey,
+    pub method: String,
+    pub payload: Vec<u8>,
+    pub aliases: Vec<Alias<PublicKey>>,
+}
+
+#[derive(Debug)]
+pub struct SimulateResponse {
+    pub returns: eyre::Result<Option<Vec<u8>>>,
+    pub logs: Vec<String>,
+    pub events: Vec<ExecuteEvent>,
+    /// The root hash the context would have had, had the call been executed.
+    pub root_hash: Hash,
+    pub changes: Vec<StateChange>,
+    pub gas_used: u64,
+}
+
+/// A change the call would have made to the context's state.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum StateChange {
+    Add {
+        id: String,
+        path: Option<String>,
+        data: Vec<u8>,
+    },
+    Update {
+        id: String,
+        path: Option<String>,
+        data: Vec<u8>,
+    },
+    Delete {
+        id: String,
+    },
+}
+
+impl Message for SimulateRequest {
+    type Result = Result<SimulateResponse, ExecuteError>;
+}
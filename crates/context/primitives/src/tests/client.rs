@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use calimero_primitives::hash::Hash;
+use calimero_store::db::InMemoryDB;
+
+use super::*;
+
+const CONTEXT_ID: [u8; 32] = [1; 32];
+
+#[test]
+fn sync_record_round_trips() {
+    let store = Store::new(Arc::new(InMemoryDB::owned()));
+    let key = key::ContextSync::new(ContextId::from(CONTEXT_ID));
+
+    let statuses = [
+        SyncStatus::Succeeded,
+        SyncStatus::Failed {
+            reason: "no peers to sync with".to_owned(),
+        },
+        SyncStatus::TimedOut,
+    ];
+
+    for (consecutive_failures, status) in (0..).zip(statuses) {
+        let record = ContextSyncRecord::new(
+            10,
+            Some(5),
+            Some("12D3KooWPeer".to_owned()),
+            status,
+            42,
+            Hash::from([2; 32]),
+            Hash::from([3; 32]),
+            consecutive_failures,
+        );
+
+        let mut handle = store.handle();
+
+        handle.put(&key, &into_stored_sync(&record)).unwrap();
+
+        let stored = handle.get(&key).unwrap().unwrap();
+
+        assert_eq!(from_stored_sync(stored), record);
+    }
+
+    let record = ContextSyncRecord::new(
+        10,
+        None,
+        None,
+        SyncStatus::TimedOut,
+        0,
+        Hash::default(),
+        Hash::default(),
+        1,
+    );
+
+    let stored = into_stored_sync(&record);
+
+    assert_eq!(from_stored_sync(stored), record);
+}
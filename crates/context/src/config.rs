@@ -1,10 +1,70 @@
 #![allow(clippy::exhaustive_structs, reason = "TODO: Allowed until reviewed")]
 
+use core::time::Duration;
+
 use calimero_context_config::client::config::ClientConfig;
+use calimero_primitives::utils::serde_duration;
+use calimero_runtime::fetch::FetchPolicy;
+use calimero_runtime::logic::VMLimits;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ContextConfig {
     #[serde(rename = "config")]
     pub client: ClientConfig,
+    #[serde(
+        rename = "execution_timeout_ms",
+        with = "serde_duration",
+        default = "default_execution_timeout"
+    )]
+    pub execution_timeout: Duration,
+    /// Bytes of keys and values each context may hold, unlimited if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_quota: Option<u64>,
+    /// What applications running on this node may fetch.
+    #[serde(default)]
+    pub fetch: FetchPolicy,
+    /// Whether applications using floats, threads or SIMD are rejected. This
+    /// is off by default, as many applications use floats, if only to parse
+    /// json numbers, and would no longer run once turned on.
+    #[serde(default = "default_deterministic_only")]
+    pub deterministic_only: bool,
+}
+
+impl ContextConfig {
+    #[must_use]
+    pub fn new(client: ClientConfig) -> Self {
+        Self {
+            client,
+            execution_timeout: default_execution_timeout(),
+            storage_quota: None,
+            fetch: FetchPolicy::default(),
+            deterministic_only: default_deterministic_only(),
+        }
+    }
+
+    /// The limits applications are installed and run under.
+    #[must_use]
+    pub fn limits(&self) -> VMLimits {
+        let mut limits = VMLimits {
+            max_execution_time: self.execution_timeout,
+            fetch: self.fetch.clone(),
+            deterministic_only: self.deterministic_only,
+            ..VMLimits::default()
+        };
+
+        if let Some(quota) = self.storage_quota {
+            limits.max_storage_usage = quota;
+        }
+
+        limits
+    }
+}
+
+fn default_execution_timeout() -> Duration {
+    VMLimits::default().max_execution_time
+}
+
+fn default_deterministic_only() -> bool {
+    VMLimits::default().deterministic_only
 }
@@ -8,6 +8,7 @@ pub mod create_context;
 pub mod delete_context;
 pub mod execute;
 pub mod join_context;
+pub mod simulate;
 pub mod update_application;
 
 impl Handler<ContextMessage> for ContextManager {
@@ -30,6 +31,9 @@ impl Handler<ContextMessage> for ContextManager {
             ContextMessage::JoinContext { request, outcome } => {
                 self.forward_handler(ctx, request, outcome)
             }
+            ContextMessage::Simulate { request, outcome } => {
+                self.forward_handler(ctx, request, outcome)
+            }
         }
     }
 }
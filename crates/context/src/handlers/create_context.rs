@@ -20,6 +20,7 @@ use rand::rngs::StdRng;
 use rand::SeedableRng;
 use tokio::sync::{Mutex, OwnedMutexGuard};
 
+use super::execute::blobs::ContextBlobs;
 use super::execute::execute;
 use super::execute::storage::ContextStorage;
 use crate::{ContextManager, ContextMeta};
@@ -260,6 +261,7 @@ async fn create_context(
         "init".into(),
         init_params.into(),
         storage,
+        ContextBlobs::new(node_client.clone(), context.id),
     )
     .await?;
 
@@ -300,11 +302,14 @@ async fn create_context(
         ),
     )?;
 
+    let bytecode = node_client.retain_application_bytecode(&application.id)?;
+
     handle.put(
         &key::ContextMeta::new(context.id),
         &types::ContextMeta::new(
             key::ApplicationMeta::new(application.id),
             *context.root_hash,
+            bytecode,
         ),
     )?;
 
@@ -72,8 +72,12 @@ async fn delete_context(
 
     let key = key::ContextMeta::new(context_id);
 
+    let meta = handle.get(&key)?;
+
     handle.delete(&key)?;
     handle.delete(&key::ContextConfig::new(context_id))?;
+    handle.delete(&key::ContextUsage::new(context_id))?;
+    handle.delete(&key::ContextSync::new(context_id))?;
 
     // fixme! store.handle() is prolematic here for lifetime reasons
     let mut datastore = handle.into_inner();
@@ -82,6 +86,12 @@ async fn delete_context(
 
     delete_context_scoped::<key::ContextState, 32>(&mut datastore, &context_id, [0; 32], None)?;
 
+    if let Some(meta) = meta {
+        node_client.release_context_bytecode(&meta).await?;
+    }
+
+    node_client.release_context_blobs(&context_id).await?;
+
     Ok(())
 }
 
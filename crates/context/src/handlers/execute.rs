@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 use std::collections::btree_map;
+use std::time::Instant;
 
 use actix::{
     ActorFuture, ActorFutureExt, ActorResponse, ActorTryFutureExt, Handler, Message, WrapFuture,
@@ -7,7 +8,8 @@ use actix::{
 use calimero_context_config::repr::ReprTransmute;
 use calimero_context_primitives::client::crypto::ContextIdentity;
 use calimero_context_primitives::messages::execute::{
-    ExecuteError, ExecuteEvent, ExecuteRequest, ExecuteResponse,
+    ExecuteBlobRead, ExecuteBudget, ExecuteError, ExecuteEvent, ExecuteReplay, ExecuteRequest,
+    ExecuteResponse,
 };
 use calimero_context_primitives::{ContextAtomic, ContextAtomicKey};
 use calimero_node_primitives::client::NodeClient;
@@ -19,7 +21,9 @@ use calimero_primitives::events::{
     StateMutationPayload,
 };
 use calimero_primitives::identity::PublicKey;
-use calimero_runtime::logic::Outcome;
+use calimero_runtime::calls::CallBudget;
+use calimero_runtime::errors::FunctionCallError;
+use calimero_runtime::logic::{BlobRead, Outcome, Replay};
 use calimero_store::{key, types, Store};
 use calimero_utils_actix::global_runtime;
 use either::Either;
@@ -28,12 +32,16 @@ use futures_util::future::TryFutureExt;
 use futures_util::io::Cursor;
 use memchr::memmem;
 use tokio::sync::OwnedMutexGuard;
+use tokio::time;
 use tracing::{debug, error};
 
 use crate::ContextManager;
 
+pub mod blobs;
+pub mod calls;
 pub mod storage;
 
+use blobs::ContextBlobs;
 use storage::ContextStorage;
 
 impl Handler<ExecuteRequest> for ContextManager {
@@ -48,6 +56,9 @@ impl Handler<ExecuteRequest> for ContextManager {
             payload,
             aliases,
             atomic,
+            view,
+            budget,
+            replay,
         }: ExecuteRequest,
         _ctx: &mut Self::Context,
     ) -> Self::Result {
@@ -57,6 +68,9 @@ impl Handler<ExecuteRequest> for ContextManager {
             method,
             aliases = ?aliases,
             payload_len = payload.len(),
+            view,
+            depth = budget.depth,
+            replayed = replay.is_some(),
             atomic = %match atomic {
                 None => "no",
                 Some(ContextAtomic::Lock) => "acquire",
@@ -81,6 +95,18 @@ impl Handler<ExecuteRequest> for ContextManager {
             return ActorResponse::reply(Err(ExecuteError::Uninitialized));
         }
 
+        if view {
+            let context = context.meta;
+
+            return self.view(context, executor, method, payload, &aliases, budget);
+        }
+
+        if let Some(replay) = replay {
+            let context = context.meta;
+
+            return self.replay(context, executor, method, payload, &aliases, replay);
+        }
+
         let (guard, is_atomic) = match atomic {
             None => (context.lock(), false),
             Some(ContextAtomic::Lock) => (context.lock(), true),
@@ -101,24 +127,25 @@ impl Handler<ExecuteRequest> for ContextManager {
             }
         };
 
-        let sender_key = match self.context_client.get_identity(&context_id, &executor) {
-            Ok(Some(ContextIdentity {
-                private_key: Some(_),
-                sender_key: Some(sender_key),
-                ..
-            })) => sender_key,
-            Ok(_) => {
-                return ActorResponse::reply(Err(ExecuteError::Unauthorized {
-                    context_id,
-                    public_key: executor,
-                }))
-            }
-            Err(err) => {
-                error!(%err, "failed to execute request");
+        let (private_key, sender_key) =
+            match self.context_client.get_identity(&context_id, &executor) {
+                Ok(Some(ContextIdentity {
+                    private_key: Some(private_key),
+                    sender_key: Some(sender_key),
+                    ..
+                })) => (private_key, sender_key),
+                Ok(_) => {
+                    return ActorResponse::reply(Err(ExecuteError::Unauthorized {
+                        context_id,
+                        public_key: executor,
+                    }))
+                }
+                Err(err) => {
+                    error!(%err, "failed to execute request");
 
-                return ActorResponse::reply(Err(ExecuteError::InternalError));
-            }
-        };
+                    return ActorResponse::reply(Err(ExecuteError::InternalError));
+                }
+            };
 
         let payload =
             match substitute_aliases_in_payload(&self.node_client, context_id, payload, &aliases) {
@@ -180,66 +207,75 @@ impl Handler<ExecuteRequest> for ContextManager {
                     artifact_len = outcome.artifact.len(),
                     logs_count = outcome.logs.len(),
                     events_count = outcome.events.len(),
+                    gas_used = outcome.gas_used,
                     "executed request"
                 );
 
-                Ok((guard, context, outcome))
+                Ok((guard, context, old_root_hash, outcome))
             }
             .into_actor(act)
         });
 
-        let external_task = execute_task.and_then(move |(guard, context, outcome), act, _ctx| {
-            if let Some(cached_context) = act.contexts.get_mut(&context_id) {
-                cached_context.meta.root_hash = context.root_hash;
-            }
+        let external_task =
+            execute_task.and_then(move |(guard, context, old_root_hash, outcome), act, _ctx| {
+                if let Some(cached_context) = act.contexts.get_mut(&context_id) {
+                    cached_context.meta.root_hash = context.root_hash;
+                }
 
-            let node_client = act.node_client.clone();
-            let context_client = act.context_client.clone();
+                let node_client = act.node_client.clone();
+                let context_client = act.context_client.clone();
 
-            async move {
-                if outcome.returns.is_err() {
-                    return Ok((guard, context.root_hash, outcome));
-                }
+                async move {
+                    if outcome.returns.is_err() {
+                        return Ok((guard, context.root_hash, outcome));
+                    }
 
-                if !(is_state_op || outcome.artifact.is_empty()) {
-                    node_client
-                        .broadcast(&context, &executor, &sender_key, outcome.artifact.clone())
-                        .await?;
-                }
+                    if !(is_state_op || outcome.artifact.is_empty()) {
+                        node_client
+                            .broadcast(
+                                &context,
+                                &old_root_hash,
+                                &executor,
+                                &private_key,
+                                &sender_key,
+                                outcome.artifact.clone(),
+                            )
+                            .await?;
+                    }
 
-                let external_client =
-                    context_client.external_client(&context_id, &external_config)?;
+                    let external_client =
+                        context_client.external_client(&context_id, &external_config)?;
 
-                let proxy_client = external_client.proxy();
+                    let proxy_client = external_client.proxy();
 
-                for (proposal_id, actions) in &outcome.proposals {
-                    let actions = borsh::from_slice(actions)?;
+                    for (proposal_id, actions) in &outcome.proposals {
+                        let actions = borsh::from_slice(actions)?;
 
-                    let proposal_id = proposal_id.rt().expect("infallible conversion");
+                        let proposal_id = proposal_id.rt().expect("infallible conversion");
 
-                    proxy_client
-                        .propose(&executor, &proposal_id, actions)
-                        .await?;
-                }
+                        proxy_client
+                            .propose(&executor, &proposal_id, actions)
+                            .await?;
+                    }
 
-                for proposal_id in &outcome.approvals {
-                    let proposal_id = proposal_id.rt().expect("infallible conversion");
+                    for proposal_id in &outcome.approvals {
+                        let proposal_id = proposal_id.rt().expect("infallible conversion");
 
-                    proxy_client.approve(&executor, &proposal_id).await?;
-                }
+                        proxy_client.approve(&executor, &proposal_id).await?;
+                    }
 
-                Ok((guard, context.root_hash, outcome))
-            }
-            .map_err(|err| {
-                error!(
+                    Ok((guard, context.root_hash, outcome))
+                }
+                .map_err(|err| {
+                    error!(
                     ?err,
                     "execution succeeded, but an error occurred while performing external actions"
                 );
 
-                err
-            })
-            .into_actor(act)
-        });
+                    err
+                })
+                .into_actor(act)
+            });
 
         let task = external_task
             .map_err(|err, _act, _ctx| {
@@ -263,6 +299,8 @@ impl Handler<ExecuteRequest> for ContextManager {
                     root_hash,
                     artifact: outcome.artifact,
                     atomic: is_atomic.then_some(ContextAtomicKey(guard)),
+                    gas_used: outcome.gas_used,
+                    replay: from_replay(outcome.replay),
                 },
             );
 
@@ -271,11 +309,197 @@ impl Handler<ExecuteRequest> for ContextManager {
 }
 
 impl ContextManager {
+    fn view(
+        &self,
+        context: Context,
+        executor: PublicKey,
+        method: String,
+        payload: Vec<u8>,
+        aliases: &[Alias<PublicKey>],
+        budget: ExecuteBudget,
+    ) -> <Self as Handler<ExecuteRequest>>::Result {
+        match self.context_client.get_identity(&context.id, &executor) {
+            Ok(Some(ContextIdentity {
+                private_key: Some(_),
+                ..
+            })) => {}
+            Ok(_) => {
+                return ActorResponse::reply(Err(ExecuteError::Unauthorized {
+                    context_id: context.id,
+                    public_key: executor,
+                }))
+            }
+            Err(err) => {
+                error!(%err, "failed to execute view");
+
+                return ActorResponse::reply(Err(ExecuteError::InternalError));
+            }
+        }
+
+        let payload =
+            match substitute_aliases_in_payload(&self.node_client, context.id, payload, aliases) {
+                Ok(payload) => payload,
+                Err(err) => {
+                    error!(%err, "failed to execute view");
+
+                    return ActorResponse::reply(Err(err));
+                }
+            };
+
+        let task = self
+            .get_module(context.application_id)
+            .and_then(move |module, act, _ctx| {
+                let storage = ContextStorage::read_only(act.datastore.clone(), context.id);
+
+                let blobs = ContextBlobs::new(act.node_client.clone(), context.id);
+
+                run(
+                    context.id,
+                    module,
+                    executor,
+                    method.into(),
+                    payload.into(),
+                    storage,
+                    blobs,
+                    true,
+                    CallBudget {
+                        depth: budget.depth,
+                        gas: budget.gas,
+                        deadline: budget.deadline,
+                    },
+                    None,
+                )
+                .into_actor(act)
+            })
+            .map_err(|err, _act, _ctx| {
+                err.downcast::<ExecuteError>().unwrap_or_else(|err| {
+                    debug!(?err, "an error occurred while executing view");
+                    ExecuteError::InternalError
+                })
+            })
+            .map_ok(move |(outcome, _storage), _act, _ctx| ExecuteResponse {
+                returns: outcome.returns.map_err(Into::into),
+                logs: outcome.logs,
+                events: outcome
+                    .events
+                    .into_iter()
+                    .map(|e| ExecuteEvent {
+                        kind: e.kind,
+                        data: e.data,
+                    })
+                    .collect(),
+                root_hash: context.root_hash,
+                artifact: vec![],
+                atomic: None,
+                gas_used: outcome.gas_used,
+                replay: from_replay(outcome.replay),
+            });
+
+        ActorResponse::r#async(task)
+    }
+
+    fn replay(
+        &self,
+        context: Context,
+        executor: PublicKey,
+        method: String,
+        payload: Vec<u8>,
+        aliases: &[Alias<PublicKey>],
+        replay: ExecuteReplay,
+    ) -> <Self as Handler<ExecuteRequest>>::Result {
+        match self.context_client.get_identity(&context.id, &executor) {
+            Ok(Some(ContextIdentity {
+                private_key: Some(_),
+                ..
+            })) => {}
+            Ok(_) => {
+                return ActorResponse::reply(Err(ExecuteError::Unauthorized {
+                    context_id: context.id,
+                    public_key: executor,
+                }))
+            }
+            Err(err) => {
+                error!(%err, "failed to replay execution");
+
+                return ActorResponse::reply(Err(ExecuteError::InternalError));
+            }
+        }
+
+        let payload =
+            match substitute_aliases_in_payload(&self.node_client, context.id, payload, aliases) {
+                Ok(payload) => payload,
+                Err(err) => {
+                    error!(%err, "failed to replay execution");
+
+                    return ActorResponse::reply(Err(err));
+                }
+            };
+
+        // a replay re-runs an execution for comparison, so like a simulation
+        // its writes land in a temporal layer which is never committed, and
+        // nothing it produces is broadcast or submitted
+        let task = self
+            .get_module(context.application_id)
+            .and_then(move |module, act, _ctx| {
+                let storage = ContextStorage::from(act.datastore.clone(), context.id);
+
+                let blobs = ContextBlobs::simulated(act.node_client.clone(), context.id);
+
+                run(
+                    context.id,
+                    module,
+                    executor,
+                    method.into(),
+                    payload.into(),
+                    storage,
+                    blobs,
+                    false,
+                    CallBudget::default(),
+                    Some(into_replay(replay)),
+                )
+                .into_actor(act)
+            })
+            .map_err(|err, _act, _ctx| {
+                err.downcast::<ExecuteError>().unwrap_or_else(|err| {
+                    debug!(?err, "an error occurred while replaying execution");
+                    ExecuteError::InternalError
+                })
+            })
+            .map_ok(move |(outcome, _storage), _act, _ctx| ExecuteResponse {
+                returns: outcome.returns.map_err(Into::into),
+                logs: outcome.logs,
+                events: outcome
+                    .events
+                    .into_iter()
+                    .map(|e| ExecuteEvent {
+                        kind: e.kind,
+                        data: e.data,
+                    })
+                    .collect(),
+                root_hash: outcome.root_hash.map_or(context.root_hash, Into::into),
+                artifact: outcome.artifact,
+                atomic: None,
+                gas_used: outcome.gas_used,
+                replay: from_replay(outcome.replay),
+            });
+
+        ActorResponse::r#async(task)
+    }
+
     pub fn get_module(
         &self,
         application_id: ApplicationId,
     ) -> impl ActorFuture<Self, Output = eyre::Result<calimero_runtime::Module>> + 'static {
         let blob_task = async {}.into_actor(self).map(move |_, act, _ctx| {
+            // an uninstalled application must not keep running from the cache
+            if !act.node_client.has_application(&application_id)? {
+                let _ignored = act.applications.remove(&application_id);
+
+                act.modules.remove(&application_id);
+
+                bail!(ExecuteError::ApplicationNotInstalled { application_id });
+            }
+
             let blob = match act.applications.entry(application_id) {
                 btree_map::Entry::Vacant(vacant) => {
                     let Some(app) = act.node_client.get_application(&application_id)? else {
@@ -287,14 +511,20 @@ impl ContextManager {
                 btree_map::Entry::Occupied(occupied) => occupied.into_mut().blob,
             };
 
-            Ok(blob)
+            let cached = act.modules.get(&application_id);
+
+            Ok((blob, cached))
         });
 
-        let module_task = blob_task.and_then(move |mut blob, act, _ctx| {
+        let module_task = blob_task.and_then(move |(mut blob, cached), act, _ctx| {
             let engine = act.runtime_engine.clone();
             let node_client = act.node_client.clone();
 
             async move {
+                if let Some(module) = cached {
+                    return Ok((module, None));
+                }
+
                 if let Some(compiled) = node_client.get_blob_bytes(&blob.compiled).await? {
                     let module = unsafe { engine.from_precompiled(&compiled) };
 
@@ -329,7 +559,9 @@ impl ContextManager {
 
                 blob.compiled = blob_id;
 
-                node_client.update_compiled_app(&application_id, &blob_id)?;
+                node_client
+                    .update_compiled_app(&application_id, &blob_id)
+                    .await?;
 
                 Ok((module, Some(blob)))
             }
@@ -344,6 +576,8 @@ impl ContextManager {
                     }
                 }
 
+                act.modules.insert(application_id, module.clone());
+
                 module
             })
             .map_err(|err, _act, _ctx| {
@@ -367,7 +601,18 @@ async fn internal_execute(
 ) -> eyre::Result<Outcome> {
     let storage = ContextStorage::from(datastore, context.id);
 
-    let (outcome, storage) = execute(guard, module, executor, method, input, storage).await?;
+    let blobs = ContextBlobs::new(node_client.clone(), context.id);
+
+    // quotas are local to each node, so applying a peer's state must not
+    // trip them, or the nodes would diverge
+    let module = if is_state_op {
+        module.without_storage_quota()
+    } else {
+        module
+    };
+
+    let (outcome, storage) =
+        execute(guard, module, executor, method, input, storage, blobs).await?;
 
     if outcome.returns.is_err() {
         return Ok(outcome);
@@ -392,11 +637,16 @@ async fn internal_execute(
 
             let mut handle = store.handle();
 
+            let key = key::ContextMeta::new(context.id);
+
+            let bytecode = handle.get(&key)?.and_then(|meta| meta.bytecode);
+
             handle.put(
-                &key::ContextMeta::new(context.id),
+                &key,
                 &types::ContextMeta::new(
                     key::ApplicationMeta::new(context.application_id),
                     *context.root_hash,
+                    bytecode,
                 ),
             )?;
 
@@ -426,27 +676,135 @@ async fn internal_execute(
     Ok(outcome)
 }
 
+#[expect(clippy::too_many_arguments, reason = "Acceptable here")]
 pub async fn execute(
     context: &OwnedMutexGuard<ContextId>,
     module: calimero_runtime::Module,
     executor: PublicKey,
     method: Cow<'static, str>,
     input: Cow<'static, [u8]>,
+    storage: ContextStorage,
+    blobs: ContextBlobs,
+) -> eyre::Result<(Outcome, ContextStorage)> {
+    run(
+        **context,
+        module,
+        executor,
+        method,
+        input,
+        storage,
+        blobs,
+        false,
+        CallBudget::default(),
+        None,
+    )
+    .await
+}
+
+#[expect(clippy::too_many_arguments, reason = "Acceptable here")]
+pub(super) async fn run(
+    context_id: ContextId,
+    module: calimero_runtime::Module,
+    executor: PublicKey,
+    method: Cow<'static, str>,
+    input: Cow<'static, [u8]>,
     mut storage: ContextStorage,
+    mut blobs: ContextBlobs,
+    view: bool,
+    budget: CallBudget,
+    replay: Option<Replay>,
 ) -> eyre::Result<(Outcome, ContextStorage)> {
-    let context_id = **context;
+    let mut deadline = module.limits().max_execution_time;
+
+    if let Some(budget) = budget.deadline {
+        deadline = deadline.min(budget.saturating_duration_since(Instant::now()));
+    }
 
-    global_runtime()
-        .spawn_blocking(move || {
-            let outcome = module.run(context_id, executor, &method, &input, &mut storage)?;
+    // the runtime interrupts the guest once the deadline passes, but a host
+    // call blocked on the network is only stopped by its own timeout, so we
+    // stop waiting on it here, releasing the context lock held by the caller,
+    // and discard whatever it eventually produces
+    let task = global_runtime().spawn_blocking(move || {
+        let outcome = if view {
+            module.view(
+                context_id,
+                executor,
+                &method,
+                &input,
+                budget,
+                &mut storage,
+                &mut blobs,
+            )?
+        } else if let Some(replay) = replay {
+            module.replay(
+                context_id,
+                executor,
+                &method,
+                &input,
+                replay,
+                &mut storage,
+                &mut blobs,
+            )?
+        } else {
+            module.run(
+                context_id,
+                executor,
+                &method,
+                &input,
+                &mut storage,
+                &mut blobs,
+            )?
+        };
+
+        Ok((outcome, storage))
+    });
+
+    let Ok(result) = time::timeout(deadline, task).await else {
+        bail!(ExecuteError::TimedOut);
+    };
 
-            Ok((outcome, storage))
+    let (outcome, storage) = result.wrap_err("failed to receive execution response")??;
+
+    if matches!(outcome.returns, Err(FunctionCallError::TimedOut)) {
+        bail!(ExecuteError::TimedOut);
+    }
+
+    Ok((outcome, storage))
+}
+
+fn into_replay(replay: ExecuteReplay) -> Replay {
+    let mut inputs = Replay::new(replay.seed, replay.timestamp);
+
+    inputs.fetches = replay.fetches;
+    inputs.blob_reads = replay
+        .blob_reads
+        .into_iter()
+        .map(|read| match read {
+            ExecuteBlobRead::Opened(found) => BlobRead::Opened(found),
+            ExecuteBlobRead::Read(chunk) => BlobRead::Read(chunk),
         })
-        .await
-        .wrap_err("failed to receive execution response")?
+        .collect();
+
+    inputs
+}
+
+fn from_replay(replay: Replay) -> ExecuteReplay {
+    ExecuteReplay {
+        seed: replay.seed,
+        timestamp: replay.timestamp,
+        fetches: replay.fetches,
+        blob_reads: replay
+            .blob_reads
+            .into_iter()
+            .map(|read| match read {
+                BlobRead::Opened(found) => ExecuteBlobRead::Opened(found),
+                BlobRead::Read(chunk) => ExecuteBlobRead::Read(chunk),
+            })
+            .collect(),
+    }
 }
 
-fn substitute_aliases_in_payload(
+pub(super) fn substitute_aliases_in_payload(
     node_client: &NodeClient,
     context_id: ContextId,
     payload: Vec<u8>,
@@ -0,0 +1,182 @@
+use std::io;
+
+use calimero_blobstore::BlobHasher;
+use calimero_node_primitives::client::NodeClient;
+use calimero_primitives::blobs::BlobId;
+use calimero_primitives::context::ContextId;
+use calimero_runtime::store::{BlobStorage, BlobWriter};
+use calimero_utils_actix::global_runtime;
+use futures_util::{stream, TryStreamExt};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+/// Gives the runtime access to the node's blob store, fetching blobs missing
+/// from it from the peers of the context.
+///
+/// This is only used from within the blocking task the runtime executes on,
+/// where it is safe to block on the global runtime.
+#[derive(Debug)]
+pub struct ContextBlobs {
+    node_client: NodeClient,
+    context_id: ContextId,
+    simulated: bool,
+}
+
+impl ContextBlobs {
+    pub const fn new(node_client: NodeClient, context_id: ContextId) -> Self {
+        Self {
+            node_client,
+            context_id,
+            simulated: false,
+        }
+    }
+
+    /// Creates blob access which leaves the blob store as it is, neither
+    /// fetching missing blobs nor storing the ones written.
+    pub const fn simulated(node_client: NodeClient, context_id: ContextId) -> Self {
+        Self {
+            node_client,
+            context_id,
+            simulated: true,
+        }
+    }
+}
+
+impl BlobStorage for ContextBlobs {
+    fn size(&self, id: &BlobId) -> Option<u64> {
+        if let Some(info) = self.node_client.get_blob_info(id).ok()? {
+            return Some(info.size);
+        }
+
+        if self.simulated {
+            return None;
+        }
+
+        let fetched = global_runtime().block_on(self.node_client.fetch_blob(&self.context_id, id));
+
+        match fetched {
+            Ok(true) => {}
+            Ok(false) => return None,
+            Err(err) => {
+                debug!(context_id=%self.context_id, blob_id=%id, %err, "Failed to fetch blob from peers");
+
+                return None;
+            }
+        }
+
+        let info = self.node_client.get_blob_info(id).ok()??;
+
+        Some(info.size)
+    }
+
+    fn read(&self, id: &BlobId, offset: u64, len: u64) -> Option<Vec<u8>> {
+        let blob = self.node_client.get_blob_range(id, offset, len).ok()??;
+
+        global_runtime()
+            .block_on(blob.try_fold(Vec::new(), |mut data, chunk| async move {
+                data.extend_from_slice(&chunk);
+                Ok(data)
+            }))
+            .ok()
+    }
+
+    fn create(&mut self) -> Box<dyn BlobWriter> {
+        if self.simulated {
+            return Box::new(DiscardingBlobWriter::default());
+        }
+
+        let (sender, mut receiver) = mpsc::channel::<io::Result<Vec<u8>>>(16);
+
+        let node_client = self.node_client.clone();
+
+        let task = global_runtime().spawn({
+            let node_client = node_client.clone();
+
+            async move {
+                let stream = stream::poll_fn(move |cx| receiver.poll_recv(cx)).into_async_read();
+
+                node_client.add_blob(stream, None, None).await
+            }
+        });
+
+        Box::new(ContextBlobWriter {
+            node_client,
+            context_id: self.context_id,
+            sender: Some(sender),
+            task: Some(task),
+        })
+    }
+}
+
+/// A blob being streamed into the blob store as the guest writes it.
+#[derive(Debug)]
+struct ContextBlobWriter {
+    node_client: NodeClient,
+    context_id: ContextId,
+    sender: Option<mpsc::Sender<io::Result<Vec<u8>>>>,
+    task: Option<JoinHandle<eyre::Result<(BlobId, u64)>>>,
+}
+
+impl BlobWriter for ContextBlobWriter {
+    fn write(&mut self, data: &[u8]) -> bool {
+        let Some(sender) = &self.sender else {
+            return false;
+        };
+
+        sender.blocking_send(Ok(data.to_vec())).is_ok()
+    }
+
+    fn finish(mut self: Box<Self>) -> Option<BlobId> {
+        // closing the channel ends the stream being stored
+        drop(self.sender.take());
+
+        let task = self.task.take()?;
+
+        let (blob_id, _size) = global_runtime().block_on(task).ok()?.ok()?;
+
+        // nothing tracks what the guest does with the blob, so the context
+        // holds it until it is deleted, rather than leaving it for garbage
+        // collection
+        let _ignored = self
+            .node_client
+            .retain_context_blob(&self.context_id, &blob_id)
+            .ok()?;
+
+        Some(blob_id)
+    }
+}
+
+impl Drop for ContextBlobWriter {
+    fn drop(&mut self) {
+        // a writer that was never finished must not be stored, so the stream
+        // is failed and the task aborted before the channel closes, leaving
+        // any written chunks to be garbage collected
+        if let Some(sender) = self.sender.take() {
+            let _ignored = sender.try_send(Err(io::Error::other("blob was not finished")));
+        }
+
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+    }
+}
+
+/// A blob written during a simulation, which is only given the id it would
+/// have been stored under.
+#[derive(Debug, Default)]
+struct DiscardingBlobWriter {
+    hasher: BlobHasher,
+}
+
+impl BlobWriter for DiscardingBlobWriter {
+    fn write(&mut self, data: &[u8]) -> bool {
+        self.hasher.update(data);
+
+        true
+    }
+
+    fn finish(self: Box<Self>) -> Option<BlobId> {
+        Some(self.hasher.finalize())
+    }
+}
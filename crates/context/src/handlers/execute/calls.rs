@@ -0,0 +1,59 @@
+use calimero_context_primitives::client::ContextClient;
+use calimero_context_primitives::messages::execute::ExecuteBudget;
+use calimero_runtime::calls::{ContextCall, ContextCallBackend, ContextCallOutcome};
+use calimero_utils_actix::global_runtime;
+
+/// Lets guests call into other contexts on the node.
+///
+/// Calls are queries made on behalf of the caller's executor, so they are
+/// subject to the same membership check as any other query, and can't change
+/// the called context's state. Since they never take a context's lock, a
+/// context calling back into one which called it can't deadlock. The callee
+/// runs on what's left of the caller's gas and time.
+///
+/// This is only used from within the blocking task the runtime executes on,
+/// where it is safe to block on the global runtime.
+#[derive(Debug)]
+pub struct ContextCalls {
+    context_client: ContextClient,
+}
+
+impl ContextCalls {
+    pub const fn new(context_client: ContextClient) -> Self {
+        Self { context_client }
+    }
+}
+
+impl ContextCallBackend for ContextCalls {
+    fn call(&self, call: &ContextCall<'_>) -> ContextCallOutcome {
+        let response = global_runtime().block_on(self.context_client.call(
+            &call.context_id.into(),
+            &call.executor_public_key.into(),
+            call.method.to_owned(),
+            call.args.to_vec(),
+            ExecuteBudget {
+                depth: call.budget.depth,
+                gas: call.budget.gas,
+                deadline: call.budget.deadline,
+            },
+        ));
+
+        let response = match response {
+            Ok(response) => response,
+            Err(err) => {
+                return ContextCallOutcome {
+                    returns: Err(err.to_string()),
+                    gas_used: 0,
+                }
+            }
+        };
+
+        ContextCallOutcome {
+            returns: response
+                .returns
+                .map(Option::unwrap_or_default)
+                .map_err(|err| err.to_string()),
+            gas_used: response.gas_used,
+        }
+    }
+}
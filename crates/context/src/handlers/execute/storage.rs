@@ -2,19 +2,32 @@
 
 use core::cell::RefCell;
 use core::mem;
+use core::ops::{Bound, RangeBounds};
+use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use calimero_primitives::context::ContextId;
 use calimero_runtime::store::{Key, Storage, Value};
 use calimero_store::layer::temporal::Temporal;
 use calimero_store::layer::{ReadLayer, WriteLayer};
-use calimero_store::{key, Store};
+use calimero_store::{key, types, Store};
 use ouroboros::self_referencing;
+use tracing::error;
+
+#[cfg(test)]
+#[path = "tests/storage.rs"]
+mod tests;
 
 #[self_referencing]
 pub struct ContextStorage {
     context_id: ContextId,
     store: Store,
+    // a handle onto the same store, for scanning past the pending writes
+    base: Store,
+    read_only: bool,
+    usage: u64,
+    // keys written during this execution, which may not be in the store yet
+    written: BTreeSet<[u8; 32]>,
 
     #[covariant]
     #[borrows(mut store)]
@@ -35,9 +48,32 @@ unsafe impl Send for ContextStorage {}
 
 impl ContextStorage {
     pub fn from(store: Store, context_id: ContextId) -> Self {
+        let usage = stored_usage(&store, context_id);
+
         ContextStorageBuilder {
             context_id,
+            base: store.clone(),
             store,
+            read_only: false,
+            usage,
+            written: BTreeSet::new(),
+            inner_builder: |store| Temporal::new(store),
+            keys: RefCell::default(),
+        }
+        .build()
+    }
+
+    /// Creates a storage which ignores writes, and can never be committed.
+    pub fn read_only(store: Store, context_id: ContextId) -> Self {
+        let usage = stored_usage(&store, context_id);
+
+        ContextStorageBuilder {
+            context_id,
+            base: store.clone(),
+            store,
+            read_only: true,
+            usage,
+            written: BTreeSet::new(),
             inner_builder: |store| Temporal::new(store),
             keys: RefCell::default(),
         }
@@ -67,9 +103,26 @@ impl ContextStorage {
     }
 
     pub fn commit(mut self) -> eyre::Result<Store> {
+        if *self.borrow_read_only() {
+            eyre::bail!("cannot commit read-only storage");
+        }
+
         self.with_inner_mut(|inner| inner.commit())?;
 
-        Ok(self.into_heads().store)
+        let usage = *self.borrow_usage();
+
+        let context_id = *self.borrow_context_id();
+
+        let store = self.into_heads().store;
+
+        let mut handle = store.handle();
+
+        handle.put(
+            &key::ContextUsage::new(context_id),
+            &types::ContextUsage::new(usage),
+        )?;
+
+        Ok(store)
     }
 
     pub fn is_empty(&self) -> bool {
@@ -87,9 +140,17 @@ impl Storage for ContextStorage {
     }
 
     fn remove(&mut self, key: &Key) -> Option<Vec<u8>> {
+        if *self.borrow_read_only() {
+            return None;
+        }
+
         let key = self.state_key(key)?;
 
-        self.with_inner_mut(|inner| {
+        self.with_written_mut(|written| {
+            let _ignored = written.insert(key.state_key());
+        });
+
+        let old = self.with_inner_mut(|inner| {
             let old = inner
                 .get(key)
                 .ok()
@@ -99,13 +160,29 @@ impl Storage for ContextStorage {
             inner.delete(key).ok()?;
 
             old
-        })
+        })?;
+
+        self.with_usage_mut(|usage| {
+            *usage = usage.saturating_sub(types::ContextUsage::entry_size(old.len() as u64));
+        });
+
+        Some(old)
     }
 
     fn set(&mut self, key: Key, value: Value) -> Option<Value> {
+        if *self.borrow_read_only() {
+            return None;
+        }
+
+        let added = types::ContextUsage::entry_size(value.len() as u64);
+
         let key = self.state_key(&key)?;
 
-        self.with_inner_mut(|inner| {
+        self.with_written_mut(|written| {
+            let _ignored = written.insert(key.state_key());
+        });
+
+        let old = self.with_inner_mut(|inner| {
             let old = inner
                 .has(key)
                 .ok()?
@@ -115,8 +192,18 @@ impl Storage for ContextStorage {
 
             inner.put(key, value.into()).ok()?;
 
-            old
-        })
+            Some(old)
+        })?;
+
+        let removed = old
+            .as_ref()
+            .map_or(0, |old| types::ContextUsage::entry_size(old.len() as u64));
+
+        self.with_usage_mut(|usage| {
+            *usage = usage.saturating_add(added).saturating_sub(removed);
+        });
+
+        old
     }
 
     fn has(&self, key: &Key) -> bool {
@@ -126,4 +213,145 @@ impl Storage for ContextStorage {
 
         self.borrow_inner().has(key).unwrap_or_default()
     }
+
+    fn usage(&self) -> u64 {
+        *self.borrow_usage()
+    }
+
+    /// Keys are stored padded to 32 bytes, which is stripped from the keys
+    /// returned. A key written with trailing zero bytes comes back without
+    /// them, and still addresses the same entry.
+    fn scan(&self, start: Bound<&[u8]>, end: Bound<&[u8]>, limit: usize) -> Vec<(Key, Value)> {
+        let context_id = *self.borrow_context_id();
+
+        let range = (padded(start, true), padded(end, false));
+
+        // BTreeSet::range panics on inverted bounds
+        let inverted = match (range.0, range.1) {
+            (Bound::Included(start), Bound::Included(end)) => start > end,
+            (
+                Bound::Included(start) | Bound::Excluded(start),
+                Bound::Included(end) | Bound::Excluded(end),
+            ) => start >= end,
+            _ => false,
+        };
+
+        if inverted {
+            return vec![];
+        }
+
+        let first = match range.0 {
+            Bound::Included(first) | Bound::Excluded(first) => first,
+            Bound::Unbounded => [0; 32],
+        };
+
+        let mut iter = match self.borrow_base().iter::<key::ContextState>() {
+            Ok(iter) => iter,
+            Err(err) => {
+                error!(%context_id, %err, "failed to scan storage");
+
+                return vec![];
+            }
+        };
+
+        let first = iter
+            .seek(key::ContextState::new(context_id, first))
+            .transpose();
+
+        let mut stored = first
+            .into_iter()
+            .chain(iter.keys())
+            .map_while(Result::ok)
+            .take_while(|key| key.context_id() == context_id)
+            .map(|key| key.state_key())
+            .skip_while(|key| !range.contains(key))
+            .take_while(|key| range.contains(key))
+            .peekable();
+
+        let mut pending = self.borrow_written().range(range).copied().peekable();
+
+        let mut entries = vec![];
+
+        while entries.len() < limit {
+            let key = match (stored.peek(), pending.peek()) {
+                (Some(a), Some(b)) if a < b => stored.next(),
+                (Some(a), Some(b)) if a > b => pending.next(),
+                (Some(_), Some(_)) => {
+                    let _ignored = stored.next();
+                    pending.next()
+                }
+                (Some(_), None) => stored.next(),
+                (None, Some(_)) => pending.next(),
+                (None, None) => break,
+            };
+
+            let Some(key) = key else {
+                break;
+            };
+
+            // keys removed during this execution are still in the store
+            let value = self
+                .borrow_inner()
+                .get(&key::ContextState::new(context_id, key));
+
+            if let Ok(Some(value)) = value {
+                entries.push((unpadded(&key).to_vec(), value.into_boxed().into_vec()));
+            }
+        }
+
+        entries
+    }
+}
+
+/// Pads a bound on keys to the 32 bytes keys are stored in. Longer bounds are
+/// cut down, while still including and excluding the same stored keys.
+fn padded(bound: Bound<&[u8]>, is_start: bool) -> Bound<[u8; 32]> {
+    let mut padded = [0; 32];
+
+    let key = match bound {
+        Bound::Included(key) | Bound::Excluded(key) => key,
+        Bound::Unbounded => return Bound::Unbounded,
+    };
+
+    if key.len() > padded.len() {
+        padded.copy_from_slice(&key[..32]);
+
+        // every stored key starting with the first 32 bytes is shorter, and
+        // so comes before the bound
+        return if is_start {
+            Bound::Excluded(padded)
+        } else {
+            Bound::Included(padded)
+        };
+    }
+
+    padded[..key.len()].copy_from_slice(key);
+
+    match bound {
+        Bound::Included(_) => Bound::Included(padded),
+        _ => Bound::Excluded(padded),
+    }
+}
+
+/// Strips the padding from a stored key.
+fn unpadded(key: &[u8; 32]) -> &[u8] {
+    let len = key
+        .iter()
+        .rposition(|&byte| byte != 0)
+        .map_or(0, |last| last.saturating_add(1));
+
+    &key[..len]
+}
+
+fn stored_usage(store: &Store, context_id: ContextId) -> u64 {
+    let handle = store.handle();
+
+    match handle.get(&key::ContextUsage::new(context_id)) {
+        Ok(usage) => usage.map_or(0, |usage| usage.storage_bytes),
+        Err(err) => {
+            error!(%context_id, %err, "failed to read storage usage");
+
+            0
+        }
+    }
 }
@@ -0,0 +1,182 @@
+use calimero_store::db::InMemoryDB;
+
+use super::*;
+
+const CONTEXT_ID: [u8; 32] = [1; 32];
+
+fn store() -> Store {
+    Store::new(Arc::new(InMemoryDB::owned()))
+}
+
+fn key(key: &[u8]) -> Key {
+    key.to_vec()
+}
+
+#[test]
+fn uncommitted_writes_leave_store_unchanged() {
+    let store = store();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    let mut storage = ContextStorage::from(store.clone(), context_id);
+
+    assert_eq!(storage.set(key(b"kept"), b"before".to_vec()), None);
+    assert_eq!(storage.set(key(b"removed"), b"before".to_vec()), None);
+
+    let store = storage.commit().unwrap();
+
+    let usage = ContextStorage::from(store.clone(), context_id).usage();
+
+    // a simulation writes into a storage which is then dropped
+    let mut storage = ContextStorage::from(store.clone(), context_id);
+
+    assert_eq!(
+        storage.set(key(b"kept"), b"after".to_vec()),
+        Some(b"before".to_vec())
+    );
+    assert_eq!(storage.set(key(b"added"), b"after".to_vec()), None);
+    assert_eq!(storage.remove(&key(b"removed")), Some(b"before".to_vec()));
+
+    assert!(!storage.is_empty());
+
+    drop(storage);
+
+    let storage = ContextStorage::from(store, context_id);
+
+    assert_eq!(storage.get(&key(b"kept")), Some(b"before".to_vec()));
+    assert_eq!(storage.get(&key(b"removed")), Some(b"before".to_vec()));
+    assert_eq!(storage.get(&key(b"added")), None);
+    assert_eq!(storage.usage(), usage);
+}
+
+#[test]
+fn usage_follows_set_overwrite_and_remove() {
+    let store = store();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    let mut storage = ContextStorage::from(store, context_id);
+
+    assert_eq!(storage.usage(), 0);
+
+    // keys count for the 32 bytes they are stored in, whatever their length
+    assert_eq!(storage.set(key(b"a"), vec![0; 10]), None);
+    assert_eq!(storage.usage(), 42);
+
+    assert_eq!(storage.set(key(b"b"), vec![0; 20]), None);
+    assert_eq!(storage.usage(), 94);
+
+    assert_eq!(storage.set(key(b"a"), vec![0; 4]), Some(vec![0; 10]));
+    assert_eq!(storage.usage(), 88);
+
+    assert_eq!(storage.set(key(b"a"), vec![0; 30]), Some(vec![0; 4]));
+    assert_eq!(storage.usage(), 114);
+
+    assert_eq!(storage.remove(&key(b"b")), Some(vec![0; 20]));
+    assert_eq!(storage.usage(), 62);
+
+    assert_eq!(storage.remove(&key(b"b")), None);
+    assert_eq!(storage.usage(), 62);
+
+    let store = storage.commit().unwrap();
+
+    let mut storage = ContextStorage::from(store, context_id);
+
+    assert_eq!(storage.usage(), 62);
+
+    assert_eq!(storage.remove(&key(b"a")), Some(vec![0; 30]));
+    assert_eq!(storage.usage(), 0);
+
+    let store = storage.commit().unwrap();
+
+    assert_eq!(ContextStorage::from(store, context_id).usage(), 0);
+}
+
+#[test]
+fn usage_is_kept_per_context() {
+    let store = store();
+
+    let mut storage = ContextStorage::from(store, ContextId::from(CONTEXT_ID));
+
+    assert_eq!(storage.set(key(b"a"), vec![0; 10]), None);
+
+    let store = storage.commit().unwrap();
+
+    let mut storage = ContextStorage::from(store, ContextId::from([2; 32]));
+
+    assert_eq!(storage.usage(), 0);
+
+    assert_eq!(storage.set(key(b"a"), vec![0; 20]), None);
+
+    let store = storage.commit().unwrap();
+
+    assert_eq!(
+        ContextStorage::from(store.clone(), ContextId::from(CONTEXT_ID)).usage(),
+        42
+    );
+    assert_eq!(
+        ContextStorage::from(store, ContextId::from([2; 32])).usage(),
+        52
+    );
+}
+
+#[test]
+fn read_only_storage_ignores_writes() {
+    let store = store();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    let mut storage = ContextStorage::from(store, context_id);
+
+    assert_eq!(storage.set(key(b"a"), vec![0; 10]), None);
+
+    let store = storage.commit().unwrap();
+
+    let mut storage = ContextStorage::read_only(store, context_id);
+
+    assert_eq!(storage.set(key(b"b"), vec![0; 10]), None);
+    assert_eq!(storage.remove(&key(b"a")), None);
+    assert_eq!(storage.usage(), 42);
+    assert!(storage.commit().is_err());
+}
+
+#[test]
+fn scan_returns_keys_as_written() {
+    let store = store();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    let mut storage = ContextStorage::from(store, context_id);
+
+    assert_eq!(storage.set(key(b"a"), b"1".to_vec()), None);
+    assert_eq!(storage.set(key(b"ab"), b"2".to_vec()), None);
+    assert_eq!(storage.set(key(&[b'c'; 32]), b"3".to_vec()), None);
+
+    let store = storage.commit().unwrap();
+
+    let mut storage = ContextStorage::from(store, context_id);
+
+    assert_eq!(storage.set(key(b"aa"), b"4".to_vec()), None);
+    assert_eq!(storage.remove(&key(b"ab")), Some(b"2".to_vec()));
+
+    let entries = storage.scan(Bound::Unbounded, Bound::Unbounded, 10);
+
+    assert_eq!(
+        entries,
+        [
+            (key(b"a"), b"1".to_vec()),
+            (key(b"aa"), b"4".to_vec()),
+            (key(&[b'c'; 32]), b"3".to_vec()),
+        ]
+    );
+
+    // resuming past a returned key doesn't return it again
+    let entries = storage.scan(Bound::Excluded(&b"a"[..]), Bound::Unbounded, 1);
+
+    assert_eq!(entries, [(key(b"aa"), b"4".to_vec())]);
+
+    let entries = storage.scan(Bound::Excluded(&b"aa"[..]), Bound::Excluded(&b"c"[..]), 10);
+
+    assert!(entries.is_empty());
+
+    // bounds longer than any key still include what comes before them
+    let entries = storage.scan(Bound::Included(&b"b"[..]), Bound::Excluded(&[b'c'; 33][..]), 10);
+
+    assert_eq!(entries, [(key(&[b'c'; 32]), b"3".to_vec())]);
+}
@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use actix::{ActorFutureExt, ActorResponse, ActorTryFutureExt, Handler, Message, WrapFuture};
+use calimero_context_primitives::client::crypto::ContextIdentity;
+use calimero_context_primitives::messages::execute::{ExecuteError, ExecuteEvent};
+use calimero_context_primitives::messages::simulate::{
+    SimulateRequest, SimulateResponse, StateChange,
+};
+use calimero_runtime::calls::CallBudget;
+use calimero_runtime::fetch::NoFetch;
+use calimero_storage::interface::Action;
+use calimero_storage::sync::SyncArtifact;
+use tracing::{debug, error};
+
+use super::execute::blobs::ContextBlobs;
+use super::execute::storage::ContextStorage;
+use super::execute::{run, substitute_aliases_in_payload};
+use crate::ContextManager;
+
+impl Handler<SimulateRequest> for ContextManager {
+    type Result = ActorResponse<Self, <SimulateRequest as Message>::Result>;
+
+    fn handle(
+        &mut self,
+        SimulateRequest {
+            context: context_id,
+            executor,
+            method,
+            payload,
+            aliases,
+        }: SimulateRequest,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        debug!(
+            %context_id,
+            %executor,
+            method,
+            aliases = ?aliases,
+            payload_len = payload.len(),
+            "simulation requested"
+        );
+
+        let context = match self.get_or_fetch_context(&context_id) {
+            Ok(Some(context)) => context.meta,
+            Ok(None) => return ActorResponse::reply(Err(ExecuteError::ContextNotFound)),
+            Err(err) => {
+                error!(%err, "failed to simulate request");
+
+                return ActorResponse::reply(Err(ExecuteError::InternalError));
+            }
+        };
+
+        if *context.root_hash == [0; 32] {
+            return ActorResponse::reply(Err(ExecuteError::Uninitialized));
+        }
+
+        match self.context_client.get_identity(&context_id, &executor) {
+            Ok(Some(ContextIdentity {
+                private_key: Some(_),
+                ..
+            })) => {}
+            Ok(_) => {
+                return ActorResponse::reply(Err(ExecuteError::Unauthorized {
+                    context_id,
+                    public_key: executor,
+                }))
+            }
+            Err(err) => {
+                error!(%err, "failed to simulate request");
+
+                return ActorResponse::reply(Err(ExecuteError::InternalError));
+            }
+        }
+
+        let payload =
+            match substitute_aliases_in_payload(&self.node_client, context_id, payload, &aliases) {
+                Ok(payload) => payload,
+                Err(err) => {
+                    error!(%err, "failed to simulate request");
+
+                    return ActorResponse::reply(Err(err));
+                }
+            };
+
+        // writes land in a temporal layer over the store which is dropped
+        // without ever being committed, so nothing here needs the context lock
+        let task = self
+            .get_module(context.application_id)
+            .and_then(move |module, act, _ctx| {
+                let storage = ContextStorage::from(act.datastore.clone(), context_id);
+
+                // nor do blobs written reach the blob store, nor requests the
+                // network, though blobs already stored can still be read
+                let blobs = ContextBlobs::simulated(act.node_client.clone(), context_id);
+
+                run(
+                    context_id,
+                    module.with_fetch_backend(Arc::new(NoFetch)),
+                    executor,
+                    method.into(),
+                    payload.into(),
+                    storage,
+                    blobs,
+                    false,
+                    CallBudget::default(),
+                    None,
+                )
+                .into_actor(act)
+            })
+            .map(move |result, _act, _ctx| {
+                let (outcome, _storage) = result?;
+
+                let changes = if outcome.returns.is_ok() && !outcome.artifact.is_empty() {
+                    state_changes(&outcome.artifact)?
+                } else {
+                    vec![]
+                };
+
+                Ok(SimulateResponse {
+                    returns: outcome.returns.map_err(Into::into),
+                    logs: outcome.logs,
+                    events: outcome
+                        .events
+                        .into_iter()
+                        .map(|e| ExecuteEvent {
+                            kind: e.kind,
+                            data: e.data,
+                        })
+                        .collect(),
+                    root_hash: outcome.root_hash.map_or(context.root_hash, Into::into),
+                    changes,
+                    gas_used: outcome.gas_used,
+                })
+            })
+            .map_err(|err: eyre::Report, _act, _ctx| {
+                err.downcast::<ExecuteError>().unwrap_or_else(|err| {
+                    debug!(?err, "an error occurred while simulating request");
+                    ExecuteError::InternalError
+                })
+            });
+
+        ActorResponse::r#async(task)
+    }
+}
+
+fn state_changes(artifact: &[u8]) -> eyre::Result<Vec<StateChange>> {
+    let SyncArtifact::Actions(actions) = borsh::from_slice(artifact)? else {
+        return Ok(vec![]);
+    };
+
+    let changes = actions
+        .into_iter()
+        .filter_map(|action| match action {
+            Action::Add { id, data, path, .. } => Some(StateChange::Add {
+                id: id.to_string(),
+                path: path.map(|path| path.to_string()),
+                data,
+            }),
+            Action::Update { id, data, path, .. } => Some(StateChange::Update {
+                id: id.to_string(),
+                path: path.map(|path| path.to_string()),
+                data,
+            }),
+            Action::Delete { id, .. } => Some(StateChange::Delete { id: id.to_string() }),
+            Action::Compare { .. } => None,
+        })
+        .collect();
+
+    Ok(changes)
+}
@@ -18,6 +18,9 @@ use tokio::sync::{Mutex, OwnedMutexGuard};
 pub mod config;
 pub mod handlers;
 
+use config::ContextConfig;
+use handlers::execute::calls::ContextCalls;
+
 #[derive(Debug)]
 struct ContextMeta {
     meta: Context,
@@ -43,10 +46,55 @@ pub struct ContextManager {
     // that might be relevant in the compilation process,
     // so we cannot blindly reuse compiled blobs across apps.
     applications: BTreeMap<ApplicationId, Application>,
-    //
-    // todo! when runtime let's us compile blobs separate from its
-    // todo! execution, we can introduce a cached::TimedSizedCache
-    // runtimes: TimedSizedCache<Exclusive<calimero_runtime::Engine>>,
+    // modules compiled for the applications above, so they're only loaded from
+    // their blobs once, applications being immutable once installed
+    modules: Modules,
+}
+
+/// The most compiled modules kept at once, each holding its own engine.
+const MAX_CACHED_MODULES: usize = 32;
+
+/// Compiled modules, by the application they were compiled for, the least
+/// recently used of which are evicted once there are too many.
+#[derive(Debug, Default)]
+struct Modules {
+    entries: BTreeMap<ApplicationId, (calimero_runtime::Module, u64)>,
+    clock: u64,
+}
+
+impl Modules {
+    fn get(&mut self, application_id: &ApplicationId) -> Option<calimero_runtime::Module> {
+        self.clock = self.clock.saturating_add(1);
+
+        let (module, used) = self.entries.get_mut(application_id)?;
+
+        *used = self.clock;
+
+        Some(module.clone())
+    }
+
+    fn insert(&mut self, application_id: ApplicationId, module: calimero_runtime::Module) {
+        self.clock = self.clock.saturating_add(1);
+
+        drop(self.entries.insert(application_id, (module, self.clock)));
+
+        while self.entries.len() > MAX_CACHED_MODULES {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(id, _)| *id)
+            else {
+                break;
+            };
+
+            drop(self.entries.remove(&oldest));
+        }
+    }
+
+    fn remove(&mut self, application_id: &ApplicationId) {
+        drop(self.entries.remove(application_id));
+    }
 }
 
 impl ContextManager {
@@ -54,17 +102,21 @@ impl ContextManager {
         datastore: Store,
         node_client: NodeClient,
         context_client: ContextClient,
-        external_config: ExternalClientConfig,
+        config: ContextConfig,
     ) -> Self {
+        let runtime_engine = calimero_runtime::Engine::with_limits(config.limits())
+            .with_context_call_backend(Arc::new(ContextCalls::new(context_client.clone())));
+
         Self {
             datastore,
             node_client,
             context_client,
-            runtime_engine: Default::default(),
-            external_config,
+            runtime_engine,
+            external_config: config.client,
 
             contexts: BTreeMap::new(),
             applications: BTreeMap::new(),
+            modules: Modules::default(),
         }
     }
 }
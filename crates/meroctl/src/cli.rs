@@ -16,6 +16,7 @@ use crate::defaults;
 use crate::output::{Format, Output, Report};
 
 mod app;
+mod blob;
 mod bootstrap;
 mod call;
 mod context;
@@ -24,6 +25,7 @@ mod peers;
 mod proxy;
 
 use app::AppCommand;
+use blob::BlobCommand;
 use bootstrap::BootstrapCommand;
 use call::CallCommand;
 use context::ContextCommand;
@@ -62,6 +64,7 @@ pub struct RootCommand {
 #[derive(Debug, Subcommand)]
 pub enum SubCommands {
     App(AppCommand),
+    Blob(BlobCommand),
     Context(ContextCommand),
     Proxy(ProxyCommand),
     Call(CallCommand),
@@ -139,6 +142,7 @@ impl RootCommand {
 
         let result = match self.action {
             SubCommands::App(application) => application.run(&environment).await,
+            SubCommands::Blob(blob) => blob.run(&environment).await,
             SubCommands::Context(context) => context.run(&environment).await,
             SubCommands::Proxy(proxy) => proxy.run(&environment).await,
             SubCommands::Call(call) => call.run(&environment).await,
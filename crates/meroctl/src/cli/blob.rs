@@ -0,0 +1,52 @@
+use clap::{Parser, Subcommand};
+use const_format::concatcp;
+use eyre::Result as EyreResult;
+
+use crate::cli::blob::download::DownloadCommand;
+use crate::cli::blob::list::ListCommand;
+use crate::cli::blob::upload::UploadCommand;
+use crate::cli::Environment;
+
+mod download;
+mod list;
+mod upload;
+
+pub const EXAMPLES: &str = r"
+  # Upload a file to the blob store
+  $ meroctl --node node1 blob upload ./video.mp4
+
+  # Download a blob, resuming a previous partial download
+  $ meroctl --node node1 blob download <BLOB_ID> --output ./video.mp4 --resume
+
+  # List all blobs
+  $ meroctl --node node1 blob ls
+";
+
+#[derive(Debug, Parser)]
+#[command(about = "Command for managing blobs")]
+#[command(after_help = concatcp!(
+    "Examples:",
+    EXAMPLES
+))]
+pub struct BlobCommand {
+    #[command(subcommand)]
+    pub subcommand: BlobSubCommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BlobSubCommands {
+    Upload(UploadCommand),
+    Download(DownloadCommand),
+    #[command(alias = "ls")]
+    List(ListCommand),
+}
+
+impl BlobCommand {
+    pub async fn run(self, environment: &Environment) -> EyreResult<()> {
+        match self.subcommand {
+            BlobSubCommands::Upload(upload) => upload.run(environment).await,
+            BlobSubCommands::Download(download) => download.run(environment).await,
+            BlobSubCommands::List(list) => list.run(environment).await,
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use calimero_primitives::blobs::BlobId;
+use camino::Utf8PathBuf;
+use clap::Parser;
+use eyre::{bail, OptionExt, Result as EyreResult};
+use futures_util::StreamExt;
+use reqwest::header::RANGE;
+use reqwest::{Client, StatusCode};
+use tokio::fs::{self, OpenOptions};
+use tokio::io::AsyncWriteExt;
+
+use crate::cli::{ApiError, Environment};
+use crate::common::send_request;
+use crate::output::InfoLine;
+
+#[derive(Debug, Parser)]
+#[command(about = "Download a blob to a file")]
+pub struct DownloadCommand {
+    #[arg(value_name = "BLOB_ID", help = "ID of the blob")]
+    pub blob_id: BlobId,
+
+    #[arg(long, short, value_name = "PATH", help = "Path to write the blob to")]
+    pub output: Utf8PathBuf,
+
+    #[arg(
+        long,
+        help = "Continue a partial download, keeping what is already in the file"
+    )]
+    pub resume: bool,
+}
+
+impl DownloadCommand {
+    pub async fn run(self, environment: &Environment) -> EyreResult<()> {
+        let connection = environment
+            .connection
+            .as_ref()
+            .ok_or_eyre("No connection configured")?;
+
+        let offset = if self.resume {
+            fs::metadata(&self.output)
+                .await
+                .map_or(0, |metadata| metadata.len())
+        } else {
+            0
+        };
+
+        let mut url = connection.api_url.clone();
+        url.set_path(&format!("admin-api/dev/blobs/{}", self.blob_id));
+
+        let mut builder = Client::new().get(url);
+
+        if offset != 0 {
+            builder = builder.header(RANGE, format!("bytes={offset}-"));
+        }
+
+        let response = match send_request(builder, connection.auth_key.as_ref()).await {
+            Ok(response) => response,
+            Err(err) => match err.downcast_ref::<ApiError>() {
+                // the file already holds the whole blob
+                Some(api_error)
+                    if offset != 0
+                        && api_error.status_code == StatusCode::RANGE_NOT_SATISFIABLE.as_u16() =>
+                {
+                    environment
+                        .output
+                        .write(&InfoLine(&format!("{} is already complete", self.output)));
+                    return Ok(());
+                }
+                _ => return Err(err),
+            },
+        };
+
+        let append = match response.status() {
+            StatusCode::PARTIAL_CONTENT => true,
+            StatusCode::OK => false,
+            status => bail!("unexpected response status: {status}"),
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(&self.output)
+            .await?;
+
+        let mut stream = response.bytes_stream();
+
+        while let Some(chunk) = stream.next().await {
+            file.write_all(&chunk?).await?;
+        }
+
+        file.flush().await?;
+
+        environment.output.write(&InfoLine(&format!(
+            "Downloaded {} to {}",
+            self.blob_id, self.output
+        )));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use calimero_server_primitives::admin::ListBlobsResponse;
+use clap::Parser;
+use comfy_table::{Cell, Color, Table};
+use eyre::{OptionExt, Result as EyreResult};
+use reqwest::Client;
+
+use crate::cli::Environment;
+use crate::common::{do_request, RequestType};
+use crate::output::Report;
+
+#[derive(Debug, Parser)]
+#[command(about = "List stored blobs")]
+pub struct ListCommand;
+
+impl Report for ListBlobsResponse {
+    fn report(&self) {
+        let mut table = Table::new();
+        let _ = table.set_header(vec![
+            Cell::new("Blob ID").fg(Color::Blue),
+            Cell::new("Size").fg(Color::Blue),
+            Cell::new("Hash").fg(Color::Blue),
+        ]);
+
+        for blob in &self.data.blobs {
+            let _ = table.add_row(vec![
+                blob.id.to_string(),
+                format!("{} bytes", blob.size),
+                blob.hash.to_string(),
+            ]);
+        }
+        println!("{table}");
+    }
+}
+
+impl ListCommand {
+    pub async fn run(self, environment: &Environment) -> EyreResult<()> {
+        let connection = environment
+            .connection
+            .as_ref()
+            .ok_or_eyre("No connection configured")?;
+
+        let mut url = connection.api_url.clone();
+        url.set_path("admin-api/dev/blobs");
+
+        let response: ListBlobsResponse = do_request(
+            &Client::new(),
+            url,
+            None::<()>,
+            connection.auth_key.as_ref(),
+            RequestType::Get,
+        )
+        .await?;
+
+        environment.output.write(&response);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use calimero_primitives::hash::Hash;
+use calimero_server_primitives::admin::{UploadBlobQuery, UploadBlobResponse};
+use camino::Utf8PathBuf;
+use clap::Parser;
+use comfy_table::{Cell, Color, Table};
+use eyre::{OptionExt, Result as EyreResult};
+use reqwest::{Body, Client};
+use tokio::fs::File;
+use tokio_util::io::ReaderStream;
+
+use crate::cli::Environment;
+use crate::common::send_request;
+use crate::output::Report;
+
+#[derive(Debug, Parser)]
+#[command(about = "Upload a file to the blob store")]
+pub struct UploadCommand {
+    #[arg(value_name = "PATH", help = "Path to the file")]
+    pub path: Utf8PathBuf,
+
+    #[arg(long, help = "Expected hash of the file")]
+    pub hash: Option<Hash>,
+}
+
+impl Report for UploadBlobResponse {
+    fn report(&self) {
+        let mut table = Table::new();
+        let _ = table.set_header(vec![Cell::new("Blob Uploaded").fg(Color::Green)]);
+        let _ = table.add_row(vec![format!("Blob ID: {}", self.data.blob_id)]);
+        let _ = table.add_row(vec![format!("Size: {} bytes", self.data.size)]);
+        println!("{table}");
+    }
+}
+
+impl UploadCommand {
+    pub async fn run(self, environment: &Environment) -> EyreResult<()> {
+        let connection = environment
+            .connection
+            .as_ref()
+            .ok_or_eyre("No connection configured")?;
+
+        let file = File::open(&self.path).await?;
+
+        let size = file.metadata().await?.len();
+
+        let mut url = connection.api_url.clone();
+        url.set_path("admin-api/dev/blobs");
+
+        let builder = Client::new()
+            .post(url)
+            .query(&UploadBlobQuery::new(Some(size), self.hash))
+            .body(Body::wrap_stream(ReaderStream::new(file)));
+
+        let response: UploadBlobResponse = send_request(builder, connection.auth_key.as_ref())
+            .await?
+            .json()
+            .await?;
+
+        environment.output.write(&response);
+
+        Ok(())
+    }
+}
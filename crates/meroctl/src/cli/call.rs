@@ -117,6 +117,7 @@ impl CallCommand {
             self.args.unwrap_or(json!({})),
             executor,
             self.substitute,
+            None,
         ));
 
         let request = Request::new(
@@ -13,6 +13,7 @@ use crate::cli::context::identity::ContextIdentityCommand;
 use crate::cli::context::invite::InviteCommand;
 use crate::cli::context::join::JoinCommand;
 use crate::cli::context::list::ListCommand;
+use crate::cli::context::sync::SyncCommand;
 use crate::cli::context::update::UpdateCommand;
 use crate::cli::context::watch::WatchCommand;
 use crate::cli::Environment;
@@ -26,6 +27,7 @@ mod identity;
 pub mod invite;
 pub mod join;
 mod list;
+mod sync;
 mod update;
 mod watch;
 
@@ -44,6 +46,9 @@ pub const EXAMPLES: &str = r"
   
   # Revoke permission to manage members
   $ meroctl context identity revoke bob ManageMembers --as alice
+
+  # Sync a context with any of its peers right away
+  $ meroctl context sync <contextId>
 ";
 
 #[derive(Debug, Parser)]
@@ -70,6 +75,7 @@ pub enum ContextSubCommands {
     #[command(alias = "ws")]
     Watch(WatchCommand),
     Update(UpdateCommand),
+    Sync(SyncCommand),
     Identity(ContextIdentityCommand),
     Alias(ContextAliasCommand),
     Use(UseCommand),
@@ -104,6 +110,7 @@ impl ContextCommand {
             ContextSubCommands::List(list) => list.run(environment).await,
             ContextSubCommands::Watch(watch) => watch.run(environment).await,
             ContextSubCommands::Update(update) => update.run(environment).await,
+            ContextSubCommands::Sync(sync) => sync.run(environment).await,
             ContextSubCommands::Identity(identity) => identity.run(environment).await,
             ContextSubCommands::Alias(alias) => alias.run(environment).await,
             ContextSubCommands::Use(use_cmd) => use_cmd.run(environment).await,
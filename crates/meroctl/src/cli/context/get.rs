@@ -1,5 +1,5 @@
 use calimero_primitives::alias::Alias;
-use calimero_primitives::context::ContextId;
+use calimero_primitives::context::{ContextId, SyncStatus};
 use calimero_server_primitives::admin::{
     GetContextClientKeysResponse, GetContextIdentitiesResponse, GetContextResponse,
     GetContextStorageResponse, GetContextUsersResponse,
@@ -41,7 +41,50 @@ pub enum GetSubcommand {
 
 impl Report for GetContextResponse {
     fn report(&self) {
-        self.data.report();
+        self.data.context.report();
+
+        let mut table = Table::new();
+        let _ = table.set_header(vec![Cell::new("Context Storage").fg(Color::Blue)]);
+        let _ = table.add_row(vec![format!("Usage: {} bytes", self.data.storage_usage)]);
+        println!("{table}");
+
+        let mut table = Table::new();
+        let _ = table.set_header(vec![Cell::new("Context Sync").fg(Color::Blue)]);
+
+        let Some(sync) = &self.data.sync else {
+            let _ = table.add_row(vec!["Never synced"]);
+            println!("{table}");
+            return;
+        };
+
+        let _ = table.add_row(vec![match &sync.status {
+            SyncStatus::Succeeded => Cell::new("✓ Succeeded").fg(Color::Green),
+            SyncStatus::Failed { reason } => {
+                Cell::new(format!("✗ Failed: {reason}")).fg(Color::Red)
+            }
+            SyncStatus::TimedOut => Cell::new("✗ Timed out").fg(Color::Red),
+        }]);
+        let _ = table.add_row(vec![format!("Last Attempt: {}", sync.last_attempt)]);
+        let _ = table.add_row(vec![format!(
+            "Last Success: {}",
+            sync.last_success
+                .map_or_else(|| "Never".to_owned(), |at| at.to_string())
+        )]);
+        let _ = table.add_row(vec![format!(
+            "Peer: {}",
+            sync.peer_id.as_deref().unwrap_or("None")
+        )]);
+        let _ = table.add_row(vec![format!(
+            "Transferred: {} bytes",
+            sync.bytes_transferred
+        )]);
+        let _ = table.add_row(vec![format!("Root Hash Before: {}", sync.root_hash_before)]);
+        let _ = table.add_row(vec![format!("Root Hash After: {}", sync.root_hash_after)]);
+        let _ = table.add_row(vec![format!(
+            "Consecutive Failures: {}",
+            sync.consecutive_failures
+        )]);
+        println!("{table}");
     }
 }
 
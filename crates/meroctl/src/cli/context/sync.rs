@@ -0,0 +1,88 @@
+use calimero_primitives::alias::Alias;
+use calimero_primitives::context::ContextId;
+use calimero_server_primitives::admin::{SyncContextRequest, SyncContextResponse};
+use clap::Parser;
+use comfy_table::{Cell, Table};
+use eyre::{OptionExt, Result as EyreResult};
+use reqwest::Client;
+
+use crate::cli::Environment;
+use crate::common::{do_request, resolve_alias, RequestType};
+use crate::output::Report;
+
+#[derive(Debug, Parser)]
+#[command(about = "Sync a context with a peer right away")]
+pub struct SyncCommand {
+    #[clap(name = "CONTEXT", help = "The context to sync")]
+    pub context: Alias<ContextId>,
+
+    #[clap(
+        long,
+        value_name = "PEER_ID",
+        help = "The peer to sync with, otherwise any peer subscribed to the context"
+    )]
+    pub peer: Option<String>,
+}
+
+impl Report for SyncContextResponse {
+    fn report(&self) {
+        let mut table = Table::new();
+        let _ = table.set_header(vec![
+            Cell::new("Context Synced").fg(comfy_table::Color::Blue)
+        ]);
+        let _ = table.add_row(vec![format!("Peer: {}", self.data.peer_id)]);
+        let _ = table.add_row(vec![format!("Duration: {}ms", self.data.duration_ms)]);
+        let _ = table.add_row(vec![format!(
+            "Root Hash Before: {}",
+            self.data.root_hash_before
+        )]);
+        let _ = table.add_row(vec![format!(
+            "Root Hash After: {}",
+            self.data.root_hash_after
+        )]);
+        let _ = table.add_row(vec![
+            if self.data.root_hash_before == self.data.root_hash_after {
+                Cell::new("Already up to date").fg(comfy_table::Color::Yellow)
+            } else {
+                Cell::new("✓ Updated").fg(comfy_table::Color::Green)
+            },
+        ]);
+        println!("{table}");
+    }
+}
+
+impl SyncCommand {
+    pub async fn run(self, environment: &Environment) -> EyreResult<()> {
+        let connection = environment
+            .connection
+            .as_ref()
+            .ok_or_eyre("No connection configured")?;
+
+        let context_id = resolve_alias(
+            &connection.api_url,
+            connection.auth_key.as_ref(),
+            self.context,
+            None,
+        )
+        .await?
+        .value()
+        .cloned()
+        .ok_or_eyre("unable to resolve")?;
+
+        let mut url = connection.api_url.clone();
+        url.set_path(&format!("admin-api/dev/contexts/{}/sync", context_id));
+
+        let response: SyncContextResponse = do_request(
+            &Client::new(),
+            url,
+            Some(SyncContextRequest::new(self.peer)),
+            connection.auth_key.as_ref(),
+            RequestType::Post,
+        )
+        .await?;
+
+        environment.output.write(&response);
+
+        Ok(())
+    }
+}
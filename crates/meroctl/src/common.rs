@@ -17,7 +17,7 @@ use eyre::{bail, eyre, Result as EyreResult, WrapErr};
 use libp2p::identity::Keypair;
 use libp2p::multiaddr::Protocol;
 use libp2p::Multiaddr;
-use reqwest::{Client, Url};
+use reqwest::{Client, RequestBuilder, Response, Url};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
@@ -59,12 +59,25 @@ where
     I: Serialize,
     O: DeserializeOwned,
 {
-    let mut builder = match req_type {
+    let builder = match req_type {
         RequestType::Get => client.get(url),
         RequestType::Post => client.post(url).json(&body),
         RequestType::Delete => client.delete(url),
     };
 
+    let response = send_request(builder, keypair).await?;
+
+    let result = response.json::<O>().await?;
+
+    Ok(result)
+}
+
+/// Sends a request, authenticating it if a keypair is provided, and turns
+/// unsuccessful responses into an [`ApiError`].
+pub async fn send_request(
+    mut builder: RequestBuilder,
+    keypair: Option<&Keypair>,
+) -> EyreResult<Response> {
     // Only add authentication if keypair is provided
     if let Some(keypair) = keypair {
         let timestamp = Utc::now().timestamp().to_string();
@@ -87,9 +100,7 @@ where
         });
     }
 
-    let result = response.json::<O>().await?;
-
-    Ok(result)
+    Ok(response)
 }
 // pub async fn do_request<I, O>(
 //     client: &Client,
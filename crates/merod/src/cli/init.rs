@@ -47,6 +47,8 @@ use crate::{cli, defaults};
 const DEFAULT_SYNC_TIMEOUT: Duration = Duration::from_secs(2 * 60);
 const DEFAULT_SYNC_INTERVAL: Duration = Duration::from_secs(5 * 60);
 const DEFAULT_SYNC_FREQUENCY: Duration = Duration::from_secs(60);
+const DEFAULT_SYNC_DELTA_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_SYNC_TOMBSTONE_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
 pub enum ConfigProtocol {
@@ -422,12 +424,12 @@ impl InitCommand {
                 timeout: DEFAULT_SYNC_TIMEOUT,
                 interval: DEFAULT_SYNC_INTERVAL,
                 frequency: DEFAULT_SYNC_FREQUENCY,
+                delta_timeout: DEFAULT_SYNC_DELTA_TIMEOUT,
+                tombstone_retention: DEFAULT_SYNC_TOMBSTONE_RETENTION,
             },
             StoreConfigFile::new("data".into()),
             BlobStoreConfig::new("blobs".into()),
-            ContextConfig {
-                client: client_config,
-            },
+            ContextConfig::new(client_config),
         );
 
         config.save(&path).await?;
@@ -55,6 +55,8 @@ impl RunCommand {
                 timeout: config.sync.timeout,
                 interval: config.sync.interval,
                 frequency: config.sync.frequency,
+                delta_timeout: config.sync.delta_timeout,
+                tombstone_retention: config.sync.tombstone_retention,
             },
             datastore: StoreConfig::new(path.join(config.datastore.path)),
             blobstore: BlobStoreConfig::new(path.join(config.blobstore.path)),
@@ -19,6 +19,7 @@ pub const CALIMERO_STREAM_PROTOCOL: StreamProtocol = StreamProtocol::new("/calim
 #[derive(Debug)]
 pub struct Stream {
     inner: Framed<BufStream<Compat<P2pStream>>, MessageCodec>,
+    transferred: u64,
 }
 
 impl Stream {
@@ -26,7 +27,22 @@ impl Stream {
     pub fn new(stream: P2pStream) -> Self {
         let stream = BufStream::new(stream.compat());
         let stream = Framed::new(stream, MessageCodec::new(MAX_MESSAGE_SIZE));
-        Self { inner: stream }
+        Self {
+            inner: stream,
+            transferred: 0,
+        }
+    }
+
+    /// Bytes of message data sent and received over the stream so far.
+    #[must_use]
+    pub const fn bytes_transferred(&self) -> u64 {
+        self.transferred
+    }
+
+    fn record(&mut self, len: usize) {
+        self.transferred = self
+            .transferred
+            .saturating_add(len.try_into().unwrap_or(u64::MAX));
     }
 }
 
@@ -34,7 +50,13 @@ impl FuturesStream for Stream {
     type Item = Result<Message<'static>, CodecError>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.inner.poll_next_unpin(cx)
+        let poll = self.inner.poll_next_unpin(cx);
+
+        if let Poll::Ready(Some(Ok(message))) = &poll {
+            self.record(message.data.len());
+        }
+
+        poll
     }
 }
 
@@ -46,6 +68,8 @@ impl<'a> FuturesSink<Message<'a>> for Stream {
     }
 
     fn start_send(mut self: Pin<&mut Self>, item: Message<'a>) -> Result<(), Self::Error> {
+        self.record(item.data.len());
+
         self.inner.start_send_unpin(item)
     }
 
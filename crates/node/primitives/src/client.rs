@@ -6,18 +6,21 @@ use calimero_crypto::SharedKey;
 use calimero_network_primitives::client::NetworkClient;
 use calimero_primitives::context::{Context, ContextId};
 use calimero_primitives::events::NodeEvent;
+use calimero_primitives::hash::Hash;
 use calimero_primitives::identity::{PrivateKey, PublicKey};
+use calimero_runtime::logic::VMLimits;
 use calimero_store::Store;
 use calimero_utils_actix::LazyRecipient;
-use eyre::{OptionExt, WrapErr};
+use eyre::{eyre, OptionExt, WrapErr};
 use futures_util::Stream;
 use libp2p::gossipsub::{IdentTopic, TopicHash};
+use libp2p::PeerId;
 use rand::Rng;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc, oneshot};
 use tracing::{debug, info};
 
 use crate::messages::NodeMessage;
-use crate::sync::BroadcastMessage;
+use crate::sync::{state_delta_payload, BlobRequest, BroadcastMessage, SyncOutcome, SyncRequest};
 
 mod alias;
 mod application;
@@ -30,15 +33,22 @@ pub struct NodeClient {
     network_client: NetworkClient,
     node_manager: LazyRecipient<NodeMessage>,
     event_sender: broadcast::Sender<NodeEvent>,
+    sync_sender: mpsc::Sender<SyncRequest>,
+    blob_sender: mpsc::Sender<BlobRequest>,
+    vm_limits: VMLimits,
 }
 
 impl NodeClient {
+    #[expect(clippy::too_many_arguments, reason = "Acceptable here")]
     pub fn new(
         datastore: Store,
         blobstore: BlobManager,
         network_client: NetworkClient,
         node_manager: LazyRecipient<NodeMessage>,
         event_sender: broadcast::Sender<NodeEvent>,
+        sync_sender: mpsc::Sender<SyncRequest>,
+        blob_sender: mpsc::Sender<BlobRequest>,
+        vm_limits: VMLimits,
     ) -> Self {
         Self {
             datastore,
@@ -46,6 +56,9 @@ impl NodeClient {
             network_client,
             node_manager,
             event_sender,
+            sync_sender,
+            blob_sender,
+            vm_limits,
         }
     }
 
@@ -82,13 +95,16 @@ impl NodeClient {
     pub async fn broadcast(
         &self,
         context: &Context,
+        parent_root_hash: &Hash,
         sender: &PublicKey,
+        private_key: &PrivateKey,
         sender_key: &PrivateKey,
         artifact: Vec<u8>,
     ) -> eyre::Result<()> {
         debug!(
             context_id=%context.id,
             %sender,
+            %parent_root_hash,
             root_hash=%context.root_hash,
             "Sending state delta"
         );
@@ -104,12 +120,23 @@ impl NodeClient {
             .encrypt(artifact, nonce)
             .ok_or_eyre("failed to encrypt artifact")?;
 
-        let payload = BroadcastMessage::StateDelta {
+        let signature = private_key.sign(&state_delta_payload(
+            &context.id,
+            sender,
+            parent_root_hash,
+            &context.root_hash,
+            &encrypted,
+            &nonce,
+        ));
+
+        let payload = BroadcastMessage::SignedStateDelta {
             context_id: context.id,
             author_id: *sender,
+            parent_root_hash: *parent_root_hash,
             root_hash: context.root_hash,
             artifact: encrypted.into(),
             nonce,
+            signature,
         };
 
         let payload = borsh::to_vec(&payload)?;
@@ -121,6 +148,29 @@ impl NodeClient {
         Ok(())
     }
 
+    /// Syncs the context right away, with the given peer or else any peer
+    /// subscribed to it, waiting for the sync to finish.
+    pub async fn sync(
+        &self,
+        context_id: &ContextId,
+        peer_id: Option<PeerId>,
+    ) -> eyre::Result<SyncOutcome> {
+        let (outcome, receiver) = oneshot::channel();
+
+        self.sync_sender
+            .send(SyncRequest {
+                context_id: *context_id,
+                peer_id,
+                outcome,
+            })
+            .await
+            .map_err(|_| eyre!("the sync manager is not running"))?;
+
+        receiver
+            .await
+            .wrap_err("the sync manager dropped the request")?
+    }
+
     pub fn send_event(&self, event: NodeEvent) -> eyre::Result<()> {
         // the caller doesn't care if there are no receivers
         // so we create a temporary receiver
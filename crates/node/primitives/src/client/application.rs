@@ -74,13 +74,23 @@ impl NodeClient {
         Ok(false)
     }
 
-    fn install_application(
+    async fn install_application(
         &self,
         blob_id: &BlobId,
         size: u64,
         source: &ApplicationSource,
         metadata: Vec<u8>,
     ) -> eyre::Result<ApplicationId> {
+        let Some(bytecode) = self.get_blob_bytes(blob_id).await? else {
+            bail!("fatal: application blob vanished before it could be installed");
+        };
+
+        if let Err(err) = calimero_runtime::prepare(&bytecode, &self.vm_limits) {
+            let _ignored = self.blobstore.discard(*blob_id).await?;
+
+            bail!("invalid application: {err}");
+        }
+
         let application = types::ApplicationMeta::new(
             key::BlobMeta::new(*blob_id),
             size,
@@ -104,6 +114,10 @@ impl NodeClient {
 
         let key = key::ApplicationMeta::new(application_id);
 
+        if !handle.has(&key)? {
+            let _ignored = self.retain_blob(blob_id)?;
+        }
+
         handle.put(&key, &application)?;
 
         Ok(application_id)
@@ -129,6 +143,7 @@ impl NodeClient {
         };
 
         self.install_application(&blob_id, size, &uri.as_str().parse()?, metadata)
+            .await
     }
 
     pub async fn install_application_from_url(
@@ -155,15 +170,67 @@ impl NodeClient {
             .await?;
 
         self.install_application(&blob_id, size, &uri, metadata)
+            .await
     }
 
-    pub fn uninstall_application(&self, application_id: &ApplicationId) -> eyre::Result<()> {
+    pub async fn uninstall_application(&self, application_id: &ApplicationId) -> eyre::Result<()> {
         let mut handle = self.datastore.handle();
 
         let key = key::ApplicationMeta::new(*application_id);
 
+        let Some(application) = handle.get(&key)? else {
+            return Ok(());
+        };
+
         handle.delete(&key)?;
 
+        let _ignored = self.release_blob(&application.bytecode.blob_id()).await?;
+
+        let _ignored = self.release_blob(&application.compiled.blob_id()).await?;
+
+        Ok(())
+    }
+
+    /// Records a context as a holder of the application's bytecode, so that it
+    /// is kept for as long as the context uses the application, even if the
+    /// application is uninstalled in the meantime. Returns the held bytecode,
+    /// to be kept in the context's meta for its release.
+    pub fn retain_application_bytecode(
+        &self,
+        application_id: &ApplicationId,
+    ) -> eyre::Result<Option<key::BlobMeta>> {
+        let handle = self.datastore.handle();
+
+        let key = key::ApplicationMeta::new(*application_id);
+
+        let Some(application) = handle.get(&key)? else {
+            return Ok(None);
+        };
+
+        let _ignored = self.retain_blob(&application.bytecode.blob_id())?;
+
+        Ok(Some(application.bytecode))
+    }
+
+    /// Releases a context's hold on its application's bytecode. Contexts
+    /// stored before the held bytecode was recorded find it through the
+    /// application instead, which only works while it is still installed.
+    pub async fn release_context_bytecode(&self, context: &types::ContextMeta) -> eyre::Result<()> {
+        let bytecode = match context.bytecode {
+            Some(bytecode) => Some(bytecode),
+            None => {
+                let handle = self.datastore.handle();
+
+                handle
+                    .get(&context.application)?
+                    .map(|application| application.bytecode)
+            }
+        };
+
+        if let Some(bytecode) = bytecode {
+            let _ignored = self.release_blob(&bytecode.blob_id()).await?;
+        }
+
         Ok(())
     }
 
@@ -191,7 +258,7 @@ impl NodeClient {
         Ok(applications)
     }
 
-    pub fn update_compiled_app(
+    pub async fn update_compiled_app(
         &self,
         application_id: &ApplicationId,
         compiled_blob_id: &BlobId,
@@ -204,10 +271,20 @@ impl NodeClient {
             bail!("application not found");
         };
 
+        let previous = application.compiled.blob_id();
+
+        if previous == *compiled_blob_id {
+            return Ok(());
+        }
+
         application.compiled = key::BlobMeta::new(*compiled_blob_id);
 
         handle.put(&key, &application)?;
 
+        let _ignored = self.retain_blob(compiled_blob_id)?;
+
+        let _ignored = self.release_blob(&previous).await?;
+
         Ok(())
     }
 }
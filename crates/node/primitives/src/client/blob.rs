@@ -1,15 +1,19 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use calimero_blobstore::{Blob, Size};
-use calimero_primitives::blobs::BlobId;
+use calimero_blobstore::{Blob, BlobError, Size};
+use calimero_primitives::blobs::{BlobId, BlobInfo};
+use calimero_primitives::context::ContextId;
 use calimero_primitives::hash::Hash;
-use eyre::bail;
+use calimero_store::key;
+use eyre::{eyre, WrapErr};
 use futures_util::AsyncRead;
 use tokio::sync::oneshot;
 
 use super::NodeClient;
 use crate::messages::get_blob_bytes::GetBlobBytesRequest;
 use crate::messages::NodeMessage;
+use crate::sync::BlobRequest;
 
 impl NodeClient {
     // todo! maybe this should be an actor method?
@@ -27,12 +31,25 @@ impl NodeClient {
             .put_sized(expected_size.map(Size::Exact), stream)
             .await?;
 
-        if matches!(expected_hash, Some(expected_hash) if hash != *expected_hash) {
-            bail!("fatal: blob hash mismatch");
-        }
+        let mismatch = if let Some(expected) = expected_hash.filter(|expected| hash != **expected) {
+            Some(BlobError::HashMismatch {
+                expected: *expected,
+                found: hash,
+            })
+        } else if let Some(expected) = expected_size.filter(|expected| size != *expected) {
+            Some(BlobError::SizeMismatch {
+                expected,
+                found: size,
+            })
+        } else {
+            None
+        };
+
+        if let Some(err) = mismatch {
+            // the blob is only kept if something else already holds it
+            let _ignored = self.blobstore.discard(blob_id).await?;
 
-        if matches!(expected_size, Some(expected_size) if size != expected_size) {
-            bail!("fatal: blob size mismatch");
+            return Err(err.into());
         }
 
         Ok((blob_id, size))
@@ -46,6 +63,22 @@ impl NodeClient {
         Ok(Some(stream))
     }
 
+    /// Streams `len` bytes of a blob, starting at `offset`.
+    ///
+    /// The range is clamped to the end of the blob.
+    pub fn get_blob_range(
+        &self,
+        blob_id: &BlobId,
+        offset: u64,
+        len: u64,
+    ) -> eyre::Result<Option<Blob>> {
+        self.blobstore.get_range(*blob_id, offset, len)
+    }
+
+    pub fn get_blob_info(&self, blob_id: &BlobId) -> eyre::Result<Option<BlobInfo>> {
+        self.blobstore.info(*blob_id)
+    }
+
     pub async fn get_blob_bytes(&self, blob_id: &BlobId) -> eyre::Result<Option<Arc<[u8]>>> {
         if **blob_id == [0; 32] {
             return Ok(None);
@@ -66,11 +99,154 @@ impl NodeClient {
         Ok(res.bytes)
     }
 
+    pub fn list_blobs(&self) -> eyre::Result<Vec<BlobInfo>> {
+        self.blobstore.list()
+    }
+
     pub fn has_blob(&self, blob_id: &BlobId) -> eyre::Result<bool> {
         self.blobstore.has(*blob_id)
     }
 
-    // pub fn remove_blob() -> eyre::Result<()> {
-    //     todo!("consider what this means for application blobs bound to contexts")
-    // }
+    /// Fetches a blob missing from the local blob store from the peers of the
+    /// context, returning whether it is available afterwards.
+    pub async fn fetch_blob(&self, context_id: &ContextId, blob_id: &BlobId) -> eyre::Result<bool> {
+        if self.has_blob(blob_id)? {
+            return Ok(true);
+        }
+
+        let (outcome, receiver) = oneshot::channel();
+
+        self.blob_sender
+            .send(BlobRequest {
+                context_id: *context_id,
+                blob_id: *blob_id,
+                outcome,
+            })
+            .await
+            .map_err(|_| eyre!("the sync manager is not running"))?;
+
+        receiver
+            .await
+            .wrap_err("the sync manager dropped the request")?
+    }
+
+    /// Removes a blob, regardless of whether it is still held by anything,
+    /// such as an installed application. Chunks shared with other blobs are
+    /// kept.
+    pub async fn remove_blob(&self, blob_id: &BlobId) -> eyre::Result<bool> {
+        self.blobstore.delete(*blob_id).await
+    }
+
+    /// Records a new holder of a blob, keeping it until released.
+    pub fn retain_blob(&self, blob_id: &BlobId) -> eyre::Result<bool> {
+        self.blobstore.retain(*blob_id)
+    }
+
+    /// Releases a holder of a blob, removing it once nothing holds it.
+    pub async fn release_blob(&self, blob_id: &BlobId) -> eyre::Result<bool> {
+        self.blobstore.release(*blob_id).await
+    }
+
+    /// Records a context as a holder of a blob written by its application,
+    /// keeping it until the context is deleted. A context holds each blob
+    /// once, however many times it is written.
+    pub fn retain_context_blob(
+        &self,
+        context_id: &ContextId,
+        blob_id: &BlobId,
+    ) -> eyre::Result<bool> {
+        let mut handle = self.datastore.handle();
+
+        let key = key::ContextBlob::new(*context_id, *blob_id);
+
+        if handle.has(&key)? {
+            return Ok(true);
+        }
+
+        if !self.retain_blob(blob_id)? {
+            return Ok(false);
+        }
+
+        handle.put(&key, &())?;
+
+        Ok(true)
+    }
+
+    /// Releases every blob held by the context, as written by its
+    /// application.
+    pub async fn release_context_blobs(&self, context_id: &ContextId) -> eyre::Result<()> {
+        let mut held = vec![];
+
+        {
+            let handle = self.datastore.handle();
+
+            let mut iter = handle.iter::<key::ContextBlob>()?;
+
+            let first = iter
+                .seek(key::ContextBlob::new(*context_id, [0; 32].into()))
+                .transpose();
+
+            for key in first.into_iter().chain(iter.keys()) {
+                let key = key?;
+
+                if key.context_id() != *context_id {
+                    break;
+                }
+
+                held.push(key);
+            }
+        }
+
+        let mut handle = self.datastore.handle();
+
+        for key in held {
+            handle.delete(&key)?;
+
+            let _ignored = self.release_blob(&key.blob_id()).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Counts the holders of blobs stored before holders were counted. These
+    /// are the installed applications, for both their bytecode and compiled
+    /// blobs, and the contexts, for the bytecode of the application they use.
+    pub fn backfill_blob_refs(&self) -> eyre::Result<usize> {
+        let handle = self.datastore.handle();
+
+        let mut holders = vec![];
+
+        let mut bytecode = BTreeMap::new();
+
+        {
+            let mut iter = handle.iter::<key::ApplicationMeta>()?;
+
+            for (key, application) in iter.entries() {
+                let (key, application) = (key?, application?);
+
+                holders.push(application.bytecode.blob_id());
+                holders.push(application.compiled.blob_id());
+
+                let _ignored =
+                    bytecode.insert(key.application_id(), application.bytecode.blob_id());
+            }
+        }
+
+        let mut iter = handle.iter::<key::ContextMeta>()?;
+
+        for context in iter.entries().map(|(_, context)| context) {
+            let context = context?;
+
+            let blob_id = match context.bytecode {
+                Some(blob) => Some(blob.blob_id()),
+                None => bytecode.get(&context.application.application_id()).copied(),
+            };
+
+            if let Some(blob_id) = blob_id {
+                holders.push(blob_id);
+            }
+        }
+
+        self.blobstore.backfill_refs(holders)
+    }
 }
@@ -1,5 +1,6 @@
 #![expect(single_use_lifetimes, reason = "borsh shenanigans")]
 
+use core::time::Duration;
 use std::borrow::Cow;
 
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -9,11 +10,16 @@ use calimero_primitives::blobs::BlobId;
 use calimero_primitives::context::ContextId;
 use calimero_primitives::hash::Hash;
 use calimero_primitives::identity::{PrivateKey, PublicKey};
+use libp2p::PeerId;
+use tokio::sync::oneshot;
 
 #[derive(Debug, BorshSerialize, BorshDeserialize)]
 #[non_exhaustive]
 #[expect(clippy::large_enum_variant, reason = "Of no consequence here")]
 pub enum BroadcastMessage<'a> {
+    /// A delta from a node predating signed deltas, which can't be
+    /// authenticated. It's kept so that such deltas still decode, and can be
+    /// rejected as unsigned rather than as malformed.
     StateDelta {
         context_id: ContextId,
         author_id: PublicKey,
@@ -21,6 +27,90 @@ pub enum BroadcastMessage<'a> {
         artifact: Cow<'a, [u8]>,
         nonce: Nonce,
     },
+    SignedStateDelta {
+        context_id: ContextId,
+        author_id: PublicKey,
+        /// The root hash the author's state had before the delta.
+        parent_root_hash: Hash,
+        root_hash: Hash,
+        artifact: Cow<'a, [u8]>,
+        nonce: Nonce,
+        /// Made by the author over [`state_delta_payload()`], with the
+        /// private key of their identity in the context.
+        signature: [u8; 64],
+    },
+}
+
+/// The bytes the author of a state delta signs, binding the encrypted
+/// artifact to its context, its author and the root hashes it moves between.
+#[must_use]
+pub fn state_delta_payload(
+    context_id: &ContextId,
+    author_id: &PublicKey,
+    parent_root_hash: &Hash,
+    root_hash: &Hash,
+    artifact: &[u8],
+    nonce: &Nonce,
+) -> Vec<u8> {
+    [
+        b"calimero:state-delta:".as_slice(),
+        &**context_id,
+        &**author_id,
+        &**parent_root_hash,
+        &**root_hash,
+        nonce,
+        artifact,
+    ]
+    .concat()
+}
+
+/// Which side of a handshake a party is on.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HandshakeRole {
+    /// The party which opened the stream.
+    Initiator,
+    /// The party which accepted it.
+    Responder,
+}
+
+/// What one party to a handshake claimed and sent.
+#[derive(Clone, Copy, Debug)]
+pub struct HandshakeParty {
+    /// The identity it claims in the context.
+    pub identity: PublicKey,
+    /// The peer it's connected as.
+    pub peer_id: PeerId,
+    /// The challenge it sent the other party.
+    pub challenge: [u8; 32],
+}
+
+/// The bytes a party signs to prove it holds the private key of the identity
+/// it claims in the context, covering what both parties claimed and sent, so
+/// that the proof is of no use on any other stream.
+#[must_use]
+pub fn handshake_payload(
+    context_id: &ContextId,
+    signer: HandshakeRole,
+    initiator: &HandshakeParty,
+    responder: &HandshakeParty,
+) -> Vec<u8> {
+    let signer = match signer {
+        HandshakeRole::Initiator => b"initiator:".as_slice(),
+        HandshakeRole::Responder => b"responder:".as_slice(),
+    };
+
+    [
+        b"calimero:sync-handshake:".as_slice(),
+        signer,
+        &**context_id,
+        &*initiator.identity,
+        &initiator.peer_id.to_bytes()[..],
+        &initiator.challenge,
+        &*responder.identity,
+        &responder.peer_id.to_bytes()[..],
+        &responder.challenge,
+    ]
+    .concat()
 }
 
 #[derive(Debug, BorshSerialize, BorshDeserialize)]
@@ -50,6 +140,11 @@ pub enum InitPayload {
         application_id: ApplicationId,
     },
     KeyShare,
+    /// Opens every stream, challenging the other party to prove it holds the
+    /// private key of the identity it claims, before anything else is shared.
+    Handshake {
+        challenge: [u8; 32],
+    },
 }
 
 #[derive(Debug, BorshSerialize, BorshDeserialize)]
@@ -58,4 +153,36 @@ pub enum MessagePayload<'a> {
     StateSync { artifact: Cow<'a, [u8]> },
     BlobShare { chunk: Cow<'a, [u8]> },
     KeyShare { sender_key: PrivateKey },
+    Handshake { signature: [u8; 64] },
+}
+
+/// A request for a context to be synced right away, rather than at the next
+/// interval.
+#[derive(Debug)]
+pub struct SyncRequest {
+    pub context_id: ContextId,
+    /// The peer to sync with, or else any peer subscribed to the context.
+    pub peer_id: Option<PeerId>,
+    pub outcome: oneshot::Sender<eyre::Result<SyncOutcome>>,
+}
+
+/// A request for a blob missing from the local blob store to be fetched from
+/// the peers of a context.
+#[derive(Debug)]
+pub struct BlobRequest {
+    pub context_id: ContextId,
+    pub blob_id: BlobId,
+    /// Whether the blob was found, or else why it couldn't be fetched.
+    pub outcome: oneshot::Sender<eyre::Result<bool>>,
+}
+
+/// The result of a sync which succeeded.
+#[derive(Clone, Copy, Debug)]
+pub struct SyncOutcome {
+    pub peer_id: PeerId,
+    pub duration: Duration,
+    pub root_hash_before: Hash,
+    pub root_hash_after: Hash,
+    /// Bytes of messages exchanged with the peer.
+    pub bytes_transferred: u64,
 }
@@ -2,7 +2,7 @@ use actix::{AsyncContext, Handler, Message, WrapFuture};
 use calimero_context_primitives::client::ContextClient;
 use calimero_crypto::{Nonce, SharedKey};
 use calimero_network_primitives::messages::NetworkEvent;
-use calimero_node_primitives::sync::BroadcastMessage;
+use calimero_node_primitives::sync::{state_delta_payload, BroadcastMessage};
 use calimero_primitives::context::ContextId;
 use calimero_primitives::hash::Hash;
 use calimero_primitives::identity::PublicKey;
@@ -10,10 +10,13 @@ use eyre::bail;
 use libp2p::PeerId;
 use tracing::{debug, info, warn};
 
-use crate::sync::SyncManager;
-use crate::utils::choose_stream;
+use crate::sync::{Delta, SyncManager};
 use crate::NodeManager;
 
+#[cfg(test)]
+#[path = "tests/network_event.rs"]
+mod tests;
+
 impl Handler<NetworkEvent> for NodeManager {
     type Result = <NetworkEvent as Message>::Result;
 
@@ -92,26 +95,25 @@ impl Handler<NetworkEvent> for NodeManager {
                     BroadcastMessage::StateDelta {
                         context_id,
                         author_id,
-                        root_hash,
-                        artifact,
-                        nonce,
+                        ..
                     } => {
+                        warn!(%author_id, %context_id, %source, "Rejected unsigned state delta");
+                    }
+                    message @ BroadcastMessage::SignedStateDelta { .. } => {
+                        let Some(delta) = verify_state_delta(message) else {
+                            warn!(%source, "Rejected state delta with invalid signature");
+
+                            return;
+                        };
+
                         let context_client = self.context_client.clone();
                         let sync_manager = self.sync_manager.clone();
 
                         let _ignored = ctx.spawn(
                             async move {
-                                if let Err(err) = handle_state_delta(
-                                    context_client,
-                                    sync_manager,
-                                    source,
-                                    context_id,
-                                    author_id,
-                                    root_hash,
-                                    artifact.into_owned(),
-                                    nonce,
-                                )
-                                .await
+                                if let Err(err) =
+                                    handle_state_delta(context_client, sync_manager, source, delta)
+                                        .await
                                 {
                                     warn!(?err, "Failed to handle state delta");
                                 }
@@ -131,7 +133,7 @@ impl Handler<NetworkEvent> for NodeManager {
 
                 let _ignored = ctx.spawn(
                     async move {
-                        sync_manager.handle_opened_stream(stream).await;
+                        sync_manager.handle_opened_stream(peer_id, stream).await;
 
                         debug!(%peer_id, "Handled opened stream");
                     }
@@ -142,15 +144,70 @@ impl Handler<NetworkEvent> for NodeManager {
     }
 }
 
-async fn handle_state_delta(
-    context_client: ContextClient,
-    sync_manager: SyncManager,
-    source: PeerId,
+/// A state delta whose signature has been checked to be its author's.
+#[derive(Debug)]
+struct SignedStateDelta {
     context_id: ContextId,
     author_id: PublicKey,
+    parent_root_hash: Hash,
     root_hash: Hash,
     artifact: Vec<u8>,
     nonce: Nonce,
+}
+
+/// Returns the delta in a message if it was signed by the author it claims.
+///
+/// The sender key is shared by every member of a context, so only the
+/// signature proves a delta was authored by who it claims to be.
+fn verify_state_delta(message: BroadcastMessage<'_>) -> Option<SignedStateDelta> {
+    let BroadcastMessage::SignedStateDelta {
+        context_id,
+        author_id,
+        parent_root_hash,
+        root_hash,
+        artifact,
+        nonce,
+        signature,
+    } = message
+    else {
+        return None;
+    };
+
+    let payload = state_delta_payload(
+        &context_id,
+        &author_id,
+        &parent_root_hash,
+        &root_hash,
+        &artifact,
+        &nonce,
+    );
+
+    if !author_id.verify(&payload, &signature) {
+        return None;
+    }
+
+    Some(SignedStateDelta {
+        context_id,
+        author_id,
+        parent_root_hash,
+        root_hash,
+        artifact: artifact.into_owned(),
+        nonce,
+    })
+}
+
+async fn handle_state_delta(
+    context_client: ContextClient,
+    sync_manager: SyncManager,
+    source: PeerId,
+    SignedStateDelta {
+        context_id,
+        author_id,
+        parent_root_hash,
+        root_hash,
+        artifact,
+        nonce,
+    }: SignedStateDelta,
 ) -> eyre::Result<()> {
     let Some(context) = context_client.get_context(&context_id)? else {
         bail!("context '{}' not found", context_id);
@@ -158,6 +215,7 @@ async fn handle_state_delta(
 
     debug!(
         %context_id, %author_id,
+        %parent_root_hash,
         expected_root_hash = %root_hash,
         current_root_hash = %context.root_hash,
         "Received state delta"
@@ -168,13 +226,21 @@ async fn handle_state_delta(
         return Ok(());
     }
 
+    if !context_client.has_member(&context_id, &author_id)? {
+        warn!(%author_id, %context_id, "Rejected state delta from unknown author");
+
+        return Ok(());
+    }
+
     let Some(sender_key) = context_client
         .get_identity(&context_id, &author_id)?
         .and_then(|i| i.sender_key)
     else {
         debug!(%author_id, %context_id, "Missing sender key, initiating sync");
 
-        return sync_manager.initiate_sync(context_id, source).await;
+        let _ignored = sync_manager.sync_with(context_id, source).await?;
+
+        return Ok(());
     };
 
     let shared_key = SharedKey::from_sk(&sender_key);
@@ -182,32 +248,20 @@ async fn handle_state_delta(
     let Some(artifact) = shared_key.decrypt(artifact, nonce) else {
         debug!(%author_id, %context_id, "State delta decryption failed, initiating sync");
 
-        return sync_manager.initiate_sync(context_id, source).await;
-    };
-
-    let identities = context_client.context_members(&context_id, Some(true));
+        let _ignored = sync_manager.sync_with(context_id, source).await?;
 
-    let Some((our_identity, _)) = choose_stream(identities, &mut rand::thread_rng())
-        .await
-        .transpose()?
-    else {
-        bail!("no owned identities found for context: {}", context.id);
+        return Ok(());
     };
 
-    let outcome = context_client
-        .execute(
-            &context_id,
-            &our_identity,
-            "__calimero_sync_next".to_owned(),
-            artifact,
-            vec![],
-            None,
-        )
-        .await?;
-
-    if outcome.root_hash != root_hash {
-        return sync_manager.initiate_sync(context_id, source).await;
-    }
+    let delta = Delta {
+        author_id,
+        parent_root_hash,
+        root_hash,
+        artifact,
+        source,
+    };
 
-    Ok(())
+    sync_manager
+        .receive_delta(context_id, context.root_hash, delta)
+        .await
 }
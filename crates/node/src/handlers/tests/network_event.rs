@@ -0,0 +1,115 @@
+use std::borrow::Cow;
+
+use calimero_primitives::identity::PrivateKey;
+use rand::thread_rng;
+
+use super::*;
+
+const CONTEXT_ID: [u8; 32] = [1; 32];
+
+const NONCE: Nonce = [2; 12];
+
+fn signed_delta(author: &PublicKey, signer: &PrivateKey) -> BroadcastMessage<'static> {
+    let context_id = ContextId::from(CONTEXT_ID);
+    let (parent_root_hash, root_hash) = (Hash::from([3; 32]), Hash::from([4; 32]));
+    let artifact = vec![5; 16];
+
+    let signature = signer.sign(&state_delta_payload(
+        &context_id,
+        author,
+        &parent_root_hash,
+        &root_hash,
+        &artifact,
+        &NONCE,
+    ));
+
+    BroadcastMessage::SignedStateDelta {
+        context_id,
+        author_id: *author,
+        parent_root_hash,
+        root_hash,
+        artifact: Cow::Owned(artifact),
+        nonce: NONCE,
+        signature,
+    }
+}
+
+#[test]
+fn delta_signed_by_its_author_is_accepted() {
+    let alice = PrivateKey::random(&mut thread_rng());
+
+    let delta = verify_state_delta(signed_delta(&alice.public_key(), &alice))
+        .expect("delta should be accepted");
+
+    assert_eq!(delta.author_id, alice.public_key());
+    assert_eq!(delta.root_hash, Hash::from([4; 32]));
+    assert_eq!(delta.artifact, vec![5; 16]);
+}
+
+#[test]
+fn forged_delta_is_rejected() {
+    let (alice, mallory) = (
+        PrivateKey::random(&mut thread_rng()),
+        PrivateKey::random(&mut thread_rng()),
+    );
+
+    // mallory shares alice's context, and so its sender key, but not her
+    // identity's private key
+    let forged = signed_delta(&alice.public_key(), &mallory);
+
+    assert!(verify_state_delta(forged).is_none());
+}
+
+#[test]
+fn tampered_delta_is_rejected() {
+    let alice = PrivateKey::random(&mut thread_rng());
+
+    let BroadcastMessage::SignedStateDelta {
+        context_id,
+        author_id,
+        parent_root_hash,
+        artifact,
+        nonce,
+        signature,
+        ..
+    } = signed_delta(&alice.public_key(), &alice)
+    else {
+        unreachable!();
+    };
+
+    let tampered = BroadcastMessage::SignedStateDelta {
+        context_id,
+        author_id,
+        parent_root_hash,
+        root_hash: Hash::from([6; 32]),
+        artifact,
+        nonce,
+        signature,
+    };
+
+    assert!(verify_state_delta(tampered).is_none());
+}
+
+#[test]
+fn unsigned_delta_decodes_and_is_rejected() {
+    let alice = PrivateKey::random(&mut thread_rng());
+
+    // laid out as nodes predating signed deltas send them
+    let mut bytes = vec![0];
+    bytes.extend_from_slice(&CONTEXT_ID);
+    bytes.extend_from_slice(&*alice.public_key());
+    bytes.extend_from_slice(&[4; 32]);
+    bytes.extend_from_slice(&16_u32.to_le_bytes());
+    bytes.extend_from_slice(&[5; 16]);
+    bytes.extend_from_slice(&NONCE);
+
+    let message: BroadcastMessage<'_> =
+        borsh::from_slice(&bytes).expect("legacy delta should decode");
+
+    assert!(matches!(
+        message,
+        BroadcastMessage::StateDelta { author_id, .. } if author_id == alice.public_key()
+    ));
+
+    assert!(verify_state_delta(message).is_none());
+}
@@ -7,6 +7,7 @@
 use std::collections::BTreeMap;
 use std::pin::pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 use actix::{Actor, AsyncContext, WrapFuture};
 use calimero_blobstore::BlobManager;
@@ -15,7 +16,7 @@ use calimero_node_primitives::client::NodeClient;
 use calimero_primitives::blobs::BlobId;
 use futures_util::StreamExt;
 use tokio::sync::Mutex;
-use tracing::error;
+use tracing::{debug, error};
 
 pub mod handlers;
 pub mod interactive_cli;
@@ -26,6 +27,13 @@ mod utils;
 pub use run::{start, NodeConfig};
 use sync::SyncManager;
 
+/// How often blob chunks that are no longer referenced get cleaned up.
+const BLOB_GC_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// How often the tombstones of deleted entities past their retention period
+/// get purged from each context's state.
+const TOMBSTONE_GC_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 #[derive(Debug)]
 pub struct NodeManager {
     blobstore: BlobManager,
@@ -81,5 +89,30 @@ impl Actor for NodeManager {
             }
             .into_actor(self),
         );
+
+        let _ignored = ctx.run_interval(BLOB_GC_INTERVAL, |act, ctx| {
+            let blobstore = act.blobstore.clone();
+
+            let _ignored = ctx.spawn(
+                async move {
+                    match blobstore.collect_garbage().await {
+                        Ok(removed) => debug!(%removed, "Collected unreferenced blobs"),
+                        Err(err) => error!("Failed to collect unreferenced blobs: {}", err),
+                    }
+                }
+                .into_actor(act),
+            );
+        });
+
+        let _ignored = ctx.run_interval(TOMBSTONE_GC_INTERVAL, |act, ctx| {
+            let sync_manager = act.sync_manager.clone();
+
+            let _ignored = ctx.spawn(
+                async move {
+                    sync_manager.purge_tombstones().await;
+                }
+                .into_actor(act),
+            );
+        });
     }
 }
@@ -109,14 +109,29 @@ pub async fn start(config: NodeConfig) -> eyre::Result<()> {
 
     let (event_sender, _) = broadcast::channel(32);
 
+    let (sync_sender, sync_receiver) = mpsc::channel(16);
+
+    let (blob_sender, blob_receiver) = mpsc::channel(16);
+
     let node_client = NodeClient::new(
         datastore.clone(),
         blobstore.clone(),
         network_client.clone(),
         node_recipient.clone(),
         event_sender,
+        sync_sender,
+        blob_sender,
+        config.context.limits(),
     );
 
+    // blobs stored before holders were counted must be counted before anything
+    // gets the chance to release them
+    let backfilled = node_client.backfill_blob_refs()?;
+
+    if backfilled > 0 {
+        info!(%backfilled, "Counted the holders of existing blobs");
+    }
+
     let external_client = ExternalClient::from_config(&config.context.client);
 
     let context_client = ContextClient::new(
@@ -126,11 +141,19 @@ pub async fn start(config: NodeConfig) -> eyre::Result<()> {
         context_recipient.clone(),
     );
 
+    // contexts created before their usage was tracked would otherwise be
+    // counted as empty, and let grow past the quota
+    let backfilled = context_client.backfill_context_usage()?;
+
+    if backfilled > 0 {
+        info!(%backfilled, "Counted the storage used by existing contexts");
+    }
+
     let context_manager = ContextManager::new(
         datastore.clone(),
         node_client.clone(),
         context_client.clone(),
-        config.context.client.clone(),
+        config.context.clone(),
     );
 
     let _ignored = Actor::start_in_arbiter(&new_arbiter().await?, move |ctx| {
@@ -140,6 +163,7 @@ pub async fn start(config: NodeConfig) -> eyre::Result<()> {
 
     let sync_manager = SyncManager::new(
         config.sync,
+        peer_id,
         node_client.clone(),
         context_client.clone(),
         network_client.clone(),
@@ -167,7 +191,7 @@ pub async fn start(config: NodeConfig) -> eyre::Result<()> {
 
     let config = Arc::new(config);
 
-    let mut sync = pin!(sync_manager.start());
+    let mut sync = pin!(sync_manager.start(sync_receiver, blob_receiver));
     let mut server = tokio::spawn(server);
 
     let (lines_tx, mut lines) = mpsc::channel(1);
@@ -1,4 +1,3 @@
-use std::collections::{hash_map, HashMap};
 use std::pin::pin;
 
 use calimero_context_primitives::client::ContextClient;
@@ -6,42 +5,60 @@ use calimero_crypto::{Nonce, SharedKey};
 use calimero_network_primitives::client::NetworkClient;
 use calimero_network_primitives::stream::{Message, Stream};
 use calimero_node_primitives::client::NodeClient;
-use calimero_node_primitives::sync::{InitPayload, StreamMessage};
+use calimero_node_primitives::sync::{
+    BlobRequest, InitPayload, StreamMessage, SyncOutcome, SyncRequest,
+};
 use calimero_primitives::context::ContextId;
-use eyre::{bail, OptionExt, WrapErr};
+use eyre::{bail, eyre, OptionExt, WrapErr};
 use futures_util::stream::FuturesUnordered;
 use futures_util::{FutureExt, SinkExt, StreamExt, TryStreamExt};
 use libp2p::gossipsub::TopicHash;
 use libp2p::PeerId;
 use rand::seq::SliceRandom;
-use tokio::time::{self, timeout, timeout_at, Instant, MissedTickBehavior};
+use tokio::sync::mpsc;
+use tokio::time::{self, timeout, Instant, MissedTickBehavior};
 use tracing::{debug, error};
 
 use crate::utils::choose_stream;
 
 mod blobs;
+mod delta;
+mod handshake;
+mod history;
 mod key;
+mod schedule;
 mod state;
+mod tombstones;
+
+pub(crate) use delta::Delta;
+use delta::DeltaBuffer;
+use handshake::Session;
+use schedule::Schedule;
 
 #[derive(Copy, Clone, Debug)]
 pub struct SyncConfig {
     pub timeout: time::Duration,
     pub interval: time::Duration,
     pub frequency: time::Duration,
+    /// How long a state delta is held waiting for its parent before falling
+    /// back to a full sync.
+    pub delta_timeout: time::Duration,
+    /// How long the tombstones of deleted entities are kept, which bounds how
+    /// long a node can be offline and still catch up on deletions.
+    pub tombstone_retention: time::Duration,
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct SyncManager {
     sync_config: SyncConfig,
+    /// The peer this node is connected as.
+    peer_id: PeerId,
 
     node_client: NodeClient,
     context_client: ContextClient,
     network_client: NetworkClient,
-}
 
-#[derive(Debug)]
-struct SyncState {
-    last_sync: Option<Instant>,
+    deltas: DeltaBuffer,
 }
 
 #[derive(Default)]
@@ -78,54 +95,110 @@ impl Sequencer {
 impl SyncManager {
     pub fn new(
         sync_config: SyncConfig,
+        peer_id: PeerId,
         node_client: NodeClient,
         context_client: ContextClient,
         network_client: NetworkClient,
     ) -> Self {
         Self {
             sync_config,
+            peer_id,
             node_client,
             context_client,
             network_client,
+            deltas: DeltaBuffer::default(),
         }
     }
 
-    pub async fn start(self) {
+    pub async fn start(
+        self,
+        mut requests: mpsc::Receiver<SyncRequest>,
+        mut blob_requests: mpsc::Receiver<BlobRequest>,
+    ) {
         let mut next_sync = time::interval(self.sync_config.frequency);
 
         next_sync.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
-        let mut state = HashMap::<_, SyncState>::new();
+        let mut state = Schedule::default();
 
         let mut futs = FuturesUnordered::new();
 
-        let advance = async |futs: &mut FuturesUnordered<_>, state: &mut HashMap<_, SyncState>| {
-            let (context_id, start, result) = futs.next().await?;
+        let schedule = |context_id, peer_id| {
+            let start = Instant::now();
+            let deadline = start.checked_add(self.sync_config.timeout)?;
 
-            let now = Instant::now();
+            let fut = self
+                .tracked_sync(context_id, peer_id, deadline)
+                .map(move |res| (context_id, start, res));
 
-            let _ignored = state
-                .entry(context_id)
-                .and_modify(|state| state.last_sync = Some(now));
+            Some(fut)
+        };
 
-            let took = Instant::saturating_duration_since(&now, start);
+        let advance = async |futs: &mut FuturesUnordered<_>, state: &mut Schedule| {
+            let (context_id, start, result) = futs.next().await?;
 
-            if let Ok(_) = result {
-                debug!(%context_id, ?took, "Sync finished");
-            } else {
-                error!(%context_id, ?took, "Sync timed out");
-            }
+            let took = start.elapsed();
+
+            let result = match result {
+                Ok(Ok(result)) => {
+                    debug!(%context_id, ?took, peer_id=%result.peer_id, "Sync finished");
+                    Ok(result)
+                }
+                Ok(Err(err)) => {
+                    debug!(%context_id, ?took, %err, "Sync failed");
+                    Err(err)
+                }
+                Err(_) => {
+                    error!(%context_id, ?took, "Sync timed out");
+                    Err(eyre!("sync timed out after {:?}", took))
+                }
+            };
+
+            state.finish(context_id, &result);
 
             Some(())
         };
 
         loop {
-            tokio::select! {
-                _ = next_sync.tick() => {}
+            let request = tokio::select! {
+                _ = next_sync.tick() => None,
                 Some(()) = async {
                     loop { advance(&mut futs, &mut state).await? }
-                } => {},
-                // todo! allow explicit sync request
+                } => None,
+                Some(request) = requests.recv() => Some(request),
+                Some(request) = blob_requests.recv() => {
+                    self.spawn_blob_fetch(request);
+
+                    continue;
+                }
+            };
+
+            if let Some(SyncRequest {
+                context_id,
+                peer_id,
+                outcome,
+            }) = request
+            {
+                debug!(%context_id, ?peer_id, "Performing requested sync");
+
+                // requested syncs don't wait for the interval, but join the
+                // context's sync if one is already running
+                if !state.request(context_id, outcome) {
+                    continue;
+                }
+
+                let Some(fut) = schedule(context_id, peer_id) else {
+                    error!(
+                        timeout=?self.sync_config.timeout,
+                        "Unable to determine when to timeout sync procedure"
+                    );
+
+                    return;
+                };
+
+                futs.push(fut);
+
+                continue;
             }
 
             debug!("Performing interval sync");
@@ -143,46 +216,14 @@ impl SyncManager {
                     }
                 };
 
-                match state.entry(context_id) {
-                    hash_map::Entry::Occupied(state) => {
-                        let state = state.into_mut();
-
-                        let Some(last_sync) = state.last_sync else {
-                            debug!(
-                                %context_id,
-                                "Sync already in progress"
-                            );
-
-                            continue;
-                        };
-
-                        let minimum = self.sync_config.interval;
-                        let time_since = last_sync.elapsed();
-
-                        if time_since < minimum {
-                            debug!(%context_id, ?time_since, ?minimum, "Skipping sync, last one was too recent");
-
-                            continue;
-                        }
-
-                        let _ignored = state.last_sync.take();
-                    }
-                    hash_map::Entry::Vacant(state) => {
-                        debug!(
-                            %context_id,
-                            "Syncing for the first time"
-                        );
-
-                        let _ignored = state.insert(SyncState { last_sync: None });
-                    }
-                };
+                if !state.due(context_id, self.sync_config.interval) {
+                    continue;
+                }
 
                 debug!(%context_id, "Scheduled sync");
 
-                let start = Instant::now();
-                let Some(deadline) = start.checked_add(self.sync_config.timeout) else {
+                let Some(fut) = schedule(context_id, None) else {
                     error!(
-                        ?start,
                         timeout=?self.sync_config.timeout,
                         "Unable to determine when to timeout sync procedure"
                     );
@@ -192,9 +233,6 @@ impl SyncManager {
                     return;
                 };
 
-                let fut = timeout_at(deadline, self.perform_interval_sync(context_id))
-                    .map(move |res| (context_id, start, res));
-
                 futs.push(fut);
 
                 if futs.len() == 30 {
@@ -204,26 +242,61 @@ impl SyncManager {
         }
     }
 
-    async fn perform_interval_sync(&self, context_id: ContextId) {
-        let peers = self
-            .network_client
-            .mesh_peers(TopicHash::from_raw(context_id))
-            .await;
+    /// Syncs the context with the given peer, or else with the first of the
+    /// peers subscribed to it that we manage to sync with.
+    async fn perform_sync(
+        &self,
+        context_id: ContextId,
+        peer_id: Option<PeerId>,
+    ) -> eyre::Result<SyncOutcome> {
+        let start = Instant::now();
+
+        let Some(context) = self.context_client.get_context(&context_id)? else {
+            bail!("context not found: {}", context_id);
+        };
+
+        let peers = match peer_id {
+            Some(peer_id) => vec![peer_id],
+            None => {
+                self.network_client
+                    .mesh_peers(TopicHash::from_raw(context_id))
+                    .await
+            }
+        };
 
         if peers.is_empty() {
-            debug!(%context_id, "No peers to sync with");
+            bail!("no peers to sync with");
         }
 
         for peer_id in peers.choose_multiple(&mut rand::thread_rng(), peers.len()) {
             debug!(%context_id, %peer_id, "Attempting to sync with peer");
 
-            let Err(err) = self.initiate_sync(context_id, *peer_id).await else {
-                debug!(%context_id, %peer_id, "Sync with peer successfully finished");
-                break;
+            let bytes_transferred = match self.initiate_sync(context_id, *peer_id).await {
+                Ok(bytes_transferred) => bytes_transferred,
+                Err(err) => {
+                    error!(%context_id, %peer_id, %err, "Failed to sync with peer, trying another..");
+
+                    continue;
+                }
             };
 
-            error!(%context_id, %peer_id, %err, "Failed to sync with peer, trying another..");
+            debug!(%context_id, %peer_id, "Sync with peer successfully finished");
+
+            let root_hash_after = self
+                .context_client
+                .get_context(&context_id)?
+                .map_or(context.root_hash, |context| context.root_hash);
+
+            return Ok(SyncOutcome {
+                peer_id: *peer_id,
+                duration: start.elapsed(),
+                root_hash_before: context.root_hash,
+                root_hash_after,
+                bytes_transferred,
+            });
         }
+
+        bail!("failed to sync with any of {} peer(s)", peers.len())
     }
 
     async fn send(
@@ -276,11 +349,8 @@ impl SyncManager {
         Ok(Some(decoded))
     }
 
-    pub async fn initiate_sync(
-        &self,
-        context_id: ContextId,
-        chosen_peer: PeerId,
-    ) -> eyre::Result<()> {
+    /// Syncs the context with the peer, returning the bytes exchanged with it.
+    async fn initiate_sync(&self, context_id: ContextId, chosen_peer: PeerId) -> eyre::Result<u64> {
         let mut context = self
             .context_client
             .sync_context_config(context_id, None)
@@ -301,27 +371,39 @@ impl SyncManager {
 
         let mut stream = self.network_client.open_stream(chosen_peer).await?;
 
-        self.initiate_key_share_process(&mut context, our_identity, &mut stream)
+        let their_identity = self
+            .initiate_handshake(&context, our_identity, chosen_peer, &mut stream)
+            .await?;
+
+        self.initiate_key_share_process(&mut context, our_identity, their_identity, &mut stream)
             .await?;
 
         if !self.node_client.has_blob(&application.blob.bytecode)? {
             self.initiate_blob_share_process(
                 &context,
                 our_identity,
+                their_identity,
                 application.blob.bytecode,
-                application.size,
+                Some(application.size),
                 &mut stream,
             )
             .await?;
         }
 
-        self.initiate_state_sync_process(&mut context, our_identity, &mut stream)
-            .await
+        self.initiate_state_sync_process(&mut context, our_identity, their_identity, &mut stream)
+            .await?;
+
+        Ok(stream.bytes_transferred())
     }
 
-    pub async fn handle_opened_stream(&self, mut stream: Box<Stream>) {
+    pub async fn handle_opened_stream(&self, their_peer_id: PeerId, mut stream: Box<Stream>) {
+        let mut session = None;
+
         loop {
-            match self.internal_handle_opened_stream(&mut stream).await {
+            match self
+                .internal_handle_opened_stream(their_peer_id, &mut stream, &mut session)
+                .await
+            {
                 Ok(None) => break,
                 Ok(Some(())) => {}
                 Err(err) => {
@@ -338,7 +420,12 @@ impl SyncManager {
         }
     }
 
-    async fn internal_handle_opened_stream(&self, stream: &mut Stream) -> eyre::Result<Option<()>> {
+    async fn internal_handle_opened_stream(
+        &self,
+        their_peer_id: PeerId,
+        stream: &mut Stream,
+        session: &mut Option<Session>,
+    ) -> eyre::Result<Option<()>> {
         let Some(message) = self.recv(stream, None).await? else {
             return Ok(None);
         };
@@ -360,39 +447,72 @@ impl SyncManager {
             bail!("context not found: {}", context_id);
         };
 
-        let mut updated = None;
+        let our_identity = match (payload, *session) {
+            (InitPayload::Handshake { challenge }, None) => {
+                if !self
+                    .context_client
+                    .has_member(&context_id, &their_identity)?
+                {
+                    let _ignored = self
+                        .context_client
+                        .sync_context_config(context_id, None)
+                        .await?;
+
+                    if !self
+                        .context_client
+                        .has_member(&context_id, &their_identity)?
+                    {
+                        bail!(
+                            "unknown context member {} in context {}",
+                            their_identity,
+                            context_id
+                        );
+                    }
+                }
 
-        if !self
-            .context_client
-            .has_member(&context_id, &their_identity)?
-        {
-            updated = Some(
-                self.context_client
-                    .sync_context_config(context_id, None)
-                    .await?,
-            );
+                let identities = self.context_client.context_members(&context.id, Some(true));
 
-            if !self
-                .context_client
-                .has_member(&context_id, &their_identity)?
-            {
-                bail!(
-                    "unknown context member {} in context {}",
+                let Some((our_identity, _)) = choose_stream(identities, &mut rand::thread_rng())
+                    .await
+                    .transpose()?
+                else {
+                    bail!("no owned identities found for context: {}", context.id);
+                };
+
+                self.handle_handshake_request(
+                    &context,
+                    our_identity,
                     their_identity,
-                    context_id
-                );
-            }
-        }
+                    their_peer_id,
+                    challenge,
+                    stream,
+                )
+                .await?;
 
-        let identities = self.context_client.context_members(&context.id, Some(true));
+                *session = Some(Session {
+                    context_id,
+                    our_identity,
+                    their_identity,
+                });
 
-        let Some((our_identity, _)) = choose_stream(identities, &mut rand::thread_rng())
-            .await
-            .transpose()?
-        else {
-            bail!("no owned identities found for context: {}", context.id);
+                return Ok(Some(()));
+            }
+            (_, None) => bail!("expected handshake before {:?}", payload),
+            (_, Some(session)) => {
+                if session.context_id != context_id || session.their_identity != their_identity {
+                    bail!(
+                        "{} in context {} is not the party authenticated on this stream",
+                        their_identity,
+                        context_id
+                    );
+                }
+
+                session.our_identity
+            }
         };
 
+        let mut updated = None;
+
         match payload {
             InitPayload::KeyShare => {
                 self.handle_key_share_request(&context, our_identity, their_identity, stream, nonce)
@@ -424,16 +544,28 @@ impl SyncManager {
                     context = updated;
                 }
 
-                self.handle_state_sync_request(
-                    &mut context,
-                    our_identity,
-                    their_identity,
-                    their_root_hash,
-                    their_application_id,
-                    stream,
-                    nonce,
-                )
-                .await?
+                let bytes_before = stream.bytes_transferred();
+
+                let sync = async {
+                    self.handle_state_sync_request(
+                        &mut context,
+                        our_identity,
+                        their_identity,
+                        their_root_hash,
+                        their_application_id,
+                        stream,
+                        nonce,
+                    )
+                    .await?;
+
+                    Ok::<_, eyre::Report>(stream.bytes_transferred().saturating_sub(bytes_before))
+                };
+
+                self.tracked_incoming_sync(context_id, their_peer_id, sync)
+                    .await?
+            }
+            InitPayload::Handshake { .. } => {
+                bail!("unexpected handshake on an authenticated stream")
             }
         };
 
@@ -1,25 +1,109 @@
 use calimero_crypto::{Nonce, SharedKey, NONCE_LEN};
 use calimero_network_primitives::stream::Stream;
-use calimero_node_primitives::sync::{InitPayload, MessagePayload, StreamMessage};
+use calimero_node_primitives::sync::{BlobRequest, InitPayload, MessagePayload, StreamMessage};
 use calimero_primitives::blobs::BlobId;
-use calimero_primitives::context::Context;
+use calimero_primitives::context::{Context, ContextId};
 use calimero_primitives::identity::PublicKey;
-use eyre::{bail, OptionExt};
+use eyre::{bail, eyre, OptionExt};
 use futures_util::stream::poll_fn;
 use futures_util::TryStreamExt;
+use libp2p::gossipsub::TopicHash;
+use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng};
 use tokio::sync::mpsc;
-use tracing::{debug, warn};
+use tokio::time::timeout;
+use tracing::debug;
 
 use super::{Sequencer, SyncManager};
+use crate::utils::choose_stream;
 
 impl SyncManager {
+    /// Fetches the blob in the background, answering the request once done.
+    pub(super) fn spawn_blob_fetch(&self, request: BlobRequest) {
+        let this = self.clone();
+
+        let _ignored = tokio::spawn(async move {
+            let BlobRequest {
+                context_id,
+                blob_id,
+                outcome,
+            } = request;
+
+            let result = timeout(
+                this.sync_config.timeout,
+                this.fetch_blob(context_id, blob_id),
+            )
+            .await
+            .unwrap_or_else(|_| Err(eyre!("timed out fetching blob {}", blob_id)));
+
+            let _ignored = outcome.send(result);
+        });
+    }
+
+    /// Fetches a blob from the first of the peers subscribed to the context
+    /// that has it, returning whether any of them did.
+    async fn fetch_blob(&self, context_id: ContextId, blob_id: BlobId) -> eyre::Result<bool> {
+        if self.node_client.has_blob(&blob_id)? {
+            return Ok(true);
+        }
+
+        let Some(context) = self.context_client.get_context(&context_id)? else {
+            bail!("context not found: {}", context_id);
+        };
+
+        let identities = self.context_client.context_members(&context.id, Some(true));
+
+        let Some((our_identity, _)) = choose_stream(identities, &mut thread_rng())
+            .await
+            .transpose()?
+        else {
+            bail!("no owned identities found for context: {}", context.id);
+        };
+
+        let peers = self
+            .network_client
+            .mesh_peers(TopicHash::from_raw(context_id))
+            .await;
+
+        for peer_id in peers.choose_multiple(&mut thread_rng(), peers.len()) {
+            debug!(%context_id, %peer_id, %blob_id, "Attempting to fetch blob from peer");
+
+            let result = async {
+                let mut stream = self.network_client.open_stream(*peer_id).await?;
+
+                let their_identity = self
+                    .initiate_handshake(&context, our_identity, *peer_id, &mut stream)
+                    .await?;
+
+                self.initiate_blob_share_process(
+                    &context,
+                    our_identity,
+                    their_identity,
+                    blob_id,
+                    None,
+                    &mut stream,
+                )
+                .await
+            };
+
+            match result.await {
+                Ok(()) => return Ok(true),
+                Err(err) => {
+                    debug!(%context_id, %peer_id, %blob_id, %err, "Failed to fetch blob from peer, trying another..");
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
     pub(super) async fn initiate_blob_share_process(
         &self,
         context: &Context,
         our_identity: PublicKey,
+        their_identity: PublicKey,
         blob_id: BlobId,
-        size: u64,
+        size: Option<u64>,
         stream: &mut Stream,
     ) -> eyre::Result<()> {
         debug!(
@@ -47,7 +131,7 @@ impl SyncManager {
             bail!("connection closed while awaiting blob share handshake");
         };
 
-        let (their_identity, mut their_nonce) = match ack {
+        let mut their_nonce = match ack {
             StreamMessage::Init {
                 party_id,
                 payload:
@@ -56,7 +140,7 @@ impl SyncManager {
                     },
                 next_nonce,
                 ..
-            } => {
+            } if party_id == their_identity => {
                 if ack_blob_id != blob_id {
                     bail!(
                         "unexpected ack blob id: expected {}, got {}",
@@ -65,7 +149,7 @@ impl SyncManager {
                     );
                 }
 
-                (party_id, next_nonce)
+                next_nonce
             }
             unexpected @ (StreamMessage::Init { .. }
             | StreamMessage::Message { .. }
@@ -84,11 +168,9 @@ impl SyncManager {
 
         let (tx, mut rx) = mpsc::channel(1);
 
-        let add_task = self.node_client.add_blob(
-            poll_fn(|cx| rx.poll_recv(cx)).into_async_read(),
-            Some(size),
-            None,
-        );
+        let add_task =
+            self.node_client
+                .add_blob(poll_fn(|cx| rx.poll_recv(cx)).into_async_read(), size, None);
 
         let read_task = async {
             let mut sequencer = Sequencer::default();
@@ -159,10 +241,9 @@ impl SyncManager {
             "Received blob share request",
         );
 
+        // failing lets the other party know, rather than leaving it waiting
         let Some(mut blob) = self.node_client.get_blob(&blob_id)? else {
-            warn!(%blob_id, "blob not found");
-
-            return Ok(());
+            bail!("blob not found: {}", blob_id);
         };
 
         let private_key = self
@@ -0,0 +1,269 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use calimero_primitives::context::ContextId;
+use calimero_primitives::hash::Hash;
+use calimero_primitives::identity::PublicKey;
+use eyre::bail;
+use libp2p::PeerId;
+use tokio::time;
+use tracing::{debug, warn};
+
+use super::SyncManager;
+use crate::utils::choose_stream;
+
+#[cfg(test)]
+#[path = "tests/delta.rs"]
+mod tests;
+
+/// How many root hashes each context remembers reaching, for deltas which
+/// build on a state the context has since moved past.
+const MAX_SEEN_ROOTS: usize = 256;
+
+/// How many deltas each context holds while waiting for the deltas they
+/// build on, beyond which it falls back to a full sync.
+const MAX_PENDING_DELTAS: usize = 1024;
+
+/// A state delta, verified and decrypted, ready to be applied once the state
+/// it builds on has been reached.
+#[derive(Debug)]
+pub(crate) struct Delta {
+    pub author_id: PublicKey,
+    pub parent_root_hash: Hash,
+    pub root_hash: Hash,
+    pub artifact: Vec<u8>,
+    pub source: PeerId,
+}
+
+/// The deltas received for each context which can't be applied yet, along
+/// with the root hashes each context is known to have reached.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DeltaBuffer {
+    contexts: Arc<Mutex<HashMap<ContextId, ContextDeltas>>>,
+}
+
+#[derive(Debug, Default)]
+struct ContextDeltas {
+    seen: VecDeque<Hash>,
+    pending: HashMap<Hash, Delta>,
+}
+
+impl ContextDeltas {
+    fn observe(&mut self, root_hash: Hash) {
+        if self.seen.contains(&root_hash) {
+            return;
+        }
+
+        if self.seen.len() == MAX_SEEN_ROOTS {
+            let _ignored = self.seen.pop_front();
+        }
+
+        self.seen.push_back(root_hash);
+    }
+}
+
+impl DeltaBuffer {
+    fn with<T>(&self, context_id: ContextId, f: impl FnOnce(&mut ContextDeltas) -> T) -> T {
+        let mut contexts = self.contexts.lock().expect("mutex should not be poisoned");
+
+        f(contexts.entry(context_id).or_default())
+    }
+
+    /// Records that the context has reached the given state.
+    pub fn observe(&self, context_id: ContextId, root_hash: Hash) {
+        self.with(context_id, |deltas| deltas.observe(root_hash));
+    }
+
+    /// Whether the context has reached the given state.
+    pub fn has_seen(&self, context_id: ContextId, root_hash: &Hash) -> bool {
+        self.with(context_id, |deltas| deltas.seen.contains(root_hash))
+    }
+
+    /// Holds on to a delta until the state it builds on is reached, returning
+    /// `false` if the context already holds too many.
+    pub fn hold(&self, context_id: ContextId, delta: Delta) -> bool {
+        self.with(context_id, |deltas| {
+            if deltas.pending.len() >= MAX_PENDING_DELTAS {
+                return false;
+            }
+
+            drop(deltas.pending.insert(delta.root_hash, delta));
+
+            true
+        })
+    }
+
+    /// Whether the delta resulting in the given state is still being held.
+    pub fn is_held(&self, context_id: ContextId, root_hash: &Hash) -> bool {
+        self.with(context_id, |deltas| deltas.pending.contains_key(root_hash))
+    }
+
+    /// Waits out the timeout, returning whether the delta resulting in the
+    /// given state is still being held by then.
+    pub async fn is_held_after(
+        &self,
+        context_id: ContextId,
+        root_hash: &Hash,
+        timeout: time::Duration,
+    ) -> bool {
+        time::sleep(timeout).await;
+
+        self.is_held(context_id, root_hash)
+    }
+
+    /// Takes a held delta which builds on a state the context has reached,
+    /// discarding those resulting in a state it has already reached.
+    pub fn take_ready(&self, context_id: ContextId) -> Option<Delta> {
+        self.with(context_id, |deltas| {
+            let ContextDeltas { seen, pending } = deltas;
+
+            pending.retain(|root_hash, _| !seen.contains(root_hash));
+
+            let root_hash = pending
+                .iter()
+                .find(|(_, delta)| seen.contains(&delta.parent_root_hash))
+                .map(|(root_hash, _)| *root_hash)?;
+
+            pending.remove(&root_hash)
+        })
+    }
+
+    /// Gives up on the delta resulting in the given state, if it's held.
+    pub fn discard(&self, context_id: ContextId, root_hash: &Hash) {
+        self.with(context_id, |deltas| drop(deltas.pending.remove(root_hash)));
+    }
+}
+
+impl SyncManager {
+    /// Applies a delta once the state it builds on has been reached, along
+    /// with any held deltas that builds on in turn.
+    ///
+    /// Deltas whose parents don't arrive in time are given up on, in favour
+    /// of a full sync with the peer they came from.
+    pub(crate) async fn receive_delta(
+        &self,
+        context_id: ContextId,
+        current_root_hash: Hash,
+        delta: Delta,
+    ) -> eyre::Result<()> {
+        self.deltas.observe(context_id, current_root_hash);
+
+        if !self.deltas.has_seen(context_id, &delta.parent_root_hash) {
+            let (root_hash, source) = (delta.root_hash, delta.source);
+
+            debug!(
+                %context_id,
+                %root_hash,
+                parent_root_hash = %delta.parent_root_hash,
+                "Holding state delta until its parent is applied"
+            );
+
+            if !self.deltas.hold(context_id, delta) {
+                warn!(%context_id, "Too many pending state deltas, initiating sync");
+
+                return self.resync(context_id, source).await;
+            }
+
+            if self
+                .deltas
+                .is_held_after(context_id, &root_hash, self.sync_config.delta_timeout)
+                .await
+            {
+                debug!(
+                    %context_id,
+                    %root_hash,
+                    "State delta parent never arrived, initiating sync"
+                );
+
+                let result = self.resync(context_id, source).await;
+
+                // nothing else waits on it, so unless the sync reached its
+                // parent and it was applied, it would be held for good
+                self.deltas.discard(context_id, &root_hash);
+
+                return result;
+            }
+
+            return Ok(());
+        }
+
+        self.apply_ready_deltas(context_id, Some(delta)).await
+    }
+
+    /// Applies the given delta, then every held delta which becomes ready as
+    /// a result, syncing in full with the author's peer whenever we diverge.
+    async fn apply_ready_deltas(
+        &self,
+        context_id: ContextId,
+        mut next: Option<Delta>,
+    ) -> eyre::Result<()> {
+        while let Some(delta) = next.take().or_else(|| self.deltas.take_ready(context_id)) {
+            let source = delta.source;
+
+            if !self.apply_delta(context_id, delta).await? {
+                let _ignored = self.sync_with(context_id, source).await?;
+
+                if let Some(context) = self.context_client.get_context(&context_id)? {
+                    self.deltas.observe(context_id, context.root_hash);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies a delta, returning `false` if we've diverged from its author.
+    async fn apply_delta(&self, context_id: ContextId, delta: Delta) -> eyre::Result<bool> {
+        let Some(context) = self.context_client.get_context(&context_id)? else {
+            bail!("context '{}' not found", context_id);
+        };
+
+        let identities = self.context_client.context_members(&context_id, Some(true));
+
+        let Some((our_identity, _)) = choose_stream(identities, &mut rand::thread_rng())
+            .await
+            .transpose()?
+        else {
+            bail!("no owned identities found for context: {}", context_id);
+        };
+
+        debug!(
+            %context_id,
+            author_id = %delta.author_id,
+            root_hash = %delta.root_hash,
+            "Applying state delta"
+        );
+
+        let outcome = self
+            .context_client
+            .execute(
+                &context_id,
+                &our_identity,
+                "__calimero_sync_next".to_owned(),
+                delta.artifact,
+                vec![],
+                None,
+            )
+            .await?;
+
+        // the state the delta leads to is only reached if we haven't diverged
+        self.deltas.observe(context_id, outcome.root_hash);
+
+        // applied on top of exactly the state the author had, the delta must
+        // lead to the same state, otherwise we've diverged from the author
+        Ok(context.root_hash != delta.parent_root_hash || outcome.root_hash == delta.root_hash)
+    }
+
+    /// Falls back to a full sync, then applies whichever held deltas build on
+    /// the state synced to. The rest stay held, as they may come from other
+    /// authors, whose parents can still arrive.
+    async fn resync(&self, context_id: ContextId, source: PeerId) -> eyre::Result<()> {
+        let _ignored = self.sync_with(context_id, source).await?;
+
+        if let Some(context) = self.context_client.get_context(&context_id)? {
+            self.deltas.observe(context_id, context.root_hash);
+        }
+
+        self.apply_ready_deltas(context_id, None).await
+    }
+}
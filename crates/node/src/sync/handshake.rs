@@ -0,0 +1,271 @@
+use calimero_network_primitives::stream::Stream;
+use calimero_node_primitives::sync::{
+    handshake_payload, HandshakeParty, HandshakeRole, InitPayload, MessagePayload, StreamMessage,
+};
+use calimero_primitives::context::{Context, ContextId};
+use calimero_primitives::identity::PublicKey;
+use eyre::{bail, OptionExt};
+use libp2p::PeerId;
+use rand::{thread_rng, Rng};
+use tracing::debug;
+
+use super::SyncManager;
+
+#[cfg(test)]
+#[path = "tests/handshake.rs"]
+mod tests;
+
+/// The identities both parties to a stream proved to each other when it was
+/// opened, which every later exchange on it must be made under.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Session {
+    pub context_id: ContextId,
+    pub our_identity: PublicKey,
+    pub their_identity: PublicKey,
+}
+
+/// What both parties to a handshake claimed and sent, which each of them
+/// signs to prove its identity.
+#[derive(Clone, Copy, Debug)]
+struct Transcript {
+    context_id: ContextId,
+    initiator: HandshakeParty,
+    responder: HandshakeParty,
+}
+
+impl Transcript {
+    const fn party(&self, role: HandshakeRole) -> &HandshakeParty {
+        match role {
+            HandshakeRole::Initiator => &self.initiator,
+            HandshakeRole::Responder => &self.responder,
+        }
+    }
+
+    fn payload(&self, signer: HandshakeRole) -> Vec<u8> {
+        handshake_payload(&self.context_id, signer, &self.initiator, &self.responder)
+    }
+
+    /// Whether the signature proves the party in the given role holds the
+    /// private key of the identity it claimed.
+    fn verify(&self, signer: HandshakeRole, signature: &[u8; 64]) -> bool {
+        self.party(signer)
+            .identity
+            .verify(&self.payload(signer), signature)
+    }
+}
+
+impl SyncManager {
+    /// Proves our identity to the peer, and has it prove its own, returning
+    /// the identity it proved.
+    pub(super) async fn initiate_handshake(
+        &self,
+        context: &Context,
+        our_identity: PublicKey,
+        their_peer_id: PeerId,
+        stream: &mut Stream,
+    ) -> eyre::Result<PublicKey> {
+        debug!(
+            context_id=%context.id,
+            our_identity=%our_identity,
+            "Initiating handshake",
+        );
+
+        let our_challenge = thread_rng().gen::<[u8; 32]>();
+
+        self.send(
+            stream,
+            &StreamMessage::Init {
+                context_id: context.id,
+                party_id: our_identity,
+                payload: InitPayload::Handshake {
+                    challenge: our_challenge,
+                },
+                next_nonce: thread_rng().gen(),
+            },
+            None,
+        )
+        .await?;
+
+        let Some(ack) = self.recv(stream, None).await? else {
+            bail!("connection closed while awaiting handshake");
+        };
+
+        let (their_identity, their_challenge) = match ack {
+            StreamMessage::Init {
+                party_id,
+                payload: InitPayload::Handshake { challenge },
+                ..
+            } => (party_id, challenge),
+            unexpected @ (StreamMessage::Init { .. }
+            | StreamMessage::Message { .. }
+            | StreamMessage::OpaqueError) => {
+                bail!("unexpected message: {:?}", unexpected)
+            }
+        };
+
+        if !self
+            .context_client
+            .has_member(&context.id, &their_identity)?
+        {
+            bail!(
+                "unknown context member {} in context {}",
+                their_identity,
+                context.id
+            );
+        }
+
+        let transcript = Transcript {
+            context_id: context.id,
+            initiator: HandshakeParty {
+                identity: our_identity,
+                peer_id: self.peer_id,
+                challenge: our_challenge,
+            },
+            responder: HandshakeParty {
+                identity: their_identity,
+                peer_id: their_peer_id,
+                challenge: their_challenge,
+            },
+        };
+
+        self.send_proof(&transcript, HandshakeRole::Initiator, stream)
+            .await?;
+
+        self.verify_proof(&transcript, HandshakeRole::Responder, stream)
+            .await?;
+
+        debug!(
+            context_id=%context.id,
+            our_identity=%our_identity,
+            their_identity=%their_identity,
+            "Handshake completed",
+        );
+
+        Ok(their_identity)
+    }
+
+    /// Responds to a handshake from a peer claiming to be a member, once its
+    /// membership has been checked. Our proof is only sent once the peer has
+    /// proven its identity.
+    pub(super) async fn handle_handshake_request(
+        &self,
+        context: &Context,
+        our_identity: PublicKey,
+        their_identity: PublicKey,
+        their_peer_id: PeerId,
+        their_challenge: [u8; 32],
+        stream: &mut Stream,
+    ) -> eyre::Result<()> {
+        debug!(
+            context_id=%context.id,
+            their_identity=%their_identity,
+            "Received handshake request",
+        );
+
+        let our_challenge = thread_rng().gen::<[u8; 32]>();
+
+        self.send(
+            stream,
+            &StreamMessage::Init {
+                context_id: context.id,
+                party_id: our_identity,
+                payload: InitPayload::Handshake {
+                    challenge: our_challenge,
+                },
+                next_nonce: thread_rng().gen(),
+            },
+            None,
+        )
+        .await?;
+
+        let transcript = Transcript {
+            context_id: context.id,
+            initiator: HandshakeParty {
+                identity: their_identity,
+                peer_id: their_peer_id,
+                challenge: their_challenge,
+            },
+            responder: HandshakeParty {
+                identity: our_identity,
+                peer_id: self.peer_id,
+                challenge: our_challenge,
+            },
+        };
+
+        self.verify_proof(&transcript, HandshakeRole::Initiator, stream)
+            .await?;
+
+        self.send_proof(&transcript, HandshakeRole::Responder, stream)
+            .await?;
+
+        debug!(
+            context_id=%context.id,
+            our_identity=%our_identity,
+            their_identity=%their_identity,
+            "Handshake completed",
+        );
+
+        Ok(())
+    }
+
+    async fn send_proof(
+        &self,
+        transcript: &Transcript,
+        role: HandshakeRole,
+        stream: &mut Stream,
+    ) -> eyre::Result<()> {
+        let our_identity = transcript.party(role).identity;
+
+        let private_key = self
+            .context_client
+            .get_identity(&transcript.context_id, &our_identity)?
+            .and_then(|i| i.private_key)
+            .ok_or_eyre("expected own identity to have private key")?;
+
+        let signature = private_key.sign(&transcript.payload(role));
+
+        self.send(
+            stream,
+            &StreamMessage::Message {
+                sequence_id: 0,
+                payload: MessagePayload::Handshake { signature },
+                next_nonce: thread_rng().gen(),
+            },
+            None,
+        )
+        .await
+    }
+
+    async fn verify_proof(
+        &self,
+        transcript: &Transcript,
+        role: HandshakeRole,
+        stream: &mut Stream,
+    ) -> eyre::Result<()> {
+        let Some(msg) = self.recv(stream, None).await? else {
+            bail!("connection closed while awaiting handshake proof");
+        };
+
+        let signature = match msg {
+            StreamMessage::Message {
+                payload: MessagePayload::Handshake { signature },
+                ..
+            } => signature,
+            unexpected @ (StreamMessage::Init { .. }
+            | StreamMessage::Message { .. }
+            | StreamMessage::OpaqueError) => {
+                bail!("unexpected message: {:?}", unexpected)
+            }
+        };
+
+        if !transcript.verify(role, &signature) {
+            bail!(
+                "{} failed to prove its identity in context {}",
+                transcript.party(role).identity,
+                transcript.context_id
+            );
+        }
+
+        Ok(())
+    }
+}
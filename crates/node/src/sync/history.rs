@@ -0,0 +1,220 @@
+use std::future::Future;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use calimero_node_primitives::sync::SyncOutcome;
+use calimero_primitives::context::{ContextId, ContextSyncRecord, SyncStatus};
+use calimero_primitives::events::{
+    ContextEvent, ContextEventPayload, NodeEvent, SyncStartedPayload,
+};
+use calimero_primitives::hash::Hash;
+use eyre::{eyre, OptionExt};
+use libp2p::PeerId;
+use tokio::time::error::Elapsed;
+use tokio::time::{timeout_at, Instant};
+use tracing::error;
+
+use super::SyncManager;
+
+#[cfg(test)]
+#[path = "tests/history.rs"]
+mod tests;
+
+/// A sync which has started, to be recorded once it finishes.
+#[derive(Clone, Copy, Debug)]
+struct SyncAttempt {
+    context_id: ContextId,
+    peer_id: Option<PeerId>,
+    attempted_at: u64,
+    root_hash_before: Hash,
+}
+
+impl SyncManager {
+    /// Syncs the context before the deadline, keeping a record of how it went,
+    /// and letting subscribers know as it starts and finishes.
+    pub(super) async fn tracked_sync(
+        &self,
+        context_id: ContextId,
+        peer_id: Option<PeerId>,
+        deadline: Instant,
+    ) -> Result<eyre::Result<SyncOutcome>, Elapsed> {
+        let attempt = self.start_sync(context_id, peer_id);
+
+        let result = timeout_at(deadline, self.perform_sync(context_id, peer_id)).await;
+
+        self.finish_sync(attempt, result.as_ref());
+
+        result
+    }
+
+    /// Keeps a record of a sync the peer started with us, as of those we
+    /// start, given the sync returning the bytes it exchanged.
+    pub(super) async fn tracked_incoming_sync(
+        &self,
+        context_id: ContextId,
+        peer_id: PeerId,
+        sync: impl Future<Output = eyre::Result<u64>>,
+    ) -> eyre::Result<()> {
+        let attempt = self.start_sync(context_id, Some(peer_id));
+
+        let start = Instant::now();
+
+        let result = sync.await.and_then(|bytes_transferred| {
+            let root_hash_after = self
+                .context_client
+                .get_context(&context_id)?
+                .map_or(attempt.root_hash_before, |context| context.root_hash);
+
+            Ok(SyncOutcome {
+                peer_id,
+                duration: start.elapsed(),
+                root_hash_before: attempt.root_hash_before,
+                root_hash_after,
+                bytes_transferred,
+            })
+        });
+
+        self.finish_sync(attempt, Ok(&result));
+
+        result.map(|_outcome| ())
+    }
+
+    /// Syncs the context with the peer right away, as when the deltas it sent
+    /// can't be applied, keeping a record of it all the same.
+    pub(crate) async fn sync_with(
+        &self,
+        context_id: ContextId,
+        peer_id: PeerId,
+    ) -> eyre::Result<SyncOutcome> {
+        let deadline = Instant::now()
+            .checked_add(self.sync_config.timeout)
+            .ok_or_eyre("unable to determine when to timeout sync procedure")?;
+
+        self.tracked_sync(context_id, Some(peer_id), deadline)
+            .await
+            .map_err(|_| eyre!("sync timed out after {:?}", self.sync_config.timeout))?
+    }
+
+    /// Lets subscribers know the context has started syncing, returning what
+    /// the sync is recorded by once it finishes.
+    fn start_sync(&self, context_id: ContextId, peer_id: Option<PeerId>) -> SyncAttempt {
+        let root_hash_before = self
+            .context_client
+            .get_context(&context_id)
+            .ok()
+            .flatten()
+            .map_or_else(Hash::default, |context| context.root_hash);
+
+        self.notify(
+            context_id,
+            ContextEventPayload::SyncStarted(SyncStartedPayload::new(
+                peer_id.map(|peer_id| peer_id.to_string()),
+                root_hash_before,
+            )),
+        );
+
+        SyncAttempt {
+            context_id,
+            peer_id,
+            attempted_at: unix_millis(),
+            root_hash_before,
+        }
+    }
+
+    /// Records how the sync went, and lets subscribers know it finished.
+    fn finish_sync(
+        &self,
+        attempt: SyncAttempt,
+        result: Result<&eyre::Result<SyncOutcome>, &Elapsed>,
+    ) {
+        let context_id = attempt.context_id;
+
+        match self.record_sync(attempt, result) {
+            Ok(record) => self.notify(context_id, ContextEventPayload::SyncFinished(record)),
+            Err(err) => error!(%context_id, %err, "Failed to record sync"),
+        }
+    }
+
+    fn record_sync(
+        &self,
+        attempt: SyncAttempt,
+        result: Result<&eyre::Result<SyncOutcome>, &Elapsed>,
+    ) -> eyre::Result<ContextSyncRecord> {
+        let previous = self.context_client.get_context_sync(&attempt.context_id)?;
+
+        let root_hash_now = self
+            .context_client
+            .get_context(&attempt.context_id)?
+            .map_or(attempt.root_hash_before, |context| context.root_hash);
+
+        let record = next_record(previous.as_ref(), &attempt, root_hash_now, result);
+
+        self.context_client
+            .put_context_sync(&attempt.context_id, &record)?;
+
+        Ok(record)
+    }
+
+    fn notify(&self, context_id: ContextId, payload: ContextEventPayload) {
+        let event = NodeEvent::Context(ContextEvent {
+            context_id,
+            payload,
+        });
+
+        if let Err(err) = self.node_client.send_event(event) {
+            error!(%context_id, %err, "Failed to send sync event");
+        }
+    }
+}
+
+/// The record of a sync which just finished, following on from the one
+/// before it, if any.
+fn next_record(
+    previous: Option<&ContextSyncRecord>,
+    attempt: &SyncAttempt,
+    root_hash_now: Hash,
+    result: Result<&eyre::Result<SyncOutcome>, &Elapsed>,
+) -> ContextSyncRecord {
+    let (status, outcome) = match result {
+        Ok(Ok(outcome)) => (SyncStatus::Succeeded, Some(outcome)),
+        Ok(Err(err)) => (
+            SyncStatus::Failed {
+                reason: err.to_string(),
+            },
+            None,
+        ),
+        Err(_) => (SyncStatus::TimedOut, None),
+    };
+
+    let (last_success, consecutive_failures, root_hash_after) = match outcome {
+        Some(outcome) => (Some(attempt.attempted_at), 0, outcome.root_hash_after),
+        None => (
+            previous.and_then(|record| record.last_success),
+            previous
+                .map_or(0, |record| record.consecutive_failures)
+                .saturating_add(1),
+            root_hash_now,
+        ),
+    };
+
+    ContextSyncRecord::new(
+        attempt.attempted_at,
+        last_success,
+        outcome
+            .map(|outcome| outcome.peer_id)
+            .or(attempt.peer_id)
+            .map(|peer_id| peer_id.to_string()),
+        status,
+        outcome.map_or(0, |outcome| outcome.bytes_transferred),
+        attempt.root_hash_before,
+        root_hash_after,
+        consecutive_failures,
+    )
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            elapsed.as_millis().try_into().unwrap_or(u64::MAX)
+        })
+}
@@ -14,11 +14,13 @@ impl SyncManager {
         &self,
         context: &mut Context,
         our_identity: PublicKey,
+        their_identity: PublicKey,
         stream: &mut Stream,
     ) -> eyre::Result<()> {
         debug!(
             context_id=%context.id,
             our_identity=%our_identity,
+            their_identity=%their_identity,
             "Initiating key share",
         );
 
@@ -40,13 +42,13 @@ impl SyncManager {
             bail!("connection closed while awaiting state sync handshake");
         };
 
-        let (their_identity, their_nonce) = match ack {
+        let their_nonce = match ack {
             StreamMessage::Init {
                 party_id,
                 payload: InitPayload::KeyShare,
                 next_nonce,
                 ..
-            } => (party_id, next_nonce),
+            } if party_id == their_identity => next_nonce,
             unexpected @ (StreamMessage::Init { .. }
             | StreamMessage::Message { .. }
             | StreamMessage::OpaqueError) => {
@@ -0,0 +1,120 @@
+use std::collections::{hash_map, HashMap};
+
+use calimero_node_primitives::sync::SyncOutcome;
+use calimero_primitives::context::ContextId;
+use eyre::eyre;
+use tokio::sync::oneshot;
+use tokio::time::{Duration, Instant};
+use tracing::debug;
+
+#[cfg(test)]
+#[path = "tests/schedule.rs"]
+mod tests;
+
+/// Where the outcome of a requested sync is sent.
+pub(super) type OutcomeSender = oneshot::Sender<eyre::Result<SyncOutcome>>;
+
+/// Which contexts are being synced, and when the others were last synced, so
+/// that no context is synced more than once at a time.
+#[derive(Debug, Default)]
+pub(super) struct Schedule {
+    contexts: HashMap<ContextId, SyncState>,
+}
+
+#[derive(Debug)]
+enum SyncState {
+    Idle {
+        last_sync: Instant,
+    },
+    /// Along with the requests waiting on the sync to finish.
+    Running {
+        waiting: Vec<OutcomeSender>,
+    },
+}
+
+impl Schedule {
+    /// Has the request wait on the context's sync, returning whether one has
+    /// to be started for it, rather than joining the one running.
+    pub fn request(&mut self, context_id: ContextId, outcome: OutcomeSender) -> bool {
+        match self.contexts.entry(context_id) {
+            hash_map::Entry::Occupied(mut state) => match state.get_mut() {
+                SyncState::Running { waiting } => {
+                    debug!(%context_id, "Sync already in progress, awaiting its outcome");
+
+                    waiting.push(outcome);
+
+                    false
+                }
+                idle @ SyncState::Idle { .. } => {
+                    *idle = SyncState::Running {
+                        waiting: vec![outcome],
+                    };
+
+                    true
+                }
+            },
+            hash_map::Entry::Vacant(state) => {
+                let _ignored = state.insert(SyncState::Running {
+                    waiting: vec![outcome],
+                });
+
+                true
+            }
+        }
+    }
+
+    /// Returns whether the context is due to be synced at the interval,
+    /// marking it as being synced if it is.
+    pub fn due(&mut self, context_id: ContextId, interval: Duration) -> bool {
+        match self.contexts.entry(context_id) {
+            hash_map::Entry::Occupied(mut state) => {
+                let state = state.get_mut();
+
+                let SyncState::Idle { last_sync } = *state else {
+                    debug!(%context_id, "Sync already in progress");
+
+                    return false;
+                };
+
+                let time_since = last_sync.elapsed();
+
+                if time_since < interval {
+                    debug!(%context_id, ?time_since, minimum=?interval, "Skipping sync, last one was too recent");
+
+                    return false;
+                }
+
+                *state = SyncState::Running { waiting: vec![] };
+            }
+            hash_map::Entry::Vacant(state) => {
+                debug!(%context_id, "Syncing for the first time");
+
+                let _ignored = state.insert(SyncState::Running { waiting: vec![] });
+            }
+        }
+
+        true
+    }
+
+    /// Records that the context's sync finished, passing its outcome on to
+    /// every request waiting on it.
+    pub fn finish(&mut self, context_id: ContextId, result: &eyre::Result<SyncOutcome>) {
+        let state = self.contexts.insert(
+            context_id,
+            SyncState::Idle {
+                last_sync: Instant::now(),
+            },
+        );
+
+        let Some(SyncState::Running { waiting }) = state else {
+            return;
+        };
+
+        for outcome in waiting {
+            let _ignored = outcome.send(match result {
+                Ok(result) => Ok(*result),
+                Err(err) => Err(eyre!("{err:#}")),
+            });
+        }
+    }
+}
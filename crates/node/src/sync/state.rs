@@ -19,11 +19,13 @@ impl SyncManager {
         &self,
         context: &mut Context,
         our_identity: PublicKey,
+        their_identity: PublicKey,
         stream: &mut Stream,
     ) -> eyre::Result<()> {
         debug!(
             context_id=%context.id,
             our_identity=%our_identity,
+            their_identity=%their_identity,
             our_root_hash=%context.root_hash,
             our_application_id=%context.application_id,
             "Initiating state sync",
@@ -46,14 +48,14 @@ impl SyncManager {
         )
         .await?;
 
-        let mut triple = None;
+        let mut pair = None;
 
         for _ in 1..=2 {
             let Some(ack) = self.recv(stream, None).await? else {
                 bail!("connection closed while awaiting state sync handshake");
             };
 
-            let (their_root_hash, their_identity, their_nonce) = match ack {
+            let (their_root_hash, their_nonce) = match ack {
                 StreamMessage::Init {
                     party_id,
                     payload:
@@ -63,7 +65,7 @@ impl SyncManager {
                         },
                     next_nonce,
                     ..
-                } => {
+                } if party_id == their_identity => {
                     if application_id != context.application_id {
                         bail!(
                             "unexpected application id: expected {}, got {}",
@@ -72,13 +74,13 @@ impl SyncManager {
                         );
                     }
 
-                    (root_hash, party_id, next_nonce)
+                    (root_hash, next_nonce)
                 }
                 StreamMessage::Init {
-                    party_id: their_identity,
+                    party_id,
                     payload: InitPayload::BlobShare { blob_id },
                     ..
-                } => {
+                } if party_id == their_identity => {
                     self.handle_blob_share_request(
                         context,
                         our_identity,
@@ -97,12 +99,12 @@ impl SyncManager {
                 }
             };
 
-            triple = Some((their_root_hash, their_identity, their_nonce));
+            pair = Some((their_root_hash, their_nonce));
 
             break;
         }
 
-        let Some((their_root_hash, their_identity, their_nonce)) = triple else {
+        let Some((their_root_hash, their_nonce)) = pair else {
             bail!("expected two state sync handshakes, got none");
         };
 
@@ -207,8 +209,9 @@ impl SyncManager {
             self.initiate_blob_share_process(
                 &context,
                 our_identity,
+                their_identity,
                 application.blob.bytecode,
-                application.size,
+                Some(application.size),
                 stream,
             )
             .await?;
@@ -0,0 +1,155 @@
+use super::*;
+
+const CONTEXT_ID: [u8; 32] = [1; 32];
+
+fn hash(byte: u8) -> Hash {
+    Hash::from([byte; 32])
+}
+
+fn delta(parent: u8, root: u8) -> Delta {
+    Delta {
+        author_id: PublicKey::from([0; 32]),
+        parent_root_hash: hash(parent),
+        root_hash: hash(root),
+        artifact: vec![root],
+        source: PeerId::random(),
+    }
+}
+
+#[test]
+fn take_ready_follows_parents() {
+    let deltas = DeltaBuffer::default();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    deltas.observe(context_id, hash(1));
+
+    // held in reverse, each waiting on the one before it
+    assert!(deltas.hold(context_id, delta(3, 4)));
+    assert!(deltas.hold(context_id, delta(2, 3)));
+    assert!(deltas.hold(context_id, delta(1, 2)));
+
+    for root in 2..=4 {
+        let ready = deltas
+            .take_ready(context_id)
+            .expect("delta should be ready");
+
+        assert_eq!(ready.root_hash, hash(root));
+        assert!(deltas.take_ready(context_id).is_none());
+
+        deltas.observe(context_id, ready.root_hash);
+    }
+
+    assert!(!deltas.is_held(context_id, &hash(4)));
+}
+
+#[test]
+fn take_ready_waits_for_missing_parent() {
+    let deltas = DeltaBuffer::default();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    deltas.observe(context_id, hash(1));
+
+    assert!(deltas.hold(context_id, delta(2, 3)));
+
+    assert!(deltas.take_ready(context_id).is_none());
+    assert!(deltas.is_held(context_id, &hash(3)));
+}
+
+#[test]
+fn take_ready_discards_reached_states() {
+    let deltas = DeltaBuffer::default();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    deltas.observe(context_id, hash(1));
+
+    assert!(deltas.hold(context_id, delta(1, 2)));
+
+    // reached some other way, say a full sync, before the delta was applied
+    deltas.observe(context_id, hash(2));
+
+    assert!(deltas.take_ready(context_id).is_none());
+    assert!(!deltas.is_held(context_id, &hash(2)));
+}
+
+#[test]
+fn hold_is_capped_per_context() {
+    let deltas = DeltaBuffer::default();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    for root in 0..MAX_PENDING_DELTAS {
+        let mut held = delta(0, 0);
+
+        held.root_hash = Hash::new(&root.to_le_bytes());
+
+        assert!(deltas.hold(context_id, held));
+    }
+
+    assert!(!deltas.hold(context_id, delta(1, 2)));
+    assert!(!deltas.is_held(context_id, &hash(2)));
+
+    // other contexts hold deltas of their own
+    assert!(deltas.hold(ContextId::from([2; 32]), delta(1, 2)));
+
+    deltas.discard(context_id, &Hash::new(&0_usize.to_le_bytes()));
+
+    assert!(deltas.hold(context_id, delta(1, 2)));
+    assert!(deltas.is_held(context_id, &hash(2)));
+    assert!(deltas.is_held(context_id, &Hash::new(&1_usize.to_le_bytes())));
+}
+
+#[test]
+fn observe_forgets_oldest_roots() {
+    let deltas = DeltaBuffer::default();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    for root in 0..=MAX_SEEN_ROOTS {
+        deltas.observe(context_id, Hash::new(&root.to_le_bytes()));
+    }
+
+    assert!(!deltas.has_seen(context_id, &Hash::new(&0_usize.to_le_bytes())));
+    assert!(deltas.has_seen(context_id, &Hash::new(&1_usize.to_le_bytes())));
+    assert!(deltas.has_seen(context_id, &Hash::new(&MAX_SEEN_ROOTS.to_le_bytes())));
+}
+
+#[tokio::test(start_paused = true)]
+async fn is_held_after_timeout_without_parent() {
+    let deltas = DeltaBuffer::default();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    deltas.observe(context_id, hash(1));
+
+    assert!(deltas.hold(context_id, delta(2, 3)));
+
+    assert!(
+        deltas
+            .is_held_after(context_id, &hash(3), time::Duration::from_secs(5))
+            .await
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn is_not_held_after_timeout_once_parent_arrives() {
+    let deltas = DeltaBuffer::default();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    deltas.observe(context_id, hash(1));
+
+    assert!(deltas.hold(context_id, delta(2, 3)));
+
+    let (held, ()) = tokio::join!(
+        deltas.is_held_after(context_id, &hash(3), time::Duration::from_secs(5)),
+        async {
+            time::sleep(time::Duration::from_secs(1)).await;
+
+            deltas.observe(context_id, hash(2));
+
+            let ready = deltas
+                .take_ready(context_id)
+                .expect("delta should be ready");
+
+            assert_eq!(ready.root_hash, hash(3));
+        }
+    );
+
+    assert!(!held);
+}
@@ -0,0 +1,106 @@
+use calimero_primitives::identity::PrivateKey;
+
+use super::*;
+
+const CONTEXT_ID: [u8; 32] = [1; 32];
+
+fn party(identity: &PrivateKey, peer_id: PeerId, challenge: u8) -> HandshakeParty {
+    HandshakeParty {
+        identity: identity.public_key(),
+        peer_id,
+        challenge: [challenge; 32],
+    }
+}
+
+#[test]
+fn proof_verifies_on_its_stream() {
+    let (alice, bob) = (
+        PrivateKey::random(&mut thread_rng()),
+        PrivateKey::random(&mut thread_rng()),
+    );
+
+    let transcript = Transcript {
+        context_id: ContextId::from(CONTEXT_ID),
+        initiator: party(&alice, PeerId::random(), 1),
+        responder: party(&bob, PeerId::random(), 2),
+    };
+
+    let proof = alice.sign(&transcript.payload(HandshakeRole::Initiator));
+
+    assert!(transcript.verify(HandshakeRole::Initiator, &proof));
+
+    let proof = bob.sign(&transcript.payload(HandshakeRole::Responder));
+
+    assert!(transcript.verify(HandshakeRole::Responder, &proof));
+}
+
+#[test]
+fn relayed_proof_is_rejected() {
+    let (alice, bob) = (
+        PrivateKey::random(&mut thread_rng()),
+        PrivateKey::random(&mut thread_rng()),
+    );
+
+    let (alice_peer, mallory_peer, bob_peer) =
+        (PeerId::random(), PeerId::random(), PeerId::random());
+
+    // mallory opens a stream to bob claiming to be alice, and passes
+    // everything bob sends on to a stream alice opened to it
+    let alice_to_mallory = Transcript {
+        context_id: ContextId::from(CONTEXT_ID),
+        initiator: party(&alice, alice_peer, 1),
+        responder: party(&bob, mallory_peer, 2),
+    };
+
+    let mallory_to_bob = Transcript {
+        context_id: ContextId::from(CONTEXT_ID),
+        initiator: party(&alice, mallory_peer, 1),
+        responder: party(&bob, bob_peer, 2),
+    };
+
+    let proof = alice.sign(&alice_to_mallory.payload(HandshakeRole::Initiator));
+
+    assert!(alice_to_mallory.verify(HandshakeRole::Initiator, &proof));
+    assert!(!mallory_to_bob.verify(HandshakeRole::Initiator, &proof));
+
+    let proof = bob.sign(&mallory_to_bob.payload(HandshakeRole::Responder));
+
+    assert!(!alice_to_mallory.verify(HandshakeRole::Responder, &proof));
+}
+
+#[test]
+fn reflected_proof_is_rejected() {
+    let alice = PrivateKey::random(&mut thread_rng());
+
+    // a peer claiming alice's identity back to her can only send her own
+    // proof, made for the other side of the handshake
+    let transcript = Transcript {
+        context_id: ContextId::from(CONTEXT_ID),
+        initiator: party(&alice, PeerId::random(), 1),
+        responder: party(&alice, PeerId::random(), 2),
+    };
+
+    let proof = alice.sign(&transcript.payload(HandshakeRole::Initiator));
+
+    assert!(!transcript.verify(HandshakeRole::Responder, &proof));
+}
+
+#[test]
+fn proof_for_other_challenge_is_rejected() {
+    let (alice, bob) = (
+        PrivateKey::random(&mut thread_rng()),
+        PrivateKey::random(&mut thread_rng()),
+    );
+
+    let mut transcript = Transcript {
+        context_id: ContextId::from(CONTEXT_ID),
+        initiator: party(&alice, PeerId::random(), 1),
+        responder: party(&bob, PeerId::random(), 2),
+    };
+
+    let proof = alice.sign(&transcript.payload(HandshakeRole::Initiator));
+
+    transcript.responder.challenge = [3; 32];
+
+    assert!(!transcript.verify(HandshakeRole::Initiator, &proof));
+}
@@ -0,0 +1,160 @@
+use std::time::Duration;
+
+use tokio::time::timeout;
+
+use super::*;
+
+const CONTEXT_ID: [u8; 32] = [1; 32];
+
+fn attempt(attempted_at: u64, peer_id: Option<PeerId>) -> SyncAttempt {
+    SyncAttempt {
+        context_id: ContextId::from(CONTEXT_ID),
+        peer_id,
+        attempted_at,
+        root_hash_before: Hash::from([2; 32]),
+    }
+}
+
+fn outcome(peer_id: PeerId) -> SyncOutcome {
+    SyncOutcome {
+        peer_id,
+        duration: Duration::from_secs(1),
+        root_hash_before: Hash::from([2; 32]),
+        root_hash_after: Hash::from([3; 32]),
+        bytes_transferred: 42,
+    }
+}
+
+#[test]
+fn success_is_recorded_with_its_outcome() {
+    let peer_id = PeerId::random();
+
+    let record = next_record(
+        None,
+        &attempt(10, None),
+        Hash::from([4; 32]),
+        Ok(&Ok(outcome(peer_id))),
+    );
+
+    assert_eq!(
+        record,
+        ContextSyncRecord::new(
+            10,
+            Some(10),
+            Some(peer_id.to_string()),
+            SyncStatus::Succeeded,
+            42,
+            Hash::from([2; 32]),
+            Hash::from([3; 32]),
+            0,
+        )
+    );
+}
+
+#[test]
+fn consecutive_failures_are_counted() {
+    let peer_id = PeerId::random();
+
+    let succeeded = next_record(
+        None,
+        &attempt(10, Some(peer_id)),
+        Hash::default(),
+        Ok(&Ok(outcome(peer_id))),
+    );
+
+    let failed = next_record(
+        Some(&succeeded),
+        &attempt(20, Some(peer_id)),
+        Hash::from([4; 32]),
+        Ok(&Err(eyre!("peer went away"))),
+    );
+
+    assert_eq!(
+        failed,
+        ContextSyncRecord::new(
+            20,
+            Some(10),
+            Some(peer_id.to_string()),
+            SyncStatus::Failed {
+                reason: "peer went away".to_owned(),
+            },
+            0,
+            Hash::from([2; 32]),
+            Hash::from([4; 32]),
+            1,
+        )
+    );
+
+    let failed = next_record(
+        Some(&failed),
+        &attempt(30, None),
+        Hash::from([4; 32]),
+        Ok(&Err(eyre!("no peers to sync with"))),
+    );
+
+    assert_eq!(failed.last_success, Some(10));
+    assert_eq!(failed.peer_id, None);
+    assert_eq!(failed.consecutive_failures, 2);
+}
+
+#[test]
+fn success_resets_failures() {
+    let peer_id = PeerId::random();
+
+    let mut record = None;
+
+    for attempted_at in 1..=3 {
+        record = Some(next_record(
+            record.as_ref(),
+            &attempt(attempted_at, None),
+            Hash::default(),
+            Ok(&Err(eyre!("no peers to sync with"))),
+        ));
+    }
+
+    let record = record.unwrap();
+
+    assert_eq!(record.last_success, None);
+    assert_eq!(record.consecutive_failures, 3);
+
+    let record = next_record(
+        Some(&record),
+        &attempt(4, None),
+        Hash::default(),
+        Ok(&Ok(outcome(peer_id))),
+    );
+
+    assert_eq!(record.status, SyncStatus::Succeeded);
+    assert_eq!(record.last_success, Some(4));
+    assert_eq!(record.consecutive_failures, 0);
+}
+
+#[tokio::test]
+async fn timeout_is_recorded_as_failure() {
+    let elapsed = timeout(Duration::ZERO, std::future::pending::<()>())
+        .await
+        .unwrap_err();
+
+    let peer_id = PeerId::random();
+
+    let record = next_record(
+        None,
+        &attempt(10, Some(peer_id)),
+        Hash::from([4; 32]),
+        Err(&elapsed),
+    );
+
+    assert_eq!(
+        record,
+        ContextSyncRecord::new(
+            10,
+            None,
+            Some(peer_id.to_string()),
+            SyncStatus::TimedOut,
+            0,
+            Hash::from([2; 32]),
+            Hash::from([4; 32]),
+            1,
+        )
+    );
+}
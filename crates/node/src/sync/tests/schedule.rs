@@ -0,0 +1,107 @@
+use calimero_primitives::hash::Hash;
+use libp2p::PeerId;
+
+use super::*;
+
+const CONTEXT_ID: [u8; 32] = [1; 32];
+
+const INTERVAL: Duration = Duration::from_secs(10);
+
+fn outcome() -> SyncOutcome {
+    SyncOutcome {
+        peer_id: PeerId::random(),
+        duration: Duration::from_secs(1),
+        root_hash_before: Hash::default(),
+        root_hash_after: Hash::from([2; 32]),
+        bytes_transferred: 42,
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn requested_sync_joins_running_one() {
+    let mut schedule = Schedule::default();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    let (first, first_outcome) = oneshot::channel();
+    let (second, second_outcome) = oneshot::channel();
+
+    assert!(schedule.request(context_id, first));
+    assert!(!schedule.request(context_id, second));
+
+    let outcome = outcome();
+
+    schedule.finish(context_id, &Ok(outcome));
+
+    for received in [first_outcome.await, second_outcome.await] {
+        let received = received.unwrap().unwrap();
+
+        assert_eq!(received.peer_id, outcome.peer_id);
+        assert_eq!(received.root_hash_after, outcome.root_hash_after);
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn requested_sync_joins_interval_sync() {
+    let mut schedule = Schedule::default();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    assert!(schedule.due(context_id, INTERVAL));
+
+    let (sender, outcome) = oneshot::channel();
+
+    assert!(!schedule.request(context_id, sender));
+
+    schedule.finish(context_id, &Err(eyre!("no peers to sync with")));
+
+    let err = outcome.await.unwrap().unwrap_err();
+
+    assert_eq!(err.to_string(), "no peers to sync with");
+}
+
+#[test]
+fn requested_sync_starts_once_previous_finished() {
+    let mut schedule = Schedule::default();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    let (sender, _outcome) = oneshot::channel();
+
+    assert!(schedule.request(context_id, sender));
+
+    schedule.finish(context_id, &Ok(outcome()));
+
+    // requested syncs don't wait for the interval
+    let (sender, _outcome) = oneshot::channel();
+
+    assert!(schedule.request(context_id, sender));
+}
+
+#[tokio::test(start_paused = true)]
+async fn interval_sync_skips_running_and_recent_syncs() {
+    let mut schedule = Schedule::default();
+    let context_id = ContextId::from(CONTEXT_ID);
+
+    let (sender, _outcome) = oneshot::channel();
+
+    assert!(schedule.request(context_id, sender));
+    assert!(!schedule.due(context_id, INTERVAL));
+
+    schedule.finish(context_id, &Ok(outcome()));
+
+    assert!(!schedule.due(context_id, INTERVAL));
+
+    tokio::time::advance(INTERVAL).await;
+
+    assert!(schedule.due(context_id, INTERVAL));
+    assert!(!schedule.due(context_id, INTERVAL));
+}
+
+#[test]
+fn contexts_are_scheduled_apart() {
+    let mut schedule = Schedule::default();
+
+    assert!(schedule.due(ContextId::from(CONTEXT_ID), INTERVAL));
+
+    let (sender, _outcome) = oneshot::channel();
+
+    assert!(schedule.request(ContextId::from([2; 32]), sender));
+}
@@ -0,0 +1,72 @@
+use std::pin::pin;
+
+use calimero_primitives::context::ContextId;
+use eyre::bail;
+use futures_util::StreamExt;
+use tracing::{debug, error};
+
+use super::SyncManager;
+use crate::utils::choose_stream;
+
+impl SyncManager {
+    /// Purges the tombstones past their retention period from the state of
+    /// every context, which has no effect on the contexts' root hashes.
+    pub(crate) async fn purge_tombstones(&self) {
+        let contexts = self.context_client.get_contexts(None);
+
+        let mut contexts = pin!(contexts);
+
+        while let Some(context_id) = contexts.next().await {
+            let context_id = match context_id {
+                Ok(context_id) => context_id,
+                Err(err) => {
+                    error!(%err, "Failed reading context id to purge tombstones of");
+                    continue;
+                }
+            };
+
+            if let Err(err) = self.purge_tombstones_of(context_id).await {
+                debug!(%context_id, %err, "Failed to purge tombstones");
+            }
+        }
+    }
+
+    async fn purge_tombstones_of(&self, context_id: ContextId) -> eyre::Result<()> {
+        let Some(context) = self.context_client.get_context(&context_id)? else {
+            return Ok(());
+        };
+
+        // there's nothing to purge before the context is initialized
+        if *context.root_hash == [0; 32] {
+            return Ok(());
+        }
+
+        let identities = self.context_client.context_members(&context_id, Some(true));
+
+        let Some((our_identity, _)) = choose_stream(identities, &mut rand::thread_rng())
+            .await
+            .transpose()?
+        else {
+            bail!("no owned identities found for context: {}", context_id);
+        };
+
+        let retention =
+            u64::try_from(self.sync_config.tombstone_retention.as_nanos()).unwrap_or(u64::MAX);
+
+        let outcome = self
+            .context_client
+            .execute(
+                &context_id,
+                &our_identity,
+                "__calimero_purge_tombstones".to_owned(),
+                borsh::to_vec(&retention)?,
+                vec![],
+                None,
+            )
+            .await?;
+
+        let _ignored = outcome.returns?;
+
+        Ok(())
+    }
+}
@@ -70,3 +70,18 @@ impl FromStr for BlobId {
         Ok(Self(s.parse().map_err(InvalidBlobId)?))
     }
 }
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct BlobInfo {
+    pub id: BlobId,
+    pub size: u64,
+    pub hash: Hash,
+}
+
+impl BlobInfo {
+    #[must_use]
+    pub const fn new(id: BlobId, size: u64, hash: Hash) -> Self {
+        Self { id, size, hash }
+    }
+}
@@ -210,6 +210,60 @@ impl Context {
     }
 }
 
+/// How the last attempt to sync a context with its peers went.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum SyncStatus {
+    Succeeded,
+    Failed { reason: String },
+    TimedOut,
+}
+
+/// A context's history of syncing with its peers.
+///
+/// Timestamps are in milliseconds since the unix epoch.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ContextSyncRecord {
+    pub last_attempt: u64,
+    pub last_success: Option<u64>,
+    /// The peer synced with, if the last attempt got as far as choosing one.
+    pub peer_id: Option<String>,
+    #[serde(flatten)]
+    pub status: SyncStatus,
+    pub bytes_transferred: u64,
+    pub root_hash_before: Hash,
+    pub root_hash_after: Hash,
+    pub consecutive_failures: u32,
+}
+
+impl ContextSyncRecord {
+    #[must_use]
+    #[expect(clippy::too_many_arguments, reason = "Acceptable here")]
+    pub const fn new(
+        last_attempt: u64,
+        last_success: Option<u64>,
+        peer_id: Option<String>,
+        status: SyncStatus,
+        bytes_transferred: u64,
+        root_hash_before: Hash,
+        root_hash_after: Hash,
+        consecutive_failures: u32,
+    ) -> Self {
+        Self {
+            last_attempt,
+            last_success,
+            peer_id,
+            status,
+            bytes_transferred,
+            root_hash_before,
+            root_hash_after,
+            consecutive_failures,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ContextConfigParams<'a> {
     pub protocol: Cow<'a, str>,
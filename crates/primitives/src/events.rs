@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::context::ContextId;
+use crate::context::{ContextId, ContextSyncRecord};
 use crate::hash::Hash;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -19,10 +19,11 @@ pub struct ContextEvent {
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(tag = "type", content = "data", rename_all = "PascalCase")]
-#[expect(variant_size_differences, reason = "fine for now")]
 pub enum ContextEventPayload {
     StateMutation(StateMutationPayload),
     ExecutionEvent(ExecutionEventPayload),
+    SyncStarted(SyncStartedPayload),
+    SyncFinished(ContextSyncRecord),
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
@@ -38,6 +39,21 @@ impl StateMutationPayload {
     }
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStartedPayload {
+    /// The peer to sync with, unless any peer subscribed to the context will do.
+    pub peer_id: Option<String>,
+    pub root_hash: Hash,
+}
+
+impl SyncStartedPayload {
+    #[must_use]
+    pub const fn new(peer_id: Option<String>, root_hash: Hash) -> Self {
+        Self { peer_id, root_hash }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ExecutionEvent {
     pub kind: String,
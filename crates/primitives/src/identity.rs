@@ -1,7 +1,12 @@
+#[cfg(test)]
+#[path = "tests/identity.rs"]
+mod tests;
+
 use core::fmt;
 use core::ops::Deref;
 use core::str::FromStr;
 
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
 #[cfg(feature = "rand")]
 use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
@@ -40,12 +45,18 @@ impl Deref for PrivateKey {
 impl PrivateKey {
     #[must_use]
     pub fn public_key(&self) -> PublicKey {
-        ed25519_dalek::SigningKey::from_bytes(self)
+        SigningKey::from_bytes(self)
             .verifying_key()
             .to_bytes()
             .into()
     }
 
+    /// Signs the message, for verification with [`PublicKey::verify()`].
+    #[must_use]
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        SigningKey::from_bytes(self).sign(message).to_bytes()
+    }
+
     #[cfg(feature = "rand")]
     pub fn random<R: CryptoRng + RngCore>(csprng: &mut R) -> Self {
         let mut secret = [0; 32];
@@ -123,6 +134,18 @@ impl PublicKey {
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
+
+    /// Checks that the signature was made over the message by the holder of
+    /// the corresponding [`PrivateKey`].
+    #[must_use]
+    pub fn verify(&self, message: &[u8], signature: &[u8; 64]) -> bool {
+        let Ok(key) = VerifyingKey::from_bytes(self) else {
+            return false;
+        };
+
+        key.verify_strict(message, &Signature::from_bytes(signature))
+            .is_ok()
+    }
 }
 
 impl fmt::Display for PublicKey {
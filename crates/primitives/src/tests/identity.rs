@@ -0,0 +1,21 @@
+use super::*;
+
+#[test]
+fn test_sign_verify() {
+    let private_key = PrivateKey::from([7; 32]);
+    let public_key = private_key.public_key();
+
+    let signature = private_key.sign(b"Hello, World");
+
+    assert!(public_key.verify(b"Hello, World", &signature));
+    assert!(!public_key.verify(b"Hello World", &signature));
+
+    let other = PrivateKey::from([8; 32]).public_key();
+
+    assert!(!other.verify(b"Hello, World", &signature));
+
+    let mut tampered = signature;
+    tampered[0] ^= 1;
+
+    assert!(!public_key.verify(b"Hello, World", &tampered));
+}
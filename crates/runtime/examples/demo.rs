@@ -6,7 +6,7 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
-use calimero_runtime::store::InMemoryStorage;
+use calimero_runtime::store::{InMemoryBlobStorage, InMemoryStorage};
 use calimero_runtime::Engine;
 use eyre::Result as EyreResult;
 use owo_colors::OwoColorize;
@@ -52,6 +52,8 @@ fn main() -> EyreResult<()> {
 
     let mut storage = InMemoryStorage::default();
 
+    let mut blobs = InMemoryBlobStorage::default();
+
     let engine = Engine::default();
 
     let module = engine.compile(&file)?;
@@ -73,7 +75,14 @@ fn main() -> EyreResult<()> {
             .transpose()?
             .unwrap_or_default();
 
-        let outcome = module.run([0; 32].into(), [0; 32].into(), &name, &input, &mut storage)?;
+        let outcome = module.run(
+            [0; 32].into(),
+            [0; 32].into(),
+            &name,
+            &input,
+            &mut storage,
+            &mut blobs,
+        )?;
 
         // dbg!(&outcome);
 
@@ -5,7 +5,7 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
-use calimero_runtime::store::InMemoryStorage;
+use calimero_runtime::store::{InMemoryBlobStorage, InMemoryStorage};
 use calimero_runtime::Engine;
 use eyre::Result as EyreResult;
 use serde_json::{json, to_vec as to_json_vec};
@@ -27,6 +27,8 @@ fn main() -> EyreResult<()> {
 
     let mut storage = InMemoryStorage::default();
 
+    let mut blobs = InMemoryBlobStorage::default();
+
     let engine = Engine::default();
 
     let module = engine.compile(&file)?;
@@ -42,6 +44,7 @@ fn main() -> EyreResult<()> {
         "view_account",
         &input,
         &mut storage,
+        &mut blobs,
     )?;
 
     let returns = String::from_utf8(outcome.returns.unwrap().unwrap()).unwrap();
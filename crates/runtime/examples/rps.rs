@@ -6,7 +6,7 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
-use calimero_runtime::store::InMemoryStorage;
+use calimero_runtime::store::{InMemoryBlobStorage, InMemoryStorage};
 use calimero_runtime::Engine;
 use eyre::Result as EyreResult;
 use owo_colors::OwoColorize;
@@ -66,6 +66,8 @@ fn main() -> EyreResult<()> {
 
     let mut storage = InMemoryStorage::default();
 
+    let mut blobs = InMemoryBlobStorage::default();
+
     let engine = Engine::default();
 
     let module = engine.compile(&file)?;
@@ -86,6 +88,7 @@ fn main() -> EyreResult<()> {
         "create_keypair",
         &input,
         &mut storage,
+        &mut blobs,
     )?;
     dbg!(&create_keypair_outcome);
 
@@ -113,6 +116,7 @@ fn main() -> EyreResult<()> {
         "create_keypair",
         &input,
         &mut storage,
+        &mut blobs,
     )?;
     dbg!(&create_keypair_outcome);
 
@@ -133,7 +137,14 @@ fn main() -> EyreResult<()> {
         "public_key": joe_keypair.pk,
     }))?;
 
-    let join_outcome = module.run([0; 32].into(), [0; 32].into(), "join", &input, &mut storage)?;
+    let join_outcome = module.run(
+        [0; 32].into(),
+        [0; 32].into(),
+        "join",
+        &input,
+        &mut storage,
+        &mut blobs,
+    )?;
     dbg!(&join_outcome);
 
     let joe_idx =
@@ -150,7 +161,14 @@ fn main() -> EyreResult<()> {
         "public_key": melissa_keypair.pk,
     }))?;
 
-    let join_outcome = module.run([0; 32].into(), [0; 32].into(), "join", &input, &mut storage)?;
+    let join_outcome = module.run(
+        [0; 32].into(),
+        [0; 32].into(),
+        "join",
+        &input,
+        &mut storage,
+        &mut blobs,
+    )?;
     dbg!(&join_outcome);
 
     let melissa_idx =
@@ -171,6 +189,7 @@ fn main() -> EyreResult<()> {
         "state",
         &input,
         &mut storage,
+        &mut blobs,
     )?;
     dbg!(&state_outcome);
 
@@ -199,6 +218,7 @@ fn main() -> EyreResult<()> {
         "prepare",
         &input,
         &mut storage,
+        &mut blobs,
     )?;
     dbg!(&prepare_outcome);
 
@@ -227,6 +247,7 @@ fn main() -> EyreResult<()> {
         "prepare",
         &input,
         &mut storage,
+        &mut blobs,
     )?;
     dbg!(&prepare_outcome);
 
@@ -252,6 +273,7 @@ fn main() -> EyreResult<()> {
         "commit",
         &input,
         &mut storage,
+        &mut blobs,
     )?;
     dbg!(&commit_outcome);
 
@@ -273,6 +295,7 @@ fn main() -> EyreResult<()> {
         "commit",
         &input,
         &mut storage,
+        &mut blobs,
     )?;
     dbg!(&commit_outcome);
 
@@ -293,6 +316,7 @@ fn main() -> EyreResult<()> {
         "reveal",
         &input,
         &mut storage,
+        &mut blobs,
     )?;
     dbg!(&reveal_outcome);
 
@@ -313,6 +337,7 @@ fn main() -> EyreResult<()> {
         "reveal",
         &input,
         &mut storage,
+        &mut blobs,
     )?;
     dbg!(&reveal_outcome);
 
@@ -331,6 +356,7 @@ fn main() -> EyreResult<()> {
         "state",
         &input,
         &mut storage,
+        &mut blobs,
     )?;
     dbg!(&state_outcome);
 
@@ -362,6 +388,7 @@ fn main() -> EyreResult<()> {
         "reset",
         &input,
         &mut storage,
+        &mut blobs,
     )?;
     dbg!(&reset_outcome);
 
@@ -373,6 +400,7 @@ fn main() -> EyreResult<()> {
         "state",
         &input,
         &mut storage,
+        &mut blobs,
     )?;
     dbg!(&state_outcome);
 
@@ -0,0 +1,60 @@
+use core::fmt::Debug;
+use std::time::Instant;
+
+/// A call made by a guest to a method of another context.
+///
+/// Calls are always made without letting the callee change state, and on
+/// behalf of the identity executing the caller.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ContextCall<'a> {
+    pub context_id: [u8; 32],
+    pub executor_public_key: [u8; 32],
+    pub method: &'a str,
+    pub args: &'a [u8],
+    /// What the callee may use, which is whatever the caller has left.
+    pub budget: CallBudget,
+}
+
+/// What an execution may use on top of the limits, when it's made by another
+/// context.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CallBudget {
+    /// How many calls deep the execution runs, starting at one for a call made
+    /// by a top-level execution.
+    pub depth: u32,
+    /// The most gas the execution may use.
+    pub gas: Option<u64>,
+    /// The instant by which the execution must finish.
+    pub deadline: Option<Instant>,
+}
+
+/// The result of a call made by a guest to another context.
+#[derive(Debug)]
+pub struct ContextCallOutcome {
+    /// The value returned by the callee, or a message describing why the call
+    /// failed, which is passed on to the guest.
+    pub returns: Result<Vec<u8>, String>,
+    /// The gas used by the callee, which is charged to the caller.
+    pub gas_used: u64,
+}
+
+/// Performs the calls made by guests to other contexts.
+pub trait ContextCallBackend: Debug + Send + Sync {
+    /// Runs the callee within the given budget.
+    fn call(&self, call: &ContextCall<'_>) -> ContextCallOutcome;
+}
+
+/// Rejects every call, for hosts which can't run other contexts.
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct NoContextCalls;
+
+impl ContextCallBackend for NoContextCalls {
+    fn call(&self, _call: &ContextCall<'_>) -> ContextCallOutcome {
+        ContextCallOutcome {
+            returns: Err("cross-context calls are not supported by this host".to_owned()),
+            gas_used: 0,
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use core::ptr::NonNull;
+use core::sync::atomic::{AtomicI32, Ordering};
+use std::collections::BTreeMap;
+use std::sync::{Condvar, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::thread;
+use std::time::Instant;
+
+use wasmer::wasmparser::{BlockType, Operator};
+use wasmer::{
+    AsStoreMut, ExportIndex, FunctionMiddleware, GlobalInit, GlobalType, Instance,
+    LocalFunctionIndex, MiddlewareError, MiddlewareReaderState, ModuleMiddleware, Mutability, Type,
+};
+use wasmer_types::{GlobalIndex, ModuleInfo};
+use wasmer_vm::{VMExtern, VMGlobalDefinition};
+
+/// The export through which the guest is told to stop, once set to non-zero.
+pub(crate) const INTERRUPTED: &str = "calimero_interrupted";
+
+/// Finds the interrupt flag of an instance, through the global exported for
+/// it by the middleware.
+pub(crate) fn interrupt_flag(
+    store: &mut impl AsStoreMut,
+    instance: &Instance,
+) -> Option<NonNull<VMGlobalDefinition>> {
+    let export = instance.exports.get_extern(INTERRUPTED)?;
+
+    let VMExtern::Global(handle) = export.to_vm_extern() else {
+        return None;
+    };
+
+    Some(handle.get(store.objects_mut()).vmglobal())
+}
+
+/// Instruments a module to check whether it has been interrupted on entering
+/// any function and on every iteration of any loop, trapping if so.
+///
+/// Like the metering middleware, it can only be used for a single module.
+#[derive(Debug, Default)]
+pub(crate) struct Deadline {
+    global: Mutex<Option<GlobalIndex>>,
+}
+
+impl ModuleMiddleware for Deadline {
+    #[expect(clippy::expect_used, reason = "Effectively infallible here")]
+    fn generate_function_middleware(&self, _: LocalFunctionIndex) -> Box<dyn FunctionMiddleware> {
+        let global = self
+            .global
+            .lock()
+            .expect("mutex should not be poisoned")
+            .expect("module info should have been transformed");
+
+        Box::new(FunctionDeadline {
+            global,
+            entered: false,
+        })
+    }
+
+    #[expect(clippy::expect_used, reason = "Effectively infallible here")]
+    fn transform_module_info(&self, info: &mut ModuleInfo) -> Result<(), MiddlewareError> {
+        let mut global = self.global.lock().expect("mutex should not be poisoned");
+
+        if global.is_some() {
+            return Err(MiddlewareError::new(
+                "Deadline",
+                "the middleware was already used for another module",
+            ));
+        }
+
+        let index = info
+            .globals
+            .push(GlobalType::new(Type::I32, Mutability::Var));
+
+        let _ignored = info.global_initializers.push(GlobalInit::I32Const(0));
+
+        let _ignored = info
+            .exports
+            .insert(INTERRUPTED.to_owned(), ExportIndex::Global(index));
+
+        *global = Some(index);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct FunctionDeadline {
+    global: GlobalIndex,
+    entered: bool,
+}
+
+impl FunctionDeadline {
+    fn check(&self, state: &mut MiddlewareReaderState<'_>) {
+        state.extend(&[
+            Operator::GlobalGet {
+                global_index: self.global.as_u32(),
+            },
+            Operator::If {
+                blockty: BlockType::Empty,
+            },
+            Operator::Unreachable,
+            Operator::End,
+        ]);
+    }
+}
+
+impl FunctionMiddleware for FunctionDeadline {
+    fn feed<'a>(
+        &mut self,
+        operator: Operator<'a>,
+        state: &mut MiddlewareReaderState<'a>,
+    ) -> Result<(), MiddlewareError> {
+        if !self.entered {
+            self.entered = true;
+
+            self.check(state);
+        }
+
+        let is_loop = matches!(operator, Operator::Loop { .. });
+
+        state.push_operator(operator);
+
+        if is_loop {
+            self.check(state);
+        }
+
+        Ok(())
+    }
+}
+
+/// The interrupt flag of an instance, as it's set from another thread.
+#[derive(Debug)]
+struct Flag(NonNull<VMGlobalDefinition>);
+
+// the flag is only ever accessed atomically, and outlives the watchdog
+unsafe impl Send for Flag {}
+
+impl Flag {
+    fn raise(&self) {
+        // the guest reads the flag on every loop iteration, so it must be
+        // written atomically, as it's done running concurrently
+        unsafe { AtomicI32::from_ptr(self.0.as_ptr().cast::<i32>()) }.store(1, Ordering::SeqCst);
+    }
+}
+
+/// The instances being watched, by their deadline and the id of their
+/// watchdog.
+#[derive(Debug, Default)]
+struct Watched {
+    flags: BTreeMap<(Instant, u64), Flag>,
+    next_id: u64,
+}
+
+/// Interrupts the instances being watched as their deadlines pass, from a
+/// single thread shared by every watchdog.
+#[derive(Debug, Default)]
+struct Timer {
+    watched: Mutex<Watched>,
+    changed: Condvar,
+}
+
+impl Timer {
+    /// The timer, whose thread is started on first use.
+    fn get() -> &'static Self {
+        static TIMER: OnceLock<Timer> = OnceLock::new();
+
+        let mut created = false;
+
+        let timer = TIMER.get_or_init(|| {
+            created = true;
+
+            Self::default()
+        });
+
+        if created {
+            let _ignored = thread::spawn(|| timer.run());
+        }
+
+        timer
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Watched> {
+        // nothing panics while holding the lock, but the map is sound anyway
+        self.watched.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn run(&self) {
+        let mut watched = self.lock();
+
+        loop {
+            let now = Instant::now();
+
+            while let Some(entry) = watched.flags.first_entry() {
+                if entry.key().0 > now {
+                    break;
+                }
+
+                entry.remove().raise();
+            }
+
+            watched = match watched.flags.keys().next() {
+                Some(&(deadline, _)) => {
+                    self.changed
+                        .wait_timeout(watched, deadline.saturating_duration_since(now))
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self
+                    .changed
+                    .wait(watched)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+}
+
+/// Interrupts a running instance once its deadline passes, unless it is
+/// stopped beforehand.
+#[derive(Debug)]
+pub(crate) struct Watchdog {
+    key: Option<(Instant, u64)>,
+}
+
+impl Watchdog {
+    /// Starts watching the instance whose interrupt flag is given.
+    ///
+    /// # Safety
+    ///
+    /// The flag must stay alive until the watchdog is stopped or dropped,
+    /// after which it is no longer touched.
+    pub(crate) unsafe fn start(flag: NonNull<VMGlobalDefinition>, deadline: Instant) -> Self {
+        let timer = Timer::get();
+
+        let mut watched = timer.lock();
+
+        let id = watched.next_id;
+
+        watched.next_id = id.wrapping_add(1);
+
+        let _ignored = watched.flags.insert((deadline, id), Flag(flag));
+
+        drop(watched);
+
+        timer.changed.notify_one();
+
+        Self {
+            key: Some((deadline, id)),
+        }
+    }
+
+    /// Stops watching the instance, returning whether it was interrupted.
+    pub(crate) fn stop(mut self) -> bool {
+        self.unwatch()
+    }
+
+    /// Stops watching the instance, returning whether the timer got to it
+    /// first. Once the lock is released, the timer no longer has the flag.
+    fn unwatch(&mut self) -> bool {
+        let Some(key) = self.key.take() else {
+            return false;
+        };
+
+        Timer::get().lock().flags.remove(&key).is_none()
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        let _ignored = self.unwatch();
+    }
+}
@@ -47,6 +47,10 @@ pub enum FunctionCallError {
     HostError(HostError),
     #[error("the method call returned an error: {0:?}")]
     ExecutionError(Vec<u8>),
+    #[error("out of gas")]
+    OutOfGas,
+    #[error("execution timed out")]
+    TimedOut,
 }
 
 #[derive(Debug, Serialize, ThisError)]
@@ -102,6 +106,30 @@ pub enum HostError {
     EventKindSizeOverflow,
     #[error("event data size overflow")]
     EventDataSizeOverflow,
+    #[error("invalid blob handle: {handle}")]
+    InvalidBlobHandle { handle: u64 },
+    #[error("blob handles overflow")]
+    BlobHandlesOverflow,
+    #[error("blob chunk size overflow")]
+    BlobChunkSizeOverflow,
+    #[error("failed to access blob")]
+    BlobAccessError,
+    #[error("state is read-only")]
+    ReadOnly,
+    #[error("fetch not allowed: {reason}")]
+    FetchNotAllowed { reason: String },
+    #[error("fetch calls overflow")]
+    FetchCallsOverflow,
+    #[error("storage quota exceeded")]
+    StorageQuotaExceeded,
+    #[error("invalid storage iterator: {handle}")]
+    InvalidStorageIterator { handle: u64 },
+    #[error("storage iterators overflow")]
+    StorageIteratorsOverflow,
+    #[error("call depth overflow")]
+    CallDepthOverflow,
+    #[error("the execution diverged from the one being replayed")]
+    ReplayDiverged,
 }
 
 #[derive(Copy, Clone, Debug, Serialize)]
@@ -0,0 +1,282 @@
+use core::fmt::Debug;
+use core::time::Duration;
+use std::collections::BTreeMap;
+use std::io::{Error as IoError, ErrorKind as IoErrorKind, Read, Result as IoResult};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::sync::Mutex;
+
+use calimero_primitives::utils::serde_duration;
+use serde::{Deserialize, Serialize};
+use url::{Host, Url};
+
+#[cfg(test)]
+#[path = "tests/fetch.rs"]
+mod tests;
+
+/// What guests are allowed to fetch.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+#[non_exhaustive]
+pub struct FetchPolicy {
+    /// Hosts which may be fetched from, none by default. Entries may be a host
+    /// name, a `*.`-prefixed domain to match any of its subdomains, or `*` to
+    /// match any host. Loopback and private addresses are only matched when
+    /// they are listed exactly.
+    pub allowed_hosts: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub max_response_size: u64,
+    #[serde(rename = "timeout_ms", with = "serde_duration")]
+    pub timeout: Duration,
+    pub max_calls: u64,
+}
+
+impl Default for FetchPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_hosts: vec![],
+            allowed_methods: ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE"]
+                .map(str::to_owned)
+                .to_vec(),
+            max_response_size: 10 << 20, // 10 MiB
+            timeout: Duration::from_secs(10),
+            max_calls: 10,
+        }
+    }
+}
+
+impl FetchPolicy {
+    /// Checks whether a request may be made, returning the reason if not.
+    pub fn check(&self, url: &str, method: &str) -> Result<(), String> {
+        let url = Url::parse(url).map_err(|err| format!("invalid url: {err}"))?;
+
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(format!("scheme `{}` is not allowed", url.scheme()));
+        }
+
+        let Some(host) = url.host_str() else {
+            return Err("url has no host".to_owned());
+        };
+
+        let host_allowed = self.allowed_hosts.iter().any(|allowed| {
+            allowed == "*"
+                || allowed.eq_ignore_ascii_case(host)
+                || allowed.strip_prefix("*.").is_some_and(|domain| {
+                    host.len() > domain.len()
+                        && host
+                            .to_ascii_lowercase()
+                            .ends_with(&format!(".{}", domain.to_ascii_lowercase()))
+                })
+        });
+
+        if !host_allowed {
+            return Err(format!("host `{host}` is not allowed"));
+        }
+
+        if url.host().is_some_and(|host| is_local(&host))
+            && !self
+                .allowed_hosts
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(host))
+        {
+            return Err(format!(
+                "host `{host}` is local and must be allowed explicitly"
+            ));
+        }
+
+        if !self
+            .allowed_methods
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(method))
+        {
+            return Err(format!("method `{method}` is not allowed"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Resolves `host:port`, refusing hosts which resolve to a local address
+/// unless they are listed exactly, as [`FetchPolicy::check`] only sees the
+/// host named in the url.
+fn resolve(netloc: &str, allowed_hosts: &[String]) -> IoResult<Vec<SocketAddr>> {
+    let addrs: Vec<_> = netloc.to_socket_addrs()?.collect();
+
+    let host = netloc.rsplit_once(':').map_or(netloc, |(host, _)| host);
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    if allowed_hosts
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(host))
+    {
+        return Ok(addrs);
+    }
+
+    let resolves_local = addrs.iter().any(|addr| match addr.ip() {
+        IpAddr::V4(ip) => is_local(&Host::Ipv4(ip)),
+        IpAddr::V6(ip) => is_local(&Host::Ipv6(ip)),
+    });
+
+    if resolves_local {
+        return Err(IoError::new(
+            IoErrorKind::PermissionDenied,
+            format!("host `{host}` resolves to a local address and must be allowed explicitly"),
+        ));
+    }
+
+    Ok(addrs)
+}
+
+#[derive(Debug)]
+struct PolicyResolver {
+    allowed_hosts: Vec<String>,
+}
+
+impl ureq::Resolver for PolicyResolver {
+    fn resolve(&self, netloc: &str) -> IoResult<Vec<SocketAddr>> {
+        resolve(netloc, &self.allowed_hosts)
+    }
+}
+
+/// Whether a host refers to the node itself or to its private network.
+fn is_local(host: &Host<&str>) -> bool {
+    match host {
+        Host::Domain(domain) => {
+            let domain = domain.to_ascii_lowercase();
+
+            domain == "localhost" || domain.ends_with(".localhost")
+        }
+        Host::Ipv4(ip) => {
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+        }
+        Host::Ipv6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_local(&Host::Ipv4(ip));
+            }
+
+            ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_unique_local()
+                || ip.is_unicast_link_local()
+        }
+    }
+}
+
+/// A request made by a guest, after it has been checked against the policy.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct FetchRequest<'a> {
+    pub url: &'a str,
+    pub method: &'a str,
+    pub headers: &'a [(String, String)],
+    pub body: &'a [u8],
+    pub timeout: Duration,
+    pub max_response_size: u64,
+    /// The hosts allowed by the policy, of which only those listed exactly
+    /// may resolve to a local address.
+    pub allowed_hosts: &'a [String],
+}
+
+/// Performs the requests made by guests.
+pub trait FetchBackend: Debug + Send + Sync {
+    /// Returns the response body, or a message describing why the request
+    /// failed, which is passed on to the guest.
+    fn fetch(&self, request: &FetchRequest<'_>) -> Result<Vec<u8>, String>;
+}
+
+/// Makes requests over the network. Redirects are not followed, since the
+/// policy was only checked against the url the guest asked for.
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct HttpFetchBackend;
+
+impl FetchBackend for HttpFetchBackend {
+    fn fetch(&self, request: &FetchRequest<'_>) -> Result<Vec<u8>, String> {
+        let agent = ureq::AgentBuilder::new()
+            .redirects(0)
+            .timeout(request.timeout)
+            .resolver(PolicyResolver {
+                allowed_hosts: request.allowed_hosts.to_vec(),
+            })
+            .build();
+
+        let mut builder = agent.request(request.method, request.url);
+
+        for (key, value) in request.headers {
+            builder = builder.set(key, value);
+        }
+
+        let response = if request.body.is_empty() {
+            builder.call()
+        } else {
+            builder.send_bytes(request.body)
+        }
+        .map_err(|err| err.to_string())?;
+
+        if (300..400).contains(&response.status()) {
+            return Err(format!(
+                "redirects are not followed, got status {}",
+                response.status()
+            ));
+        }
+
+        let mut buffer = vec![];
+
+        let _ignored = response
+            .into_reader()
+            .take(request.max_response_size.saturating_add(1))
+            .read_to_end(&mut buffer)
+            .map_err(|_| "Failed to read the response body.".to_owned())?;
+
+        if buffer.len() as u64 > request.max_response_size {
+            return Err("The response body exceeds the size limit.".to_owned());
+        }
+
+        Ok(buffer)
+    }
+}
+
+/// Rejects every request, for executions which must not reach the network.
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct NoFetch;
+
+impl FetchBackend for NoFetch {
+    fn fetch(&self, _request: &FetchRequest<'_>) -> Result<Vec<u8>, String> {
+        Err("requests are not made by this execution".to_owned())
+    }
+}
+
+/// Serves canned responses, for tests and nodes without network access.
+#[derive(Debug, Default)]
+pub struct InMemoryFetchBackend {
+    responses: Mutex<BTreeMap<(String, String), Result<Vec<u8>, String>>>,
+}
+
+impl InMemoryFetchBackend {
+    /// Sets the response to requests for the given method and url.
+    pub fn respond(&self, method: &str, url: &str, response: Result<Vec<u8>, String>) {
+        let mut responses = self.responses.lock().expect("mutex should not be poisoned");
+
+        drop(responses.insert((method.to_ascii_uppercase(), url.to_owned()), response));
+    }
+}
+
+impl FetchBackend for InMemoryFetchBackend {
+    fn fetch(&self, request: &FetchRequest<'_>) -> Result<Vec<u8>, String> {
+        let responses = self.responses.lock().expect("mutex should not be poisoned");
+
+        responses
+            .get(&(request.method.to_ascii_uppercase(), request.url.to_owned()))
+            .cloned()
+            .unwrap_or_else(|| {
+                Err(format!(
+                    "no response for {} {}",
+                    request.method, request.url
+                ))
+            })
+    }
+}
@@ -0,0 +1,40 @@
+use wasmer::wasmparser::Operator;
+
+/// The export through which the metering middleware exposes the gas left to
+/// the guest.
+pub(crate) const REMAINING_POINTS: &str = "wasmer_metering_remaining_points";
+
+/// The cost of executing a single wasm instruction.
+#[expect(
+    clippy::wildcard_enum_match_arm,
+    reason = "Every other instruction has the same cost"
+)]
+pub(crate) const fn instruction_cost(operator: &Operator<'_>) -> u64 {
+    match operator {
+        Operator::Call { .. } | Operator::CallIndirect { .. } => 10,
+        Operator::MemoryGrow { .. } => 1_000,
+        _ => 1,
+    }
+}
+
+/// The cost of each byte read, written or fetched by a host function, charged
+/// on top of the cost of calling it.
+const BYTE_COST: u64 = 10;
+
+/// The cost of the bytes moved by a host function call, charged once it has
+/// been made.
+pub(crate) const fn bytes_cost(bytes: u64) -> u64 {
+    bytes.saturating_mul(BYTE_COST)
+}
+
+/// The cost of calling a host function, charged before the call is made.
+pub(crate) fn host_function_cost(name: &str) -> u64 {
+    match name {
+        "storage_read" | "storage_write" | "storage_remove" => 10_000,
+        "storage_iter_prefix" | "storage_iter_range" | "storage_iter_next" => 10_000,
+        "blob_open" | "blob_read" | "blob_create" | "blob_write" | "blob_close" => 10_000,
+        "send_proposal" | "approve_proposal" => 10_000,
+        "fetch" | "call_context" => 1_000_000,
+        _ => 100,
+    }
+}
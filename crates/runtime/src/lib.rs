@@ -1,60 +1,123 @@
+use core::fmt;
+use std::sync::Arc;
+
 use calimero_primitives::context::ContextId;
 use calimero_primitives::identity::PublicKey;
-use wasmer::{CompileError, DeserializeError, Instance, NativeEngineExt, SerializeError, Store};
+use wasmer::sys::EngineBuilder;
+use wasmer::{
+    CompileError, CompilerConfig, Cranelift, DeserializeError, Instance, NativeEngineExt,
+    SerializeError, Store,
+};
+use wasmer_middlewares::metering::{get_remaining_points, set_remaining_points, MeteringPoints};
+use wasmer_middlewares::Metering;
 
+pub mod calls;
 mod constraint;
+mod deadline;
 pub mod errors;
+pub mod fetch;
+mod gas;
 pub mod logic;
 mod memory;
+mod prepare;
 pub mod store;
 
+use calls::{CallBudget, ContextCallBackend, NoContextCalls};
 pub use constraint::Constraint;
+use deadline::{Deadline, Watchdog};
 use errors::{FunctionCallError, VMRuntimeError};
-use logic::{Outcome, VMContext, VMLimits, VMLogic, VMLogicError};
+use fetch::{FetchBackend, HttpFetchBackend};
+use logic::{Outcome, Replay, VMContext, VMLimits, VMLogic, VMLogicError};
 use memory::WasmerTunables;
-use store::Storage;
+pub use prepare::prepare;
+use store::{BlobStorage, Storage};
 
 pub type RuntimeResult<T, E = VMRuntimeError> = Result<T, E>;
 
-#[derive(Clone, Debug)]
+/// Makes the compiler configuration a module is compiled with.
+type CompilerFactory = Arc<dyn Fn() -> Box<dyn CompilerConfig> + Send + Sync>;
+
+#[derive(Clone)]
 pub struct Engine {
     limits: VMLimits,
+    compiler: CompilerFactory,
     engine: wasmer::Engine,
+    fetch: Arc<dyn FetchBackend>,
+    calls: Arc<dyn ContextCallBackend>,
+}
+
+impl fmt::Debug for Engine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Engine")
+            .field("limits", &self.limits)
+            .field("engine", &self.engine)
+            .field("fetch", &self.fetch)
+            .field("calls", &self.calls)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Default for Engine {
     fn default() -> Self {
         let limits = VMLimits::default();
 
-        let engine = wasmer::Engine::default();
-
-        Self::new(engine, limits)
+        Self::with_limits(limits)
     }
 }
 
 impl Engine {
-    pub fn new(mut engine: wasmer::Engine, limits: VMLimits) -> Self {
-        engine.set_tunables(WasmerTunables::new(&limits));
+    #[must_use]
+    pub fn with_limits(limits: VMLimits) -> Self {
+        Self::new(Cranelift::default(), limits)
+    }
+
+    pub fn new<C>(compiler: C, limits: VMLimits) -> Self
+    where
+        C: CompilerConfig + Clone + Send + Sync + 'static,
+    {
+        let compiler: CompilerFactory =
+            Arc::new(move || -> Box<dyn CompilerConfig> { Box::new(compiler.clone()) });
+
+        let engine = build_engine(&compiler, &limits);
 
-        Self { limits, engine }
+        Self {
+            limits,
+            compiler,
+            engine,
+            fetch: Arc::new(HttpFetchBackend),
+            calls: Arc::new(NoContextCalls),
+        }
+    }
+
+    /// Replaces the backend which performs the requests made by guests.
+    #[must_use]
+    pub fn with_fetch_backend(mut self, backend: Arc<dyn FetchBackend>) -> Self {
+        self.fetch = backend;
+        self
+    }
+
+    /// Replaces the backend which performs the calls made by guests to other
+    /// contexts.
+    #[must_use]
+    pub fn with_context_call_backend(mut self, backend: Arc<dyn ContextCallBackend>) -> Self {
+        self.calls = backend;
+        self
     }
 
     pub fn compile(&self, bytes: &[u8]) -> Result<Module, CompileError> {
-        // todo! apply a prepare step
-        // todo! - parse the wasm blob, validate and apply transformations
-        // todo!   - validations:
-        // todo!     - there is no memory import
-        // todo!     - there is no _start function
-        // todo!   - transformations:
-        // todo!     - remove memory export
-        // todo!     - remove memory section
-        // todo! cache the compiled module in storage for later
-
-        let module = wasmer::Module::new(&self.engine, bytes)?;
+        prepare(bytes, &self.limits)?;
+
+        // the middlewares instrumenting a module keep state about it, so every
+        // module is compiled by an engine of its own
+        let engine = build_engine(&self.compiler, &self.limits);
+
+        let module = wasmer::Module::new(&engine, bytes)?;
 
         Ok(Module {
             limits: self.limits.clone(),
-            engine: self.engine.clone(),
+            engine,
+            fetch: Arc::clone(&self.fetch),
+            calls: Arc::clone(&self.calls),
             module,
         })
     }
@@ -62,22 +125,85 @@ impl Engine {
     pub unsafe fn from_precompiled(&self, bytes: &[u8]) -> Result<Module, DeserializeError> {
         let module = wasmer::Module::deserialize(&self.engine, bytes)?;
 
+        // modules compiled before metering was introduced would run unmetered
+        if !module
+            .exports()
+            .any(|export| export.name() == gas::REMAINING_POINTS)
+        {
+            return Err(DeserializeError::Incompatible(
+                "module was compiled without gas metering".to_owned(),
+            ));
+        }
+
+        // and those compiled before deadlines were, could run forever
+        if !module
+            .exports()
+            .any(|export| export.name() == deadline::INTERRUPTED)
+        {
+            return Err(DeserializeError::Incompatible(
+                "module was compiled without deadline checks".to_owned(),
+            ));
+        }
+
         Ok(Module {
             limits: self.limits.clone(),
             engine: self.engine.clone(),
+            fetch: Arc::clone(&self.fetch),
+            calls: Arc::clone(&self.calls),
             module,
         })
     }
 }
 
-#[derive(Debug)]
+fn build_engine(compiler: &CompilerFactory, limits: &VMLimits) -> wasmer::Engine {
+    let mut compiler = compiler();
+
+    compiler.canonicalize_nans(true);
+
+    compiler.push_middleware(Arc::new(Metering::new(
+        limits.max_gas,
+        gas::instruction_cost,
+    )));
+
+    compiler.push_middleware(Arc::new(Deadline::default()));
+
+    let mut engine: wasmer::Engine = EngineBuilder::new(compiler).into();
+
+    engine.set_tunables(WasmerTunables::new(limits));
+
+    engine
+}
+
+#[derive(Clone, Debug)]
 pub struct Module {
     limits: VMLimits,
     engine: wasmer::Engine,
+    fetch: Arc<dyn FetchBackend>,
+    calls: Arc<dyn ContextCallBackend>,
     module: wasmer::Module,
 }
 
 impl Module {
+    #[must_use]
+    pub const fn limits(&self) -> &VMLimits {
+        &self.limits
+    }
+
+    /// Replaces the backend which performs the requests made by the guest.
+    #[must_use]
+    pub fn with_fetch_backend(mut self, backend: Arc<dyn FetchBackend>) -> Self {
+        self.fetch = backend;
+        self
+    }
+
+    /// Lifts the storage quota, for executions applying state agreed on
+    /// with peers, which must not fail because of a limit local to this node.
+    #[must_use]
+    pub fn without_storage_quota(mut self) -> Self {
+        self.limits.max_storage_usage = u64::MAX;
+        self
+    }
+
     pub fn to_bytes(&self) -> Result<Box<[u8]>, SerializeError> {
         let bytes = self.module.serialize()?;
 
@@ -91,10 +217,76 @@ impl Module {
         method: &str,
         input: &[u8],
         storage: &mut dyn Storage,
+        blobs: &mut dyn BlobStorage,
     ) -> RuntimeResult<Outcome> {
         let context = VMContext::new(input.into(), *context, *executor);
 
-        let mut logic = VMLogic::new(storage, context, &self.limits);
+        self.execute(context, method, storage, blobs)
+    }
+
+    /// Runs a method without letting it change state, failing the call if
+    /// it attempts to.
+    ///
+    /// `budget` bounds what the method may use when it's called by another
+    /// context, and is the default otherwise.
+    #[expect(clippy::too_many_arguments, reason = "Acceptable here")]
+    pub fn view(
+        &self,
+        context: ContextId,
+        executor: PublicKey,
+        method: &str,
+        input: &[u8],
+        budget: CallBudget,
+        storage: &mut dyn Storage,
+        blobs: &mut dyn BlobStorage,
+    ) -> RuntimeResult<Outcome> {
+        let mut context = VMContext::new(input.into(), *context, *executor);
+
+        context.read_only = true;
+        context.budget = budget;
+
+        self.execute(context, method, storage, blobs)
+    }
+
+    /// Runs a method again, with the same randomness, time, responses to
+    /// requests and blobs as the execution that recorded the given inputs in
+    /// its [`Outcome`]. The replay fails if it makes requests or reads blobs
+    /// the recorded execution didn't.
+    #[expect(clippy::too_many_arguments, reason = "Acceptable here")]
+    pub fn replay(
+        &self,
+        context: ContextId,
+        executor: PublicKey,
+        method: &str,
+        input: &[u8],
+        replay: Replay,
+        storage: &mut dyn Storage,
+        blobs: &mut dyn BlobStorage,
+    ) -> RuntimeResult<Outcome> {
+        let mut context = VMContext::new(input.into(), *context, *executor);
+
+        context.replay = Some(replay);
+
+        self.execute(context, method, storage, blobs)
+    }
+
+    fn execute(
+        &self,
+        context: VMContext<'_>,
+        method: &str,
+        storage: &mut dyn Storage,
+        blobs: &mut dyn BlobStorage,
+    ) -> RuntimeResult<Outcome> {
+        let mut logic = VMLogic::new(
+            storage,
+            blobs,
+            &*self.fetch,
+            &*self.calls,
+            context,
+            &self.limits,
+        );
+
+        let max_gas = logic.max_gas();
 
         let mut store = Store::new(self.engine.clone());
 
@@ -105,12 +297,23 @@ impl Module {
             Err(err) => return Ok(logic.finish(Some(err.into()))),
         };
 
+        let interrupt_flag = deadline::interrupt_flag(&mut store, &instance);
+
         let _ = match instance.exports.get_memory("memory") {
             Ok(memory) => logic.with_memory(memory.clone()),
             // todo! test memory returns MethodNotFound
             Err(err) => return Ok(logic.finish(Some(err.into()))),
         };
 
+        let metered = match instance.exports.get_global(gas::REMAINING_POINTS) {
+            Ok(counter) => {
+                set_remaining_points(&mut store, &instance, max_gas);
+                let _ = logic.with_gas_counter(counter.clone());
+                true
+            }
+            Err(_) => false,
+        };
+
         let function = match instance.exports.get_function(method) {
             Ok(function) => function,
             Err(err) => return Ok(logic.finish(Some(err.into()))),
@@ -126,7 +329,34 @@ impl Module {
             ))));
         }
 
-        if let Err(err) = function.call(&mut store, &[]) {
+        // the flag belongs to the store, and the watchdog is stopped as soon
+        // as the call returns, so it's alive for as long as it may be set
+        let watchdog = interrupt_flag
+            .zip(logic.deadline())
+            .map(|(flag, deadline)| unsafe { Watchdog::start(flag, deadline) });
+
+        let result = function.call(&mut store, &[]);
+
+        let interrupted = watchdog.is_some_and(Watchdog::stop);
+
+        let guest_gas = if metered {
+            match get_remaining_points(&mut store, &instance) {
+                MeteringPoints::Remaining(remaining) => Some(max_gas.saturating_sub(remaining)),
+                MeteringPoints::Exhausted => None,
+            }
+        } else {
+            Some(0)
+        };
+
+        if !logic.settle_gas(guest_gas) {
+            return Ok(logic.finish(Some(FunctionCallError::OutOfGas)));
+        }
+
+        if let Err(err) = result {
+            if interrupted {
+                return Ok(logic.finish(Some(FunctionCallError::TimedOut)));
+            }
+
             return match err.downcast::<VMLogicError>() {
                 Ok(err) => Ok(logic.finish(Some(err.try_into()?))),
                 Err(err) => Ok(logic.finish(Some(err.into()))),
@@ -139,5 +369,7 @@ impl Module {
 
 #[cfg(test)]
 mod integration_tests_package_usage {
-    use {eyre as _, owo_colors as _, rand as _};
+    use eyre as _;
+    use owo_colors as _;
+    use rand as _;
 }
@@ -1,38 +1,59 @@
 #![allow(single_use_lifetimes, unused_lifetimes, reason = "False positive")]
 #![allow(clippy::mem_forget, reason = "Safe for now")]
 
+#[cfg(test)]
+#[path = "tests/logic.rs"]
+mod tests;
+
 use core::fmt;
 use core::num::NonZeroU64;
+use core::ops::Bound;
 use std::borrow::Cow;
-use std::collections::BTreeMap;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use std::vec;
 
 use borsh::from_slice as from_borsh_slice;
+use calimero_primitives::blobs::BlobId;
 use ouroboros::self_referencing;
-use rand::RngCore;
-use serde::Serialize;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+use wasmer::{AsStoreMut, AsStoreRef};
 
+use crate::calls::{CallBudget, ContextCall, ContextCallBackend};
 use crate::constraint::{Constrained, MaxU64};
 use crate::errors::{FunctionCallError, HostError, Location, PanicContext};
-use crate::store::Storage;
-use crate::Constraint;
+use crate::fetch::{FetchBackend, FetchPolicy, FetchRequest};
+use crate::store::{BlobStorage, BlobWriter, Storage};
+use crate::{gas, Constraint};
 
 mod errors;
 mod imports;
 mod registers;
 
 pub use errors::VMLogicError;
+pub(crate) use imports::host_signatures;
 use registers::Registers;
 
 pub type VMLogicResult<T, E = VMLogicError> = Result<T, E>;
 
+/// The latest time given to a guest by [`VMLogic::time_now()`].
+static LAST_TIME_NOW: AtomicU64 = AtomicU64::new(0);
+
 #[derive(Debug)]
 #[non_exhaustive]
 pub struct VMContext<'a> {
     pub input: Cow<'a, [u8]>,
     pub context_id: [u8; 32],
     pub executor_public_key: [u8; 32],
+    // rejects any attempt by the guest to change state
+    pub read_only: bool,
+    // reproduces the randomness and time of an earlier execution
+    pub replay: Option<Replay>,
+    // what the execution may use when made by another context
+    pub budget: CallBudget,
 }
 
 impl<'a> VMContext<'a> {
@@ -46,11 +67,64 @@ impl<'a> VMContext<'a> {
             input,
             context_id,
             executor_public_key,
+            read_only: false,
+            replay: None,
+            budget: CallBudget {
+                depth: 0,
+                gas: None,
+                deadline: None,
+            },
+        }
+    }
+}
+
+/// The inputs to an execution which come from the host rather than from the
+/// caller or the state, recorded in the [`Outcome`] so that the execution can
+/// be reproduced exactly.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct Replay {
+    /// Seeds the random bytes and proposal IDs given to the guest.
+    pub seed: [u8; 32],
+    /// The time given to the guest, in nanoseconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The responses to the requests made by the guest, in order.
+    pub fetches: Vec<Result<Vec<u8>, String>>,
+    /// What the guest found in the blob store, in order.
+    pub blob_reads: Vec<BlobRead>,
+}
+
+impl Replay {
+    /// Creates the inputs of an execution yet to make any requests or read
+    /// any blobs.
+    #[must_use]
+    pub const fn new(seed: [u8; 32], timestamp: u64) -> Self {
+        Self {
+            seed,
+            timestamp,
+            fetches: vec![],
+            blob_reads: vec![],
         }
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+/// A lookup in the blob store made by the guest.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum BlobRead {
+    /// A blob was opened, if it was found.
+    Opened(bool),
+    /// A chunk of an open blob was read, if it could be.
+    Read(Option<Vec<u8>>),
+}
+
+/// The inputs recorded by an earlier execution, taken as it's replayed.
+#[derive(Debug)]
+struct Recorded {
+    fetches: VecDeque<Result<Vec<u8>, String>>,
+    blob_reads: VecDeque<BlobRead>,
+}
+
+#[derive(Debug, Clone)]
 pub struct VMLimits {
     pub max_memory_pages: u32,
     pub max_stack_size: usize,
@@ -66,8 +140,22 @@ pub struct VMLimits {
     pub max_event_data_size: u64,
     pub max_storage_key_size: NonZeroU64,
     pub max_storage_value_size: NonZeroU64,
-    // pub max_execution_time: u64,
-    // number of functions per contract
+    // bytes of keys and values a context may hold in total
+    pub max_storage_usage: u64,
+    pub max_blob_handles: u64,
+    pub max_blob_chunk_size: u64,
+    pub max_storage_iterators: u64,
+    // how deeply contexts may call into one another
+    pub max_call_depth: u32,
+    pub max_gas: u64,
+    pub max_execution_time: Duration,
+    pub max_functions: u32,
+    pub max_tables: u32,
+    pub max_table_elements: u32,
+    // rejects floats, threads and SIMD, which may behave differently across
+    // hosts, though applications parsing json numbers use floats
+    pub deterministic_only: bool,
+    pub fetch: FetchPolicy,
 }
 
 impl Default for VMLimits {
@@ -90,6 +178,18 @@ impl Default for VMLimits {
             max_event_data_size: 16 << 10,                           // 16 KiB
             max_storage_key_size: is_valid((1 << 20).try_into()),    // 1 MiB
             max_storage_value_size: is_valid((10 << 20).try_into()), // 10 MiB
+            max_storage_usage: u64::MAX,                             // unlimited
+            max_blob_handles: 100,                                   //
+            max_blob_chunk_size: 10 << 20,                           // 10 MiB
+            max_storage_iterators: 100,                              //
+            max_call_depth: 4,                                       //
+            max_gas: 10_000_000_000,                                 //
+            max_execution_time: Duration::from_secs(10),             //
+            max_functions: 10_000,                                   //
+            max_tables: 1,                                           //
+            max_table_elements: 10_000,                              //
+            deterministic_only: false,                               //
+            fetch: FetchPolicy::default(),                           //
         }
     }
 }
@@ -97,7 +197,25 @@ impl Default for VMLimits {
 #[expect(missing_debug_implementations, reason = "storage can't impl Debug")]
 pub struct VMLogic<'a> {
     storage: &'a mut dyn Storage,
+    blobs: &'a mut dyn BlobStorage,
+    fetch: &'a dyn FetchBackend,
+    fetch_calls: u64,
+    calls: &'a dyn ContextCallBackend,
+    blob_handles: BTreeMap<u64, BlobHandle>,
+    next_blob_handle: u64,
+    storage_iters: BTreeMap<u64, StorageIter>,
+    next_storage_iter: u64,
     memory: Option<wasmer::Memory>,
+    gas_counter: Option<wasmer::Global>,
+    max_gas: u64,
+    host_gas: u64,
+    gas_used: u64,
+    storage_written: u64,
+    storage_removed: u64,
+    deadline: Option<Instant>,
+    replay: Replay,
+    recorded: Option<Recorded>,
+    rng: StdRng,
     context: VMContext<'a>,
     limits: &'a VMLimits,
     registers: Registers,
@@ -111,10 +229,55 @@ pub struct VMLogic<'a> {
 }
 
 impl<'a> VMLogic<'a> {
-    pub fn new(storage: &'a mut dyn Storage, context: VMContext<'a>, limits: &'a VMLimits) -> Self {
+    pub fn new(
+        storage: &'a mut dyn Storage,
+        blobs: &'a mut dyn BlobStorage,
+        fetch: &'a dyn FetchBackend,
+        calls: &'a dyn ContextCallBackend,
+        mut context: VMContext<'a>,
+        limits: &'a VMLimits,
+    ) -> Self {
+        let (replay, recorded) = match context.replay.take() {
+            Some(replay) => (
+                Replay::new(replay.seed, replay.timestamp),
+                Some(Recorded {
+                    fetches: replay.fetches.into(),
+                    blob_reads: replay.blob_reads.into(),
+                }),
+            ),
+            None => (Replay::new(rand::random(), system_time_now()), None),
+        };
+
+        let rng = StdRng::from_seed(replay.seed);
+
         VMLogic {
             storage,
+            blobs,
+            fetch,
+            fetch_calls: 0,
+            calls,
+            blob_handles: BTreeMap::new(),
+            next_blob_handle: 0,
+            storage_iters: BTreeMap::new(),
+            next_storage_iter: 0,
             memory: None,
+            gas_counter: None,
+            max_gas: context
+                .budget
+                .gas
+                .map_or(limits.max_gas, |gas| gas.min(limits.max_gas)),
+            host_gas: 0,
+            gas_used: 0,
+            storage_written: 0,
+            storage_removed: 0,
+            deadline: Instant::now()
+                .checked_add(limits.max_execution_time)
+                .into_iter()
+                .chain(context.budget.deadline)
+                .min(),
+            replay,
+            recorded,
+            rng,
             context,
             limits,
             registers: Registers::default(),
@@ -133,6 +296,105 @@ impl<'a> VMLogic<'a> {
         self
     }
 
+    pub fn with_gas_counter(&mut self, counter: wasmer::Global) -> &mut Self {
+        self.gas_counter = Some(counter);
+        self
+    }
+
+    /// The most gas the call may use.
+    pub(crate) const fn max_gas(&self) -> u64 {
+        self.max_gas
+    }
+
+    /// The instant past which the call is interrupted.
+    pub(crate) const fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Fails if the call has run past its deadline.
+    fn check_deadline(&self) -> VMLogicResult<()> {
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Err(VMLogicError::TimedOut);
+        }
+
+        Ok(())
+    }
+
+    /// Charges the cost of a host function call, along with that of the
+    /// bytes moved by the calls before it, taking it from the points the
+    /// guest is metered with, so that the limit applies to the guest and the
+    /// host together. Fails if the call has used more than the limit.
+    fn charge_host_gas(&mut self, store: &mut impl AsStoreMut, cost: u64) -> VMLogicResult<()> {
+        self.host_gas = self.host_gas.saturating_add(cost);
+
+        if let Some(counter) = &self.gas_counter {
+            let remaining = self.max_gas.saturating_sub(self.guest_gas(&*store));
+
+            let charged = self.host_gas.min(remaining);
+
+            let points = remaining.saturating_sub(charged);
+
+            // what the points can't cover is left for the call to settle
+            if counter
+                .set(
+                    store,
+                    wasmer::Value::I64(i64::from_ne_bytes(points.to_ne_bytes())),
+                )
+                .is_ok()
+            {
+                self.host_gas = self.host_gas.saturating_sub(charged);
+            }
+        }
+
+        if self.guest_gas(&*store).saturating_add(self.host_gas) > self.max_gas {
+            return Err(VMLogicError::OutOfGas);
+        }
+
+        Ok(())
+    }
+
+    /// The gas used so far by the guest, and by the host calls charged to it.
+    fn guest_gas(&self, store: &impl AsStoreRef) -> u64 {
+        match self.gas_counter.as_ref().map(|counter| counter.get(store)) {
+            Some(wasmer::Value::I64(remaining)) => self
+                .max_gas
+                .saturating_sub(u64::from_ne_bytes(remaining.to_ne_bytes())),
+            _ => 0,
+        }
+    }
+
+    /// The gas the call has left, for the guest and the host alike.
+    fn remaining_gas(&self, store: &impl AsStoreRef) -> u64 {
+        self.max_gas
+            .saturating_sub(self.guest_gas(store).saturating_add(self.host_gas))
+    }
+
+    /// Charges for the bytes moved by a host function call, which is settled
+    /// along with the cost of the next call, or once the call returns.
+    fn charge_bytes(&mut self, bytes: u64) {
+        self.host_gas = self.host_gas.saturating_add(gas::bytes_cost(bytes));
+    }
+
+    /// Settles the gas used by the call, given the gas used by the guest, or
+    /// [`None`] if it ran out. Returns whether the call stayed within the
+    /// limit.
+    pub(crate) fn settle_gas(&mut self, guest_gas: Option<u64>) -> bool {
+        let Some(guest_gas) = guest_gas else {
+            self.gas_used = self.max_gas;
+            return false;
+        };
+
+        let gas_used = guest_gas.saturating_add(self.host_gas);
+
+        // a call which runs out is charged everything it was given
+        self.gas_used = gas_used.min(self.max_gas);
+
+        gas_used <= self.max_gas
+    }
+
     pub fn host_functions(&'a mut self, store: wasmer::StoreMut<'a>) -> VMHostFunctions<'a> {
         let memory = self.memory.clone().expect("VM Memory not initialized");
 
@@ -144,6 +406,153 @@ impl<'a> VMLogic<'a> {
         }
         .build()
     }
+
+    fn open_blob(&mut self, blob: BlobHandle) -> VMLogicResult<u64> {
+        if self.blob_handles.len()
+            >= usize::try_from(self.limits.max_blob_handles)
+                .map_err(|_| HostError::IntegerOverflow)?
+        {
+            return Err(HostError::BlobHandlesOverflow.into());
+        }
+
+        // handles start at 1, leaving 0 to signal a missing blob
+        self.next_blob_handle = self
+            .next_blob_handle
+            .checked_add(1)
+            .ok_or(HostError::IntegerOverflow)?;
+
+        drop(self.blob_handles.insert(self.next_blob_handle, blob));
+
+        Ok(self.next_blob_handle)
+    }
+
+    fn open_storage_iter(&mut self, iter: StorageIter) -> VMLogicResult<u64> {
+        if self.storage_iters.len()
+            >= usize::try_from(self.limits.max_storage_iterators)
+                .map_err(|_| HostError::IntegerOverflow)?
+        {
+            return Err(HostError::StorageIteratorsOverflow.into());
+        }
+
+        self.next_storage_iter = self
+            .next_storage_iter
+            .checked_add(1)
+            .ok_or(HostError::IntegerOverflow)?;
+
+        drop(self.storage_iters.insert(self.next_storage_iter, iter));
+
+        Ok(self.next_storage_iter)
+    }
+
+    /// Drops the entries looked up by storage iterators, which may no longer
+    /// be current after a write.
+    fn drop_storage_iter_batches(&mut self) {
+        for iter in self.storage_iters.values_mut() {
+            iter.batch.clear();
+        }
+    }
+
+    /// Takes the response to the next request made by the execution being
+    /// replayed, if one is, and records the response either way.
+    fn fetch_response(
+        &mut self,
+        fetch: impl FnOnce() -> Result<Vec<u8>, String>,
+    ) -> Result<Result<Vec<u8>, String>, HostError> {
+        let response = match &mut self.recorded {
+            Some(recorded) => recorded
+                .fetches
+                .pop_front()
+                .ok_or(HostError::ReplayDiverged)?,
+            None => fetch(),
+        };
+
+        self.replay.fetches.push(response.clone());
+
+        Ok(response)
+    }
+
+    /// Takes what the execution being replayed found in the blob store next,
+    /// if one is, and records what was found either way.
+    fn read_blob_store(
+        &mut self,
+        read: impl FnOnce(&dyn BlobStorage) -> BlobRead,
+    ) -> Result<BlobRead, HostError> {
+        let found = match &mut self.recorded {
+            Some(recorded) => recorded
+                .blob_reads
+                .pop_front()
+                .ok_or(HostError::ReplayDiverged)?,
+            None => read(&*self.blobs),
+        };
+
+        self.replay.blob_reads.push(found.clone());
+
+        Ok(found)
+    }
+}
+
+/// How many entries a storage iterator looks up at once.
+const STORAGE_ITER_BATCH: usize = 16;
+
+/// An iteration over storage started by the guest. Entries are looked up a
+/// batch at a time, which is dropped on any write, so that writes made while
+/// iterating are observed.
+struct StorageIter {
+    /// Where the next batch is looked up from, just past the last entry returned.
+    cursor: Bound<Vec<u8>>,
+    end: Bound<Vec<u8>>,
+    /// Entries looked up, but not yet returned.
+    batch: VecDeque<(Vec<u8>, Vec<u8>)>,
+}
+
+impl StorageIter {
+    const fn new(start: Vec<u8>, end: Bound<Vec<u8>>) -> Self {
+        Self {
+            cursor: Bound::Included(start),
+            end,
+            batch: VecDeque::new(),
+        }
+    }
+}
+
+/// Returns the bound just past every key starting with the prefix.
+fn prefix_end(prefix: &[u8]) -> Bound<Vec<u8>> {
+    let Some(last) = prefix.iter().rposition(|&byte| byte != u8::MAX) else {
+        return Bound::Unbounded;
+    };
+
+    let mut end = prefix[..=last].to_vec();
+
+    end[last] = end[last].wrapping_add(1);
+
+    Bound::Excluded(end)
+}
+
+/// Returns the current time, in nanoseconds since the Unix epoch, never going
+/// backwards from the last time returned.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "Impossible to overflow in normal circumstances"
+)]
+#[expect(clippy::expect_used, reason = "Effectively infallible here")]
+fn system_time_now() -> u64 {
+    let system_now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards to before the Unix epoch!")
+        .as_nanos() as u64;
+
+    LAST_TIME_NOW
+        .fetch_max(system_now, Ordering::Relaxed)
+        .max(system_now)
+}
+
+/// A blob opened by the guest.
+enum BlobHandle {
+    /// A blob being read, along with how far into it has been read.
+    Reader { id: BlobId, offset: u64 },
+    /// A new blob being written. Writers which are not closed by the guest
+    /// are discarded at the end of the call.
+    Writer(Box<dyn BlobWriter>),
 }
 
 #[derive(Debug, Serialize)]
@@ -157,8 +566,15 @@ pub struct Outcome {
     pub proposals: BTreeMap<[u8; 32], Vec<u8>>,
     //list of ids for approved proposals
     pub approvals: Vec<[u8; 32]>,
+    pub gas_used: u64,
     // execution runtime
-    // current storage usage of the app
+    // bytes of keys and values written and removed by this execution
+    pub storage_written: u64,
+    pub storage_removed: u64,
+    // total bytes held by the app's storage once this execution is applied
+    pub storage_usage: u64,
+    // what it takes to reproduce this execution
+    pub replay: Replay,
 }
 
 #[derive(Debug, Serialize)]
@@ -187,6 +603,11 @@ impl VMLogic<'_> {
             artifact: self.artifact,
             proposals: self.proposals,
             approvals: self.approvals,
+            gas_used: self.gas_used,
+            storage_written: self.storage_written,
+            storage_removed: self.storage_removed,
+            storage_usage: self.storage.usage(),
+            replay: self.replay,
         }
     }
 }
@@ -233,6 +654,14 @@ impl VMHostFunctions<'_> {
 
         String::from_utf8(buf).map_err(|_| HostError::BadUTF8.into())
     }
+
+    fn ensure_writable(&self) -> VMLogicResult<()> {
+        if self.borrow_logic().context.read_only {
+            return Err(HostError::ReadOnly.into());
+        }
+
+        Ok(())
+    }
 }
 
 impl VMHostFunctions<'_> {
@@ -377,6 +806,8 @@ impl VMHostFunctions<'_> {
         artifact_ptr: u64,
         artifact_len: u64,
     ) -> VMLogicResult<()> {
+        self.ensure_writable()?;
+
         let root_hash = self.read_guest_memory_sized::<32>(root_hash_ptr, root_hash_len)?;
         let artifact = self.read_guest_memory(artifact_ptr, artifact_len)?;
 
@@ -408,7 +839,13 @@ impl VMHostFunctions<'_> {
 
         let key = self.read_guest_memory(key_ptr, key_len)?;
 
-        if let Some(value) = logic.storage.get(&key) {
+        let value = logic.storage.get(&key);
+
+        let value_len = value.as_ref().map_or(0, |value| value.len() as u64);
+
+        self.with_logic_mut(|logic| logic.charge_bytes(key_len.saturating_add(value_len)));
+
+        if let Some(value) = value {
             self.with_logic_mut(|logic| logic.registers.set(logic.limits, register_id, value))?;
 
             return Ok(1);
@@ -423,6 +860,8 @@ impl VMHostFunctions<'_> {
         key_len: u64,
         register_id: u64,
     ) -> VMLogicResult<u32> {
+        self.ensure_writable()?;
+
         let logic = self.borrow_logic();
 
         if key_len > logic.limits.max_storage_key_size.get() {
@@ -431,9 +870,20 @@ impl VMHostFunctions<'_> {
 
         let key = self.read_guest_memory(key_ptr, key_len)?;
 
-        if let Some(value) = logic.storage.get(&key) {
+        let value = logic.storage.get(&key);
+
+        let value_len = value.as_ref().map_or(0, |value| value.len() as u64);
+
+        self.with_logic_mut(|logic| logic.charge_bytes(key_len.saturating_add(value_len)));
+
+        if let Some(value) = value {
             self.with_logic_mut(|logic| {
                 drop(logic.storage.remove(&key));
+                logic.drop_storage_iter_batches();
+                logic.storage_removed = logic
+                    .storage_removed
+                    .saturating_add(key_len)
+                    .saturating_add(value.len() as u64);
                 logic.registers.set(logic.limits, register_id, value)
             })?;
 
@@ -451,6 +901,8 @@ impl VMHostFunctions<'_> {
         value_len: u64,
         register_id: u64,
     ) -> VMLogicResult<u32> {
+        self.ensure_writable()?;
+
         let logic = self.borrow_logic();
 
         if key_len > logic.limits.max_storage_key_size.get() {
@@ -464,7 +916,34 @@ impl VMHostFunctions<'_> {
         let key = self.read_guest_memory(key_ptr, key_len)?;
         let value = self.read_guest_memory(value_ptr, value_len)?;
 
-        let evicted = self.with_logic_mut(|logic| logic.storage.set(key, value));
+        let evicted = self.with_logic_mut(|logic| {
+            logic.charge_bytes(key_len.saturating_add(value_len));
+
+            let evicted = logic.storage.set(key, value);
+
+            logic.drop_storage_iter_batches();
+
+            let evicted_len = evicted.as_ref().map_or(0, |evicted| evicted.len() as u64);
+
+            logic.storage_written = logic
+                .storage_written
+                .saturating_add(key_len)
+                .saturating_add(value_len);
+
+            if evicted.is_some() {
+                logic.storage_removed = logic
+                    .storage_removed
+                    .saturating_add(key_len)
+                    .saturating_add(evicted_len);
+            }
+
+            // shrinking a value is always allowed, even past the quota
+            if value_len > evicted_len && logic.storage.usage() > logic.limits.max_storage_usage {
+                return Err(HostError::StorageQuotaExceeded);
+            }
+
+            Ok(evicted)
+        })?;
 
         if let Some(evicted) = evicted {
             self.with_logic_mut(|logic| logic.registers.set(logic.limits, register_id, evicted))?;
@@ -475,6 +954,139 @@ impl VMHostFunctions<'_> {
         Ok(0)
     }
 
+    /// Starts iterating over the entries whose keys start with a prefix, in
+    /// ascending order of key.
+    ///
+    /// Returns a handle to the iterator.
+    ///
+    /// # Parameters
+    ///
+    /// * `prefix_ptr` - Pointer to the start of the prefix in WASM memory.
+    /// * `prefix_len` - Length of the prefix. An empty prefix matches all keys.
+    ///
+    pub fn storage_iter_prefix(&mut self, prefix_ptr: u64, prefix_len: u64) -> VMLogicResult<u64> {
+        if prefix_len > self.borrow_logic().limits.max_storage_key_size.get() {
+            return Err(HostError::KeyLengthOverflow.into());
+        }
+
+        let prefix = self.read_guest_memory(prefix_ptr, prefix_len)?;
+
+        let end = prefix_end(&prefix);
+
+        self.with_logic_mut(|logic| logic.open_storage_iter(StorageIter::new(prefix, end)))
+    }
+
+    /// Starts iterating over the entries whose keys fall within a range, in
+    /// ascending order of key.
+    ///
+    /// Returns a handle to the iterator.
+    ///
+    /// # Parameters
+    ///
+    /// * `start_ptr` - Pointer to the start of the first key in WASM memory.
+    /// * `start_len` - Length of the first key, which is included.
+    /// * `end_ptr`   - Pointer to the start of the end key in WASM memory.
+    /// * `end_len`   - Length of the end key, which is excluded. An empty end
+    ///                 key leaves the range unbounded.
+    ///
+    pub fn storage_iter_range(
+        &mut self,
+        start_ptr: u64,
+        start_len: u64,
+        end_ptr: u64,
+        end_len: u64,
+    ) -> VMLogicResult<u64> {
+        let max_key_size = self.borrow_logic().limits.max_storage_key_size.get();
+
+        if start_len > max_key_size || end_len > max_key_size {
+            return Err(HostError::KeyLengthOverflow.into());
+        }
+
+        let start = self.read_guest_memory(start_ptr, start_len)?;
+        let end = self.read_guest_memory(end_ptr, end_len)?;
+
+        let end = if end.is_empty() {
+            Bound::Unbounded
+        } else {
+            Bound::Excluded(end)
+        };
+
+        self.with_logic_mut(|logic| logic.open_storage_iter(StorageIter::new(start, end)))
+    }
+
+    /// Advances a storage iterator, placing the key and value of the next
+    /// entry into registers.
+    ///
+    /// Returns `1` if there was an entry, or `0` once the iterator is done.
+    ///
+    /// # Parameters
+    ///
+    /// * `handle`            - The handle of the iterator.
+    /// * `key_register_id`   - The register to place the key into.
+    /// * `value_register_id` - The register to place the value into.
+    ///
+    pub fn storage_iter_next(
+        &mut self,
+        handle: u64,
+        key_register_id: u64,
+        value_register_id: u64,
+    ) -> VMLogicResult<u32> {
+        let entry = self.with_logic_mut(|logic| {
+            let Some(iter) = logic.storage_iters.get_mut(&handle) else {
+                return Err(HostError::InvalidStorageIterator { handle });
+            };
+
+            if iter.batch.is_empty() {
+                iter.batch = logic
+                    .storage
+                    .scan(
+                        iter.cursor.as_ref().map(Vec::as_slice),
+                        iter.end.as_ref().map(Vec::as_slice),
+                        STORAGE_ITER_BATCH,
+                    )
+                    .into();
+            }
+
+            let entry = iter.batch.pop_front();
+
+            if let Some((key, _)) = &entry {
+                iter.cursor = Bound::Excluded(key.clone());
+            }
+
+            Ok(entry)
+        })?;
+
+        let Some((key, value)) = entry else {
+            return Ok(0);
+        };
+
+        self.with_logic_mut(|logic| {
+            logic.charge_bytes((key.len() as u64).saturating_add(value.len() as u64));
+
+            logic.registers.set(logic.limits, key_register_id, key)?;
+            logic.registers.set(logic.limits, value_register_id, value)
+        })?;
+
+        Ok(1)
+    }
+
+    /// Closes a storage iterator.
+    ///
+    /// # Parameters
+    ///
+    /// * `handle` - The handle of the iterator.
+    ///
+    pub fn storage_iter_close(&mut self, handle: u64) -> VMLogicResult<()> {
+        if self
+            .with_logic_mut(|logic| logic.storage_iters.remove(&handle))
+            .is_none()
+        {
+            return Err(HostError::InvalidStorageIterator { handle }.into());
+        }
+
+        Ok(())
+    }
+
     #[expect(clippy::too_many_arguments, reason = "Acceptable here")]
     pub fn fetch(
         &mut self,
@@ -498,37 +1110,135 @@ impl VMHostFunctions<'_> {
         let headers: Vec<(String, String)> =
             from_borsh_slice(&headers).map_err(|_| HostError::DeserializationError)?;
         let body = self.read_guest_memory(body_ptr, body_len)?;
-        let mut request = ureq::request(&method, &url);
 
-        for (key, value) in &headers {
-            request = request.set(key, value);
+        let policy = &self.borrow_logic().limits.fetch;
+
+        if let Err(reason) = policy.check(&url, &method) {
+            return Err(HostError::FetchNotAllowed { reason }.into());
         }
 
-        let response = if body.is_empty() {
-            request.call()
-        } else {
-            request.send_bytes(&body)
-        };
+        self.with_logic_mut(|logic| {
+            if logic.fetch_calls >= logic.limits.fetch.max_calls {
+                return Err(HostError::FetchCallsOverflow);
+            }
+
+            logic.fetch_calls = logic.fetch_calls.saturating_add(1);
+
+            Ok(())
+        })?;
+
+        let response = self.with_logic_mut(|logic| {
+            let limits = logic.limits;
+
+            let request = FetchRequest {
+                url: &url,
+                method: &method,
+                headers: &headers,
+                body: &body,
+                timeout: limits.fetch.timeout,
+                max_response_size: limits.fetch.max_response_size,
+                allowed_hosts: &limits.fetch.allowed_hosts,
+            };
+
+            let backend = logic.fetch;
+
+            logic.fetch_response(|| backend.fetch(&request))
+        })?;
 
         let (status, data) = match response {
-            Ok(response) => {
-                let mut buffer = vec![];
-                match response.into_reader().read_to_end(&mut buffer) {
-                    Ok(_) => (0, buffer),
-                    Err(_) => (1, "Failed to read the response body.".into()),
-                }
-            }
-            Err(e) => (1, e.to_string().into_bytes()),
+            Ok(data) => (0, data),
+            Err(err) => (1, err.into_bytes()),
+        };
+
+        self.with_logic_mut(|logic| {
+            logic.charge_bytes(body_len.saturating_add(data.len() as u64));
+
+            logic.registers.set(logic.limits, register_id, data)
+        })?;
+        Ok(status)
+    }
+
+    /// Calls a method of another context, without letting it change state.
+    ///
+    /// The call is made on behalf of the identity executing this one, which
+    /// must be a member of the called context as well.
+    ///
+    /// # Parameters
+    ///
+    /// * `context_id_ptr` - Pointer to the ID of the context to call.
+    /// * `context_id_len` - Length of the ID, which must be 32 bytes.
+    /// * `method_ptr` - Pointer to the name of the method.
+    /// * `method_len` - Length of the name.
+    /// * `args_ptr` - Pointer to the input passed to the method.
+    /// * `args_len` - Length of the input.
+    /// * `register_id` - The register to put the returned value, or the
+    ///   reason the call failed, in.
+    ///
+    /// # Returns
+    ///
+    /// `0` if the call succeeded, or `1` if it failed.
+    ///
+    /// # Errors
+    ///
+    /// * `HostError::CallDepthOverflow` if the call would nest deeper than
+    ///   allowed.
+    ///
+    #[expect(clippy::too_many_arguments, reason = "Acceptable here")]
+    pub fn call_context(
+        &mut self,
+        context_id_ptr: u64,
+        context_id_len: u64,
+        method_ptr: u64,
+        method_len: u64,
+        args_ptr: u64,
+        args_len: u64,
+        register_id: u64,
+    ) -> VMLogicResult<u32> {
+        let context_id = self.read_guest_memory_sized::<32>(context_id_ptr, context_id_len)?;
+        let method = self.get_string(method_ptr, method_len)?;
+        let args = self.read_guest_memory(args_ptr, args_len)?;
+
+        let remaining_gas = self.with(|fields| fields.logic.remaining_gas(fields.store));
+
+        let logic = self.borrow_logic();
+
+        if logic.context.budget.depth >= logic.limits.max_call_depth {
+            return Err(HostError::CallDepthOverflow.into());
+        }
+
+        let outcome = logic.calls.call(&ContextCall {
+            context_id,
+            executor_public_key: logic.context.executor_public_key,
+            method: &method,
+            args: &args,
+            budget: CallBudget {
+                depth: logic.context.budget.depth.saturating_add(1),
+                gas: Some(remaining_gas),
+                deadline: logic.deadline,
+            },
+        });
+
+        let (status, data) = match outcome.returns {
+            Ok(data) => (0, data),
+            Err(err) => (1, err.into_bytes()),
         };
 
-        self.with_logic_mut(|logic| logic.registers.set(logic.limits, register_id, data))?;
+        self.with_logic_mut(|logic| {
+            // the callee spends the caller's gas, as it does its time, which
+            // passes while the caller waits on it
+            logic.host_gas = logic.host_gas.saturating_add(outcome.gas_used);
+
+            logic.charge_bytes(args_len.saturating_add(data.len() as u64));
+
+            logic.registers.set(logic.limits, register_id, data)
+        })?;
         Ok(status)
     }
 
     pub fn random_bytes(&mut self, ptr: u64, len: u64) -> VMLogicResult<()> {
         let mut buf = vec![0; usize::try_from(len).map_err(|_| HostError::IntegerOverflow)?];
 
-        rand::thread_rng().fill_bytes(&mut buf);
+        self.with_logic_mut(|logic| logic.rng.fill_bytes(&mut buf));
         self.borrow_memory().write(ptr, &buf)?;
 
         Ok(())
@@ -540,30 +1250,183 @@ impl VMHostFunctions<'_> {
     /// [`SystemTime`] is not available inside the guest runtime. Therefore the
     /// guest needs to request this from the host.
     ///
-    #[expect(
-        clippy::cast_possible_truncation,
-        reason = "Impossible to overflow in normal circumstances"
-    )]
-    #[expect(
-        clippy::expect_used,
-        clippy::unwrap_in_result,
-        reason = "Effectively infallible here"
-    )]
+    /// The time is taken once, when the call starts, and stays the same for
+    /// the rest of it, so that the call can be replayed. It never goes
+    /// backwards from one call to the next, even if the system clock does.
+    /// Guests use it as the physical part of their hybrid logical clocks, which
+    /// rely on it to order the writes made across separate calls.
+    ///
     pub fn time_now(&mut self, ptr: u64, len: u64) -> VMLogicResult<()> {
         if len != 8 {
             return Err(HostError::InvalidMemoryAccess.into());
         }
 
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards to before the Unix epoch!")
-            .as_nanos() as u64;
+        let now = self.borrow_logic().replay.timestamp;
 
         self.borrow_memory().write(ptr, &now.to_le_bytes())?;
 
         Ok(())
     }
 
+    /// Opens an existing blob for reading.
+    ///
+    /// Returns a handle to the blob, or `0` if there is no such blob.
+    ///
+    /// # Parameters
+    ///
+    /// * `blob_id_ptr` - Pointer to the start of the blob ID in WASM memory.
+    /// * `blob_id_len` - Length of the blob ID. This should be 32 bytes.
+    ///
+    pub fn blob_open(&mut self, blob_id_ptr: u64, blob_id_len: u64) -> VMLogicResult<u64> {
+        let blob_id = BlobId::from(self.read_guest_memory_sized::<32>(blob_id_ptr, blob_id_len)?);
+
+        let found = self.with_logic_mut(|logic| {
+            logic.read_blob_store(|blobs| BlobRead::Opened(blobs.size(&blob_id).is_some()))
+        })?;
+
+        let BlobRead::Opened(found) = found else {
+            return Err(HostError::ReplayDiverged.into());
+        };
+
+        if !found {
+            return Ok(0);
+        }
+
+        self.with_logic_mut(|logic| {
+            logic.open_blob(BlobHandle::Reader {
+                id: blob_id,
+                offset: 0,
+            })
+        })
+    }
+
+    /// Reads the next chunk of an open blob into a register.
+    ///
+    /// Returns the number of bytes read, which is `0` once the end of the
+    /// blob has been reached.
+    ///
+    /// # Parameters
+    ///
+    /// * `handle`      - The handle of a blob opened for reading.
+    /// * `len`         - The maximum number of bytes to read.
+    /// * `register_id` - The register to read the chunk into.
+    ///
+    pub fn blob_read(&mut self, handle: u64, len: u64, register_id: u64) -> VMLogicResult<u64> {
+        let logic = self.borrow_logic();
+
+        if len > logic.limits.max_blob_chunk_size {
+            return Err(HostError::BlobChunkSizeOverflow.into());
+        }
+
+        let Some(&BlobHandle::Reader { id, offset }) = logic.blob_handles.get(&handle) else {
+            return Err(HostError::InvalidBlobHandle { handle }.into());
+        };
+
+        let read = self.with_logic_mut(|logic| {
+            logic.read_blob_store(|blobs| BlobRead::Read(blobs.read(&id, offset, len)))
+        })?;
+
+        let BlobRead::Read(data) = read else {
+            return Err(HostError::ReplayDiverged.into());
+        };
+
+        let data = data.ok_or(HostError::BlobAccessError)?;
+
+        let read = data.len() as u64;
+
+        self.with_logic_mut(|logic| {
+            logic.charge_bytes(read);
+
+            if let Some(BlobHandle::Reader { offset, .. }) = logic.blob_handles.get_mut(&handle) {
+                *offset = offset.saturating_add(read);
+            }
+
+            logic.registers.set(logic.limits, register_id, data)
+        })?;
+
+        Ok(read)
+    }
+
+    /// Starts writing a new blob.
+    ///
+    /// Returns a handle to the blob, which is only stored once it is closed.
+    ///
+    pub fn blob_create(&mut self) -> VMLogicResult<u64> {
+        self.with_logic_mut(|logic| {
+            if logic.blob_handles.len()
+                >= usize::try_from(logic.limits.max_blob_handles)
+                    .map_err(|_| HostError::IntegerOverflow)?
+            {
+                return Err(HostError::BlobHandlesOverflow.into());
+            }
+
+            let writer = logic.blobs.create();
+
+            logic.open_blob(BlobHandle::Writer(writer))
+        })
+    }
+
+    /// Appends data to a blob being written.
+    ///
+    /// # Parameters
+    ///
+    /// * `handle`   - The handle of a blob opened for writing.
+    /// * `data_ptr` - Pointer to the start of the data in WASM memory.
+    /// * `data_len` - Length of the data.
+    ///
+    pub fn blob_write(&mut self, handle: u64, data_ptr: u64, data_len: u64) -> VMLogicResult<()> {
+        if data_len > self.borrow_logic().limits.max_blob_chunk_size {
+            return Err(HostError::BlobChunkSizeOverflow.into());
+        }
+
+        let data = self.read_guest_memory(data_ptr, data_len)?;
+
+        self.with_logic_mut(|logic| {
+            logic.charge_bytes(data_len);
+
+            match logic.blob_handles.get_mut(&handle) {
+                Some(BlobHandle::Writer(writer)) => {
+                    if writer.write(&data) {
+                        Ok(())
+                    } else {
+                        Err(HostError::BlobAccessError)
+                    }
+                }
+                Some(BlobHandle::Reader { .. }) | None => {
+                    Err(HostError::InvalidBlobHandle { handle })
+                }
+            }
+        })?;
+
+        Ok(())
+    }
+
+    /// Closes an open blob.
+    ///
+    /// Blobs being written are stored, and their ID is placed into the
+    /// register, in which case `1` is returned. Otherwise, `0` is returned.
+    ///
+    /// # Parameters
+    ///
+    /// * `handle`      - The handle of an open blob.
+    /// * `register_id` - The register to place the ID of a written blob into.
+    ///
+    pub fn blob_close(&mut self, handle: u64, register_id: u64) -> VMLogicResult<u32> {
+        let Some(blob) = self.with_logic_mut(|logic| logic.blob_handles.remove(&handle)) else {
+            return Err(HostError::InvalidBlobHandle { handle }.into());
+        };
+
+        let BlobHandle::Writer(writer) = blob else {
+            return Ok(0);
+        };
+
+        let blob_id = writer.finish().ok_or(HostError::BlobAccessError)?;
+
+        self.with_logic_mut(|logic| logic.registers.set(logic.limits, register_id, *blob_id))?;
+
+        Ok(1)
+    }
+
     /// Call the contract's `send_proposal()` function through the bridge.
     ///
     /// The proposal actions are obtained as raw data and pushed onto a list of
@@ -587,6 +1450,8 @@ impl VMHostFunctions<'_> {
         id_ptr: u64,
         id_len: u64,
     ) -> VMLogicResult<()> {
+        self.ensure_writable()?;
+
         if id_len != 32 {
             return Err(HostError::InvalidMemoryAccess.into());
         }
@@ -594,8 +1459,10 @@ impl VMHostFunctions<'_> {
         let actions_bytes: Vec<u8> = self.read_guest_memory(actions_ptr, actions_len)?;
         let mut proposal_id = [0; 32];
 
-        rand::thread_rng().fill_bytes(&mut proposal_id);
-        drop(self.with_logic_mut(|logic| logic.proposals.insert(proposal_id, actions_bytes)));
+        drop(self.with_logic_mut(|logic| {
+            logic.rng.fill_bytes(&mut proposal_id);
+            logic.proposals.insert(proposal_id, actions_bytes)
+        }));
 
         self.borrow_memory().write(id_ptr, &proposal_id)?;
 
@@ -603,6 +1470,8 @@ impl VMHostFunctions<'_> {
     }
 
     pub fn approve_proposal(&mut self, approval_ptr: u64, approval_len: u64) -> VMLogicResult<()> {
+        self.ensure_writable()?;
+
         if approval_len != 32 {
             return Err(HostError::InvalidMemoryAccess.into());
         }
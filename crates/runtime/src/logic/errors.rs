@@ -10,6 +10,10 @@ pub enum VMLogicError {
     HostError(#[from] HostError),
     #[error(transparent)]
     StorageError(StorageError),
+    #[error("out of gas")]
+    OutOfGas,
+    #[error("execution timed out")]
+    TimedOut,
 }
 
 impl From<MemoryAccessError> for VMLogicError {
@@ -32,6 +36,8 @@ impl TryFrom<VMLogicError> for FunctionCallError {
             //     message,
             // }) => Err(VMRuntimeError::HostError(err)),
             VMLogicError::HostError(err) => Ok(Self::HostError(err)),
+            VMLogicError::OutOfGas => Ok(Self::OutOfGas),
+            VMLogicError::TimedOut => Ok(Self::TimedOut),
         }
     }
 }
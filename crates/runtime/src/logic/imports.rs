@@ -1,10 +1,12 @@
 use core::cell::RefCell;
 use core::sync::atomic::{AtomicBool, Ordering};
+use std::collections::BTreeMap;
 use std::sync::Once;
 
-use wasmer::{Imports, Store};
+use wasmer::{FunctionType, Imports, Store};
 
 use super::{HostError, Location, PanicContext, VMLogic};
+use crate::gas::host_function_cost;
 
 thread_local! {
     // https://open.spotify.com/track/7DPUuTaTZCtQ6o4Xx00qzT
@@ -16,9 +18,21 @@ thread_local! {
 impl VMLogic<'_> {
     #[expect(clippy::too_many_arguments, reason = "Acceptable here")]
     pub fn imports(&mut self, store: &mut Store) -> Imports {
-        imports! {
-            store;
-            logic: self;
+        host_functions!(imports!(store; logic: self;))
+    }
+}
+
+/// The signatures of the functions provided by the host, keyed by name.
+pub(crate) fn host_signatures() -> BTreeMap<&'static str, FunctionType> {
+    host_functions!(signatures!()).into_iter().collect()
+}
+
+/// Passes the functions provided by the host to the given macro, following
+/// any leading arguments.
+macro_rules! _host_functions {
+    ($mac:ident!($($args:tt)*)) => {
+        $mac! {
+            $($args)*
 
             fn panic(file_ptr: u64, file_len: u64, line: u32, column: u32);
             fn panic_utf8(
@@ -53,6 +67,19 @@ impl VMLogic<'_> {
             ) -> u32;
             fn storage_read(key_ptr: u64, key_len: u64, register_id: u64) -> u32;
             fn storage_remove(key_ptr: u64, key_len: u64, register_id: u64) -> u32;
+            fn storage_iter_prefix(prefix_ptr: u64, prefix_len: u64) -> u64;
+            fn storage_iter_range(
+                start_ptr: u64,
+                start_len: u64,
+                end_ptr: u64,
+                end_len: u64,
+            ) -> u64;
+            fn storage_iter_next(
+                handle: u64,
+                key_register_id: u64,
+                value_register_id: u64,
+            ) -> u32;
+            fn storage_iter_close(handle: u64);
 
             fn fetch(
                 url_ptr: u64,
@@ -66,13 +93,46 @@ impl VMLogic<'_> {
                 register_id: u64
             ) -> u32;
 
+            fn call_context(
+                context_id_ptr: u64,
+                context_id_len: u64,
+                method_ptr: u64,
+                method_len: u64,
+                args_ptr: u64,
+                args_len: u64,
+                register_id: u64
+            ) -> u32;
+
+            fn blob_open(blob_id_ptr: u64, blob_id_len: u64) -> u64;
+            fn blob_read(handle: u64, len: u64, register_id: u64) -> u64;
+            fn blob_create() -> u64;
+            fn blob_write(handle: u64, data_ptr: u64, data_len: u64);
+            fn blob_close(handle: u64, register_id: u64) -> u32;
+
             fn random_bytes(ptr: u64, len: u64);
             fn time_now(ptr: u64, len: u64);
 
             fn send_proposal(actions_ptr: u64, actions_len: u64, id_ptr: u64, id_len: u64);
             fn approve_proposal(approval_ptr: u64, approval_len: u64);
         }
-    }
+    };
+}
+
+macro_rules! _signatures {
+    ($(fn $func:ident($($arg:ident: $arg_ty:ty),*$(,)?) $(-> $returns:ty)?;)*) => {
+        [$(
+            (stringify!($func), {
+                let params: &[wasmer::Type] = &[$(
+                    <<$arg_ty as wasmer::FromToNativeWasmType>::Native as wasmer::NativeWasmType>::WASM_TYPE
+                ),*];
+                let results: &[wasmer::Type] = &[$(
+                    <<$returns as wasmer::FromToNativeWasmType>::Native as wasmer::NativeWasmType>::WASM_TYPE
+                )?];
+
+                FunctionType::new(params, results)
+            }),
+        )*]
+    };
 }
 
 macro_rules! _imports {
@@ -111,10 +171,21 @@ macro_rules! _imports {
 
                     HOST_CTX.with(|ctx| ctx.store(true, Ordering::Relaxed));
                     let res = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
-                        let (data, store) = env.data_and_store_mut();
+                        let (data, mut store) = env.data_and_store_mut();
                         let data = unsafe { &mut *(*data.get_mut()).cast::<VMLogic<'_>>() };
 
-                        data.host_functions(store).$func($($arg),*)
+                        data.check_deadline()?;
+                        data.charge_host_gas(&mut store, host_function_cost(stringify!($func)))?;
+
+                        let res = data.host_functions(store).$func($($arg),*)?;
+
+                        // the bytes moved by the call are only known once it's made
+                        let (data, mut store) = env.data_and_store_mut();
+                        let data = unsafe { &mut *(*data.get_mut()).cast::<VMLogic<'_>>() };
+
+                        data.charge_host_gas(&mut store, 0)?;
+
+                        Ok(res)
                     })).unwrap_or_else(|_| {
                         let (message, location) = PAYLOAD.with(|payload| {
                             payload.borrow_mut().take().unwrap_or_else(|| ("<no message>".to_owned(), Location::Unknown))
@@ -187,4 +258,6 @@ macro_rules! _imports {
     };
 }
 
+use _host_functions as host_functions;
 use _imports as imports;
+use _signatures as signatures;
@@ -12,6 +12,7 @@ use crate::logic::VMLimits;
 pub struct WasmerTunables {
     base: BaseTunables,
     vmconfig: VMConfig,
+    max_memory_pages: Pages,
 }
 
 impl WasmerTunables {
@@ -26,7 +27,35 @@ impl WasmerTunables {
             wasm_stack_size: Some(limits.max_stack_size),
         };
 
-        Self { base, vmconfig }
+        Self {
+            base,
+            vmconfig,
+            max_memory_pages: Pages(limits.max_memory_pages),
+        }
+    }
+
+    /// Caps the maximum size of a memory to the configured limit, in place of
+    /// stripping the memory a module defines and providing one of our own.
+    fn capped(&self, ty: &MemoryType) -> MemoryType {
+        let maximum = ty.maximum.map_or(self.max_memory_pages, |maximum| {
+            maximum.min(self.max_memory_pages)
+        });
+
+        MemoryType {
+            maximum: Some(maximum),
+            ..*ty
+        }
+    }
+
+    fn limited(&self, ty: &MemoryType) -> Result<MemoryType, MemoryError> {
+        if ty.minimum > self.max_memory_pages {
+            return Err(MemoryError::MinimumMemoryTooLarge {
+                min_requested: ty.minimum,
+                max_allowed: self.max_memory_pages,
+            });
+        }
+
+        Ok(self.capped(ty))
     }
 }
 
@@ -36,7 +65,7 @@ impl Tunables for WasmerTunables {
     }
 
     fn memory_style(&self, memory: &MemoryType) -> MemoryStyle {
-        self.base.memory_style(memory)
+        self.base.memory_style(&self.capped(memory))
     }
 
     fn table_style(&self, table: &TableType) -> TableStyle {
@@ -48,7 +77,7 @@ impl Tunables for WasmerTunables {
         ty: &MemoryType,
         style: &MemoryStyle,
     ) -> Result<VMMemory, MemoryError> {
-        self.base.create_host_memory(ty, style)
+        self.base.create_host_memory(&self.limited(ty)?, style)
     }
 
     unsafe fn create_vm_memory(
@@ -58,7 +87,7 @@ impl Tunables for WasmerTunables {
         vm_definition_location: NonNull<VMMemoryDefinition>,
     ) -> Result<VMMemory, MemoryError> {
         self.base
-            .create_vm_memory(ty, style, vm_definition_location)
+            .create_vm_memory(&self.limited(ty)?, style, vm_definition_location)
     }
 
     fn create_host_table(&self, ty: &TableType, style: &TableStyle) -> Result<VMTable, String> {
@@ -0,0 +1,311 @@
+use wasmer::wasmparser::{
+    ExternalKind, FuncType, Operator, Parser, Payload, Type, TypeRef, ValType, Validator,
+    WasmFeatures,
+};
+use wasmer::FunctionType;
+use wasmer_types::CompileError;
+
+use crate::logic::{host_signatures, VMLimits};
+
+#[cfg(test)]
+#[path = "tests/prepare.rs"]
+mod tests;
+
+/// Validates a module against what the host provides and the given limits,
+/// before it is handed over to the compiler.
+///
+/// # Errors
+///
+/// Returns [`CompileError::Validate`] describing the first violation found.
+///
+pub fn prepare(bytes: &[u8], limits: &VMLimits) -> Result<(), CompileError> {
+    let features = WasmFeatures {
+        simd: !limits.deterministic_only,
+        threads: !limits.deterministic_only,
+        ..WasmFeatures::default()
+    };
+
+    let _ignored = Validator::new_with_features(features)
+        .validate_all(bytes)
+        .map_err(|err| invalid(err.to_string()))?;
+
+    let host = host_signatures();
+
+    let mut types = vec![];
+    let mut functions = 0_u32;
+    let mut tables = vec![];
+    let mut has_memory = false;
+
+    for payload in Parser::new(0).parse_all(bytes) {
+        match payload.map_err(|err| invalid(err.to_string()))? {
+            Payload::TypeSection(reader) => {
+                for ty in reader {
+                    let Type::Func(ty) = ty.map_err(|err| invalid(err.to_string()))?;
+
+                    if limits.deterministic_only && uses_floats(&ty) {
+                        return Err(invalid("floats are not allowed".to_owned()));
+                    }
+
+                    types.push(ty);
+                }
+            }
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import = import.map_err(|err| invalid(err.to_string()))?;
+
+                    let (module, name) = (import.module, import.name);
+
+                    let ty = match import.ty {
+                        TypeRef::Func(index) => usize::try_from(index)
+                            .ok()
+                            .and_then(|index| types.get(index)),
+                        TypeRef::Memory(_) => {
+                            return Err(invalid(format!(
+                                "memory must not be imported, found `{module}::{name}`"
+                            )))
+                        }
+                        TypeRef::Table(_) | TypeRef::Global(_) | TypeRef::Tag(_) => {
+                            return Err(invalid(format!(
+                                "only functions may be imported, found `{module}::{name}`"
+                            )))
+                        }
+                    };
+
+                    let expected = (module == "env").then(|| host.get(name)).flatten();
+
+                    let Some(expected) = expected else {
+                        return Err(invalid(format!(
+                            "import `{module}::{name}` is not provided by the host"
+                        )));
+                    };
+
+                    if !ty.is_some_and(|ty| signature_matches(ty, expected)) {
+                        return Err(invalid(format!(
+                            "import `{module}::{name}` has the wrong signature, expected {expected}"
+                        )));
+                    }
+                }
+            }
+            Payload::FunctionSection(reader) => {
+                functions = functions.saturating_add(reader.count());
+            }
+            Payload::TableSection(reader) => {
+                for table in reader {
+                    tables.push(table.map_err(|err| invalid(err.to_string()))?);
+                }
+            }
+            Payload::MemorySection(reader) => {
+                for memory in reader {
+                    let memory = memory.map_err(|err| invalid(err.to_string()))?;
+
+                    // the memory is capped by the tunables when it's created,
+                    // this only reports it ahead of time
+                    if memory.initial > u64::from(limits.max_memory_pages) {
+                        return Err(invalid(format!(
+                            "memory too large: {} pages, at most {} are allowed",
+                            memory.initial, limits.max_memory_pages
+                        )));
+                    }
+
+                    has_memory = true;
+                }
+            }
+            Payload::GlobalSection(reader) => {
+                for global in reader {
+                    let global = global.map_err(|err| invalid(err.to_string()))?;
+
+                    if limits.deterministic_only && is_float(global.ty.content_type) {
+                        return Err(invalid("floats are not allowed".to_owned()));
+                    }
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export.map_err(|err| invalid(err.to_string()))?;
+
+                    if export.name == "_start" && export.kind == ExternalKind::Func {
+                        return Err(invalid("`_start` must not be exported".to_owned()));
+                    }
+                }
+            }
+            Payload::StartSection { .. } => {
+                return Err(invalid("start functions are not allowed".to_owned()));
+            }
+            Payload::CodeSectionEntry(body) if limits.deterministic_only => {
+                for local in body
+                    .get_locals_reader()
+                    .map_err(|err| invalid(err.to_string()))?
+                {
+                    let (_, ty) = local.map_err(|err| invalid(err.to_string()))?;
+
+                    if is_float(ty) {
+                        return Err(invalid("floats are not allowed".to_owned()));
+                    }
+                }
+
+                let mut reader = body
+                    .get_operators_reader()
+                    .map_err(|err| invalid(err.to_string()))?;
+
+                while !reader.eof() {
+                    let operator = reader.read().map_err(|err| invalid(err.to_string()))?;
+
+                    if is_float_operator(&operator) {
+                        return Err(invalid(format!(
+                            "floats are not allowed, found `{operator:?}`"
+                        )));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !has_memory {
+        return Err(invalid("module must define its own memory".to_owned()));
+    }
+
+    if functions > limits.max_functions {
+        return Err(invalid(format!(
+            "too many functions: {functions}, at most {} are allowed",
+            limits.max_functions
+        )));
+    }
+
+    if tables.len() > usize::try_from(limits.max_tables).unwrap_or(usize::MAX) {
+        return Err(invalid(format!(
+            "too many tables: {}, at most {} are allowed",
+            tables.len(),
+            limits.max_tables
+        )));
+    }
+
+    if let Some(table) = tables
+        .iter()
+        .find(|table| table.initial > limits.max_table_elements)
+    {
+        return Err(invalid(format!(
+            "table too large: {} elements, at most {} are allowed",
+            table.initial, limits.max_table_elements
+        )));
+    }
+
+    Ok(())
+}
+
+const fn invalid(message: String) -> CompileError {
+    CompileError::Validate(message)
+}
+
+fn signature_matches(ty: &FuncType, expected: &FunctionType) -> bool {
+    fn eq(ty: &[ValType], expected: &[wasmer::Type]) -> bool {
+        ty.len() == expected.len()
+            && ty.iter().zip(expected).all(|(ty, expected)| {
+                matches!(
+                    (ty, expected),
+                    (ValType::I32, wasmer::Type::I32)
+                        | (ValType::I64, wasmer::Type::I64)
+                        | (ValType::F32, wasmer::Type::F32)
+                        | (ValType::F64, wasmer::Type::F64)
+                        | (ValType::V128, wasmer::Type::V128)
+                        | (ValType::FuncRef, wasmer::Type::FuncRef)
+                        | (ValType::ExternRef, wasmer::Type::ExternRef)
+                )
+            })
+    }
+
+    eq(ty.params(), expected.params()) && eq(ty.results(), expected.results())
+}
+
+const fn is_float(ty: ValType) -> bool {
+    matches!(ty, ValType::F32 | ValType::F64)
+}
+
+fn uses_floats(ty: &FuncType) -> bool {
+    ty.params()
+        .iter()
+        .chain(ty.results())
+        .any(|ty| is_float(*ty))
+}
+
+const fn is_float_operator(operator: &Operator<'_>) -> bool {
+    matches!(
+        operator,
+        Operator::F32Load { .. }
+            | Operator::F64Load { .. }
+            | Operator::F32Store { .. }
+            | Operator::F64Store { .. }
+            | Operator::F32Const { .. }
+            | Operator::F64Const { .. }
+            | Operator::F32Eq
+            | Operator::F32Ne
+            | Operator::F32Lt
+            | Operator::F32Gt
+            | Operator::F32Le
+            | Operator::F32Ge
+            | Operator::F64Eq
+            | Operator::F64Ne
+            | Operator::F64Lt
+            | Operator::F64Gt
+            | Operator::F64Le
+            | Operator::F64Ge
+            | Operator::F32Abs
+            | Operator::F32Neg
+            | Operator::F32Ceil
+            | Operator::F32Floor
+            | Operator::F32Trunc
+            | Operator::F32Nearest
+            | Operator::F32Sqrt
+            | Operator::F32Add
+            | Operator::F32Sub
+            | Operator::F32Mul
+            | Operator::F32Div
+            | Operator::F32Min
+            | Operator::F32Max
+            | Operator::F32Copysign
+            | Operator::F64Abs
+            | Operator::F64Neg
+            | Operator::F64Ceil
+            | Operator::F64Floor
+            | Operator::F64Trunc
+            | Operator::F64Nearest
+            | Operator::F64Sqrt
+            | Operator::F64Add
+            | Operator::F64Sub
+            | Operator::F64Mul
+            | Operator::F64Div
+            | Operator::F64Min
+            | Operator::F64Max
+            | Operator::F64Copysign
+            | Operator::I32TruncF32S
+            | Operator::I32TruncF32U
+            | Operator::I32TruncF64S
+            | Operator::I32TruncF64U
+            | Operator::I64TruncF32S
+            | Operator::I64TruncF32U
+            | Operator::I64TruncF64S
+            | Operator::I64TruncF64U
+            | Operator::I32TruncSatF32S
+            | Operator::I32TruncSatF32U
+            | Operator::I32TruncSatF64S
+            | Operator::I32TruncSatF64U
+            | Operator::I64TruncSatF32S
+            | Operator::I64TruncSatF32U
+            | Operator::I64TruncSatF64S
+            | Operator::I64TruncSatF64U
+            | Operator::F32ConvertI32S
+            | Operator::F32ConvertI32U
+            | Operator::F32ConvertI64S
+            | Operator::F32ConvertI64U
+            | Operator::F32DemoteF64
+            | Operator::F64ConvertI32S
+            | Operator::F64ConvertI32U
+            | Operator::F64ConvertI64S
+            | Operator::F64ConvertI64U
+            | Operator::F64PromoteF32
+            | Operator::I32ReinterpretF32
+            | Operator::I64ReinterpretF64
+            | Operator::F32ReinterpretI32
+            | Operator::F64ReinterpretI64
+    )
+}
@@ -1,7 +1,12 @@
+use core::cell::RefCell;
 use core::fmt::Debug;
+use core::ops::Bound;
 use std::collections::btree_map::IntoIter;
 use std::collections::BTreeMap;
+use std::rc::Rc;
 
+use calimero_primitives::blobs::BlobId;
+use calimero_primitives::hash::Hash;
 use calimero_primitives::reflect::Reflect;
 
 pub type Key = Vec<u8>;
@@ -12,11 +17,17 @@ pub trait Storage: Reflect {
     fn set(&mut self, key: Key, value: Value) -> Option<Value>;
     fn remove(&mut self, key: &Key) -> Option<Vec<u8>>;
     fn has(&self, key: &Key) -> bool;
+    /// Returns the number of bytes held, counting both keys and values.
+    fn usage(&self) -> u64;
+    /// Returns up to `limit` entries whose keys fall within the bounds, in
+    /// ascending order of key.
+    fn scan(&self, start: Bound<&[u8]>, end: Bound<&[u8]>, limit: usize) -> Vec<(Key, Value)>;
 }
 
 #[derive(Debug, Default)]
 pub struct InMemoryStorage {
     inner: BTreeMap<Key, Value>,
+    usage: u64,
 }
 
 impl Storage for InMemoryStorage {
@@ -25,17 +36,61 @@ impl Storage for InMemoryStorage {
     }
 
     fn set(&mut self, key: Key, value: Value) -> Option<Value> {
-        self.inner.insert(key, value)
+        let key_len = key.len();
+
+        let added = (key_len + value.len()) as u64;
+
+        let evicted = self.inner.insert(key, value);
+
+        let removed = evicted
+            .as_ref()
+            .map_or(0, |evicted| (key_len + evicted.len()) as u64);
+
+        self.usage = self.usage.saturating_add(added).saturating_sub(removed);
+
+        evicted
     }
 
     // todo! revisit this, should we return the value by default?
     fn remove(&mut self, key: &Key) -> Option<Vec<u8>> {
-        self.inner.remove(key)
+        let removed = self.inner.remove(key)?;
+
+        self.usage = self
+            .usage
+            .saturating_sub((key.len() + removed.len()) as u64);
+
+        Some(removed)
     }
 
     fn has(&self, key: &Key) -> bool {
         self.inner.contains_key(key)
     }
+
+    fn usage(&self) -> u64 {
+        self.usage
+    }
+
+    fn scan(&self, start: Bound<&[u8]>, end: Bound<&[u8]>, limit: usize) -> Vec<(Key, Value)> {
+        // BTreeMap::range panics on inverted bounds
+        let inverted = match (start, end) {
+            (Bound::Included(start), Bound::Included(end)) => start > end,
+            (
+                Bound::Included(start) | Bound::Excluded(start),
+                Bound::Included(end) | Bound::Excluded(end),
+            ) => start >= end,
+            _ => false,
+        };
+
+        if inverted {
+            return vec![];
+        }
+
+        self.inner
+            .range::<[u8], _>((start, end))
+            .take(limit)
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
 }
 
 impl IntoIterator for InMemoryStorage {
@@ -47,3 +102,73 @@ impl IntoIterator for InMemoryStorage {
         self.inner.into_iter()
     }
 }
+
+/// Access to the node's blob store, for blobs opened or created by the guest.
+pub trait BlobStorage {
+    /// Returns the size of the blob, if it exists.
+    fn size(&self, id: &BlobId) -> Option<u64>;
+    /// Reads up to `len` bytes of the blob, starting `offset` bytes in.
+    fn read(&self, id: &BlobId, offset: u64, len: u64) -> Option<Vec<u8>>;
+    /// Starts writing a new blob, which is only stored once finished.
+    fn create(&mut self) -> Box<dyn BlobWriter>;
+}
+
+/// A blob being written incrementally by the guest.
+pub trait BlobWriter {
+    /// Appends data to the blob, returning `false` if it could not be written.
+    fn write(&mut self, data: &[u8]) -> bool;
+    /// Stores the blob, returning its ID.
+    fn finish(self: Box<Self>) -> Option<BlobId>;
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryBlobStorage {
+    inner: Rc<RefCell<BTreeMap<BlobId, Vec<u8>>>>,
+}
+
+impl BlobStorage for InMemoryBlobStorage {
+    fn size(&self, id: &BlobId) -> Option<u64> {
+        self.inner.borrow().get(id).map(|data| data.len() as u64)
+    }
+
+    fn read(&self, id: &BlobId, offset: u64, len: u64) -> Option<Vec<u8>> {
+        let inner = self.inner.borrow();
+
+        let data = inner.get(id)?;
+
+        let start = usize::try_from(offset).map_or(data.len(), |offset| offset.min(data.len()));
+        let end = usize::try_from(len)
+            .map_or(data.len(), |len| start.saturating_add(len).min(data.len()));
+
+        Some(data[start..end].to_vec())
+    }
+
+    fn create(&mut self) -> Box<dyn BlobWriter> {
+        Box::new(InMemoryBlobWriter {
+            inner: Rc::clone(&self.inner),
+            data: vec![],
+        })
+    }
+}
+
+#[derive(Debug)]
+struct InMemoryBlobWriter {
+    inner: Rc<RefCell<BTreeMap<BlobId, Vec<u8>>>>,
+    data: Vec<u8>,
+}
+
+impl BlobWriter for InMemoryBlobWriter {
+    fn write(&mut self, data: &[u8]) -> bool {
+        self.data.extend_from_slice(data);
+
+        true
+    }
+
+    fn finish(self: Box<Self>) -> Option<BlobId> {
+        let id = BlobId::from(*Hash::new(&self.data));
+
+        drop(self.inner.borrow_mut().insert(id, self.data));
+
+        Some(id)
+    }
+}
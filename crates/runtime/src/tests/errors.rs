@@ -110,6 +110,24 @@ fn invalid_memory_access() {
     assert_json_eq!(json!(error), expected);
 }
 
+#[test]
+fn invalid_blob_handle() {
+    let error = FunctionCallError::HostError(HostError::InvalidBlobHandle { handle: 7 });
+
+    let expected = json!({
+        "type": "HostError",
+        "data": {
+            "type": "InvalidBlobHandle",
+            "data": {
+                "handle": 7
+            }
+        }
+    });
+
+    assert_eq!(error.to_string(), "invalid blob handle: 7");
+    assert_json_eq!(json!(error), expected);
+}
+
 #[test]
 fn panic_host() {
     let error = FunctionCallError::HostError(HostError::Panic {
@@ -167,3 +185,90 @@ fn panic_guest() {
     assert_eq!(error.to_string(), "guest panicked: explicit panic");
     assert_json_eq!(json!(error), expected);
 }
+
+#[test]
+fn out_of_gas() {
+    let error = FunctionCallError::OutOfGas;
+
+    let expected = json!({
+        "type": "OutOfGas"
+    });
+
+    assert_eq!(error.to_string(), "out of gas");
+    assert_json_eq!(json!(error), expected);
+}
+
+#[test]
+fn timed_out() {
+    let error = FunctionCallError::TimedOut;
+
+    let expected = json!({
+        "type": "TimedOut"
+    });
+
+    assert_eq!(error.to_string(), "execution timed out");
+    assert_json_eq!(json!(error), expected);
+}
+
+#[test]
+fn read_only() {
+    let error = FunctionCallError::HostError(HostError::ReadOnly);
+
+    let expected = json!({
+        "type": "HostError",
+        "data": {
+            "type": "ReadOnly"
+        }
+    });
+
+    assert_eq!(error.to_string(), "state is read-only");
+    assert_json_eq!(json!(error), expected);
+}
+
+#[test]
+fn storage_quota_exceeded() {
+    let error = FunctionCallError::HostError(HostError::StorageQuotaExceeded);
+
+    let expected = json!({
+        "type": "HostError",
+        "data": {
+            "type": "StorageQuotaExceeded"
+        }
+    });
+
+    assert_eq!(error.to_string(), "storage quota exceeded");
+    assert_json_eq!(json!(error), expected);
+}
+
+#[test]
+fn invalid_storage_iterator() {
+    let error = FunctionCallError::HostError(HostError::InvalidStorageIterator { handle: 3 });
+
+    let expected = json!({
+        "type": "HostError",
+        "data": {
+            "type": "InvalidStorageIterator",
+            "data": {
+                "handle": 3
+            }
+        }
+    });
+
+    assert_eq!(error.to_string(), "invalid storage iterator: 3");
+    assert_json_eq!(json!(error), expected);
+}
+
+#[test]
+fn call_depth_overflow() {
+    let error = FunctionCallError::HostError(HostError::CallDepthOverflow);
+
+    let expected = json!({
+        "type": "HostError",
+        "data": {
+            "type": "CallDepthOverflow"
+        }
+    });
+
+    assert_eq!(error.to_string(), "call depth overflow");
+    assert_json_eq!(json!(error), expected);
+}
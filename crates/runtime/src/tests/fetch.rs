@@ -0,0 +1,264 @@
+use std::io::Write;
+use std::net::TcpListener;
+use std::thread;
+
+use super::*;
+
+fn policy(allowed_hosts: &[&str]) -> FetchPolicy {
+    FetchPolicy {
+        allowed_hosts: allowed_hosts
+            .iter()
+            .map(|host| (*host).to_owned())
+            .collect(),
+        ..FetchPolicy::default()
+    }
+}
+
+fn rejection(policy: &FetchPolicy, url: &str, method: &str) -> String {
+    policy
+        .check(url, method)
+        .expect_err("request should be rejected")
+}
+
+#[test]
+fn denies_everything_by_default() {
+    let policy = FetchPolicy::default();
+
+    assert_eq!(
+        rejection(&policy, "https://example.com/", "GET"),
+        "host `example.com` is not allowed"
+    );
+}
+
+#[test]
+fn allows_listed_host() {
+    let policy = policy(&["example.com"]);
+
+    assert_eq!(policy.check("https://example.com/data", "GET"), Ok(()));
+    assert_eq!(policy.check("http://example.com:8080/data", "GET"), Ok(()));
+
+    assert_eq!(
+        rejection(&policy, "https://api.example.com/", "GET"),
+        "host `api.example.com` is not allowed"
+    );
+    assert_eq!(
+        rejection(&policy, "https://example.org/", "GET"),
+        "host `example.org` is not allowed"
+    );
+}
+
+#[test]
+fn matches_hosts_ignoring_case() {
+    let policy = policy(&["Example.COM", "*.Calimero.NETWORK"]);
+
+    assert_eq!(policy.check("https://EXAMPLE.com/", "GET"), Ok(()));
+    assert_eq!(policy.check("https://api.calimero.network/", "GET"), Ok(()));
+    assert_eq!(policy.check("https://API.CALIMERO.network/", "GET"), Ok(()));
+}
+
+#[test]
+fn subdomain_wildcard() {
+    let policy = policy(&["*.example.com"]);
+
+    assert_eq!(policy.check("https://api.example.com/", "GET"), Ok(()));
+    assert_eq!(policy.check("https://a.b.example.com/", "GET"), Ok(()));
+
+    assert_eq!(
+        rejection(&policy, "https://example.com/", "GET"),
+        "host `example.com` is not allowed"
+    );
+    assert_eq!(
+        rejection(&policy, "https://badexample.com/", "GET"),
+        "host `badexample.com` is not allowed"
+    );
+    assert_eq!(
+        rejection(&policy, "https://example.com.evil.org/", "GET"),
+        "host `example.com.evil.org` is not allowed"
+    );
+}
+
+#[test]
+fn any_host_wildcard() {
+    let policy = policy(&["*"]);
+
+    assert_eq!(policy.check("https://example.com/", "GET"), Ok(()));
+    assert_eq!(policy.check("https://93.184.215.14/", "GET"), Ok(()));
+}
+
+#[test]
+fn wildcards_do_not_match_local_hosts() {
+    let policy = policy(&["*", "*.localhost"]);
+
+    for url in [
+        "http://localhost/",
+        "http://LOCALHOST:8080/",
+        "http://api.localhost/",
+        "http://127.0.0.1/",
+        "http://10.0.0.1/",
+        "http://172.16.0.1/",
+        "http://192.168.1.1/",
+        "http://169.254.169.254/latest/meta-data/",
+        "http://0.0.0.0/",
+        "http://[::1]/",
+        "http://[fd00::1]/",
+        "http://[fe80::1]/",
+        "http://[::ffff:127.0.0.1]/",
+    ] {
+        let reason = rejection(&policy, url, "GET");
+
+        assert!(
+            reason.ends_with("is local and must be allowed explicitly"),
+            "{url}: {reason}"
+        );
+    }
+}
+
+#[test]
+fn local_hosts_listed_exactly_are_allowed() {
+    let policy = policy(&["localhost", "127.0.0.1", "[::1]"]);
+
+    assert_eq!(policy.check("http://localhost:2428/", "GET"), Ok(()));
+    assert_eq!(policy.check("http://127.0.0.1/", "GET"), Ok(()));
+    assert_eq!(policy.check("http://[::1]/", "GET"), Ok(()));
+
+    assert_eq!(
+        rejection(&policy, "http://127.0.0.2/", "GET"),
+        "host `127.0.0.2` is not allowed"
+    );
+}
+
+#[test]
+fn rejects_other_schemes() {
+    let policy = policy(&["*"]);
+
+    assert_eq!(
+        rejection(&policy, "file:///etc/passwd", "GET"),
+        "scheme `file` is not allowed"
+    );
+    assert_eq!(
+        rejection(&policy, "ftp://example.com/", "GET"),
+        "scheme `ftp` is not allowed"
+    );
+    assert_eq!(policy.check("HTTPS://example.com/", "GET"), Ok(()));
+}
+
+#[test]
+fn rejects_invalid_urls() {
+    let policy = policy(&["*"]);
+
+    assert!(rejection(&policy, "not a url", "GET").starts_with("invalid url: "));
+}
+
+#[test]
+fn checks_methods_ignoring_case() {
+    let policy = FetchPolicy {
+        allowed_methods: vec!["GET".to_owned()],
+        ..policy(&["example.com"])
+    };
+
+    assert_eq!(policy.check("https://example.com/", "get"), Ok(()));
+
+    assert_eq!(
+        rejection(&policy, "https://example.com/", "POST"),
+        "method `POST` is not allowed"
+    );
+}
+
+#[test]
+fn in_memory_backend_serves_canned_responses() {
+    let backend = InMemoryFetchBackend::default();
+
+    backend.respond("get", "https://example.com/data", Ok(b"hello".to_vec()));
+    backend.respond("POST", "https://example.com/data", Err("boom".to_owned()));
+
+    let request = |method, url| FetchRequest {
+        url,
+        method,
+        headers: &[],
+        body: &[],
+        timeout: Duration::from_secs(1),
+        max_response_size: 1 << 10,
+        allowed_hosts: &[],
+    };
+
+    assert_eq!(
+        backend.fetch(&request("GET", "https://example.com/data")),
+        Ok(b"hello".to_vec())
+    );
+    assert_eq!(
+        backend.fetch(&request("post", "https://example.com/data")),
+        Err("boom".to_owned())
+    );
+    assert_eq!(
+        backend.fetch(&request("GET", "https://example.com/other")),
+        Err("no response for GET https://example.com/other".to_owned())
+    );
+}
+
+#[test]
+fn resolve_rejects_local_addresses() {
+    let err = resolve("localhost:80", &[]).expect_err("localhost should be rejected");
+    assert_eq!(err.kind(), IoErrorKind::PermissionDenied);
+
+    let err = resolve("127.0.0.1:80", &["*".to_owned()]).expect_err("loopback should be rejected");
+    assert_eq!(err.kind(), IoErrorKind::PermissionDenied);
+
+    let err = resolve("[::1]:80", &[]).expect_err("loopback should be rejected");
+    assert_eq!(err.kind(), IoErrorKind::PermissionDenied);
+
+    let err = resolve("10.0.0.1:80", &[]).expect_err("private address should be rejected");
+    assert_eq!(err.kind(), IoErrorKind::PermissionDenied);
+}
+
+#[test]
+fn resolve_allows_exactly_listed_local_hosts() {
+    assert!(resolve("localhost:80", &["LocalHost".to_owned()]).is_ok());
+    assert!(resolve("127.0.0.1:80", &["127.0.0.1".to_owned()]).is_ok());
+    assert!(resolve("[::1]:80", &["::1".to_owned()]).is_ok());
+
+    assert!(resolve("93.184.215.14:80", &[]).is_ok());
+}
+
+#[test]
+fn http_backend_does_not_follow_redirects() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("listener should bind");
+    let addr = listener
+        .local_addr()
+        .expect("listener should have an address");
+
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("connection should be accepted");
+
+        let mut buffer = [0; 1024];
+        let _ignored = stream.read(&mut buffer).expect("request should be read");
+
+        stream
+            .write_all(
+                b"HTTP/1.1 302 Found\r\n\
+                  Location: http://169.254.169.254/\r\n\
+                  Content-Length: 0\r\n\
+                  Connection: close\r\n\r\n",
+            )
+            .expect("response should be written");
+    });
+
+    let url = format!("http://{addr}/");
+    let allowed_hosts = [addr.ip().to_string()];
+
+    let request = FetchRequest {
+        url: &url,
+        method: "GET",
+        headers: &[],
+        body: &[],
+        timeout: Duration::from_secs(5),
+        max_response_size: 1 << 10,
+        allowed_hosts: &allowed_hosts,
+    };
+
+    assert_eq!(
+        HttpFetchBackend.fetch(&request),
+        Err("redirects are not followed, got status 302".to_owned())
+    );
+
+    server.join().expect("server should not panic");
+}
@@ -0,0 +1,1389 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use calimero_primitives::hash::Hash;
+
+use super::*;
+use crate::calls::ContextCallOutcome;
+use crate::fetch::InMemoryFetchBackend;
+use crate::store::{InMemoryBlobStorage, InMemoryStorage};
+use crate::{Engine, Module};
+
+const CONTEXT_ID: [u8; 32] = [1; 32];
+const EXECUTOR: [u8; 32] = [2; 32];
+
+/// The host functions used by the test modules.
+const IMPORTS: &str = r#"
+    (import "env" "input" (func $input (param i64)))
+    (import "env" "register_len" (func $register_len (param i64) (result i64)))
+    (import "env" "read_register" (func $read_register (param i64 i64 i64) (result i32)))
+    (import "env" "value_return" (func $value_return (param i64 i64 i64)))
+    (import "env" "storage_write"
+        (func $storage_write (param i64 i64 i64 i64 i64) (result i32)))
+    (import "env" "storage_remove" (func $storage_remove (param i64 i64 i64) (result i32)))
+    (import "env" "storage_iter_prefix" (func $storage_iter_prefix (param i64 i64) (result i64)))
+    (import "env" "storage_iter_range"
+        (func $storage_iter_range (param i64 i64 i64 i64) (result i64)))
+    (import "env" "storage_iter_next" (func $storage_iter_next (param i64 i64 i64) (result i32)))
+    (import "env" "storage_iter_close" (func $storage_iter_close (param i64)))
+    (import "env" "blob_open" (func $blob_open (param i64 i64) (result i64)))
+    (import "env" "blob_read" (func $blob_read (param i64 i64 i64) (result i64)))
+    (import "env" "blob_create" (func $blob_create (result i64)))
+    (import "env" "blob_write" (func $blob_write (param i64 i64 i64)))
+    (import "env" "blob_close" (func $blob_close (param i64 i64) (result i32)))
+    (import "env" "call_context"
+        (func $call_context (param i64 i64 i64 i64 i64 i64 i64) (result i32)))
+    (import "env" "random_bytes" (func $random_bytes (param i64 i64)))
+    (import "env" "time_now" (func $time_now (param i64 i64)))
+    (import "env" "fetch"
+        (func $fetch (param i64 i64 i64 i64 i64 i64 i64 i64 i64) (result i32)))
+"#;
+
+/// Compiles a module exporting its memory, made up of the given functions
+/// and data, with the host functions above imported.
+fn module(engine: &Engine, body: &str) -> Module {
+    let wat = format!(r#"(module {IMPORTS} (memory (export "memory") 1) {body})"#);
+
+    let bytes = wat::parse_str(wat).expect("module should be valid");
+
+    engine.compile(&bytes).expect("module should compile")
+}
+
+fn run(
+    module: &Module,
+    method: &str,
+    input: &[u8],
+    storage: &mut InMemoryStorage,
+    blobs: &mut InMemoryBlobStorage,
+) -> Outcome {
+    module
+        .run(
+            CONTEXT_ID.into(),
+            EXECUTOR.into(),
+            method,
+            input,
+            storage,
+            blobs,
+        )
+        .expect("execution should not fail")
+}
+
+fn view(
+    module: &Module,
+    method: &str,
+    budget: CallBudget,
+    storage: &mut InMemoryStorage,
+    blobs: &mut InMemoryBlobStorage,
+) -> Outcome {
+    module
+        .view(
+            CONTEXT_ID.into(),
+            EXECUTOR.into(),
+            method,
+            &[],
+            budget,
+            storage,
+            blobs,
+        )
+        .expect("execution should not fail")
+}
+
+/// Answers every call with `pong`, as if the callee used the given gas,
+/// recording the budget each call was made with.
+#[derive(Debug)]
+struct RecordingCalls {
+    gas_used: u64,
+    budgets: Mutex<Vec<CallBudget>>,
+}
+
+impl RecordingCalls {
+    fn new(gas_used: u64) -> Arc<Self> {
+        Arc::new(Self {
+            gas_used,
+            budgets: Mutex::default(),
+        })
+    }
+
+    fn budgets(&self) -> Vec<CallBudget> {
+        self.budgets
+            .lock()
+            .expect("mutex should not be poisoned")
+            .clone()
+    }
+}
+
+impl ContextCallBackend for RecordingCalls {
+    fn call(&self, call: &ContextCall<'_>) -> ContextCallOutcome {
+        self.budgets
+            .lock()
+            .expect("mutex should not be poisoned")
+            .push(call.budget);
+
+        ContextCallOutcome {
+            returns: Ok(b"pong".to_vec()),
+            gas_used: self.gas_used,
+        }
+    }
+}
+
+/// Calls `pong` on the context with the all-zero id, returning its result.
+const CALL: &str = r#"
+    (data (i32.const 32) "pong")
+    (func (export "call")
+        (if (call $call_context
+                (i64.const 0) (i64.const 32)
+                (i64.const 32) (i64.const 4)
+                (i64.const 0) (i64.const 0)
+                (i64.const 0))
+            (then unreachable))
+        (drop (call $read_register (i64.const 0) (i64.const 64) (i64.const 4)))
+        (call $value_return (i64.const 0) (i64.const 64) (i64.const 4)))
+"#;
+
+/// Fetches `https://example.com/data`, returning the response with the
+/// status of the request as the tag, so failures come back as errors.
+const FETCH: &str = r#"
+    (data (i32.const 0) "https://example.com/data")
+    (data (i32.const 32) "GET")
+    (func (export "fetch")
+        (local $status i32)
+        (local $len i64)
+        (local.set $status
+            (call $fetch
+                (i64.const 0) (i64.const 24)
+                (i64.const 32) (i64.const 3)
+                (i64.const 40) (i64.const 4)
+                (i64.const 0) (i64.const 0)
+                (i64.const 0)))
+        (local.set $len (call $register_len (i64.const 0)))
+        (drop (call $read_register (i64.const 0) (i64.const 64) (local.get $len)))
+        (call $value_return
+            (i64.extend_i32_u (local.get $status))
+            (i64.const 64)
+            (local.get $len)))
+"#;
+
+/// Limits which allow fetching from `example.com`.
+fn fetch_limits() -> VMLimits {
+    VMLimits {
+        fetch: FetchPolicy {
+            allowed_hosts: vec!["example.com".to_owned()],
+            ..FetchPolicy::default()
+        },
+        ..VMLimits::default()
+    }
+}
+
+/// Drains storage iterators, returning the entries as `key=value;` pairs.
+const ITERATE: &str = r#"
+    (global $out (mut i64) (i64.const 1024))
+    (func $append (param $register i64)
+        (local $len i64)
+        (local.set $len (call $register_len (local.get $register)))
+        (drop (call $read_register (local.get $register) (global.get $out) (local.get $len)))
+        (global.set $out (i64.add (global.get $out) (local.get $len))))
+    (func $append_byte (param $byte i32)
+        (i32.store8 (i32.wrap_i64 (global.get $out)) (local.get $byte))
+        (global.set $out (i64.add (global.get $out) (i64.const 1))))
+    (func $next (param $iter i64) (result i32)
+        (if (i32.eqz (call $storage_iter_next (local.get $iter) (i64.const 1) (i64.const 2)))
+            (then (return (i32.const 0))))
+        (call $append (i64.const 1))
+        (call $append_byte (i32.const 61))
+        (call $append (i64.const 2))
+        (call $append_byte (i32.const 59))
+        (i32.const 1))
+    (func $drain (param $iter i64)
+        (loop $next
+            (br_if $next (call $next (local.get $iter))))
+        (call $storage_iter_close (local.get $iter))
+        (call $value_return
+            (i64.const 0)
+            (i64.const 1024)
+            (i64.sub (global.get $out) (i64.const 1024))))
+"#;
+
+/// Fills a storage with the given keys, each holding its position.
+fn storage_with(keys: &[&str]) -> InMemoryStorage {
+    let mut storage = InMemoryStorage::default();
+
+    for (value, key) in keys.iter().enumerate() {
+        assert_eq!(
+            storage.set(key.as_bytes().to_vec(), value.to_string().into_bytes()),
+            None
+        );
+    }
+
+    storage
+}
+
+fn store_blob(blobs: &mut InMemoryBlobStorage, data: &[u8]) -> BlobId {
+    let mut writer = blobs.create();
+
+    assert!(writer.write(data));
+
+    writer.finish().expect("blob should be stored")
+}
+
+#[test]
+fn blob_write_and_close() {
+    let module = module(
+        &Engine::default(),
+        r#"
+        (data (i32.const 0) "hello, world")
+        (func (export "write")
+            (local $handle i64)
+            (local.set $handle (call $blob_create))
+            (call $blob_write (local.get $handle) (i64.const 0) (i64.const 5))
+            (call $blob_write (local.get $handle) (i64.const 5) (i64.const 7))
+            (if (i32.ne (call $blob_close (local.get $handle) (i64.const 0)) (i32.const 1))
+                (then unreachable))
+            (drop (call $read_register (i64.const 0) (i64.const 64) (i64.const 32)))
+            (call $value_return (i64.const 0) (i64.const 64) (i64.const 32)))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "write", &[], &mut storage, &mut blobs);
+
+    let id = BlobId::from(*Hash::new(b"hello, world"));
+
+    assert_eq!(outcome.returns.unwrap(), Some(id.to_vec()));
+    assert_eq!(blobs.size(&id), Some(12));
+    assert_eq!(blobs.read(&id, 0, 12).unwrap(), b"hello, world");
+}
+
+#[test]
+fn blob_open_and_read_in_chunks() {
+    let module = module(
+        &Engine::default(),
+        r#"
+        (func (export "read")
+            (local $handle i64)
+            (call $input (i64.const 0))
+            (drop (call $read_register (i64.const 0) (i64.const 0) (i64.const 32)))
+            (local.set $handle (call $blob_open (i64.const 0) (i64.const 32)))
+            (if (i64.eqz (local.get $handle)) (then unreachable))
+
+            (if (i64.ne (call $blob_read (local.get $handle) (i64.const 5) (i64.const 1)) (i64.const 5))
+                (then unreachable))
+            (drop (call $read_register (i64.const 1) (i64.const 100) (i64.const 5)))
+
+            (if (i64.ne (call $blob_read (local.get $handle) (i64.const 5) (i64.const 1)) (i64.const 3))
+                (then unreachable))
+            (drop (call $read_register (i64.const 1) (i64.const 105) (i64.const 3)))
+
+            (if (i64.ne (call $blob_read (local.get $handle) (i64.const 5) (i64.const 1)) (i64.const 0))
+                (then unreachable))
+
+            (if (i32.ne (call $blob_close (local.get $handle) (i64.const 1)) (i32.const 0))
+                (then unreachable))
+            (call $value_return (i64.const 0) (i64.const 100) (i64.const 8)))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let id = store_blob(&mut blobs, b"abcdefgh");
+
+    let outcome = run(&module, "read", id.as_ref(), &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), Some(b"abcdefgh".to_vec()));
+}
+
+#[test]
+fn blob_open_missing() {
+    let module = module(
+        &Engine::default(),
+        r#"
+        (func (export "open")
+            (if (i64.ne (call $blob_open (i64.const 0) (i64.const 32)) (i64.const 0))
+                (then unreachable)))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "open", &[], &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), None);
+}
+
+#[test]
+fn blob_unclosed_writer_is_discarded() {
+    let module = module(
+        &Engine::default(),
+        r#"
+        (data (i32.const 0) "discarded")
+        (func (export "write")
+            (call $blob_write (call $blob_create) (i64.const 0) (i64.const 9)))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "write", &[], &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), None);
+    assert_eq!(blobs.size(&BlobId::from(*Hash::new(b"discarded"))), None);
+}
+
+#[test]
+fn blob_read_from_writer() {
+    let module = module(
+        &Engine::default(),
+        r#"
+        (func (export "read")
+            (drop (call $blob_read (call $blob_create) (i64.const 1) (i64.const 0))))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "read", &[], &mut storage, &mut blobs);
+
+    assert!(matches!(
+        outcome.returns,
+        Err(FunctionCallError::HostError(HostError::InvalidBlobHandle {
+            handle: 1
+        }))
+    ));
+}
+
+#[test]
+fn blob_read_chunk_size_overflow() {
+    let limits = VMLimits {
+        max_blob_chunk_size: 10,
+        ..VMLimits::default()
+    };
+
+    let module = module(
+        &Engine::with_limits(limits),
+        r#"
+        (func (export "read")
+            (call $input (i64.const 0))
+            (drop (call $read_register (i64.const 0) (i64.const 0) (i64.const 32)))
+            (drop (call $blob_read
+                (call $blob_open (i64.const 0) (i64.const 32))
+                (i64.const 11)
+                (i64.const 1))))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let id = store_blob(&mut blobs, b"abcdefgh");
+
+    let outcome = run(&module, "read", id.as_ref(), &mut storage, &mut blobs);
+
+    assert!(matches!(
+        outcome.returns,
+        Err(FunctionCallError::HostError(
+            HostError::BlobChunkSizeOverflow
+        ))
+    ));
+}
+
+#[test]
+fn gas_used_is_reported() {
+    let module = module(
+        &Engine::default(),
+        r#"
+        (func (export "add")
+            (drop (i64.add (i64.const 1) (i64.const 2))))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "add", &[], &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), None);
+    assert!(outcome.gas_used > 0);
+    assert!(outcome.gas_used < VMLimits::default().max_gas);
+}
+
+#[test]
+fn gas_is_metered_for_every_module_of_an_engine() {
+    let engine = Engine::default();
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    for _ in 0..2 {
+        let module = module(
+            &engine,
+            r#"
+            (func (export "add")
+                (drop (i64.add (i64.const 1) (i64.const 2))))
+            "#,
+        );
+
+        let outcome = run(&module, "add", &[], &mut storage, &mut blobs);
+
+        assert_eq!(outcome.returns.unwrap(), None);
+        assert!(outcome.gas_used > 0);
+    }
+}
+
+#[test]
+fn gas_runs_out_in_loop() {
+    let limits = VMLimits {
+        max_gas: 1_000_000,
+        ..VMLimits::default()
+    };
+
+    let module = module(
+        &Engine::with_limits(limits),
+        r#"
+        (func (export "spin")
+            (loop $spin (br $spin)))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "spin", &[], &mut storage, &mut blobs);
+
+    assert!(matches!(outcome.returns, Err(FunctionCallError::OutOfGas)));
+    assert_eq!(outcome.gas_used, 1_000_000);
+}
+
+#[test]
+fn deadline_interrupts_infinite_loop() {
+    let limits = VMLimits {
+        max_gas: u64::MAX,
+        max_execution_time: Duration::from_millis(100),
+        ..VMLimits::default()
+    };
+
+    let module = module(
+        &Engine::with_limits(limits),
+        r#"
+        (func (export "spin")
+            (loop $spin (br $spin)))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let started = Instant::now();
+
+    let outcome = run(&module, "spin", &[], &mut storage, &mut blobs);
+
+    assert!(matches!(outcome.returns, Err(FunctionCallError::TimedOut)));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn deadline_interrupts_concurrent_calls() {
+    let spin = |millis| {
+        thread::spawn(move || {
+            let limits = VMLimits {
+                max_gas: u64::MAX,
+                max_execution_time: Duration::from_millis(millis),
+                ..VMLimits::default()
+            };
+
+            let module = module(
+                &Engine::with_limits(limits),
+                r#"
+                (func (export "spin")
+                    (loop $spin (br $spin)))
+                "#,
+            );
+
+            let mut storage = InMemoryStorage::default();
+            let mut blobs = InMemoryBlobStorage::default();
+
+            run(&module, "spin", &[], &mut storage, &mut blobs)
+        })
+    };
+
+    // the later deadline is watched first, so the timer has to be woken for
+    // the earlier one
+    let late = spin(300);
+    let early = spin(100);
+
+    for call in [early, late] {
+        let outcome = call.join().unwrap();
+
+        assert!(matches!(outcome.returns, Err(FunctionCallError::TimedOut)));
+    }
+}
+
+#[test]
+fn deadline_does_not_interrupt_finished_call() {
+    let limits = VMLimits {
+        max_execution_time: Duration::from_millis(100),
+        ..VMLimits::default()
+    };
+
+    let module = module(
+        &Engine::with_limits(limits),
+        r#"
+        (func (export "noop"))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "noop", &[], &mut storage, &mut blobs);
+
+    assert!(matches!(outcome.returns, Ok(None)));
+}
+
+#[test]
+fn gas_scales_with_bytes_written() {
+    let module = module(
+        &Engine::default(),
+        r#"
+        (data (i32.const 4096) "key")
+        (func (export "write")
+            (local $len i64)
+            (call $input (i64.const 0))
+            (local.set $len (call $register_len (i64.const 0)))
+            (drop (call $read_register (i64.const 0) (i64.const 0) (local.get $len)))
+            (drop (call $storage_write
+                (i64.const 4096) (i64.const 3)
+                (i64.const 0) (local.get $len)
+                (i64.const 1))))
+        "#,
+    );
+
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let small = run(
+        &module,
+        "write",
+        &[0; 10],
+        &mut InMemoryStorage::default(),
+        &mut blobs,
+    );
+
+    let large = run(
+        &module,
+        "write",
+        &[0; 1010],
+        &mut InMemoryStorage::default(),
+        &mut blobs,
+    );
+
+    assert_eq!(small.returns.unwrap(), None);
+    assert_eq!(large.returns.unwrap(), None);
+    assert_eq!(large.gas_used - small.gas_used, gas::bytes_cost(1000));
+}
+
+#[test]
+fn gas_for_bytes_runs_out() {
+    let limits = VMLimits {
+        max_gas: 100_000,
+        ..VMLimits::default()
+    };
+
+    let module = module(
+        &Engine::with_limits(limits),
+        r#"
+        (data (i32.const 4096) "key")
+        (func (export "write")
+            (drop (call $storage_write
+                (i64.const 4096) (i64.const 3)
+                (i64.const 0) (i64.const 20000)
+                (i64.const 1))))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "write", &[], &mut storage, &mut blobs);
+
+    assert!(matches!(outcome.returns, Err(FunctionCallError::OutOfGas)));
+    assert_eq!(outcome.gas_used, 100_000);
+}
+
+/// Writes the input under the key `k`.
+const WRITE_INPUT: &str = r#"
+    (data (i32.const 0) "k")
+    (func (export "write")
+        (local $len i64)
+        (call $input (i64.const 0))
+        (local.set $len (call $register_len (i64.const 0)))
+        (drop (call $read_register (i64.const 0) (i64.const 64) (local.get $len)))
+        (drop (call $storage_write
+            (i64.const 0) (i64.const 1)
+            (i64.const 64) (local.get $len)
+            (i64.const 1))))
+"#;
+
+#[test]
+fn storage_quota_is_enforced() {
+    let limits = VMLimits {
+        max_storage_usage: 10,
+        ..VMLimits::default()
+    };
+
+    let module = module(&Engine::with_limits(limits), WRITE_INPUT);
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "write", &[0; 5], &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), None);
+    assert_eq!(outcome.storage_usage, 6);
+
+    let outcome = run(&module, "write", &[0; 9], &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), None);
+    assert_eq!(outcome.storage_usage, 10);
+
+    let outcome = run(&module, "write", &[0; 10], &mut storage, &mut blobs);
+
+    assert!(matches!(
+        outcome.returns,
+        Err(FunctionCallError::HostError(
+            HostError::StorageQuotaExceeded
+        ))
+    ));
+}
+
+#[test]
+fn storage_quota_allows_shrinking() {
+    let limits = VMLimits {
+        max_storage_usage: 10,
+        ..VMLimits::default()
+    };
+
+    let module = module(&Engine::with_limits(limits), WRITE_INPUT);
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    // held before the quota was lowered
+    assert_eq!(storage.set(b"k".to_vec(), vec![0; 30]), None);
+    assert_eq!(storage.set(b"x".to_vec(), vec![0; 20]), None);
+
+    let outcome = run(&module, "write", &[0; 5], &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), None);
+    assert_eq!(outcome.storage_usage, 27);
+    assert_eq!(outcome.storage_written, 6);
+    assert_eq!(outcome.storage_removed, 31);
+
+    let outcome = run(&module, "write", &[0; 6], &mut storage, &mut blobs);
+
+    assert!(matches!(
+        outcome.returns,
+        Err(FunctionCallError::HostError(
+            HostError::StorageQuotaExceeded
+        ))
+    ));
+}
+
+#[test]
+fn storage_quota_can_be_lifted() {
+    let limits = VMLimits {
+        max_storage_usage: 10,
+        ..VMLimits::default()
+    };
+
+    let module = module(&Engine::with_limits(limits), WRITE_INPUT).without_storage_quota();
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "write", &[0; 20], &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), None);
+    assert_eq!(outcome.storage_usage, 21);
+}
+
+#[test]
+fn view_rejects_writes() {
+    let module = module(
+        &Engine::default(),
+        r#"
+        (data (i32.const 0) "keyvalue")
+        (func (export "write")
+            (drop (call $storage_write
+                (i64.const 0) (i64.const 3)
+                (i64.const 3) (i64.const 5)
+                (i64.const 0))))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = view(
+        &module,
+        "write",
+        CallBudget::default(),
+        &mut storage,
+        &mut blobs,
+    );
+
+    assert!(matches!(
+        outcome.returns,
+        Err(FunctionCallError::HostError(HostError::ReadOnly))
+    ));
+
+    assert!(!storage.has(&b"key".to_vec()));
+    assert_eq!(storage.usage(), 0);
+
+    let outcome = run(&module, "write", &[], &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), None);
+    assert_eq!(storage.get(&b"key".to_vec()), Some(b"value".to_vec()));
+}
+
+#[test]
+fn call_context_returns_callee_result() {
+    let calls = RecordingCalls::new(0);
+
+    let engine = Engine::default().with_context_call_backend(calls.clone());
+
+    let module = module(&engine, CALL);
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "call", &[], &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), Some(b"pong".to_vec()));
+
+    let [budget] = calls.budgets()[..] else {
+        panic!("expected exactly one call");
+    };
+
+    assert_eq!(budget.depth, 1);
+    assert!(budget.deadline.is_some());
+    assert!(budget
+        .gas
+        .is_some_and(|gas| gas > 0 && gas < VMLimits::default().max_gas));
+}
+
+#[test]
+fn call_context_enforces_max_call_depth() {
+    let limits = VMLimits {
+        max_call_depth: 2,
+        ..VMLimits::default()
+    };
+
+    let calls = RecordingCalls::new(0);
+
+    let engine = Engine::with_limits(limits).with_context_call_backend(calls.clone());
+
+    let module = module(&engine, CALL);
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let nested = CallBudget {
+        depth: 1,
+        ..CallBudget::default()
+    };
+
+    let outcome = view(&module, "call", nested, &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), Some(b"pong".to_vec()));
+    assert_eq!(calls.budgets().first().map(|budget| budget.depth), Some(2));
+
+    let too_deep = CallBudget {
+        depth: 2,
+        ..CallBudget::default()
+    };
+
+    let outcome = view(&module, "call", too_deep, &mut storage, &mut blobs);
+
+    assert!(matches!(
+        outcome.returns,
+        Err(FunctionCallError::HostError(HostError::CallDepthOverflow))
+    ));
+    assert_eq!(calls.budgets().len(), 1);
+}
+
+#[test]
+fn call_context_charges_callee_gas() {
+    let used = |callee_gas| {
+        let engine = Engine::default().with_context_call_backend(RecordingCalls::new(callee_gas));
+
+        let module = module(&engine, CALL);
+
+        let mut storage = InMemoryStorage::default();
+        let mut blobs = InMemoryBlobStorage::default();
+
+        let outcome = run(&module, "call", &[], &mut storage, &mut blobs);
+
+        assert!(outcome.returns.is_ok());
+
+        outcome.gas_used
+    };
+
+    assert_eq!(used(1_000_000).saturating_sub(used(0)), 1_000_000);
+}
+
+#[test]
+fn call_context_callee_gas_runs_out() {
+    let limits = VMLimits {
+        max_gas: 1_000_000,
+        ..VMLimits::default()
+    };
+
+    let engine =
+        Engine::with_limits(limits).with_context_call_backend(RecordingCalls::new(2_000_000));
+
+    let module = module(&engine, CALL);
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "call", &[], &mut storage, &mut blobs);
+
+    assert!(matches!(outcome.returns, Err(FunctionCallError::OutOfGas)));
+    assert_eq!(outcome.gas_used, 1_000_000);
+}
+
+#[test]
+fn host_gas_is_taken_from_guest_budget() {
+    let limits = VMLimits {
+        max_gas: u64::MAX,
+        max_execution_time: Duration::from_secs(10),
+        ..VMLimits::default()
+    };
+
+    // the callee leaves too little gas for the caller to spin for long, so
+    // it runs out well before the deadline
+    let engine = Engine::with_limits(limits)
+        .with_context_call_backend(RecordingCalls::new(u64::MAX - 1_000_000));
+
+    let module = module(
+        &engine,
+        r#"
+        (data (i32.const 32) "pong")
+        (func (export "call")
+            (drop (call $call_context
+                (i64.const 0) (i64.const 32)
+                (i64.const 32) (i64.const 4)
+                (i64.const 0) (i64.const 0)
+                (i64.const 0)))
+            (loop $spin (br $spin)))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "call", &[], &mut storage, &mut blobs);
+
+    assert!(matches!(outcome.returns, Err(FunctionCallError::OutOfGas)));
+    assert_eq!(outcome.gas_used, u64::MAX);
+}
+
+#[test]
+fn budget_caps_gas() {
+    let module = module(
+        &Engine::default(),
+        r#"
+        (func (export "spin")
+            (loop $spin (br $spin)))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let budget = CallBudget {
+        gas: Some(100_000),
+        ..CallBudget::default()
+    };
+
+    let outcome = view(&module, "spin", budget, &mut storage, &mut blobs);
+
+    assert!(matches!(outcome.returns, Err(FunctionCallError::OutOfGas)));
+    assert_eq!(outcome.gas_used, 100_000);
+}
+
+#[test]
+fn budget_caps_time() {
+    let limits = VMLimits {
+        max_gas: u64::MAX,
+        ..VMLimits::default()
+    };
+
+    let module = module(
+        &Engine::with_limits(limits),
+        r#"
+        (func (export "spin")
+            (loop $spin (br $spin)))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let budget = CallBudget {
+        deadline: Instant::now().checked_add(Duration::from_millis(100)),
+        ..CallBudget::default()
+    };
+
+    let started = Instant::now();
+
+    let outcome = view(&module, "spin", budget, &mut storage, &mut blobs);
+
+    assert!(matches!(outcome.returns, Err(FunctionCallError::TimedOut)));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn fetch_returns_response() {
+    let backend = Arc::new(InMemoryFetchBackend::default());
+
+    backend.respond("GET", "https://example.com/data", Ok(b"hello".to_vec()));
+
+    let engine = Engine::with_limits(fetch_limits()).with_fetch_backend(backend);
+
+    let module = module(&engine, FETCH);
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "fetch", &[], &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), Some(b"hello".to_vec()));
+}
+
+#[test]
+fn fetch_passes_failures_to_guest() {
+    let backend = Arc::new(InMemoryFetchBackend::default());
+
+    backend.respond("GET", "https://example.com/data", Err("boom".to_owned()));
+
+    let engine = Engine::with_limits(fetch_limits()).with_fetch_backend(backend);
+
+    let module = module(&engine, FETCH);
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "fetch", &[], &mut storage, &mut blobs);
+
+    assert!(matches!(
+        outcome.returns,
+        Err(FunctionCallError::ExecutionError(message)) if message == b"boom"
+    ));
+}
+
+#[test]
+fn fetch_denied_by_default() {
+    let backend = Arc::new(InMemoryFetchBackend::default());
+
+    backend.respond("GET", "https://example.com/data", Ok(b"hello".to_vec()));
+
+    let engine = Engine::default().with_fetch_backend(backend);
+
+    let module = module(&engine, FETCH);
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "fetch", &[], &mut storage, &mut blobs);
+
+    assert!(matches!(
+        outcome.returns,
+        Err(FunctionCallError::HostError(HostError::FetchNotAllowed { reason }))
+            if reason == "host `example.com` is not allowed"
+    ));
+}
+
+#[test]
+fn fetch_calls_overflow() {
+    let mut limits = fetch_limits();
+
+    limits.fetch.max_calls = 1;
+
+    let backend = Arc::new(InMemoryFetchBackend::default());
+
+    backend.respond("GET", "https://example.com/data", Ok(b"hello".to_vec()));
+
+    let engine = Engine::with_limits(limits).with_fetch_backend(backend);
+
+    let module = module(
+        &engine,
+        &format!(
+            r#"
+            {FETCH}
+            (func (export "fetch_twice")
+                (call $fetch_once)
+                (call $fetch_once))
+            (func $fetch_once
+                (drop (call $fetch
+                    (i64.const 0) (i64.const 24)
+                    (i64.const 32) (i64.const 3)
+                    (i64.const 40) (i64.const 4)
+                    (i64.const 0) (i64.const 0)
+                    (i64.const 0))))
+            "#
+        ),
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "fetch", &[], &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), Some(b"hello".to_vec()));
+
+    let outcome = run(&module, "fetch_twice", &[], &mut storage, &mut blobs);
+
+    assert!(matches!(
+        outcome.returns,
+        Err(FunctionCallError::HostError(HostError::FetchCallsOverflow))
+    ));
+}
+
+#[test]
+fn storage_iter_prefix() {
+    let module = module(
+        &Engine::default(),
+        &format!(
+            r#"
+            {ITERATE}
+            (func (export "prefix")
+                (local $len i64)
+                (call $input (i64.const 0))
+                (local.set $len (call $register_len (i64.const 0)))
+                (drop (call $read_register (i64.const 0) (i64.const 0) (local.get $len)))
+                (call $drain (call $storage_iter_prefix (i64.const 0) (local.get $len))))
+            "#
+        ),
+    );
+
+    let mut storage = storage_with(&["a", "k", "k1", "k2", "ka", "l"]);
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "prefix", b"k", &mut storage, &mut blobs);
+
+    assert_eq!(
+        outcome.returns.unwrap(),
+        Some(b"k=1;k1=2;k2=3;ka=4;".to_vec())
+    );
+
+    let outcome = run(&module, "prefix", b"k1", &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), Some(b"k1=2;".to_vec()));
+
+    let outcome = run(&module, "prefix", b"z", &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), Some(vec![]));
+
+    let outcome = run(&module, "prefix", b"", &mut storage, &mut blobs);
+
+    assert_eq!(
+        outcome.returns.unwrap(),
+        Some(b"a=0;k=1;k1=2;k2=3;ka=4;l=5;".to_vec())
+    );
+}
+
+#[test]
+fn storage_iter_prefix_of_max_bytes() {
+    let module = module(
+        &Engine::default(),
+        &format!(
+            r#"
+            {ITERATE}
+            (data (i32.const 0) "k\ff")
+            (func (export "prefix")
+                (call $drain (call $storage_iter_prefix (i64.const 0) (i64.const 2))))
+            "#
+        ),
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    for key in [&b"k\xfe"[..], b"k\xff", b"k\xff\xff", b"l"] {
+        assert_eq!(storage.set(key.to_vec(), b"v".to_vec()), None);
+    }
+
+    let outcome = run(&module, "prefix", &[], &mut storage, &mut blobs);
+
+    assert_eq!(
+        outcome.returns.unwrap(),
+        Some(b"k\xff=v;k\xff\xff=v;".to_vec())
+    );
+}
+
+#[test]
+fn storage_iter_range() {
+    let module = module(
+        &Engine::default(),
+        &format!(
+            r#"
+            {ITERATE}
+            (data (i32.const 0) "b")
+            (data (i32.const 8) "cz")
+            (func (export "bounded")
+                (call $drain (call $storage_iter_range
+                    (i64.const 0) (i64.const 1)
+                    (i64.const 8) (i64.const 2))))
+            (func (export "unbounded")
+                (call $drain (call $storage_iter_range
+                    (i64.const 8) (i64.const 2)
+                    (i64.const 0) (i64.const 0))))
+            (func (export "inverted")
+                (call $drain (call $storage_iter_range
+                    (i64.const 8) (i64.const 2)
+                    (i64.const 0) (i64.const 1))))
+            "#
+        ),
+    );
+
+    let mut storage = storage_with(&["a", "b", "c", "cz", "d"]);
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "bounded", &[], &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), Some(b"b=1;c=2;".to_vec()));
+
+    let outcome = run(&module, "unbounded", &[], &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), Some(b"cz=3;d=4;".to_vec()));
+
+    let outcome = run(&module, "inverted", &[], &mut storage, &mut blobs);
+
+    assert_eq!(outcome.returns.unwrap(), Some(vec![]));
+}
+
+#[test]
+fn storage_iter_continues_past_a_batch() {
+    let module = module(
+        &Engine::default(),
+        &format!(
+            r#"
+            {ITERATE}
+            (func (export "all")
+                (call $drain (call $storage_iter_prefix (i64.const 0) (i64.const 0))))
+            "#
+        ),
+    );
+
+    let keys = (0..40).map(|key| format!("{key:02}")).collect::<Vec<_>>();
+
+    let mut storage = storage_with(&keys.iter().map(String::as_str).collect::<Vec<_>>());
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "all", &[], &mut storage, &mut blobs);
+
+    let expected = keys
+        .iter()
+        .enumerate()
+        .map(|(value, key)| format!("{key}={value};"))
+        .collect::<String>();
+
+    assert_eq!(outcome.returns.unwrap(), Some(expected.into_bytes()));
+}
+
+#[test]
+fn storage_iter_observes_writes() {
+    let module = module(
+        &Engine::default(),
+        &format!(
+            r#"
+            {ITERATE}
+            (data (i32.const 0) "k")
+            (data (i32.const 8) "k3")
+            (data (i32.const 16) "k0")
+            (data (i32.const 24) "k4")
+            (data (i32.const 32) "new")
+            (func (export "rewrite")
+                (local $iter i64)
+                (local.set $iter (call $storage_iter_prefix (i64.const 0) (i64.const 1)))
+                (if (i32.eqz (call $next (local.get $iter))) (then unreachable))
+                ;; added past the cursor, so it is returned
+                (drop (call $storage_write
+                    (i64.const 8) (i64.const 2)
+                    (i64.const 32) (i64.const 3)
+                    (i64.const 3)))
+                ;; added behind the cursor, so it is not
+                (drop (call $storage_write
+                    (i64.const 16) (i64.const 2)
+                    (i64.const 32) (i64.const 3)
+                    (i64.const 3)))
+                (drop (call $storage_remove (i64.const 24) (i64.const 2) (i64.const 3)))
+                (call $drain (local.get $iter)))
+            "#
+        ),
+    );
+
+    let mut storage = storage_with(&["k1", "k2", "k4"]);
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "rewrite", &[], &mut storage, &mut blobs);
+
+    assert_eq!(
+        outcome.returns.unwrap(),
+        Some(b"k1=0;k2=1;k3=new;".to_vec())
+    );
+}
+
+#[test]
+fn storage_iter_closed_handle_is_invalid() {
+    let module = module(
+        &Engine::default(),
+        r#"
+        (func (export "close_twice")
+            (local $iter i64)
+            (local.set $iter (call $storage_iter_prefix (i64.const 0) (i64.const 0)))
+            (call $storage_iter_close (local.get $iter))
+            (call $storage_iter_close (local.get $iter)))
+        "#,
+    );
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = run(&module, "close_twice", &[], &mut storage, &mut blobs);
+
+    assert!(matches!(
+        outcome.returns,
+        Err(FunctionCallError::HostError(
+            HostError::InvalidStorageIterator { handle: 1 }
+        ))
+    ));
+}
+
+/// Returns everything the host gives the guest besides its input and state:
+/// random bytes, the time, the response to a request and the start of the
+/// blob whose id is the input.
+const NONDETERMINISTIC: &str = r#"
+    (data (i32.const 512) "https://example.com/data")
+    (data (i32.const 544) "GET")
+    (func (export "inputs")
+        (local $blob i64)
+        (call $random_bytes (i64.const 0) (i64.const 32))
+        (call $time_now (i64.const 32) (i64.const 8))
+        (if (call $fetch
+                (i64.const 512) (i64.const 24)
+                (i64.const 544) (i64.const 3)
+                (i64.const 552) (i64.const 4)
+                (i64.const 0) (i64.const 0)
+                (i64.const 0))
+            (then unreachable))
+        (drop (call $read_register (i64.const 0) (i64.const 40) (i64.const 5)))
+        (call $input (i64.const 1))
+        (drop (call $read_register (i64.const 1) (i64.const 600) (i64.const 32)))
+        (local.set $blob (call $blob_open (i64.const 600) (i64.const 32)))
+        (if (i64.eqz (local.get $blob)) (then unreachable))
+        (drop (call $blob_read (local.get $blob) (i64.const 5) (i64.const 2)))
+        (drop (call $read_register (i64.const 2) (i64.const 45) (i64.const 5)))
+        (call $value_return (i64.const 0) (i64.const 0) (i64.const 50)))
+"#;
+
+#[test]
+fn replay_reproduces_outcome() {
+    let backend = Arc::new(InMemoryFetchBackend::default());
+
+    backend.respond("GET", "https://example.com/data", Ok(b"hello".to_vec()));
+
+    let engine = Engine::with_limits(fetch_limits()).with_fetch_backend(backend.clone());
+
+    let module = module(&engine, NONDETERMINISTIC);
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let blob = store_blob(&mut blobs, b"world");
+
+    let recorded = run(&module, "inputs", blob.as_ref(), &mut storage, &mut blobs);
+
+    let returns = recorded
+        .returns
+        .unwrap()
+        .expect("inputs should be returned");
+
+    assert_eq!(&returns[40..], b"helloworld");
+    assert_eq!(recorded.replay.fetches, [Ok(b"hello".to_vec())]);
+    assert_eq!(
+        recorded.replay.blob_reads,
+        [
+            BlobRead::Opened(true),
+            BlobRead::Read(Some(b"world".to_vec()))
+        ]
+    );
+
+    // neither the response nor the blob are needed to replay the execution
+    backend.respond("GET", "https://example.com/data", Ok(b"howdy".to_vec()));
+
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let replay = |storage: &mut InMemoryStorage, blobs: &mut InMemoryBlobStorage| {
+        module
+            .replay(
+                CONTEXT_ID.into(),
+                EXECUTOR.into(),
+                "inputs",
+                blob.as_ref(),
+                recorded.replay.clone(),
+                storage,
+                blobs,
+            )
+            .expect("execution should not fail")
+    };
+
+    let first = replay(&mut storage, &mut blobs);
+    let second = replay(&mut storage, &mut blobs);
+
+    for replayed in [first, second] {
+        assert_eq!(replayed.returns.unwrap(), Some(returns.clone()));
+        assert_eq!(replayed.replay, recorded.replay);
+        assert_eq!(replayed.gas_used, recorded.gas_used);
+    }
+
+    // while running it afresh takes new inputs
+    let fresh = run(&module, "inputs", blob.as_ref(), &mut storage, &mut blobs);
+
+    assert_ne!(fresh.replay.seed, recorded.replay.seed);
+    assert_eq!(fresh.replay.fetches, [Ok(b"howdy".to_vec())]);
+    assert_eq!(fresh.replay.blob_reads, [BlobRead::Opened(false)]);
+}
+
+#[test]
+fn replay_fails_on_unrecorded_inputs() {
+    let engine = Engine::with_limits(fetch_limits())
+        .with_fetch_backend(Arc::new(InMemoryFetchBackend::default()));
+
+    let module = module(&engine, NONDETERMINISTIC);
+
+    let mut storage = InMemoryStorage::default();
+    let mut blobs = InMemoryBlobStorage::default();
+
+    let outcome = module
+        .replay(
+            CONTEXT_ID.into(),
+            EXECUTOR.into(),
+            "inputs",
+            &[0; 32],
+            Replay::new([0; 32], 0),
+            &mut storage,
+            &mut blobs,
+        )
+        .expect("execution should not fail");
+
+    assert!(matches!(
+        outcome.returns,
+        Err(FunctionCallError::HostError(HostError::ReplayDiverged))
+    ));
+}
@@ -0,0 +1,287 @@
+use super::*;
+
+/// Prepares a module made up of the given fields, under the given limits.
+fn prepare_wat(fields: &str, limits: &VMLimits) -> Result<(), CompileError> {
+    let bytes = wat::parse_str(format!("(module {fields})")).expect("module should be valid");
+
+    prepare(&bytes, limits)
+}
+
+/// Limits which reject floats, threads and SIMD.
+fn deterministic() -> VMLimits {
+    VMLimits {
+        deterministic_only: true,
+        ..VMLimits::default()
+    }
+}
+
+/// Asserts that a module is rejected with a message containing `needle`.
+fn assert_rejected(fields: &str, limits: &VMLimits, needle: &str) {
+    match prepare_wat(fields, limits) {
+        Err(CompileError::Validate(message)) => assert!(
+            message.contains(needle),
+            "expected `{needle}` in `{message}`"
+        ),
+        other => panic!("expected a validation error, got {other:?}"),
+    }
+}
+
+#[test]
+fn accepts_valid_module() {
+    let result = prepare_wat(
+        r#"
+        (import "env" "input" (func $input (param i64)))
+        (memory (export "memory") 1)
+        (func (export "main")
+            (call $input (i64.const 0)))
+        "#,
+        &VMLimits::default(),
+    );
+
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn rejects_memory_import() {
+    assert_rejected(
+        r#"(import "env" "memory" (memory 1))"#,
+        &VMLimits::default(),
+        "memory must not be imported, found `env::memory`",
+    );
+}
+
+#[test]
+fn rejects_missing_memory() {
+    assert_rejected(
+        r#"(func (export "main"))"#,
+        &VMLimits::default(),
+        "module must define its own memory",
+    );
+}
+
+#[test]
+fn rejects_memory_too_large() {
+    let limits = VMLimits {
+        max_memory_pages: 2,
+        ..VMLimits::default()
+    };
+
+    assert_rejected("(memory 3)", &limits, "memory too large: 3 pages");
+}
+
+#[test]
+fn rejects_start_export() {
+    assert_rejected(
+        r#"
+        (memory 1)
+        (func (export "_start"))
+        "#,
+        &VMLimits::default(),
+        "`_start` must not be exported",
+    );
+}
+
+#[test]
+fn rejects_start_function() {
+    assert_rejected(
+        "
+        (memory 1)
+        (func $init)
+        (start $init)
+        ",
+        &VMLimits::default(),
+        "start functions are not allowed",
+    );
+}
+
+#[test]
+fn rejects_unknown_import() {
+    assert_rejected(
+        r#"
+        (import "env" "no_such_function" (func))
+        (memory 1)
+        "#,
+        &VMLimits::default(),
+        "import `env::no_such_function` is not provided by the host",
+    );
+}
+
+#[test]
+fn rejects_import_from_other_module() {
+    assert_rejected(
+        r#"
+        (import "wasi" "input" (func (param i64)))
+        (memory 1)
+        "#,
+        &VMLimits::default(),
+        "import `wasi::input` is not provided by the host",
+    );
+}
+
+#[test]
+fn rejects_mistyped_import() {
+    assert_rejected(
+        r#"
+        (import "env" "input" (func (param i32)))
+        (memory 1)
+        "#,
+        &VMLimits::default(),
+        "import `env::input` has the wrong signature",
+    );
+}
+
+#[test]
+fn rejects_non_function_import() {
+    assert_rejected(
+        r#"
+        (import "env" "counter" (global i64))
+        (memory 1)
+        "#,
+        &VMLimits::default(),
+        "only functions may be imported, found `env::counter`",
+    );
+}
+
+#[test]
+fn rejects_too_many_functions() {
+    let limits = VMLimits {
+        max_functions: 2,
+        ..VMLimits::default()
+    };
+
+    assert_rejected(
+        "
+        (memory 1)
+        (func) (func) (func)
+        ",
+        &limits,
+        "too many functions: 3, at most 2 are allowed",
+    );
+}
+
+#[test]
+fn rejects_too_many_tables() {
+    let limits = VMLimits {
+        max_tables: 1,
+        ..VMLimits::default()
+    };
+
+    let one_table = prepare_wat(
+        "
+        (memory 1)
+        (table 1 funcref)
+        ",
+        &limits,
+    );
+
+    assert!(one_table.is_ok(), "{one_table:?}");
+
+    let limits = VMLimits {
+        max_tables: 0,
+        ..VMLimits::default()
+    };
+
+    assert_rejected(
+        "
+        (memory 1)
+        (table 1 funcref)
+        ",
+        &limits,
+        "too many tables: 1, at most 0 are allowed",
+    );
+}
+
+#[test]
+fn rejects_table_too_large() {
+    let limits = VMLimits {
+        max_table_elements: 10,
+        ..VMLimits::default()
+    };
+
+    assert_rejected(
+        "
+        (memory 1)
+        (table 11 funcref)
+        ",
+        &limits,
+        "table too large: 11 elements, at most 10 are allowed",
+    );
+}
+
+#[test]
+fn rejects_float_operators() {
+    assert_rejected(
+        r#"
+        (memory 1)
+        (func (export "main")
+            (drop (i32.trunc_f32_s (f32.const 1.5))))
+        "#,
+        &deterministic(),
+        "floats are not allowed",
+    );
+}
+
+#[test]
+fn rejects_float_signatures() {
+    assert_rejected(
+        r#"
+        (memory 1)
+        (func (export "main") (param f64))
+        "#,
+        &deterministic(),
+        "floats are not allowed",
+    );
+}
+
+#[test]
+fn rejects_float_locals() {
+    assert_rejected(
+        r#"
+        (memory 1)
+        (func (export "main") (local f32))
+        "#,
+        &deterministic(),
+        "floats are not allowed",
+    );
+}
+
+#[test]
+fn allows_floats_by_default() {
+    let result = prepare_wat(
+        r#"
+        (memory 1)
+        (func (export "main") (param f64)
+            (drop (f64.add (local.get 0) (f64.const 1.5))))
+        "#,
+        &VMLimits::default(),
+    );
+
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn rejects_simd() {
+    let result = prepare_wat(
+        r#"
+        (memory 1)
+        (func (export "main")
+            (drop (i32x4.splat (i32.const 1))))
+        "#,
+        &deterministic(),
+    );
+
+    assert!(
+        matches!(result, Err(CompileError::Validate(_))),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn rejects_threads() {
+    let result = prepare_wat("(memory 1 1 shared)", &deterministic());
+
+    assert!(
+        matches!(result, Err(CompileError::Validate(_))),
+        "{result:?}"
+    );
+}
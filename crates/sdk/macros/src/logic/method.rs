@@ -171,9 +171,21 @@ impl ToTokens for PublicLogicMethod<'_> {
                         ::calimero_sdk::env::panic_str("Expected payload to sync method.")
                     };
 
+                    <#self_ as ::calimero_sdk::state::AppState>::register_merges();
+
                     ::calimero_storage::collections::Root::<#self_>::sync(&args).expect("fatal: sync failed");
                 }
 
+                #[cfg(target_arch = "wasm32")]
+                #[no_mangle]
+                pub extern "C" fn __calimero_purge_tombstones() {
+                    let Some(args) = ::calimero_sdk::env::input() else {
+                        ::calimero_sdk::env::panic_str("Expected payload to purge method.")
+                    };
+
+                    let _purged = ::calimero_storage::collections::Root::<#self_>::purge_tombstones(&args).expect("fatal: purge failed");
+                }
+
                 impl ::calimero_sdk::state::AppStateInit for #self_ {
                     type Return = #ret;
                 }
@@ -1,8 +1,10 @@
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::Bracket;
 use syn::{
-    parse2, BoundLifetimes, Error as SynError, GenericParam, Generics, Ident, Lifetime,
+    bracketed, parse2, BoundLifetimes, Error as SynError, GenericParam, Generics, Ident, Lifetime,
     LifetimeParam, Result as SynResult, Token, Type,
 };
 
@@ -17,6 +19,7 @@ pub struct StateImpl<'a> {
     ident: &'a Ident,
     generics: &'a Generics,
     emits: &'a Option<MaybeBoundEvent>,
+    merges: &'a [Type],
     orig: &'a StructOrEnumItem,
 }
 
@@ -26,6 +29,7 @@ impl ToTokens for StateImpl<'_> {
             ident,
             generics,
             emits,
+            merges,
             orig,
         } = *self;
 
@@ -49,6 +53,10 @@ impl ToTokens for StateImpl<'_> {
 
             impl #impl_generics ::calimero_sdk::state::AppState for #ident #ty_generics #where_clause {
                 type Event<#lifetime> = #event;
+
+                fn register_merges() {
+                    #(<#merges as ::calimero_storage::entities::Data>::register_merges();)*
+                }
             }
 
             impl #impl_generics #ident #ty_generics #where_clause {
@@ -183,8 +191,7 @@ impl Parse for MaybeBoundEvent {
 
         let ty = infallible!({ parse2(sanitizer.into_token_stream()) });
 
-        input
-            .is_empty()
+        (input.is_empty() || input.peek(Token![,]))
             .then(|| Self { lifetime, ty })
             .ok_or_else(|| input.error("unexpected token"))
     }
@@ -192,13 +199,15 @@ impl Parse for MaybeBoundEvent {
 
 pub struct StateArgs {
     emits: Option<MaybeBoundEvent>,
+    merges: Vec<Type>,
 }
 
 impl Parse for StateArgs {
     fn parse(input: ParseStream<'_>) -> SynResult<Self> {
         let mut emits = None;
+        let mut merges = Vec::new();
 
-        if !input.is_empty() {
+        while !input.is_empty() {
             if !input.peek(Ident) {
                 return Err(input.error("expected an identifier"));
             }
@@ -229,6 +238,19 @@ impl Parse for StateArgs {
                     }
                     emits = Some(input.parse::<MaybeBoundEvent>()?);
                 }
+                "merges" => {
+                    if !input.peek(Bracket) {
+                        return Err(SynError::new_spanned(
+                            eq,
+                            "expected a list of mergeable types after `=`",
+                        ));
+                    }
+                    let content;
+                    let _bracket = bracketed!(content in input);
+                    merges = Punctuated::<Type, Token![,]>::parse_terminated(&content)?
+                        .into_iter()
+                        .collect();
+                }
                 _ => {
                     return Err(SynError::new_spanned(
                         &ident,
@@ -238,11 +260,11 @@ impl Parse for StateArgs {
             }
 
             if !input.is_empty() {
-                return Err(input.error("unexpected token"));
+                let _comma = input.parse::<Token![,]>()?;
             }
         }
 
-        Ok(Self { emits })
+        Ok(Self { emits, merges })
     }
 }
 
@@ -292,6 +314,7 @@ impl<'a> TryFrom<StateImplInput<'a>> for StateImpl<'a> {
             ident,
             generics,
             emits: &input.args.emits,
+            merges: &input.args.merges,
             orig: input.item,
         })
     }
@@ -10,6 +10,7 @@ use crate::sys::{
 pub mod ext;
 
 const DATA_REGISTER: RegisterId = RegisterId::new(PtrSizedInt::MAX.as_usize() - 1);
+const VALUE_REGISTER: RegisterId = RegisterId::new(PtrSizedInt::MAX.as_usize() - 2);
 
 #[track_caller]
 #[inline]
@@ -182,6 +183,146 @@ pub fn storage_write(key: &[u8], value: &[u8]) -> bool {
         .unwrap_or_else(expected_boolean)
 }
 
+/// Iterates over the entries in storage whose keys start with the prefix,
+/// in ascending order of key.
+#[inline]
+#[must_use]
+pub fn storage_iter_prefix(prefix: &[u8]) -> StorageIter {
+    let handle = unsafe { sys::storage_iter_prefix(Buffer::from(prefix)) };
+
+    StorageIter {
+        handle: handle.as_usize(),
+    }
+}
+
+/// Iterates over the entries in storage whose keys are at least `start`, and
+/// less than `end` if given, in ascending order of key.
+#[inline]
+#[must_use]
+pub fn storage_iter_range(start: &[u8], end: Option<&[u8]>) -> StorageIter {
+    let handle = unsafe {
+        sys::storage_iter_range(Buffer::from(start), Buffer::from(end.unwrap_or_default()))
+    };
+
+    StorageIter {
+        handle: handle.as_usize(),
+    }
+}
+
+/// An iterator over entries in storage, yielding their keys and values.
+///
+/// Entries are looked up as the iterator advances, so writes made in the
+/// meantime are observed.
+#[derive(Debug)]
+pub struct StorageIter {
+    handle: usize,
+}
+
+impl Iterator for StorageIter {
+    type Item = (Vec<u8>, Vec<u8>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let found: bool = unsafe {
+            sys::storage_iter_next(PtrSizedInt::new(self.handle), DATA_REGISTER, VALUE_REGISTER)
+        }
+        .try_into()
+        .unwrap_or_else(expected_boolean);
+
+        if !found {
+            return None;
+        }
+
+        let key = read_register(DATA_REGISTER).unwrap_or_else(expected_register);
+        let value = read_register(VALUE_REGISTER).unwrap_or_else(expected_register);
+
+        Some((key, value))
+    }
+}
+
+impl Drop for StorageIter {
+    fn drop(&mut self) {
+        unsafe { sys::storage_iter_close(PtrSizedInt::new(self.handle)) }
+    }
+}
+
+/// Opens a blob for reading, returning a handle to it, or `None` if there is
+/// no such blob.
+#[inline]
+#[must_use]
+pub fn blob_open(blob_id: &[u8; 32]) -> Option<usize> {
+    let handle = unsafe { sys::blob_open(Buffer::from(&blob_id[..])) };
+
+    (handle.as_usize() != 0).then(|| handle.as_usize())
+}
+
+/// Reads up to `len` bytes from an open blob, continuing from where the
+/// previous read left off. An empty chunk is returned at the end of the blob.
+#[inline]
+#[must_use]
+pub fn blob_read(handle: usize, len: usize) -> Vec<u8> {
+    let read = unsafe {
+        sys::blob_read(
+            PtrSizedInt::new(handle),
+            PtrSizedInt::new(len),
+            DATA_REGISTER,
+        )
+    };
+
+    if read.as_usize() == 0 {
+        return vec![];
+    }
+
+    read_register(DATA_REGISTER).unwrap_or_else(expected_register)
+}
+
+/// Starts writing a new blob, returning a handle to it.
+#[inline]
+#[must_use]
+pub fn blob_create() -> usize {
+    unsafe { sys::blob_create() }.as_usize()
+}
+
+/// Appends data to a blob being written.
+#[inline]
+pub fn blob_write(handle: usize, data: &[u8]) {
+    unsafe { sys::blob_write(PtrSizedInt::new(handle), Buffer::from(data)) }
+}
+
+/// Closes an open blob. For blobs being written, this stores the blob and
+/// returns its ID, which can be kept in state and shared with other nodes.
+#[inline]
+pub fn blob_close(handle: usize) -> Option<[u8; 32]> {
+    unsafe { sys::blob_close(PtrSizedInt::new(handle), DATA_REGISTER) }
+        .try_into()
+        .unwrap_or_else(expected_boolean::<bool>)
+        .then(|| read_register_sized(DATA_REGISTER).unwrap_or_else(expected_register))
+}
+
+/// Calls a method of another context on this node, returning its return
+/// value, or the reason the call failed.
+///
+/// The method is run on behalf of this execution's executor, which must be a
+/// member of the called context, and can't change the called context's state.
+pub fn call_context(context_id: &[u8; 32], method: &str, args: &[u8]) -> Result<Vec<u8>, String> {
+    let failed = unsafe {
+        sys::call_context(
+            Buffer::from(&context_id[..]),
+            Buffer::from(method),
+            Buffer::from(args),
+            DATA_REGISTER,
+        )
+    }
+    .try_into()
+    .unwrap_or_else(expected_boolean);
+    let data = read_register(DATA_REGISTER).unwrap_or_else(expected_register);
+
+    if failed {
+        return Err(String::from_utf8_lossy(&data).into_owned());
+    }
+
+    Ok(data)
+}
+
 /// Fill the buffer with random bytes.
 #[inline]
 pub fn random_bytes(buf: &mut [u8]) {
@@ -4,6 +4,14 @@ use crate::event::AppEvent;
 
 pub trait AppState: BorshSerialize + BorshDeserialize + AppStateInit {
     type Event<'a>: AppEvent + 'a;
+
+    /// Registers the merge functions of the mergeable types the app stores.
+    ///
+    /// Every execution starts with no merge functions registered, so this is
+    /// called before applying changes from other nodes. The types are listed
+    /// using `#[app::state(merges = [...])]`.
+    ///
+    fn register_merges() {}
 }
 
 pub trait Identity<This = Self> {}
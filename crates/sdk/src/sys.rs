@@ -25,6 +25,14 @@ wasm_imports! {
         fn storage_read(key: Buffer<'_>, register_id: RegisterId) -> Bool;
         fn storage_remove(key: Buffer<'_>, register_id: RegisterId) -> Bool;
         fn storage_write(key: Buffer<'_>, value: Buffer<'_>, register_id: RegisterId) -> Bool;
+        fn storage_iter_prefix(prefix: Buffer<'_>) -> PtrSizedInt;
+        fn storage_iter_range(start: Buffer<'_>, end: Buffer<'_>) -> PtrSizedInt;
+        fn storage_iter_next(
+            handle: PtrSizedInt,
+            key_register_id: RegisterId,
+            value_register_id: RegisterId
+        ) -> Bool;
+        fn storage_iter_close(handle: PtrSizedInt);
         // --
         fn fetch(
             url: Buffer<'_>,
@@ -33,6 +41,18 @@ wasm_imports! {
             body: Buffer<'_>,
             register_id: RegisterId
         ) -> Bool;
+        fn call_context(
+            context_id: Buffer<'_>,
+            method: Buffer<'_>,
+            args: Buffer<'_>,
+            register_id: RegisterId
+        ) -> Bool;
+        // --
+        fn blob_open(blob_id: Buffer<'_>) -> PtrSizedInt;
+        fn blob_read(handle: PtrSizedInt, len: PtrSizedInt, register_id: RegisterId) -> PtrSizedInt;
+        fn blob_create() -> PtrSizedInt;
+        fn blob_write(handle: PtrSizedInt, data: Buffer<'_>);
+        fn blob_close(handle: PtrSizedInt, register_id: RegisterId) -> Bool;
         // --
         fn random_bytes(buf: BufferMut<'_>);
         fn time_now(buf: BufferMut<'_>);
@@ -54,7 +74,8 @@ macro_rules! wasm_imports {
                 }
             } else {
                 $(
-                    #[expect(unused_variables, reason = "Needed due to macro expansion")]
+                    #[expect(clippy::allow_attributes, reason = "Needed for the macro")]
+                    #[allow(unused_variables, reason = "Needed due to macro expansion")]
                     pub unsafe fn $func_name($($arg: $arg_ty),*) $(-> $returns)? {
                         panic!("host function `{}` is only available when compiled for wasm32", stringify!($func_name));
                     }
@@ -3,7 +3,10 @@ use calimero_context_config::types::{Capability, ContextIdentity, ContextStorage
 use calimero_context_config::{Proposal, ProposalWithApprovals};
 use calimero_primitives::alias::Alias;
 use calimero_primitives::application::{Application, ApplicationId};
-use calimero_primitives::context::{Context, ContextId, ContextInvitationPayload};
+use calimero_primitives::blobs::{BlobId, BlobInfo};
+use calimero_primitives::context::{
+    Context, ContextId, ContextInvitationPayload, ContextSyncRecord,
+};
 use calimero_primitives::hash::Hash;
 use calimero_primitives::identity::{ClientKey, ContextUser, PrivateKey, PublicKey, WalletType};
 use camino::Utf8PathBuf;
@@ -137,6 +140,61 @@ impl GetApplicationResponse {
         }
     }
 }
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct UploadBlobQuery {
+    pub size: Option<u64>,
+    pub hash: Option<Hash>,
+}
+
+impl UploadBlobQuery {
+    pub const fn new(size: Option<u64>, hash: Option<Hash>) -> Self {
+        Self { size, hash }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadBlobResponseData {
+    pub blob_id: BlobId,
+    pub size: u64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadBlobResponse {
+    pub data: UploadBlobResponseData,
+}
+
+impl UploadBlobResponse {
+    pub const fn new(blob_id: BlobId, size: u64) -> Self {
+        Self {
+            data: UploadBlobResponseData { blob_id, size },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListBlobsResponseData {
+    pub blobs: Vec<BlobInfo>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListBlobsResponse {
+    pub data: ListBlobsResponseData,
+}
+
+impl ListBlobsResponse {
+    pub const fn new(blobs: Vec<BlobInfo>) -> Self {
+        Self {
+            data: ListBlobsResponseData { blobs },
+        }
+    }
+}
 // -------------------------------------------- Context API --------------------------------------------
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -210,7 +268,81 @@ impl DeleteContextResponse {
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetContextResponse {
-    pub data: Context,
+    pub data: GetContextResponseData,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetContextResponseData {
+    #[serde(flatten)]
+    pub context: Context,
+    /// Bytes of keys and values held by the context's state.
+    pub storage_usage: u64,
+    /// How the context's syncs with its peers have gone, if it ever synced.
+    pub sync: Option<ContextSyncRecord>,
+}
+
+impl GetContextResponse {
+    pub const fn new(
+        context: Context,
+        storage_usage: u64,
+        sync: Option<ContextSyncRecord>,
+    ) -> Self {
+        Self {
+            data: GetContextResponseData {
+                context,
+                storage_usage,
+                sync,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncContextRequest {
+    /// The peer to sync with, otherwise any peer subscribed to the context.
+    #[serde(default)]
+    pub peer_id: Option<String>,
+}
+
+impl SyncContextRequest {
+    pub const fn new(peer_id: Option<String>) -> Self {
+        Self { peer_id }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncContextResponseData {
+    pub peer_id: String,
+    pub duration_ms: u64,
+    pub root_hash_before: Hash,
+    pub root_hash_after: Hash,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncContextResponse {
+    pub data: SyncContextResponseData,
+}
+
+impl SyncContextResponse {
+    pub const fn new(
+        peer_id: String,
+        duration_ms: u64,
+        root_hash_before: Hash,
+        root_hash_after: Hash,
+    ) -> Self {
+        Self {
+            data: SyncContextResponseData {
+                peer_id,
+                duration_ms,
+                root_hash_before,
+                root_hash_after,
+            },
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
@@ -1,6 +1,8 @@
-use calimero_context_primitives::messages::execute::ExecuteError;
+use calimero_context_primitives::messages::execute::{ExecuteError, ExecuteReplay};
+use calimero_context_primitives::messages::simulate::StateChange;
 use calimero_primitives::alias::Alias;
 use calimero_primitives::context::ContextId;
+use calimero_primitives::hash::Hash;
 use calimero_primitives::identity::PublicKey;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
@@ -71,6 +73,8 @@ impl Request<RequestPayload> {
 #[serde(tag = "method", content = "params", rename_all = "snake_case")]
 pub enum RequestPayload {
     Execute(ExecutionRequest),
+    Query(QueryRequest),
+    Simulate(SimulationRequest),
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -144,6 +148,9 @@ pub struct ExecutionRequest {
     pub executor_public_key: PublicKey,
     #[serde(default)]
     pub substitute: Vec<Alias<PublicKey>>,
+    /// The inputs returned by an earlier execution, to reproduce it.
+    #[serde(default)]
+    pub replay: Option<ExecuteReplay>,
 }
 
 impl ExecutionRequest {
@@ -154,6 +161,7 @@ impl ExecutionRequest {
         args_json: serde_json::Value,
         executor_public_key: PublicKey,
         substitute: Vec<Alias<PublicKey>>,
+        replay: Option<ExecuteReplay>,
     ) -> Self {
         Self {
             context_id,
@@ -161,6 +169,98 @@ impl ExecutionRequest {
             args_json,
             executor_public_key,
             substitute,
+            replay,
+        }
+    }
+}
+
+/// A request to run a method without changing the context's state.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct QueryRequest {
+    pub context_id: ContextId,
+    pub method: String,
+    pub args_json: serde_json::Value,
+    pub executor_public_key: PublicKey,
+    #[serde(default)]
+    pub substitute: Vec<Alias<PublicKey>>,
+}
+
+impl QueryRequest {
+    #[must_use]
+    pub const fn new(
+        context_id: ContextId,
+        method: String,
+        args_json: serde_json::Value,
+        executor_public_key: PublicKey,
+        substitute: Vec<Alias<PublicKey>>,
+    ) -> Self {
+        Self {
+            context_id,
+            method,
+            args_json,
+            executor_public_key,
+            substitute,
+        }
+    }
+}
+
+/// A request to preview the effect of a call, without applying it.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SimulationRequest {
+    pub context_id: ContextId,
+    pub method: String,
+    pub args_json: serde_json::Value,
+    pub executor_public_key: PublicKey,
+    #[serde(default)]
+    pub substitute: Vec<Alias<PublicKey>>,
+}
+
+impl SimulationRequest {
+    #[must_use]
+    pub const fn new(
+        context_id: ContextId,
+        method: String,
+        args_json: serde_json::Value,
+        executor_public_key: PublicKey,
+        substitute: Vec<Alias<PublicKey>>,
+    ) -> Self {
+        Self {
+            context_id,
+            method,
+            args_json,
+            executor_public_key,
+            substitute,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SimulationResponse {
+    pub output: Option<serde_json::Value>,
+    pub root_hash: Hash,
+    pub changes: Vec<StateChange>,
+    pub gas_used: u64,
+}
+
+impl SimulationResponse {
+    #[must_use]
+    pub const fn new(
+        output: Option<serde_json::Value>,
+        root_hash: Hash,
+        changes: Vec<StateChange>,
+        gas_used: u64,
+    ) -> Self {
+        Self {
+            output,
+            root_hash,
+            changes,
+            gas_used,
         }
     }
 }
@@ -170,12 +270,28 @@ impl ExecutionRequest {
 #[non_exhaustive]
 pub struct ExecutionResponse {
     pub output: Option<serde_json::Value>,
+    /// The root hash of the context's state after the execution. A replay
+    /// leaves the state as it was, and reports the hash it arrived at.
+    pub root_hash: Hash,
+    pub gas_used: u64,
+    /// What it takes to reproduce the execution.
+    pub replay: ExecuteReplay,
 }
 
 impl ExecutionResponse {
     #[must_use]
-    pub const fn new(output: Option<serde_json::Value>) -> Self {
-        Self { output }
+    pub const fn new(
+        output: Option<serde_json::Value>,
+        root_hash: Hash,
+        gas_used: u64,
+        replay: ExecuteReplay,
+    ) -> Self {
+        Self {
+            output,
+            root_hash,
+            gas_used,
+            replay,
+        }
     }
 }
 
@@ -1,6 +1,7 @@
 pub mod add_client_key;
 pub mod alias;
 pub mod applications;
+pub mod blobs;
 pub mod challenge;
 pub mod context;
 pub mod did;
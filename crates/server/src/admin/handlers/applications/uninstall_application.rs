@@ -14,7 +14,11 @@ pub async fn handler(
     Extension(state): Extension<Arc<AdminState>>,
     Json(req): Json<UninstallApplicationRequest>,
 ) -> impl IntoResponse {
-    match state.node_client.uninstall_application(&req.application_id) {
+    match state
+        .node_client
+        .uninstall_application(&req.application_id)
+        .await
+    {
         Ok(()) => ApiResponse {
             payload: UninstallApplicationResponse::new(req.application_id),
         }
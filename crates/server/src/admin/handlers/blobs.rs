@@ -0,0 +1,4 @@
+pub mod blob_info;
+pub mod download_blob;
+pub mod list_blobs;
+pub mod upload_blob;
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use axum::extract::Path;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::IntoResponse;
+use axum::Extension;
+use calimero_primitives::blobs::BlobId;
+
+use crate::admin::service::{parse_api_error, ApiError};
+use crate::AdminState;
+
+/// Describes a blob through the headers a download of it would have, without
+/// reading any of its content.
+pub async fn handler(
+    Extension(state): Extension<Arc<AdminState>>,
+    Path(blob_id): Path<BlobId>,
+) -> impl IntoResponse {
+    let info = match state.node_client.get_blob_info(&blob_id) {
+        Ok(Some(info)) => info,
+        Ok(None) => {
+            return ApiError {
+                status_code: StatusCode::NOT_FOUND,
+                message: "Blob not found".into(),
+            }
+            .into_response()
+        }
+        Err(err) => return parse_api_error(err).into_response(),
+    };
+
+    let mut headers = HeaderMap::new();
+
+    drop(headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/octet-stream"),
+    ));
+    drop(headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes")));
+    drop(headers.insert(header::CONTENT_LENGTH, HeaderValue::from(info.size)));
+
+    if let Ok(etag) = HeaderValue::from_str(&format!("\"{}\"", info.hash)) {
+        drop(headers.insert(header::ETAG, etag));
+    }
+
+    (StatusCode::OK, headers).into_response()
+}
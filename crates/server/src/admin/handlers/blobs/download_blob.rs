@@ -0,0 +1,164 @@
+#[cfg(test)]
+#[path = "../../../tests/admin/handlers/blobs/download_blob.rs"]
+mod tests;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract::Path;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::IntoResponse;
+use axum::Extension;
+use calimero_primitives::blobs::BlobId;
+
+use crate::admin::service::{parse_api_error, ApiError};
+use crate::AdminState;
+
+/// A byte range requested through the `Range` header.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ByteRange {
+    /// No usable range was requested, so the whole blob is served.
+    Full,
+    /// The inclusive range of bytes to serve.
+    Partial { start: u64, end: u64 },
+    /// The range lies entirely outside of the blob.
+    Unsatisfiable,
+}
+
+pub async fn handler(
+    Extension(state): Extension<Arc<AdminState>>,
+    Path(blob_id): Path<BlobId>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let info = match state.node_client.get_blob_info(&blob_id) {
+        Ok(Some(info)) => info,
+        Ok(None) => {
+            return ApiError {
+                status_code: StatusCode::NOT_FOUND,
+                message: "Blob not found".into(),
+            }
+            .into_response()
+        }
+        Err(err) => return parse_api_error(err).into_response(),
+    };
+
+    let (status, offset, len, content_range) =
+        match parse_range(headers.get(header::RANGE), info.size) {
+            ByteRange::Full => (StatusCode::OK, 0, info.size, None),
+            ByteRange::Partial { start, end } => (
+                StatusCode::PARTIAL_CONTENT,
+                start,
+                end.saturating_sub(start).saturating_add(1),
+                Some(format!("bytes {start}-{end}/{}", info.size)),
+            ),
+            ByteRange::Unsatisfiable => {
+                return (
+                    StatusCode::RANGE_NOT_SATISFIABLE,
+                    [(header::CONTENT_RANGE, format!("bytes */{}", info.size))],
+                )
+                    .into_response()
+            }
+        };
+
+    let blob = match state.node_client.get_blob_range(&blob_id, offset, len) {
+        Ok(Some(blob)) => blob,
+        Ok(None) => {
+            return ApiError {
+                status_code: StatusCode::NOT_FOUND,
+                message: "Blob not found".into(),
+            }
+            .into_response()
+        }
+        Err(err) => return parse_api_error(err).into_response(),
+    };
+
+    let mut headers = HeaderMap::new();
+
+    drop(headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/octet-stream"),
+    ));
+    drop(headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes")));
+    drop(headers.insert(header::CONTENT_LENGTH, HeaderValue::from(len)));
+
+    if let Ok(etag) = HeaderValue::from_str(&format!("\"{}\"", info.hash)) {
+        drop(headers.insert(header::ETAG, etag));
+    }
+
+    if let Some(content_range) = content_range {
+        if let Ok(value) = HeaderValue::from_str(&content_range) {
+            drop(headers.insert(header::CONTENT_RANGE, value));
+        }
+    }
+
+    (status, headers, Body::from_stream(blob)).into_response()
+}
+
+/// Parses a `Range` header against a blob of the given size.
+///
+/// Only a single range in bytes is supported. Anything else, including
+/// malformed headers, is ignored and the whole blob is served instead, as
+/// permitted by RFC 9110.
+///
+fn parse_range(value: Option<&HeaderValue>, size: u64) -> ByteRange {
+    let Some(spec) = value
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().strip_prefix("bytes="))
+    else {
+        return ByteRange::Full;
+    };
+
+    if spec.contains(',') {
+        return ByteRange::Full;
+    }
+
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return ByteRange::Full;
+    };
+
+    let last = size.checked_sub(1);
+
+    match (start.trim(), end.trim()) {
+        ("", "") => ByteRange::Full,
+        ("", suffix) => {
+            let Ok(suffix) = suffix.parse::<u64>() else {
+                return ByteRange::Full;
+            };
+
+            match last {
+                Some(last) if suffix != 0 => ByteRange::Partial {
+                    start: size.saturating_sub(suffix),
+                    end: last,
+                },
+                _ => ByteRange::Unsatisfiable,
+            }
+        }
+        (start, end) => {
+            let Ok(start) = start.parse::<u64>() else {
+                return ByteRange::Full;
+            };
+
+            let end = if end.is_empty() {
+                None
+            } else {
+                let Ok(end) = end.parse::<u64>() else {
+                    return ByteRange::Full;
+                };
+
+                if end < start {
+                    return ByteRange::Full;
+                }
+
+                Some(end)
+            };
+
+            match last {
+                Some(last) if start <= last => ByteRange::Partial {
+                    start,
+                    end: end.map_or(last, |end| end.min(last)),
+                },
+                _ => ByteRange::Unsatisfiable,
+            }
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use axum::response::IntoResponse;
+use axum::Extension;
+use calimero_server_primitives::admin::ListBlobsResponse;
+
+use crate::admin::service::{parse_api_error, ApiResponse};
+use crate::AdminState;
+
+pub async fn handler(Extension(state): Extension<Arc<AdminState>>) -> impl IntoResponse {
+    match state.node_client.list_blobs() {
+        Ok(blobs) => ApiResponse {
+            payload: ListBlobsResponse::new(blobs),
+        }
+        .into_response(),
+        Err(err) => parse_api_error(err).into_response(),
+    }
+}
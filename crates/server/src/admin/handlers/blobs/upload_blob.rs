@@ -0,0 +1,79 @@
+#[cfg(test)]
+#[path = "../../../tests/admin/handlers/blobs/upload_blob.rs"]
+mod tests;
+
+use std::io;
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract::Query;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Extension;
+use calimero_blobstore::BlobError;
+use calimero_server_primitives::admin::{UploadBlobQuery, UploadBlobResponse};
+use eyre::Report;
+use futures_util::TryStreamExt;
+
+use crate::admin::service::{parse_api_error, ApiError, ApiResponse};
+use crate::AdminState;
+
+pub async fn handler(
+    Extension(state): Extension<Arc<AdminState>>,
+    Query(query): Query<UploadBlobQuery>,
+    body: Body,
+) -> impl IntoResponse {
+    let stream = body
+        .into_data_stream()
+        .map_err(io::Error::other)
+        .into_async_read();
+
+    let (blob_id, size) = match state
+        .node_client
+        .add_blob(stream, query.size, query.hash.as_ref())
+        .await
+    {
+        Ok(blob) => blob,
+        Err(err) => return error_response(err).into_response(),
+    };
+
+    // the uploader is the blob's holder, so it survives garbage collection
+    if let Err(err) = state.node_client.retain_blob(&blob_id) {
+        return error_response(err).into_response();
+    }
+
+    ApiResponse {
+        payload: UploadBlobResponse::new(blob_id, size),
+    }
+    .into_response()
+}
+
+fn error_response(err: Report) -> ApiError {
+    if !is_client_error(&err) {
+        return parse_api_error(err);
+    }
+
+    ApiError {
+        status_code: StatusCode::BAD_REQUEST,
+        message: err.to_string(),
+    }
+}
+
+/// Whether the upload failed because of what the client sent,
+/// as opposed to a failure on our side (disk, database, etc.)
+fn is_client_error(err: &Report) -> bool {
+    err.chain().any(|cause| {
+        if let Some(err) = cause.downcast_ref::<BlobError>() {
+            return matches!(
+                err,
+                BlobError::SizeMismatch { .. } | BlobError::HashMismatch { .. }
+            );
+        }
+
+        // the request body stream failed, e.g. the client disconnected
+        cause
+            .downcast_ref::<io::Error>()
+            .and_then(io::Error::get_ref)
+            .is_some_and(|inner| inner.is::<axum::Error>())
+    })
+}
@@ -9,4 +9,5 @@ pub mod grant_capabilities;
 pub mod invite_to_context;
 pub mod join_context;
 pub mod revoke_capabilities;
+pub mod sync_context;
 pub mod update_context_application;
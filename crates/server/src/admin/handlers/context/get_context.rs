@@ -18,13 +18,23 @@ pub async fn handler(
     let context = state
         .ctx_client
         .get_context(&context_id)
+        .and_then(|context| {
+            context
+                .map(|context| {
+                    let usage = state.ctx_client.get_context_usage(&context_id)?;
+                    let sync = state.ctx_client.get_context_sync(&context_id)?;
+
+                    Ok((context, usage, sync))
+                })
+                .transpose()
+        })
         .map_err(|err| parse_api_error(err).into_response());
 
     #[expect(clippy::option_if_let_else, reason = "Clearer here")]
     match context {
         Ok(ctx) => match ctx {
-            Some(context) => ApiResponse {
-                payload: GetContextResponse { data: context },
+            Some((context, usage, sync)) => ApiResponse {
+                payload: GetContextResponse::new(context, usage, sync),
             }
             .into_response(),
             None => ApiError {
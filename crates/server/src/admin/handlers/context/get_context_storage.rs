@@ -3,17 +3,21 @@ use std::sync::Arc;
 use axum::extract::Path;
 use axum::response::IntoResponse;
 use axum::Extension;
+use calimero_primitives::context::ContextId;
 use calimero_server_primitives::admin::GetContextStorageResponse;
 
-use crate::admin::service::ApiResponse;
+use crate::admin::service::{parse_api_error, ApiResponse};
 use crate::AdminState;
 
 pub async fn handler(
-    Path(_context_id): Path<String>,
-    Extension(_state): Extension<Arc<AdminState>>,
+    Path(context_id): Path<ContextId>,
+    Extension(state): Extension<Arc<AdminState>>,
 ) -> impl IntoResponse {
-    ApiResponse {
-        payload: GetContextStorageResponse::new(0),
+    match state.ctx_client.get_context_usage(&context_id) {
+        Ok(usage) => ApiResponse {
+            payload: GetContextStorageResponse::new(usage),
+        }
+        .into_response(),
+        Err(err) => parse_api_error(err).into_response(),
     }
-    .into_response()
 }
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use axum::extract::{Json, Path};
+use axum::response::IntoResponse;
+use axum::Extension;
+use calimero_primitives::context::ContextId;
+use calimero_server_primitives::admin::{SyncContextRequest, SyncContextResponse};
+use libp2p::PeerId;
+use reqwest::StatusCode;
+
+use crate::admin::service::{parse_api_error, ApiError, ApiResponse};
+use crate::AdminState;
+
+pub async fn handler(
+    Path(context_id): Path<ContextId>,
+    Extension(state): Extension<Arc<AdminState>>,
+    Json(request): Json<SyncContextRequest>,
+) -> impl IntoResponse {
+    let peer_id = match request.peer_id.as_deref().map(str::parse::<PeerId>) {
+        None => None,
+        Some(Ok(peer_id)) => Some(peer_id),
+        Some(Err(_)) => {
+            return ApiError {
+                status_code: StatusCode::BAD_REQUEST,
+                message: "Invalid peer id".into(),
+            }
+            .into_response()
+        }
+    };
+
+    let result = state
+        .node_client
+        .sync(&context_id, peer_id)
+        .await
+        .map_err(parse_api_error);
+
+    match result {
+        Ok(outcome) => ApiResponse {
+            payload: SyncContextResponse::new(
+                outcome.peer_id.to_string(),
+                outcome.duration.as_millis().try_into().unwrap_or(u64::MAX),
+                outcome.root_hash_before,
+                outcome.root_hash_after,
+            ),
+        }
+        .into_response(),
+        Err(err) => err.into_response(),
+    }
+}
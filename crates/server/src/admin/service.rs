@@ -19,7 +19,7 @@ use tower_sessions::{MemoryStore, SessionManagerLayer};
 use tracing::info;
 
 use super::handlers::alias;
-use super::handlers::context::{grant_capabilities, revoke_capabilities};
+use super::handlers::context::{grant_capabilities, revoke_capabilities, sync_context};
 use super::handlers::did::delete_did_handler;
 use super::handlers::proposals::{
     get_context_storage_entries_handler, get_context_value_handler,
@@ -35,6 +35,7 @@ use crate::admin::handlers::applications::{
     get_application, install_application, install_dev_application, list_applications,
     uninstall_application,
 };
+use crate::admin::handlers::blobs::{blob_info, download_blob, list_blobs, upload_blob};
 use crate::admin::handlers::challenge::request_challenge_handler;
 use crate::admin::handlers::context::{
     create_context, delete_context, get_context, get_context_client_keys, get_context_identities,
@@ -116,6 +117,14 @@ pub(crate) fn setup(
             "/applications/:application_id",
             get(get_application::handler),
         )
+        .route(
+            "/blobs",
+            get(list_blobs::handler).post(upload_blob::handler),
+        )
+        .route(
+            "/blobs/:blob_id",
+            get(download_blob::handler).head(blob_info::handler),
+        )
         .route("/did", get(fetch_did_handler).delete(delete_did_handler))
         .route("/contexts", post(create_context::handler))
         .route("/contexts/:context_id", delete(delete_context::handler))
@@ -144,6 +153,7 @@ pub(crate) fn setup(
             "/contexts/:context_id/capabilities/revoke",
             post(revoke_capabilities::handler),
         )
+        .route("/contexts/:context_id/sync", post(sync_context::handler))
         .route("/contexts/invite", post(invite_to_context::handler))
         .route("/contexts/join", post(join_context::handler))
         .route("/contexts", get(get_contexts::handler))
@@ -244,6 +254,10 @@ pub(crate) fn setup(
             get(get_context_identities::handler),
         )
         .route("/dev/contexts/:context_id", delete(delete_context::handler))
+        .route(
+            "/dev/contexts/:context_id/sync",
+            post(sync_context::handler),
+        )
         .route(
             "/dev/identity/context",
             post(generate_context_identity::handler),
@@ -269,6 +283,14 @@ pub(crate) fn setup(
             get(get_proposal_handler),
         )
         .route("/dev/peers", get(get_peers_count_handler))
+        .route(
+            "/dev/blobs",
+            get(list_blobs::handler).post(upload_blob::handler),
+        )
+        .route(
+            "/dev/blobs/:blob_id",
+            get(download_blob::handler).head(blob_info::handler),
+        )
         .nest("/dev/alias", alias::service());
 
     let dev_router = if config.admin.as_ref().map_or(false, |c| c.auth_enabled) {
@@ -17,6 +17,8 @@ use crate::middleware::dev_auth::dev_mode_auth;
 use crate::middleware::jwt::JwtLayer;
 
 mod execute;
+mod query;
+mod simulate;
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 #[non_exhaustive]
@@ -92,6 +94,8 @@ async fn handle_request(
     let body = match serde_json::from_value(request.payload) {
         Ok(payload) => match payload {
             RequestPayload::Execute(request) => request.handle(state).await.to_res_body(),
+            RequestPayload::Query(request) => request.handle(state).await.to_res_body(),
+            RequestPayload::Simulate(request) => request.handle(state).await.to_res_body(),
         },
         Err(err) => {
             debug!(%err, "Failed to deserialize RequestPayload");
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use calimero_context_primitives::messages::execute::ExecuteResponse;
 use calimero_server_primitives::jsonrpc::{ExecutionError, ExecutionRequest, ExecutionResponse};
 use tracing::{error, info};
 
@@ -33,19 +34,40 @@ async fn handle(
             message: err.to_string(),
         })?;
 
-    let outcome = state
-        .ctx_client
-        .execute(
-            &request.context_id,
-            &request.executor_public_key,
-            request.method,
-            args,
-            request.substitute,
-            None,
-        )
-        .await
-        .map_err(ExecutionError::ExecuteError)?;
+    let outcome = match request.replay {
+        Some(replay) => {
+            state
+                .ctx_client
+                .replay(
+                    &request.context_id,
+                    &request.executor_public_key,
+                    request.method,
+                    args,
+                    request.substitute,
+                    replay,
+                )
+                .await
+        }
+        None => {
+            state
+                .ctx_client
+                .execute(
+                    &request.context_id,
+                    &request.executor_public_key,
+                    request.method,
+                    args,
+                    request.substitute,
+                    None,
+                )
+                .await
+        }
+    }
+    .map_err(ExecutionError::ExecuteError)?;
 
+    into_response(outcome)
+}
+
+pub(super) fn into_response(outcome: ExecuteResponse) -> Result<ExecutionResponse, ExecutionError> {
     let x = outcome.logs.len().checked_ilog10().unwrap_or(0) as usize + 1;
     for (i, log) in outcome.logs.iter().enumerate() {
         info!("execution log {i:>x$}| {}", log);
@@ -55,12 +77,22 @@ async fn handle(
         .returns
         .map_err(|e| ExecutionError::FunctionCallError(e.to_string()))?
     else {
-        return Ok(ExecutionResponse::new(None));
+        return Ok(ExecutionResponse::new(
+            None,
+            outcome.root_hash,
+            outcome.gas_used,
+            outcome.replay,
+        ));
     };
 
     let returns = serde_json::from_slice(&returns).map_err(|err| ExecutionError::SerdeError {
         message: err.to_string(),
     })?;
 
-    Ok(ExecutionResponse::new(Some(returns)))
+    Ok(ExecutionResponse::new(
+        Some(returns),
+        outcome.root_hash,
+        outcome.gas_used,
+        outcome.replay,
+    ))
 }
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use calimero_server_primitives::jsonrpc::{ExecutionError, ExecutionResponse, QueryRequest};
+use tracing::error;
+
+use super::execute::into_response;
+use super::{Request, RpcError, ServiceState};
+
+impl Request for QueryRequest {
+    type Response = ExecutionResponse;
+    type Error = ExecutionError;
+
+    async fn handle(
+        self,
+        state: Arc<ServiceState>,
+    ) -> Result<Self::Response, RpcError<Self::Error>> {
+        let context_id = self.context_id;
+        let executor_id = self.executor_public_key;
+
+        handle(self, &state).await.map_err(|err| {
+            error!(%context_id, %executor_id, %err, "Failed to run query");
+
+            RpcError::MethodCallError(err)
+        })
+    }
+}
+
+async fn handle(
+    request: QueryRequest,
+    state: &ServiceState,
+) -> Result<ExecutionResponse, ExecutionError> {
+    let args =
+        serde_json::to_vec(&request.args_json).map_err(|err| ExecutionError::SerdeError {
+            message: err.to_string(),
+        })?;
+
+    let outcome = state
+        .ctx_client
+        .query(
+            &request.context_id,
+            &request.executor_public_key,
+            request.method,
+            args,
+            request.substitute,
+        )
+        .await
+        .map_err(ExecutionError::ExecuteError)?;
+
+    into_response(outcome)
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use calimero_server_primitives::jsonrpc::{ExecutionError, SimulationRequest, SimulationResponse};
+use tracing::{error, info};
+
+use super::{Request, RpcError, ServiceState};
+
+impl Request for SimulationRequest {
+    type Response = SimulationResponse;
+    type Error = ExecutionError;
+
+    async fn handle(
+        self,
+        state: Arc<ServiceState>,
+    ) -> Result<Self::Response, RpcError<Self::Error>> {
+        let context_id = self.context_id;
+        let executor_id = self.executor_public_key;
+
+        handle(self, &state).await.map_err(|err| {
+            error!(%context_id, %executor_id, %err, "Failed to simulate request");
+
+            RpcError::MethodCallError(err)
+        })
+    }
+}
+
+async fn handle(
+    request: SimulationRequest,
+    state: &ServiceState,
+) -> Result<SimulationResponse, ExecutionError> {
+    let args =
+        serde_json::to_vec(&request.args_json).map_err(|err| ExecutionError::SerdeError {
+            message: err.to_string(),
+        })?;
+
+    let outcome = state
+        .ctx_client
+        .simulate(
+            &request.context_id,
+            &request.executor_public_key,
+            request.method,
+            args,
+            request.substitute,
+        )
+        .await
+        .map_err(ExecutionError::ExecuteError)?;
+
+    let x = outcome.logs.len().checked_ilog10().unwrap_or(0) as usize + 1;
+    for (i, log) in outcome.logs.iter().enumerate() {
+        info!("simulation log {i:>x$}| {}", log);
+    }
+
+    let output = outcome
+        .returns
+        .map_err(|e| ExecutionError::FunctionCallError(e.to_string()))?
+        .map(|returns| serde_json::from_slice(&returns))
+        .transpose()
+        .map_err(|err| ExecutionError::SerdeError {
+            message: err.to_string(),
+        })?;
+
+    Ok(SimulationResponse::new(
+        output,
+        outcome.root_hash,
+        outcome.changes,
+        outcome.gas_used,
+    ))
+}
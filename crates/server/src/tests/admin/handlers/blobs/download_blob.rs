@@ -0,0 +1,96 @@
+use super::*;
+
+fn parse(value: &str, size: u64) -> ByteRange {
+    parse_range(Some(&HeaderValue::from_str(value).unwrap()), size)
+}
+
+#[test]
+fn test_parse_range_absent() {
+    assert_eq!(parse_range(None, 1000), ByteRange::Full);
+}
+
+#[test]
+fn test_parse_range_bounded() {
+    assert_eq!(
+        parse("bytes=0-99", 1000),
+        ByteRange::Partial { start: 0, end: 99 }
+    );
+    assert_eq!(
+        parse("bytes=500-500", 1000),
+        ByteRange::Partial {
+            start: 500,
+            end: 500
+        }
+    );
+    assert_eq!(
+        parse(" bytes= 10 - 20 ", 1000),
+        ByteRange::Partial { start: 10, end: 20 }
+    );
+}
+
+#[test]
+fn test_parse_range_open_ended() {
+    assert_eq!(
+        parse("bytes=900-", 1000),
+        ByteRange::Partial {
+            start: 900,
+            end: 999
+        }
+    );
+    assert_eq!(
+        parse("bytes=0-", 1000),
+        ByteRange::Partial { start: 0, end: 999 }
+    );
+}
+
+#[test]
+fn test_parse_range_end_past_size() {
+    assert_eq!(
+        parse("bytes=900-5000", 1000),
+        ByteRange::Partial {
+            start: 900,
+            end: 999
+        }
+    );
+}
+
+#[test]
+fn test_parse_range_suffix() {
+    assert_eq!(
+        parse("bytes=-100", 1000),
+        ByteRange::Partial {
+            start: 900,
+            end: 999
+        }
+    );
+    assert_eq!(
+        parse("bytes=-5000", 1000),
+        ByteRange::Partial { start: 0, end: 999 }
+    );
+    assert_eq!(parse("bytes=-0", 1000), ByteRange::Unsatisfiable);
+}
+
+#[test]
+fn test_parse_range_out_of_range() {
+    assert_eq!(parse("bytes=1000-", 1000), ByteRange::Unsatisfiable);
+    assert_eq!(parse("bytes=1000-2000", 1000), ByteRange::Unsatisfiable);
+    assert_eq!(parse("bytes=0-", 0), ByteRange::Unsatisfiable);
+    assert_eq!(parse("bytes=-10", 0), ByteRange::Unsatisfiable);
+}
+
+#[test]
+fn test_parse_range_multiple_ranges() {
+    assert_eq!(parse("bytes=0-1,5-6", 1000), ByteRange::Full);
+    assert_eq!(parse("bytes=-10, 0-5", 1000), ByteRange::Full);
+}
+
+#[test]
+fn test_parse_range_malformed() {
+    assert_eq!(parse("items=0-10", 1000), ByteRange::Full);
+    assert_eq!(parse("bytes=", 1000), ByteRange::Full);
+    assert_eq!(parse("bytes=-", 1000), ByteRange::Full);
+    assert_eq!(parse("bytes=10", 1000), ByteRange::Full);
+    assert_eq!(parse("bytes=abc-", 1000), ByteRange::Full);
+    assert_eq!(parse("bytes=0-abc", 1000), ByteRange::Full);
+    assert_eq!(parse("bytes=20-10", 1000), ByteRange::Full);
+}
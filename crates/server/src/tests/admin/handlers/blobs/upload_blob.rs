@@ -0,0 +1,74 @@
+use calimero_primitives::hash::Hash;
+use eyre::WrapErr;
+
+use super::*;
+
+#[test]
+fn test_size_mismatch_is_client_error() {
+    let err = Report::from(BlobError::SizeMismatch {
+        expected: 10,
+        found: 11,
+    });
+
+    assert!(is_client_error(&err));
+    assert_eq!(error_response(err).status_code, StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn test_hash_mismatch_is_client_error() {
+    let err = Report::from(BlobError::HashMismatch {
+        expected: Hash::new(b"expected"),
+        found: Hash::new(b"found"),
+    });
+
+    assert!(is_client_error(&err));
+    assert_eq!(error_response(err).status_code, StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn test_body_stream_failure_is_client_error() {
+    let err = Report::from(io::Error::other(axum::Error::new(io::Error::from(
+        io::ErrorKind::ConnectionReset,
+    ))));
+
+    assert!(is_client_error(&err));
+    assert_eq!(error_response(err).status_code, StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn test_context_wrapped_mismatch_is_client_error() {
+    let err = Report::from(BlobError::SizeMismatch {
+        expected: 10,
+        found: 9,
+    })
+    .wrap_err("failed to add blob");
+
+    assert!(is_client_error(&err));
+}
+
+#[test]
+fn test_storage_failure_is_server_error() {
+    let err = Report::from(io::Error::from(io::ErrorKind::PermissionDenied));
+
+    assert!(!is_client_error(&err));
+    assert_eq!(
+        error_response(err).status_code,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+
+    let err = Report::from(BlobError::DanglingBlob { id: [0; 32].into() });
+
+    assert!(!is_client_error(&err));
+    assert_eq!(
+        error_response(err).status_code,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+
+    let err = eyre::eyre!("database is unavailable");
+
+    assert!(!is_client_error(&err));
+    assert_eq!(
+        error_response(err).status_code,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
@@ -1,11 +1,14 @@
 use borsh as _;
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, Data, DeriveInput, Fields, Type};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr, Path, Token, Type};
 
 #[cfg(test)]
 mod integration_tests_package_usage {
-    use {borsh as _, calimero_sdk as _, calimero_storage as _, trybuild as _};
+    use borsh as _;
+    use calimero_sdk as _;
+    use calimero_storage as _;
+    use trybuild as _;
 }
 
 /// Derives the [`AtomicUnit`](calimero_storage::entities::AtomicUnit) trait for
@@ -30,6 +33,8 @@ mod integration_tests_package_usage {
 ///   - [`Data`](calimero_storage::entities::Data) trait implementation.
 ///   - [`AtomicUnit`](calimero_storage::entities::AtomicUnit) trait
 ///     implementation.
+///   - [`Mergeable`](calimero_storage::entities::Mergeable) trait
+///     implementation, if a merge function is given using `#[storage(merge)]`.
 ///   - Getter and setter methods for each field. These help to ensure that the
 ///     access to the fields is controlled, and that any changes to the fields
 ///     are reflected in the [`Element`](calimero_storage::entities::Element)'s
@@ -43,6 +48,22 @@ mod integration_tests_package_usage {
 /// * `#[root]`       - Indicates that the type represents a root in the
 ///                     hierarchy, and doesn't have a parent. This is an
 ///                     optional attribute.
+/// * `#[storage(merge)]`
+///                   - Indicates that conflicting versions of the struct should
+///                     be merged using its [`Mergeable`](calimero_storage::entities::Mergeable)
+///                     implementation, rather than on a "last write wins"
+///                     basis. This is an optional attribute. The form
+///                     `#[storage(merge = "path::to::function")]` can be used
+///                     instead to have [`Mergeable`](calimero_storage::entities::Mergeable)
+///                     implemented by calling a function with the signature
+///                     `fn(&mut Self, Self)`. The merge function is registered
+///                     automatically whenever the struct, or an entity with
+///                     it as a descendant, is loaded or saved.
+/// * `#[storage(merge_id = "id")]`
+///                   - Sets the identifier recorded against each entity to
+///                     select the merge function. This defaults to the name of
+///                     the struct, and must be stable across builds and unique
+///                     within the application. Requires `#[storage(merge)]`.
 /// * `#[type_id(n)]` - Indicates the type ID for the struct. This is a
 ///                     mandatory attribute, and the value `n` must be a `u8`.
 ///                     This is used to differentiate between different types
@@ -131,6 +152,26 @@ mod integration_tests_package_usage {
 /// struct Friends;
 /// ```
 ///
+/// ```
+/// use calimero_storage::entities::Element;
+/// use calimero_storage_macros::AtomicUnit;
+/// use borsh::{BorshSerialize, BorshDeserialize};
+///
+/// #[derive(AtomicUnit, Clone, Debug, Eq, PartialEq, PartialOrd, BorshSerialize, BorshDeserialize)]
+/// #[storage(merge = "Tally::add")]
+/// struct Tally {
+///     votes: u64,
+///     #[storage]
+///     storage: Element,
+/// }
+///
+/// impl Tally {
+///     fn add(&mut self, incoming: Self) {
+///         self.votes = self.votes.max(incoming.votes);
+///     }
+/// }
+/// ```
+///
 /// # Panics
 ///
 /// This macro will panic during compilation if:
@@ -138,6 +179,8 @@ mod integration_tests_package_usage {
 ///   - It is applied to anything other than a struct
 ///   - The struct has unnamed fields
 ///   - The struct does not have a field annotated as `#[storage]`
+///   - The struct has a `#[storage(...)]` attribute other than `merge` or
+///     `merge_id`, or has `merge_id` without `merge`
 ///   - The struct has fields with types that do not implement [`Default`]
 ///   - The struct already has methods with the same names as the generated
 ///     getter and setter methods
@@ -193,6 +236,49 @@ pub fn atomic_unit_derive(input: TokenStream) -> TokenStream {
         .map(|f| f.ident.as_ref().unwrap())
         .collect();
 
+    let collection_types: Vec<_> = named_fields
+        .iter()
+        .filter(|f| {
+            f.attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("collection"))
+        })
+        .map(|f| &f.ty)
+        .collect();
+
+    // Find the merge function and identifier, if any, from #[storage(merge)]
+    // on the struct
+    let mut merge = None;
+    let mut merge_id = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("storage"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("merge") {
+                let function = meta
+                    .input
+                    .peek(Token![=])
+                    .then(|| meta.value()?.parse::<LitStr>()?.parse::<Path>())
+                    .transpose()?;
+                merge = Some(function);
+                Ok(())
+            } else if meta.path.is_ident("merge_id") {
+                merge_id = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported storage attribute"))
+            }
+        })
+        .expect("Invalid #[storage] attribute");
+    }
+
+    assert!(
+        merge.is_some() || merge_id.is_none(),
+        "#[storage(merge_id)] requires #[storage(merge)]"
+    );
+
     let mut serde_where_clause = where_clause.clone();
 
     for ty in input.generics.type_params() {
@@ -203,6 +289,31 @@ pub fn atomic_unit_derive(input: TokenStream) -> TokenStream {
         ));
     }
 
+    let merge_strategy_impl = merge.is_some().then(|| {
+        let merge_id = merge_id.map_or_else(|| quote!(stringify!(#name)), |id| quote!(#id));
+        quote! {
+            fn merge_strategy() -> calimero_storage::entities::MergeStrategy {
+                calimero_storage::entities::MergeStrategy::custom(#merge_id)
+            }
+        }
+    });
+
+    let register_merge = merge.is_some().then(|| {
+        quote! {
+            calimero_storage::interface::MainInterface::register_merge::<Self>();
+        }
+    });
+
+    let mergeable_impl = merge.flatten().map(|function| {
+        quote! {
+            impl #impl_generics calimero_storage::entities::Mergeable for #name #ty_generics #serde_where_clause {
+                fn merge(&mut self, incoming: Self) {
+                    #function(self, incoming);
+                }
+            }
+        }
+    });
+
     let expanded = quote! {
         impl #impl_generics calimero_storage::entities::Data for #name #ty_generics #serde_where_clause {
             fn collections(&self) -> std::collections::BTreeMap<String, Vec<calimero_storage::entities::ChildInfo>> {
@@ -224,9 +335,23 @@ pub fn atomic_unit_derive(input: TokenStream) -> TokenStream {
             fn element_mut(&mut self) -> &mut calimero_storage::entities::Element {
                 &mut self.#storage_ident
             }
+
+            #merge_strategy_impl
+
+            fn register_merges() {
+                if !calimero_storage::interface::mark_merges_registered::<Self>() {
+                    return;
+                }
+                #register_merge
+                #(
+                    <<#collection_types as calimero_storage::entities::Collection>::Child as calimero_storage::entities::Data>::register_merges();
+                )*
+            }
         }
 
         impl #impl_generics calimero_storage::entities::AtomicUnit for #name #ty_generics #serde_where_clause {}
+
+        #mergeable_impl
     };
 
     TokenStream::from(quote! {
@@ -11,6 +11,14 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use indexmap::IndexSet;
 use sha2::{Digest, Sha256};
 
+pub mod counter;
+pub use counter::Counter;
+pub mod lww_register;
+pub use lww_register::LwwRegister;
+pub mod multi_value_register;
+pub use multi_value_register::MultiValueRegister;
+pub mod ordered_map;
+pub use ordered_map::OrderedMap;
 pub mod unordered_map;
 pub use unordered_map::UnorderedMap;
 pub mod unordered_set;
@@ -29,7 +37,7 @@ use crate::address::{Id, Path};
 use crate::entities::{ChildInfo, Data, Element};
 use crate::interface::{Interface, StorageError};
 use crate::store::{MainStorage, StorageAdaptor};
-use crate::{AtomicUnit, Collection};
+use crate::{env, AtomicUnit, Collection};
 
 /// Compute the ID for a key.
 fn compute_id(parent: Id, key: &[u8]) -> Id {
@@ -39,6 +47,17 @@ fn compute_id(parent: Id, key: &[u8]) -> Id {
     Id::new(hasher.finalize().into())
 }
 
+/// Compute the ID of the current executor's slot in a collection.
+///
+/// Collections that need to converge under concurrent updates give each
+/// executor a slot of its own, which only that executor ever writes to. Since
+/// every slot has a single writer, the last-write-wins resolution applied when
+/// syncing is never asked to choose between two concurrent writes to it.
+///
+fn executor_slot_id(parent: Id) -> Id {
+    compute_id(parent, &env::executor_id())
+}
+
 mod compat {
     use std::collections::BTreeMap;
 
@@ -0,0 +1,248 @@
+//! This module provides functionality for the counter data structure.
+
+use core::fmt;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::Serialize;
+
+use super::{executor_slot_id, Collection};
+use crate::collections::error::StoreError;
+use crate::entities::Data;
+use crate::store::{MainStorage, StorageAdaptor};
+
+/// A counter that converges when updated concurrently on different nodes.
+///
+/// This is a PN-counter: each executor has a slot recording how much it has
+/// added and subtracted in total, and only ever writes to its own slot. The
+/// value of the counter is the sum across all slots, so concurrent increments
+/// made on different nodes are all retained once synced, rather than one
+/// overwriting the other.
+///
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct Counter<S: StorageAdaptor = MainStorage> {
+    #[borsh(bound(serialize = "", deserialize = ""))]
+    inner: Collection<Slot, S>,
+}
+
+/// The totals recorded by a single executor.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default)]
+struct Slot {
+    /// The total added by the executor.
+    increments: u64,
+    /// The total subtracted by the executor.
+    decrements: u64,
+}
+
+impl Counter<MainStorage> {
+    /// Create a new counter, starting at zero.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::new_internal()
+    }
+}
+
+impl<S: StorageAdaptor> Counter<S> {
+    /// Create a new counter, starting at zero.
+    fn new_internal() -> Self {
+        Self {
+            inner: Collection::new(None),
+        }
+    }
+
+    /// Add one to the counter, returning the new value.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn increment(&mut self) -> Result<i64, StoreError> {
+        self.increment_by(1)
+    }
+
+    /// Add an amount to the counter, returning the new value.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn increment_by(&mut self, amount: u64) -> Result<i64, StoreError> {
+        self.update_slot(|slot| slot.increments = slot.increments.saturating_add(amount))?;
+
+        self.value()
+    }
+
+    /// Subtract one from the counter, returning the new value.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn decrement(&mut self) -> Result<i64, StoreError> {
+        self.decrement_by(1)
+    }
+
+    /// Subtract an amount from the counter, returning the new value.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn decrement_by(&mut self, amount: u64) -> Result<i64, StoreError> {
+        self.update_slot(|slot| slot.decrements = slot.decrements.saturating_add(amount))?;
+
+        self.value()
+    }
+
+    /// Get the current value of the counter.
+    ///
+    /// The value saturates at the bounds of [`i64`].
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn value(&self) -> Result<i64, StoreError> {
+        let mut total = 0_i128;
+
+        for entry in self.inner.entries()? {
+            let slot = entry?;
+
+            total = total
+                .saturating_add(i128::from(slot.increments))
+                .saturating_sub(i128::from(slot.decrements));
+        }
+
+        Ok(i64::try_from(total).unwrap_or_else(|_| {
+            if total.is_negative() {
+                i64::MIN
+            } else {
+                i64::MAX
+            }
+        }))
+    }
+
+    /// Apply a change to the current executor's slot, creating it if needed.
+    fn update_slot(&mut self, f: impl FnOnce(&mut Slot)) -> Result<(), StoreError> {
+        let id = executor_slot_id(self.inner.id());
+
+        if let Some(mut slot) = self.inner.get_mut(id)? {
+            f(&mut slot);
+
+            return Ok(());
+        }
+
+        let mut slot = Slot::default();
+
+        f(&mut slot);
+
+        let _ignored = self.inner.insert(Some(id), slot)?;
+
+        Ok(())
+    }
+}
+
+impl<S: StorageAdaptor> Eq for Counter<S> {}
+
+impl<S: StorageAdaptor> PartialEq for Counter<S> {
+    #[expect(clippy::unwrap_used, reason = "'tis fine")]
+    fn eq(&self, other: &Self) -> bool {
+        self.value().unwrap() == other.value().unwrap()
+    }
+}
+
+impl<S: StorageAdaptor> fmt::Debug for Counter<S> {
+    #[expect(clippy::unwrap_used, clippy::unwrap_in_result, reason = "'tis fine")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("Counter")
+                .field("slots", &self.inner)
+                .finish()
+        } else {
+            fmt::Debug::fmt(&self.value().unwrap(), f)
+        }
+    }
+}
+
+impl<S: StorageAdaptor> Default for Counter<S> {
+    fn default() -> Self {
+        Self::new_internal()
+    }
+}
+
+impl<S: StorageAdaptor> Serialize for Counter<S> {
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: serde::Serializer,
+    {
+        let value = self.value().map_err(serde::ser::Error::custom)?;
+
+        serializer.serialize_i64(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::{Counter, Root};
+    use crate::env;
+
+    #[test]
+    fn test_counter_operations() {
+        let mut counter = Root::new(|| Counter::new());
+
+        assert_eq!(counter.value().expect("value failed"), 0);
+
+        assert_eq!(counter.increment().expect("increment failed"), 1);
+        assert_eq!(counter.increment_by(4).expect("increment failed"), 5);
+        assert_eq!(counter.decrement().expect("decrement failed"), 4);
+        assert_eq!(counter.decrement_by(10).expect("decrement failed"), -6);
+
+        assert_eq!(counter.value().expect("value failed"), -6);
+    }
+
+    #[test]
+    fn test_counter_concurrent_executors() {
+        let mut counter = Root::new(|| Counter::new());
+
+        env::set_executor_id([1; 32]);
+        assert_eq!(counter.increment().expect("increment failed"), 1);
+
+        env::set_executor_id([2; 32]);
+        assert_eq!(counter.increment().expect("increment failed"), 2);
+        assert_eq!(counter.increment_by(3).expect("increment failed"), 5);
+
+        env::set_executor_id([3; 32]);
+        assert_eq!(counter.decrement().expect("decrement failed"), 4);
+
+        assert_eq!(counter.inner.len().expect("len failed"), 3);
+        assert_eq!(counter.value().expect("value failed"), 4);
+    }
+
+    #[test]
+    fn test_counter_saturates() {
+        let mut counter = Root::new(|| Counter::new());
+
+        env::set_executor_id([1; 32]);
+        assert_eq!(
+            counter.increment_by(u64::MAX).expect("increment failed"),
+            i64::MAX
+        );
+
+        env::set_executor_id([2; 32]);
+        assert_eq!(
+            counter.increment_by(u64::MAX).expect("increment failed"),
+            i64::MAX
+        );
+
+        assert_eq!(counter.value().expect("value failed"), i64::MAX);
+    }
+}
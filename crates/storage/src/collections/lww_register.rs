@@ -0,0 +1,261 @@
+//! This module provides functionality for the last-write-wins register data
+//! structure.
+
+use core::fmt;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::Serialize;
+
+use super::{executor_slot_id, Collection};
+use crate::collections::error::StoreError;
+use crate::entities::{Data, HybridTimestamp};
+use crate::env;
+use crate::store::{MainStorage, StorageAdaptor};
+
+/// A register holding a single value, where the most recent write wins.
+///
+/// Each executor writes to a slot of its own, stamping the value with a
+/// [`HybridTimestamp`] that is later than every write it has already seen. The
+/// value of the register is the one with the latest timestamp, with ties broken
+/// by executor identity, so all nodes agree on the same value once synced,
+/// regardless of the order in which writes arrive.
+///
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct LwwRegister<T, S: StorageAdaptor = MainStorage> {
+    #[borsh(bound(serialize = "", deserialize = ""))]
+    inner: Collection<Slot<T>, S>,
+}
+
+/// The latest value written by a single executor.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+struct Slot<T> {
+    /// The value written.
+    value: T,
+    /// When the value was written.
+    timestamp: HybridTimestamp,
+    /// The executor that wrote the value.
+    executor: [u8; 32],
+}
+
+impl<T> Slot<T> {
+    /// The key used to order writes.
+    const fn key(&self) -> (HybridTimestamp, [u8; 32]) {
+        (self.timestamp, self.executor)
+    }
+}
+
+impl<T> LwwRegister<T, MainStorage>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    /// Create a new, empty register.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::new_internal()
+    }
+}
+
+impl<T, S> LwwRegister<T, S>
+where
+    T: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    /// Create a new, empty register.
+    fn new_internal() -> Self {
+        Self {
+            inner: Collection::new(None),
+        }
+    }
+
+    /// Get the current value of the register, if one has been set.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn get(&self) -> Result<Option<T>, StoreError> {
+        Ok(self.latest()?.map(|slot| slot.value))
+    }
+
+    /// Set the value of the register.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn set(&mut self, value: T) -> Result<(), StoreError> {
+        if let Some(latest) = self.latest()? {
+            env::hlc_observe(latest.timestamp);
+        }
+
+        let slot = Slot {
+            value,
+            timestamp: env::hlc_now(),
+            executor: env::executor_id(),
+        };
+
+        let id = executor_slot_id(self.inner.id());
+
+        if let Some(mut existing) = self.inner.get_mut(id)? {
+            *existing = slot;
+
+            return Ok(());
+        }
+
+        let _ignored = self.inner.insert(Some(id), slot)?;
+
+        Ok(())
+    }
+
+    /// Get the winning write across all executors.
+    fn latest(&self) -> Result<Option<Slot<T>>, StoreError> {
+        let mut latest: Option<Slot<T>> = None;
+
+        for entry in self.inner.entries()? {
+            let slot = entry?;
+
+            if latest.as_ref().is_none_or(|l| slot.key() > l.key()) {
+                latest = Some(slot);
+            }
+        }
+
+        Ok(latest)
+    }
+}
+
+impl<T, S> Eq for LwwRegister<T, S>
+where
+    T: Eq + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+}
+
+impl<T, S> PartialEq for LwwRegister<T, S>
+where
+    T: PartialEq + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    #[expect(clippy::unwrap_used, reason = "'tis fine")]
+    fn eq(&self, other: &Self) -> bool {
+        self.get().unwrap() == other.get().unwrap()
+    }
+}
+
+impl<T, S> fmt::Debug for LwwRegister<T, S>
+where
+    T: fmt::Debug + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    #[expect(clippy::unwrap_used, clippy::unwrap_in_result, reason = "'tis fine")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("LwwRegister")
+                .field("slots", &self.inner)
+                .finish()
+        } else {
+            fmt::Debug::fmt(&self.get().unwrap(), f)
+        }
+    }
+}
+
+impl<T, S> Default for LwwRegister<T, S>
+where
+    T: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn default() -> Self {
+        Self::new_internal()
+    }
+}
+
+impl<T, S> Serialize for LwwRegister<T, S>
+where
+    T: BorshSerialize + BorshDeserialize + Serialize,
+    S: StorageAdaptor,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: serde::Serializer,
+    {
+        let value = self.get().map_err(serde::ser::Error::custom)?;
+
+        Serialize::serialize(&value, serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{executor_slot_id, Slot};
+    use crate::collections::{LwwRegister, Root};
+    use crate::entities::{Data, HybridTimestamp};
+    use crate::env;
+
+    #[test]
+    fn test_lww_register_operations() {
+        let mut register = Root::new(|| LwwRegister::new());
+
+        assert_eq!(register.get().expect("get failed"), None);
+
+        register.set("first".to_owned()).expect("set failed");
+        assert_eq!(
+            register.get().expect("get failed"),
+            Some("first".to_owned())
+        );
+
+        register.set("second".to_owned()).expect("set failed");
+        assert_eq!(
+            register.get().expect("get failed"),
+            Some("second".to_owned())
+        );
+
+        assert_eq!(register.inner.len().expect("len failed"), 1);
+    }
+
+    #[test]
+    fn test_lww_register_later_write_wins_across_executors() {
+        let mut register = Root::new(|| LwwRegister::new());
+
+        env::set_executor_id([2; 32]);
+        register.set("from two".to_owned()).expect("set failed");
+
+        env::set_executor_id([1; 32]);
+        register.set("from one".to_owned()).expect("set failed");
+
+        assert_eq!(
+            register.get().expect("get failed"),
+            Some("from one".to_owned())
+        );
+        assert_eq!(register.inner.len().expect("len failed"), 2);
+    }
+
+    #[test]
+    fn test_lww_register_tie_broken_by_executor() {
+        let mut register = Root::new(|| LwwRegister::<String>::new());
+
+        for (executor, value) in [([2; 32], "from two"), ([1; 32], "from one")] {
+            env::set_executor_id(executor);
+
+            let slot = Slot {
+                value: value.to_owned(),
+                timestamp: HybridTimestamp::new(42, 0, [0; 32]),
+                executor,
+            };
+
+            let id = executor_slot_id(register.inner.id());
+
+            let _ignored = register
+                .inner
+                .insert(Some(id), slot)
+                .expect("insert failed");
+        }
+
+        assert_eq!(
+            register.get().expect("get failed"),
+            Some("from two".to_owned())
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! This module provides functionality for the multi-value register data
+//! structure.
+
+use core::fmt;
+use std::collections::{BTreeMap, BTreeSet};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::ser::SerializeSeq;
+use serde::Serialize;
+
+use super::{executor_slot_id, Collection};
+use crate::collections::error::StoreError;
+use crate::entities::Data;
+use crate::env;
+use crate::store::{MainStorage, StorageAdaptor};
+
+/// A register that keeps every concurrently-written value.
+///
+/// Each executor writes to a slot of its own, recording alongside the value
+/// the version of every other executor's slot that it had seen at the time. A
+/// write therefore supersedes the writes it knew about, but not ones made
+/// concurrently elsewhere, and the register holds all values that have not
+/// been superseded. Resolving the conflict is left to the application, which
+/// does so simply by setting a new value.
+///
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MultiValueRegister<T, S: StorageAdaptor = MainStorage> {
+    #[borsh(bound(serialize = "", deserialize = ""))]
+    inner: Collection<Slot<T>, S>,
+}
+
+/// The latest value written by a single executor.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+struct Slot<T> {
+    /// The value written.
+    value: T,
+    /// The version of the write, increasing with every write by the executor.
+    version: u64,
+    /// The executor that wrote the value.
+    executor: [u8; 32],
+    /// The versions of the other executors' writes seen at the time.
+    seen: BTreeMap<[u8; 32], u64>,
+}
+
+impl<T> Slot<T> {
+    /// Whether this write knew about the given write when it was made.
+    fn supersedes(&self, other: &Self) -> bool {
+        self.seen
+            .get(&other.executor)
+            .is_some_and(|version| *version >= other.version)
+    }
+}
+
+impl<T> MultiValueRegister<T, MainStorage>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    /// Create a new, empty register.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::new_internal()
+    }
+}
+
+impl<T, S> MultiValueRegister<T, S>
+where
+    T: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    /// Create a new, empty register.
+    fn new_internal() -> Self {
+        Self {
+            inner: Collection::new(None),
+        }
+    }
+
+    /// Get the current values of the register.
+    ///
+    /// This will contain a single value unless there have been concurrent
+    /// writes, and will be empty if no value has been set. Values are ordered
+    /// by the identity of the executor that wrote them.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn values(&self) -> Result<Vec<T>, StoreError> {
+        let slots = self.slots()?;
+
+        let superseded = slots
+            .values()
+            .filter(|slot| slots.values().any(|other| other.supersedes(slot)))
+            .map(|slot| slot.executor)
+            .collect::<BTreeSet<_>>();
+
+        Ok(slots
+            .into_iter()
+            .filter_map(|(executor, slot)| (!superseded.contains(&executor)).then_some(slot.value))
+            .collect())
+    }
+
+    /// Whether the register holds more than one value due to concurrent
+    /// writes.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn is_conflicted(&self) -> Result<bool, StoreError> {
+        Ok(self.values()?.len() > 1)
+    }
+
+    /// Set the value of the register, superseding all values currently held.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn set(&mut self, value: T) -> Result<(), StoreError> {
+        let executor = env::executor_id();
+
+        let mut seen = self
+            .slots()?
+            .into_iter()
+            .map(|(id, slot)| (id, slot.version))
+            .collect::<BTreeMap<_, _>>();
+
+        let previous = seen.remove(&executor).unwrap_or(0);
+
+        let slot = Slot {
+            value,
+            version: env::time_now().max(previous.saturating_add(1)),
+            executor,
+            seen,
+        };
+
+        let id = executor_slot_id(self.inner.id());
+
+        if let Some(mut existing) = self.inner.get_mut(id)? {
+            *existing = slot;
+
+            return Ok(());
+        }
+
+        let _ignored = self.inner.insert(Some(id), slot)?;
+
+        Ok(())
+    }
+
+    /// Get the latest write of every executor, keyed by executor.
+    fn slots(&self) -> Result<BTreeMap<[u8; 32], Slot<T>>, StoreError> {
+        let mut slots = BTreeMap::new();
+
+        for entry in self.inner.entries()? {
+            let slot = entry?;
+
+            let _ignored = slots.insert(slot.executor, slot);
+        }
+
+        Ok(slots)
+    }
+}
+
+impl<T, S> Eq for MultiValueRegister<T, S>
+where
+    T: Eq + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+}
+
+impl<T, S> PartialEq for MultiValueRegister<T, S>
+where
+    T: PartialEq + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    #[expect(clippy::unwrap_used, reason = "'tis fine")]
+    fn eq(&self, other: &Self) -> bool {
+        self.values().unwrap() == other.values().unwrap()
+    }
+}
+
+impl<T, S> fmt::Debug for MultiValueRegister<T, S>
+where
+    T: fmt::Debug + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    #[expect(clippy::unwrap_used, clippy::unwrap_in_result, reason = "'tis fine")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("MultiValueRegister")
+                .field("slots", &self.inner)
+                .finish()
+        } else {
+            f.debug_set().entries(self.values().unwrap()).finish()
+        }
+    }
+}
+
+impl<T, S> Default for MultiValueRegister<T, S>
+where
+    T: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn default() -> Self {
+        Self::new_internal()
+    }
+}
+
+impl<T, S> Serialize for MultiValueRegister<T, S>
+where
+    T: BorshSerialize + BorshDeserialize + Serialize,
+    S: StorageAdaptor,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: serde::Serializer,
+    {
+        let values = self.values().map_err(serde::ser::Error::custom)?;
+
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+
+        for v in values {
+            seq.serialize_element(&v)?;
+        }
+
+        seq.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{executor_slot_id, Slot};
+    use crate::collections::{MultiValueRegister, Root};
+    use crate::entities::Data;
+    use crate::env;
+
+    #[test]
+    fn test_multi_value_register_operations() {
+        let mut register = Root::new(|| MultiValueRegister::new());
+
+        assert!(register.values().expect("values failed").is_empty());
+
+        register.set("first".to_owned()).expect("set failed");
+        assert_eq!(register.values().expect("values failed"), vec!["first"]);
+
+        register.set("second".to_owned()).expect("set failed");
+        assert_eq!(register.values().expect("values failed"), vec!["second"]);
+
+        assert!(!register.is_conflicted().expect("is_conflicted failed"));
+    }
+
+    #[test]
+    fn test_multi_value_register_sequential_writes_supersede() {
+        let mut register = Root::new(|| MultiValueRegister::new());
+
+        env::set_executor_id([1; 32]);
+        register.set("from one".to_owned()).expect("set failed");
+
+        env::set_executor_id([2; 32]);
+        register.set("from two".to_owned()).expect("set failed");
+
+        assert_eq!(register.values().expect("values failed"), vec!["from two"]);
+        assert!(!register.is_conflicted().expect("is_conflicted failed"));
+    }
+
+    #[test]
+    fn test_multi_value_register_concurrent_writes_kept() {
+        let mut register = Root::new(|| MultiValueRegister::<String>::new());
+
+        // Neither write has seen the other, as though made on separate nodes
+        // and then synced.
+        for (executor, value) in [([2; 32], "from two"), ([1; 32], "from one")] {
+            env::set_executor_id(executor);
+
+            let slot = Slot {
+                value: value.to_owned(),
+                version: 1,
+                executor,
+                seen: BTreeMap::new(),
+            };
+
+            let id = executor_slot_id(register.inner.id());
+
+            let _ignored = register
+                .inner
+                .insert(Some(id), slot)
+                .expect("insert failed");
+        }
+
+        assert_eq!(
+            register.values().expect("values failed"),
+            vec!["from one", "from two"]
+        );
+        assert!(register.is_conflicted().expect("is_conflicted failed"));
+
+        env::set_executor_id([3; 32]);
+        register.set("resolved".to_owned()).expect("set failed");
+
+        assert_eq!(register.values().expect("values failed"), vec!["resolved"]);
+        assert!(!register.is_conflicted().expect("is_conflicted failed"));
+    }
+}
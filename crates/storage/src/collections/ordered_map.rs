@@ -0,0 +1,879 @@
+//! This module provides functionality for the ordered map data structure.
+
+use core::borrow::Borrow;
+use core::cell::RefCell;
+use core::ops::{Bound, RangeBounds};
+use core::{fmt, mem};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use key_order::KeyOrder;
+use serde::ser::SerializeMap;
+use serde::Serialize;
+
+use super::{compute_id, Collection, StorageAdaptor};
+use crate::address::Id;
+use crate::collections::error::StoreError;
+use crate::entities::Data;
+use crate::interface::StorageError;
+use crate::store::MainStorage;
+
+mod key_order;
+
+/// A map collection that stores key-value pairs, ordered by key.
+///
+/// The entries are stored in exactly the same way as for an
+/// [`UnorderedMap`](super::UnorderedMap), with the ID of each entry derived
+/// from its key, and so they participate in Merkle hashing and synchronisation
+/// in the same way. The difference is that the keys are kept in order, which
+/// allows the map to be iterated in key order, and for ranges of keys to be
+/// retrieved without loading and sorting all of the entries each time.
+///
+/// The key order is kept in storage alongside the entries, as a tree of pages
+/// that each hold a bounded number of keys, and so adding or removing a key
+/// only rewrites the few pages on the path to it. It is local to each node,
+/// and so when read it is first brought up to date with any entries added or
+/// removed without going through the map, such as when syncing, which only
+/// requires those entries to be loaded.
+///
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct OrderedMap<K, V, S: StorageAdaptor = MainStorage> {
+    #[borsh(bound(serialize = "", deserialize = ""))]
+    inner: Collection<(K, V), S>,
+
+    #[borsh(skip, bound(deserialize = ""))]
+    keys: RefCell<Option<KeyOrder<K, S>>>,
+}
+
+impl<K, V> OrderedMap<K, V, MainStorage>
+where
+    K: BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize,
+{
+    /// Create a new ordered map collection.
+    pub fn new() -> Self {
+        Self::new_internal()
+    }
+}
+
+impl<K, V, S> OrderedMap<K, V, S>
+where
+    K: BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    /// Create a new ordered map collection.
+    fn new_internal() -> Self {
+        Self {
+            inner: Collection::new(None),
+            keys: RefCell::new(None),
+        }
+    }
+
+    /// Insert a key-value pair into the map.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, StoreError>
+    where
+        K: Ord + Clone + AsRef<[u8]>,
+    {
+        let id = compute_id(self.inner.id(), key.as_ref());
+
+        if let Some(mut entry) = self.inner.get_mut(id)? {
+            let (_, v) = &mut *entry;
+
+            return Ok(Some(mem::replace(v, value)));
+        }
+
+        self.with_keys(|_| Ok(()))?;
+
+        let (key, _) = self.inner.insert(Some(id), (key, value))?;
+
+        self.update_keys(|keys| keys.insert(key, id))?;
+
+        Ok(None)
+    }
+
+    /// Get an iterator over the entries in the map, in key order.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn entries(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<(K, V), StoreError>> + '_, StoreError>
+    where
+        K: Ord + AsRef<[u8]>,
+    {
+        self.range::<K, _>(..)
+    }
+
+    /// Get an iterator over the entries in the map whose keys are within the
+    /// given range, in key order.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn range<Q, R>(
+        &self,
+        range: R,
+    ) -> Result<impl Iterator<Item = Result<(K, V), StoreError>> + '_, StoreError>
+    where
+        K: Ord + AsRef<[u8]> + Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        Ok(self.entries_for(self.ids_in(range, usize::MAX)?))
+    }
+
+    /// Get an iterator over the entries in the map, in key order, starting
+    /// from the given key. If the key is not present, iteration starts from
+    /// the next key after it.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn iter_from<Q>(
+        &self,
+        key: &Q,
+    ) -> Result<impl Iterator<Item = Result<(K, V), StoreError>> + '_, StoreError>
+    where
+        K: Ord + AsRef<[u8]> + Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        Ok(self.entries_for(self.ids_in((Bound::Included(key), Bound::Unbounded), usize::MAX)?))
+    }
+
+    /// Get a page of entries from the map, in key order.
+    ///
+    /// This returns up to `limit` entries, starting after the given key, or
+    /// from the beginning of the map if no key is given. The key of the last
+    /// entry returned can be used to request the next page. As the position is
+    /// identified by key rather than by index, pages remain consistent even if
+    /// entries are inserted or removed between requests.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn page<Q>(&self, after: Option<&Q>, limit: usize) -> Result<Vec<(K, V)>, StoreError>
+    where
+        K: Ord + AsRef<[u8]> + Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let start = after.map_or(Bound::Unbounded, Bound::Excluded);
+
+        self.entries_for(self.ids_in((start, Bound::Unbounded), limit)?)
+            .collect()
+    }
+
+    /// Get the entry with the lowest key in the map.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn first(&self) -> Result<Option<(K, V)>, StoreError>
+    where
+        K: Ord + AsRef<[u8]>,
+    {
+        let Some(&id) = self
+            .with_keys(|keys| keys.ids::<K, _>(.., 1, false))?
+            .first()
+        else {
+            return Ok(None);
+        };
+
+        self.inner.get(id)
+    }
+
+    /// Get the entry with the highest key in the map.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn last(&self) -> Result<Option<(K, V)>, StoreError>
+    where
+        K: Ord + AsRef<[u8]>,
+    {
+        let Some(&id) = self
+            .with_keys(|keys| keys.ids::<K, _>(.., 1, true))?
+            .first()
+        else {
+            return Ok(None);
+        };
+
+        self.inner.get(id)
+    }
+
+    /// Get the number of entries in the map.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    #[expect(clippy::len_without_is_empty, reason = "TODO: will be implemented")]
+    pub fn len(&self) -> Result<usize, StoreError> {
+        self.inner.len()
+    }
+
+    /// Get the value for a key in the map.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn get<Q>(&self, key: &Q) -> Result<Option<V>, StoreError>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + AsRef<[u8]> + ?Sized,
+    {
+        let id = compute_id(self.inner.id(), key.as_ref());
+
+        Ok(self.inner.get(id)?.map(|(_, v)| v))
+    }
+
+    /// Check if the map contains a key.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn contains<Q>(&self, key: &Q) -> Result<bool, StoreError>
+    where
+        K: Borrow<Q> + PartialEq,
+        Q: PartialEq + AsRef<[u8]> + ?Sized,
+    {
+        let id = compute_id(self.inner.id(), key.as_ref());
+
+        self.inner.contains(id)
+    }
+
+    /// Remove a key from the map, returning the value at the key if it previously existed.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn remove<Q>(&mut self, key: &Q) -> Result<Option<V>, StoreError>
+    where
+        K: Ord + Clone + Borrow<Q>,
+        Q: Ord + AsRef<[u8]> + ?Sized,
+    {
+        let id = compute_id(self.inner.id(), key.as_ref());
+
+        let Some(entry) = self.inner.get_mut(id)? else {
+            return Ok(None);
+        };
+
+        let (_, v) = entry.remove()?;
+
+        self.update_keys(|keys| keys.remove(key, id))?;
+
+        Ok(Some(v))
+    }
+
+    /// Clear the map, removing all entries.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or a child
+    /// [`Element`](crate::entities::Element) cannot be found, an error will be
+    /// returned.
+    ///
+    pub fn clear(&mut self) -> Result<(), StoreError>
+    where
+        K: Ord,
+    {
+        let mut keys = match self.keys.get_mut().take() {
+            Some(keys) => keys,
+            None => KeyOrder::open(self.inner.id())?,
+        };
+
+        keys.clear()?;
+
+        *self.keys.get_mut() = Some(keys);
+
+        self.inner.clear()
+    }
+
+    /// Get the IDs of up to `limit` entries whose keys are within the given
+    /// range, in key order.
+    fn ids_in<Q, R>(&self, range: R, limit: usize) -> Result<Vec<Id>, StoreError>
+    where
+        K: Ord + AsRef<[u8]> + Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        self.with_keys(|keys| keys.ids(range, limit, false))
+    }
+
+    /// Lazily iterate over the entries for the given IDs.
+    fn entries_for(&self, ids: Vec<Id>) -> impl Iterator<Item = Result<(K, V), StoreError>> + '_ {
+        ids.into_iter().map(|id| {
+            self.inner
+                .get(id)?
+                .ok_or(StoreError::StorageError(StorageError::NotFound(id)))
+        })
+    }
+
+    /// Run a function against the key order, loading it if necessary.
+    fn with_keys<R>(
+        &self,
+        f: impl FnOnce(&KeyOrder<K, S>) -> Result<R, StoreError>,
+    ) -> Result<R, StoreError>
+    where
+        K: Ord,
+    {
+        if let Some(keys) = &*self.keys.borrow() {
+            return f(keys);
+        }
+
+        let keys = self.load_keys()?;
+
+        let result = f(&keys);
+
+        *self.keys.borrow_mut() = Some(keys);
+
+        result
+    }
+
+    /// Modify the key order, which saves the changes to storage.
+    fn update_keys(
+        &mut self,
+        f: impl FnOnce(&mut KeyOrder<K, S>) -> Result<(), StoreError>,
+    ) -> Result<(), StoreError>
+    where
+        K: Ord,
+    {
+        self.with_keys(|_| Ok(()))?;
+
+        match self.keys.get_mut() {
+            Some(keys) => f(keys),
+            None => Ok(()),
+        }
+    }
+
+    /// Load the key order from storage, and bring it up to date with the
+    /// entries actually present.
+    ///
+    /// Only the entries missing from the stored key order are loaded, which
+    /// will be those added without going through this map, or all of them if
+    /// the key order has never been stored.
+    ///
+    fn load_keys(&self) -> Result<KeyOrder<K, S>, StoreError>
+    where
+        K: Ord,
+    {
+        let children = self.inner.children_cache()?;
+
+        KeyOrder::load(self.inner.id(), children, |id| {
+            let (key, _) = self
+                .inner
+                .get(id)?
+                .ok_or(StoreError::StorageError(StorageError::NotFound(id)))?;
+
+            Ok(key)
+        })
+    }
+}
+
+impl<K, V, S> Eq for OrderedMap<K, V, S>
+where
+    K: Eq + Ord + AsRef<[u8]> + BorshSerialize + BorshDeserialize,
+    V: Eq + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+}
+
+impl<K, V, S> PartialEq for OrderedMap<K, V, S>
+where
+    K: Ord + AsRef<[u8]> + BorshSerialize + BorshDeserialize,
+    V: PartialEq + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    #[expect(clippy::unwrap_used, reason = "'tis fine")]
+    fn eq(&self, other: &Self) -> bool {
+        let l = self.entries().unwrap().map(Result::unwrap);
+        let r = other.entries().unwrap().map(Result::unwrap);
+
+        l.eq(r)
+    }
+}
+
+impl<K, V, S> Ord for OrderedMap<K, V, S>
+where
+    K: Ord + AsRef<[u8]> + BorshSerialize + BorshDeserialize,
+    V: Ord + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    #[expect(clippy::unwrap_used, reason = "'tis fine")]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let l = self.entries().unwrap().map(Result::unwrap);
+        let r = other.entries().unwrap().map(Result::unwrap);
+
+        l.cmp(r)
+    }
+}
+
+impl<K, V, S> PartialOrd for OrderedMap<K, V, S>
+where
+    K: Ord + AsRef<[u8]> + BorshSerialize + BorshDeserialize,
+    V: PartialOrd + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        let l = self.entries().ok()?.collect::<Result<Vec<_>, _>>().ok()?;
+        let r = other.entries().ok()?.collect::<Result<Vec<_>, _>>().ok()?;
+
+        l.partial_cmp(&r)
+    }
+}
+
+impl<K, V, S> fmt::Debug for OrderedMap<K, V, S>
+where
+    K: fmt::Debug + Ord + AsRef<[u8]> + BorshSerialize + BorshDeserialize,
+    V: fmt::Debug + BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    #[expect(clippy::unwrap_used, clippy::unwrap_in_result, reason = "'tis fine")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("OrderedMap")
+                .field("entries", &self.inner)
+                .finish_non_exhaustive()
+        } else {
+            f.debug_map()
+                .entries(self.entries().unwrap().map(Result::unwrap))
+                .finish()
+        }
+    }
+}
+
+impl<K, V, S> Default for OrderedMap<K, V, S>
+where
+    K: BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn default() -> Self {
+        Self::new_internal()
+    }
+}
+
+impl<K, V, S> Serialize for OrderedMap<K, V, S>
+where
+    K: Ord + AsRef<[u8]> + BorshSerialize + BorshDeserialize + Serialize,
+    V: BorshSerialize + BorshDeserialize + Serialize,
+    S: StorageAdaptor,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: serde::Serializer,
+    {
+        let len = self.len().map_err(serde::ser::Error::custom)?;
+
+        let mut seq = serializer.serialize_map(Some(len))?;
+
+        for entry in self.entries().map_err(serde::ser::Error::custom)? {
+            let (k, v) = entry.map_err(serde::ser::Error::custom)?;
+
+            seq.serialize_entry(&k, &v)?;
+        }
+
+        seq.end()
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for OrderedMap<K, V, S>
+where
+    K: BorshSerialize + BorshDeserialize + Ord + Clone + AsRef<[u8]>,
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let parent = self.inner.id();
+
+        let iter = iter.into_iter().map(|(k, v)| {
+            let id = compute_id(parent, k.as_ref());
+
+            (Some(id), (k, v))
+        });
+
+        self.inner.extend(iter);
+
+        // The new keys are picked up from storage, and written to the key order
+        *self.keys.get_mut() = None;
+
+        self.update_keys(KeyOrder::settle)
+            .expect("key order update failed");
+    }
+}
+
+impl<K, V, S> FromIterator<(K, V)> for OrderedMap<K, V, S>
+where
+    K: BorshSerialize + BorshDeserialize + Ord + Clone + AsRef<[u8]>,
+    V: BorshSerialize + BorshDeserialize,
+    S: StorageAdaptor,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = OrderedMap::new_internal();
+
+        map.extend(iter);
+
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Debug;
+    use core::ops::Bound;
+
+    use super::KeyOrder;
+    use crate::collections::{compute_id, OrderedMap, Root};
+    use crate::entities::Data;
+    use crate::store::MainStorage;
+
+    fn populated() -> Root<OrderedMap<String, String>> {
+        let mut map = Root::new(|| OrderedMap::new());
+
+        for key in ["delta", "alpha", "echo", "charlie", "bravo"] {
+            assert!(map
+                .insert(key.to_owned(), key.to_uppercase())
+                .expect("insert failed")
+                .is_none());
+        }
+
+        map
+    }
+
+    fn keys<E: Debug>(
+        entries: impl IntoIterator<Item = Result<(String, String), E>>,
+    ) -> Vec<String> {
+        entries
+            .into_iter()
+            .map(|entry| entry.expect("entry failed").0)
+            .collect()
+    }
+
+    #[test]
+    fn test_ordered_map_basic_operations() {
+        let mut map = Root::new(|| OrderedMap::new());
+
+        assert!(map
+            .insert("key".to_owned(), "value".to_owned())
+            .expect("insert failed")
+            .is_none());
+        assert_eq!(
+            map.insert("key".to_owned(), "value2".to_owned())
+                .expect("insert failed")
+                .as_deref(),
+            Some("value")
+        );
+
+        assert_eq!(
+            map.get("key").expect("get failed").as_deref(),
+            Some("value2")
+        );
+        assert!(map.contains("key").expect("contains failed"));
+        assert_eq!(map.len().expect("len failed"), 1);
+
+        assert_eq!(
+            map.remove("key").expect("remove failed").as_deref(),
+            Some("value2")
+        );
+        assert_eq!(map.remove("key").expect("remove failed"), None);
+        assert_eq!(map.get("key").expect("get failed"), None);
+        assert_eq!(map.first().expect("first failed"), None);
+    }
+
+    #[test]
+    fn test_ordered_map_entries_in_key_order() {
+        let map = populated();
+
+        assert_eq!(
+            keys(map.entries().expect("entries failed")),
+            vec!["alpha", "bravo", "charlie", "delta", "echo"]
+        );
+    }
+
+    #[test]
+    fn test_ordered_map_range() {
+        let map = populated();
+
+        assert_eq!(
+            keys(
+                map.range("bravo".to_owned().."delta".to_owned())
+                    .expect("range failed")
+            ),
+            vec!["bravo", "charlie"]
+        );
+        assert_eq!(
+            keys(
+                map.range("c".to_owned()..="delta".to_owned())
+                    .expect("range failed")
+            ),
+            vec!["charlie", "delta"]
+        );
+        assert_eq!(
+            keys(
+                map.range::<str, _>((Bound::Unbounded, Bound::Excluded("b")))
+                    .expect("range failed")
+            ),
+            vec!["alpha"]
+        );
+        assert_eq!(
+            keys(map.range("x".to_owned()..).expect("range failed")),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_ordered_map_first_and_last() {
+        let mut map = populated();
+
+        assert_eq!(
+            map.first().expect("first failed"),
+            Some(("alpha".to_owned(), "ALPHA".to_owned()))
+        );
+        assert_eq!(
+            map.last().expect("last failed"),
+            Some(("echo".to_owned(), "ECHO".to_owned()))
+        );
+
+        assert!(map.remove("alpha").expect("remove failed").is_some());
+        assert!(map
+            .insert("foxtrot".to_owned(), "FOXTROT".to_owned())
+            .expect("insert failed")
+            .is_none());
+
+        assert_eq!(
+            map.first()
+                .expect("first failed")
+                .map(|(k, _)| k)
+                .as_deref(),
+            Some("bravo")
+        );
+        assert_eq!(
+            map.last().expect("last failed").map(|(k, _)| k).as_deref(),
+            Some("foxtrot")
+        );
+    }
+
+    #[test]
+    fn test_ordered_map_iter_from() {
+        let map = populated();
+
+        assert_eq!(
+            keys(map.iter_from("charlie").expect("iter_from failed")),
+            vec!["charlie", "delta", "echo"]
+        );
+        assert_eq!(
+            keys(map.iter_from("cat").expect("iter_from failed")),
+            vec!["charlie", "delta", "echo"]
+        );
+    }
+
+    #[test]
+    fn test_ordered_map_page() {
+        let map = populated();
+
+        let first = map.page::<str>(None, 2).expect("page failed");
+        assert_eq!(
+            keys(first.clone().into_iter().map(Ok::<_, ()>)),
+            vec!["alpha", "bravo"]
+        );
+
+        let after_first = first.last().map(|(k, _)| k.as_str());
+        let second = map.page(after_first, 2).expect("page failed");
+        assert_eq!(
+            keys(second.clone().into_iter().map(Ok::<_, ()>)),
+            vec!["charlie", "delta"]
+        );
+
+        let after_second = second.last().map(|(k, _)| k.as_str());
+        let third = map.page(after_second, 2).expect("page failed");
+        assert_eq!(keys(third.into_iter().map(Ok::<_, ()>)), vec!["echo"]);
+    }
+
+    #[test]
+    fn test_ordered_map_keys_rebuilt_from_storage() {
+        let map = populated();
+
+        let fetched = Root::<OrderedMap<String, String>>::fetch().expect("fetch failed");
+
+        assert_eq!(
+            keys(fetched.entries().expect("entries failed")),
+            keys(map.entries().expect("entries failed"))
+        );
+    }
+
+    #[test]
+    fn test_ordered_map_key_order_stored() {
+        let mut map = populated();
+
+        let id = map.inner.id();
+
+        let stored = || {
+            let keys = KeyOrder::<String, MainStorage>::open(id).expect("open failed");
+
+            keys.ids::<String, _>(.., usize::MAX, false)
+                .expect("ids failed")
+        };
+
+        let ids = |keys: &[&str]| {
+            keys.iter()
+                .map(|key| compute_id(id, key.as_bytes()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            stored(),
+            ids(&["alpha", "bravo", "charlie", "delta", "echo"])
+        );
+
+        assert!(map.remove("charlie").expect("remove failed").is_some());
+
+        assert_eq!(stored(), ids(&["alpha", "bravo", "delta", "echo"]));
+    }
+
+    #[test]
+    fn test_ordered_map_key_order_split_into_pages() {
+        let mut map = Root::new(|| OrderedMap::new());
+
+        // Insert enough keys, out of order, to need several levels of pages
+        let mut expected = (0..500_u32)
+            .map(|n| format!("{:04}", n.wrapping_mul(7919) % 500))
+            .collect::<Vec<_>>();
+
+        for key in &expected {
+            assert!(map
+                .insert(key.clone(), key.clone())
+                .expect("insert failed")
+                .is_none());
+        }
+
+        expected.sort();
+
+        assert_eq!(keys(map.entries().expect("entries failed")), expected);
+
+        for key in expected.iter().step_by(3) {
+            assert!(map.remove(key).expect("remove failed").is_some());
+        }
+
+        let expected = expected
+            .into_iter()
+            .enumerate()
+            .filter(|(n, _)| n % 3 != 0)
+            .map(|(_, key)| key)
+            .collect::<Vec<_>>();
+
+        let fetched = Root::<OrderedMap<String, String>>::fetch().expect("fetch failed");
+
+        assert_eq!(keys(fetched.entries().expect("entries failed")), expected);
+        assert_eq!(
+            keys(
+                fetched
+                    .range("0100".to_owned().."0110".to_owned())
+                    .expect("range failed")
+            ),
+            expected
+                .iter()
+                .filter(|key| ("0100".."0110").contains(&key.as_str()))
+                .cloned()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            fetched.last().expect("last failed").map(|(k, _)| k),
+            expected.last().cloned()
+        );
+    }
+
+    #[test]
+    fn test_ordered_map_key_order_reconciled() {
+        let mut map = populated();
+
+        // Simulate changes applied from another node, which bypass the map
+        let parent = map.inner.id();
+        let _ignored = map
+            .inner
+            .insert(
+                Some(compute_id(parent, b"able")),
+                ("able".to_owned(), "ABLE".to_owned()),
+            )
+            .expect("insert failed");
+        let _removed = map
+            .inner
+            .get_mut(compute_id(parent, b"delta"))
+            .expect("get failed")
+            .expect("entry missing")
+            .remove()
+            .expect("remove failed");
+
+        let mut fetched = Root::<OrderedMap<String, String>>::fetch().expect("fetch failed");
+
+        assert_eq!(
+            keys(fetched.entries().expect("entries failed")),
+            vec!["able", "alpha", "bravo", "charlie", "echo"]
+        );
+
+        // The next change made through the map writes the changes to the tree
+        assert!(fetched
+            .insert("foxtrot".to_owned(), "FOXTROT".to_owned())
+            .expect("insert failed")
+            .is_none());
+
+        let stored = KeyOrder::<String, MainStorage>::open(parent)
+            .expect("open failed")
+            .ids::<String, _>(.., usize::MAX, false)
+            .expect("ids failed");
+
+        assert_eq!(
+            stored,
+            ["able", "alpha", "bravo", "charlie", "echo", "foxtrot"]
+                .iter()
+                .map(|key| compute_id(parent, key.as_bytes()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_ordered_map_clear() {
+        let mut map = populated();
+
+        map.clear().expect("clear failed");
+
+        assert_eq!(map.len().expect("len failed"), 0);
+        assert_eq!(map.first().expect("first failed"), None);
+        assert_eq!(map.entries().expect("entries failed").count(), 0);
+    }
+}
@@ -0,0 +1,610 @@
+//! The key order of an [`OrderedMap`](super::OrderedMap).
+//!
+//! The key order is kept in storage as a B+ tree, with each page holding a
+//! bounded number of keys, so that adding or removing a key only reads and
+//! rewrites the pages on the path to it, rather than the whole key order. A
+//! small header is kept under the ID of the map itself, recording the root page
+//! along with a count and fingerprint of the entries in the tree, which is used
+//! to tell whether any entries have been added or removed without going through
+//! the map.
+//!
+//! Pages are not merged when they become sparse, only removed once empty, which
+//! keeps each change to the tree local to a single path through it.
+//!
+
+use core::borrow::Borrow;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::RangeBounds;
+use std::collections::{BTreeMap, BTreeSet};
+
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+use indexmap::IndexSet;
+
+use crate::address::Id;
+use crate::collections::compute_id;
+use crate::collections::error::StoreError;
+use crate::interface::StorageError;
+use crate::store::{Key, StorageAdaptor};
+
+/// The most entries held by a single page, beyond which it is split in two.
+const PAGE_CAPACITY: usize = 32;
+
+/// The record describing the tree, kept under the ID of the map.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct Header {
+    /// The number of the root page, if the tree has any entries.
+    root: Option<u64>,
+
+    /// The number to give the next page created.
+    next_page: u64,
+
+    /// The number of entries in the tree.
+    len: u64,
+
+    /// The IDs of the entries in the tree, combined with XOR.
+    fingerprint: [u8; 32],
+}
+
+/// A page of the tree.
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+enum Page<K> {
+    /// The keys of entries, and their IDs, in key order.
+    Leaf(Vec<(K, Id)>),
+
+    /// The lowest key routed to each child page, and its number, in key order.
+    /// The key of the first child is not used for routing, as any key below
+    /// that of the second child is routed to the first.
+    Branch(Vec<(K, u64)>),
+}
+
+/// The key order of a map, as loaded from storage.
+///
+/// Any entries added or removed without going through the map, such as when
+/// syncing, are held alongside the tree and taken into account when reading,
+/// and are only written to the tree on the next change made through the map,
+/// so that reading does not need to write to storage.
+///
+#[derive(Debug)]
+pub(super) struct KeyOrder<K, S> {
+    /// The ID of the map.
+    map: Id,
+
+    /// The header of the tree.
+    header: Header,
+
+    /// Entries missing from the tree.
+    added: BTreeMap<K, Id>,
+
+    /// Entries in the tree that are no longer present, by ID.
+    removed: BTreeMap<Id, K>,
+
+    /// The storage adaptor.
+    _storage: PhantomData<S>,
+}
+
+impl<K, S> KeyOrder<K, S>
+where
+    K: BorshSerialize + BorshDeserialize + Ord,
+    S: StorageAdaptor,
+{
+    /// Open the key order of a map, without checking it against the entries
+    /// actually present.
+    pub(super) fn open(map: Id) -> Result<Self, StoreError> {
+        let header = match S::storage_read(Key::Order(map)) {
+            Some(data) => from_slice(&data).map_err(StorageError::DeserializationError)?,
+            None => Header::default(),
+        };
+
+        Ok(Self {
+            map,
+            header,
+            added: BTreeMap::new(),
+            removed: BTreeMap::new(),
+            _storage: PhantomData,
+        })
+    }
+
+    /// Load the key order of a map, and bring it up to date with the entries
+    /// actually present.
+    ///
+    /// The tree is only walked if its count or fingerprint differ from those of
+    /// the entries, and then only the entries missing from it are loaded, by
+    /// way of the given function.
+    ///
+    pub(super) fn load(
+        map: Id,
+        children: &IndexSet<Id>,
+        mut key_of: impl FnMut(Id) -> Result<K, StoreError>,
+    ) -> Result<Self, StoreError> {
+        let mut order = Self::open(map)?;
+
+        if usize::try_from(order.header.len) == Ok(children.len())
+            && order.header.fingerprint == fingerprint(children)
+        {
+            return Ok(order);
+        }
+
+        let mut stored = Vec::new();
+
+        if let Some(root) = order.header.root {
+            order.gather::<K, _>(root, &.., false, usize::MAX, &mut stored)?;
+        }
+
+        let known = stored.iter().map(|(_, id)| *id).collect::<BTreeSet<_>>();
+
+        order.removed = stored
+            .into_iter()
+            .filter(|(_, id)| !children.contains(id))
+            .map(|(key, id)| (id, key))
+            .collect();
+
+        for id in children.iter().filter(|id| !known.contains(id)) {
+            let _ignored = order.added.insert(key_of(*id)?, *id);
+        }
+
+        Ok(order)
+    }
+
+    /// Get the IDs of up to `limit` entries whose keys are within the given
+    /// range, in key order, or in reverse key order if `rev` is set.
+    pub(super) fn ids<Q, R>(&self, range: R, limit: usize, rev: bool) -> Result<Vec<Id>, StoreError>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let mut stored = Vec::new();
+
+        if let Some(root) = self.header.root {
+            self.gather(root, &range, rev, limit, &mut stored)?;
+        }
+
+        let mut added = self.added.range(range).collect::<Vec<_>>();
+
+        if rev {
+            added.reverse();
+        }
+
+        let mut stored = stored.into_iter().peekable();
+        let mut added = added.into_iter().peekable();
+        let mut ids = Vec::new();
+
+        while ids.len() < limit {
+            let next = match (stored.peek(), added.peek()) {
+                (None, None) => break,
+                (Some((stored_key, _)), Some((added_key, _)))
+                    if (*added_key < stored_key) == rev =>
+                {
+                    stored.next().map(|(_, id)| id)
+                }
+                (Some(_), None) => stored.next().map(|(_, id)| id),
+                (_, Some(_)) => added.next().map(|(_, id)| *id),
+            };
+
+            ids.extend(next);
+        }
+
+        Ok(ids)
+    }
+
+    /// Add a key to the key order.
+    pub(super) fn insert(&mut self, key: K, id: Id) -> Result<(), StoreError>
+    where
+        K: Clone,
+    {
+        self.apply_pending()?;
+
+        self.insert_stored(key, id)?;
+
+        self.save_header()
+    }
+
+    /// Remove a key from the key order.
+    pub(super) fn remove<Q>(&mut self, key: &Q, id: Id) -> Result<(), StoreError>
+    where
+        K: Borrow<Q> + Clone,
+        Q: Ord + ?Sized,
+    {
+        self.apply_pending()?;
+
+        self.remove_stored(key, id)?;
+
+        self.save_header()
+    }
+
+    /// Write any entries added or removed without going through the map to the
+    /// tree.
+    pub(super) fn settle(&mut self) -> Result<(), StoreError>
+    where
+        K: Clone,
+    {
+        if self.added.is_empty() && self.removed.is_empty() {
+            return Ok(());
+        }
+
+        self.apply_pending()?;
+
+        self.save_header()
+    }
+
+    /// Remove all keys from the key order, deleting every page of the tree.
+    pub(super) fn clear(&mut self) -> Result<(), StoreError> {
+        let mut pages = self.header.root.into_iter().collect::<Vec<_>>();
+
+        while let Some(number) = pages.pop() {
+            if let Page::Branch(children) = self.read(number)? {
+                pages.extend(children.into_iter().map(|(_, child)| child));
+            }
+
+            self.delete(number);
+        }
+
+        self.header = Header::default();
+        self.added.clear();
+        self.removed.clear();
+
+        self.save_header()
+    }
+
+    /// Apply the pending changes to the tree, without saving the header.
+    fn apply_pending(&mut self) -> Result<(), StoreError>
+    where
+        K: Clone,
+    {
+        for (id, key) in mem::take(&mut self.removed) {
+            self.remove_stored(&key, id)?;
+        }
+
+        for (key, id) in mem::take(&mut self.added) {
+            self.insert_stored(key, id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Gather up to `limit` entries from the tree below the given page whose
+    /// keys are within the range, skipping any that have been removed.
+    fn gather<Q, R>(
+        &self,
+        number: u64,
+        range: &R,
+        rev: bool,
+        limit: usize,
+        found: &mut Vec<(K, Id)>,
+    ) -> Result<(), StoreError>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        match self.read(number)? {
+            Page::Leaf(entries) => {
+                let remaining = limit.saturating_sub(found.len());
+
+                let entries = entries.into_iter().filter(|(key, id)| {
+                    range.contains(key.borrow()) && !self.removed.contains_key(id)
+                });
+
+                if rev {
+                    found.extend(entries.rev().take(remaining));
+                } else {
+                    found.extend(entries.take(remaining));
+                }
+            }
+            Page::Branch(children) => {
+                let mut overlapping = children
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| {
+                        let lower = (*index > 0)
+                            .then(|| children.get(*index).map(|(key, _)| key.borrow()))
+                            .flatten();
+                        let upper = children
+                            .get(index.saturating_add(1))
+                            .map(|(key, _)| key.borrow());
+
+                        overlaps(range, lower, upper)
+                    })
+                    .map(|(_, (_, child))| *child)
+                    .collect::<Vec<_>>();
+
+                if rev {
+                    overlapping.reverse();
+                }
+
+                for child in overlapping {
+                    if found.len() >= limit {
+                        break;
+                    }
+
+                    self.gather(child, range, rev, limit, found)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add an entry to the tree, splitting pages as needed.
+    fn insert_stored(&mut self, key: K, id: Id) -> Result<(), StoreError>
+    where
+        K: Clone,
+    {
+        let Some(root) = self.header.root else {
+            let number = self.new_page();
+
+            self.write(number, &Page::Leaf(vec![(key, id)]))?;
+
+            self.header.root = Some(number);
+            self.record(id, true);
+
+            return Ok(());
+        };
+
+        let mut path = Vec::new();
+        let mut number = root;
+
+        let mut entries = loop {
+            match self.read(number)? {
+                Page::Branch(children) => {
+                    let index = route(&children, &key);
+
+                    let Some(&(_, child)) = children.get(index) else {
+                        return Err(StorageError::InvalidDataFound(self.page_id(number)).into());
+                    };
+
+                    path.push((number, children, index));
+                    number = child;
+                }
+                Page::Leaf(entries) => break entries,
+            }
+        };
+
+        let Err(index) = entries.binary_search_by(|(k, _)| k.cmp(&key)) else {
+            return Ok(());
+        };
+
+        entries.insert(index, (key, id));
+
+        self.record(id, true);
+
+        let mut split = self.store(number, entries, Page::Leaf)?;
+
+        while let Some((lowest, separator, right)) = split {
+            split = match path.pop() {
+                Some((parent, mut children, index)) => {
+                    children.insert(index.saturating_add(1), (separator, right));
+
+                    let split = self.store(parent, children, Page::Branch)?;
+
+                    number = parent;
+
+                    split
+                }
+                None => {
+                    // The root itself was split, so a new root is needed above it
+                    let root = self.new_page();
+
+                    self.write(
+                        root,
+                        &Page::Branch(vec![(lowest, number), (separator, right)]),
+                    )?;
+
+                    self.header.root = Some(root);
+
+                    None
+                }
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Remove an entry from the tree, removing any pages left empty.
+    fn remove_stored<Q>(&mut self, key: &Q, id: Id) -> Result<(), StoreError>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let Some(root) = self.header.root else {
+            return Ok(());
+        };
+
+        let mut path = Vec::new();
+        let mut number = root;
+
+        let mut entries = loop {
+            match self.read(number)? {
+                Page::Branch(children) => {
+                    let index = route(&children, key);
+
+                    let Some(&(_, child)) = children.get(index) else {
+                        return Err(StorageError::InvalidDataFound(self.page_id(number)).into());
+                    };
+
+                    path.push((number, children, index));
+                    number = child;
+                }
+                Page::Leaf(entries) => break entries,
+            }
+        };
+
+        let Ok(index) = entries.binary_search_by(|(k, _)| k.borrow().cmp(key)) else {
+            return Ok(());
+        };
+
+        let _removed = entries.remove(index);
+
+        self.record(id, false);
+
+        if !entries.is_empty() {
+            return self.write(number, &Page::Leaf(entries));
+        }
+
+        self.delete(number);
+
+        while let Some((parent, mut children, index)) = path.pop() {
+            let _removed = children.remove(index);
+
+            if children.is_empty() {
+                self.delete(parent);
+
+                continue;
+            }
+
+            if path.is_empty() && children.len() == 1 {
+                // The root only has one child left, which can take its place
+                self.delete(parent);
+
+                self.header.root = children.pop().map(|(_, child)| child);
+
+                return Ok(());
+            }
+
+            return self.write(parent, &Page::Branch(children));
+        }
+
+        self.header.root = None;
+
+        Ok(())
+    }
+
+    /// Write a page, splitting it in two if it has grown beyond capacity.
+    ///
+    /// If the page is split, the lowest key of each half is returned, along
+    /// with the number of the new page holding the upper half.
+    ///
+    fn store<T: BorshSerialize>(
+        &mut self,
+        number: u64,
+        mut items: Vec<(K, T)>,
+        page: fn(Vec<(K, T)>) -> Page<K>,
+    ) -> Result<Option<(K, K, u64)>, StoreError>
+    where
+        K: Clone,
+    {
+        if items.len() <= PAGE_CAPACITY {
+            self.write(number, &page(items))?;
+
+            return Ok(None);
+        }
+
+        let upper = items.split_off(items.len().div_ceil(2));
+
+        let (Some((lowest, _)), Some((separator, _))) = (items.first(), upper.first()) else {
+            return Err(StorageError::InvalidDataFound(self.page_id(number)).into());
+        };
+
+        let (lowest, separator) = (lowest.clone(), separator.clone());
+
+        let right = self.new_page();
+
+        self.write(number, &page(items))?;
+        self.write(right, &page(upper))?;
+
+        Ok(Some((lowest, separator, right)))
+    }
+
+    /// Record an entry being added to or removed from the tree.
+    fn record(&mut self, id: Id, added: bool) {
+        self.header.len = if added {
+            self.header.len.saturating_add(1)
+        } else {
+            self.header.len.saturating_sub(1)
+        };
+
+        xor(&mut self.header.fingerprint, &id);
+    }
+
+    /// Allocate the number of a new page.
+    fn new_page(&mut self) -> u64 {
+        let number = self.header.next_page;
+
+        self.header.next_page = number.saturating_add(1);
+
+        number
+    }
+
+    /// The ID under which a page is stored.
+    fn page_id(&self, number: u64) -> Id {
+        compute_id(self.map, &number.to_le_bytes())
+    }
+
+    /// Read a page from storage.
+    fn read(&self, number: u64) -> Result<Page<K>, StoreError> {
+        let id = self.page_id(number);
+
+        let data = S::storage_read(Key::Order(id)).ok_or(StorageError::NotFound(id))?;
+
+        Ok(from_slice(&data).map_err(StorageError::DeserializationError)?)
+    }
+
+    /// Write a page to storage.
+    fn write<T: BorshSerialize>(&self, number: u64, page: &T) -> Result<(), StoreError> {
+        let data = to_vec(page).map_err(StorageError::SerializationError)?;
+
+        let _ignored = S::storage_write(Key::Order(self.page_id(number)), &data);
+
+        Ok(())
+    }
+
+    /// Delete a page from storage.
+    fn delete(&self, number: u64) {
+        let _ignored = S::storage_remove(Key::Order(self.page_id(number)));
+    }
+
+    /// Write the header to storage.
+    fn save_header(&self) -> Result<(), StoreError> {
+        let data = to_vec(&self.header).map_err(StorageError::SerializationError)?;
+
+        let _ignored = S::storage_write(Key::Order(self.map), &data);
+
+        Ok(())
+    }
+}
+
+/// Find the index of the child of a branch that a key is routed to.
+fn route<K: Borrow<Q>, Q: Ord + ?Sized>(children: &[(K, u64)], key: &Q) -> usize {
+    children
+        .partition_point(|(k, _)| k.borrow() <= key)
+        .saturating_sub(1)
+}
+
+/// Check whether keys between the given bounds, the lower inclusive and the
+/// upper exclusive, could fall within a range.
+fn overlaps<Q: Ord + ?Sized>(
+    range: &impl RangeBounds<Q>,
+    lower: Option<&Q>,
+    upper: Option<&Q>,
+) -> bool {
+    use core::ops::Bound;
+
+    let after_start = match (range.start_bound(), upper) {
+        (Bound::Included(start) | Bound::Excluded(start), Some(upper)) => upper > start,
+        _ => true,
+    };
+
+    let before_end = match (range.end_bound(), lower) {
+        (Bound::Included(end), Some(lower)) => lower <= end,
+        (Bound::Excluded(end), Some(lower)) => lower < end,
+        _ => true,
+    };
+
+    after_start && before_end
+}
+
+/// Combine the given IDs with XOR.
+fn fingerprint(ids: &IndexSet<Id>) -> [u8; 32] {
+    ids.iter().fold([0; 32], |mut fingerprint, id| {
+        xor(&mut fingerprint, id);
+        fingerprint
+    })
+}
+
+/// Combine an ID into a fingerprint with XOR.
+fn xor(fingerprint: &mut [u8; 32], id: &Id) {
+    for (byte, other) in fingerprint.iter_mut().zip(id.as_bytes()) {
+        *byte ^= other;
+    }
+}
@@ -122,6 +122,10 @@ where
     }
 
     /// Syncs the root collection.
+    ///
+    /// Any merge functions needed for the incoming changes must have been
+    /// registered beforehand, as each execution starts without them.
+    ///
     #[expect(clippy::missing_errors_doc, reason = "NO")]
     pub fn sync(args: &[u8]) -> Result<(), StorageError> {
         let artifact =
@@ -167,6 +171,19 @@ where
 
         Ok(())
     }
+
+    /// Purges the tombstones older than the retention period given in the
+    /// arguments, in nanoseconds, returning how many were purged.
+    #[expect(clippy::missing_errors_doc, reason = "NO")]
+    pub fn purge_tombstones(args: &[u8]) -> Result<usize, StorageError> {
+        let retention = from_slice::<u64>(args).map_err(StorageError::DeserializationError)?;
+
+        let purged = <Interface<S>>::purge_tombstones(retention)?;
+
+        Self::commit_headless();
+
+        Ok(purged)
+    }
 }
 
 impl<T, S> Deref for Root<T, S>
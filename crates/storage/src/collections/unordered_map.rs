@@ -412,6 +412,27 @@ mod tests {
         assert_eq!(map.get("key").expect("get failed"), None);
     }
 
+    #[test]
+    fn test_remove_and_reinsert() {
+        let mut map = Root::new(|| UnorderedMap::new());
+
+        assert!(map
+            .insert("key".to_string(), "value".to_string())
+            .expect("insert failed")
+            .is_none());
+        assert!(map.remove("key").expect("remove failed").is_some());
+        assert!(map
+            .insert("key".to_string(), "value2".to_string())
+            .expect("insert failed")
+            .is_none());
+
+        assert_eq!(
+            map.get("key").expect("get failed").as_deref(),
+            Some("value2")
+        );
+        assert_eq!(map.len().expect("len failed"), 1);
+    }
+
     #[test]
     fn test_clear() {
         let mut map = Root::new(|| UnorderedMap::new());
@@ -214,12 +214,13 @@ mod tests;
 
 use core::fmt::{self, Debug, Display, Formatter};
 use std::collections::BTreeMap;
-use std::ops::{Deref, DerefMut};
+use std::io::{Error as IoError, ErrorKind, Read, Write};
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use sha2::{Digest, Sha256};
 
 use crate::address::{Id, Path};
-use crate::env::time_now;
+use crate::env;
 
 /// Represents an atomic unit in the storage system.
 ///
@@ -373,6 +374,92 @@ pub trait Data: BorshDeserialize + BorshSerialize {
     fn path(&self) -> Path {
         self.element().path()
     }
+
+    /// The strategy used to resolve conflicting versions of the [`Data`].
+    ///
+    /// By default conflicts are resolved on a "last write wins" basis. Types
+    /// that implement [`Mergeable`] can instead have conflicting versions
+    /// merged, by returning [`MergeStrategy::custom()`] here. When deriving
+    /// [`AtomicUnit`], this is done using the `#[storage(merge)]` attribute.
+    ///
+    #[must_use]
+    fn merge_strategy() -> MergeStrategy
+    where
+        Self: Sized,
+    {
+        MergeStrategy::LastWriteWins
+    }
+
+    /// Registers the merge functions of the [`Data`] and its descendants.
+    ///
+    /// This is called by the [`Interface`](crate::interface::Interface)
+    /// whenever the type is loaded or saved, so that conflicting versions of
+    /// [`Mergeable`] types are merged when actions are applied. When deriving
+    /// [`AtomicUnit`], this registers the type if it uses `#[storage(merge)]`,
+    /// followed by the child types of each of its collections. Types that
+    /// implement [`Data`] by hand need to do the same if they are mergeable.
+    ///
+    fn register_merges()
+    where
+        Self: Sized,
+    {
+    }
+}
+
+/// [`Data`] that can merge conflicting versions of itself.
+///
+/// Normally, when an update is received for an [`Element`] that has since been
+/// updated locally, the incoming version is discarded, on a "last write wins"
+/// basis. For types that implement this trait and use the
+/// [`Custom`](MergeStrategy::Custom) strategy, the incoming version is instead
+/// merged into the local one, and the result is saved as a new version, which
+/// will then be propagated to other nodes in the usual way.
+///
+/// The merge function is registered by [`Data::register_merges()`], which the
+/// [`AtomicUnit`] derive generates for types marked with `#[storage(merge)]`.
+/// Until the type has been registered, the default strategy will be used.
+/// Registration does not outlast an execution, so apps list their mergeable
+/// types in `#[app::state(merges = [...])]` to have them registered before
+/// changes from other nodes are applied.
+///
+/// # Examples
+///
+/// ```
+/// use borsh::{BorshSerialize, BorshDeserialize};
+/// use calimero_storage::entities::{Element, Mergeable};
+/// use calimero_storage_macros::AtomicUnit;
+///
+/// #[derive(AtomicUnit, BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+/// #[storage(merge)]
+/// struct Tags {
+///     tags: Vec<String>,
+///     #[storage]
+///     storage: Element,
+/// }
+///
+/// impl Mergeable for Tags {
+///     fn merge(&mut self, incoming: Self) {
+///         for tag in incoming.tags {
+///             if !self.tags.contains(&tag) {
+///                 self.tags.push(tag);
+///             }
+///         }
+///     }
+/// }
+/// ```
+///
+pub trait Mergeable: Data {
+    /// Merges a conflicting version of the [`Data`] into this one.
+    ///
+    /// This is called on the local version, which is the more recently updated
+    /// of the two. The merge should be deterministic, so that nodes merging the
+    /// same versions arrive at the same result.
+    ///
+    /// # Parameters
+    ///
+    /// * `incoming` - The conflicting version, usually from a remote node.
+    ///
+    fn merge(&mut self, incoming: Self);
 }
 
 /// Summary information for the child of an [`Element`] in the storage.
@@ -449,8 +536,20 @@ impl ChildInfo {
 
     /// The timestamp when the child was last updated.
     #[must_use]
-    pub fn updated_at(&self) -> u64 {
-        *self.metadata.updated_at
+    pub const fn updated_at(&self) -> HybridTimestamp {
+        self.metadata.updated_at
+    }
+
+    /// The timestamp when the child was deleted, if it has been.
+    #[must_use]
+    pub const fn deleted_at(&self) -> Option<HybridTimestamp> {
+        self.metadata.deleted_at
+    }
+
+    /// Whether the child has been deleted, i.e. is a tombstone.
+    #[must_use]
+    pub const fn is_deleted(&self) -> bool {
+        self.metadata.is_deleted()
     }
 }
 
@@ -606,14 +705,16 @@ impl Element {
     ///
     #[must_use]
     pub fn new(path: &Path, id: Option<Id>) -> Self {
-        let timestamp = time_now();
+        let timestamp = env::hlc_now();
         let element_id = id.unwrap_or_else(Id::random);
         Self {
             id: element_id,
             is_dirty: true,
             metadata: Metadata {
-                created_at: timestamp,
-                updated_at: timestamp.into(),
+                created_at: timestamp.physical(),
+                updated_at: timestamp,
+                deleted_at: None,
+                merge_strategy: MergeStrategy::LastWriteWins,
             },
             merkle_hash: [0; 32],
             path: path.clone(),
@@ -624,13 +725,15 @@ impl Element {
     #[must_use]
     #[expect(clippy::missing_panics_doc, reason = "This is expected to be valid")]
     pub fn root() -> Self {
-        let timestamp = time_now();
+        let timestamp = env::hlc_now();
         Self {
             id: Id::root(),
             is_dirty: true,
             metadata: Metadata {
-                created_at: timestamp,
-                updated_at: timestamp.into(),
+                created_at: timestamp.physical(),
+                updated_at: timestamp,
+                deleted_at: None,
+                merge_strategy: MergeStrategy::LastWriteWins,
             },
             merkle_hash: [0; 32],
             #[expect(clippy::unwrap_used, reason = "This is expected to be valid")]
@@ -707,7 +810,8 @@ impl Element {
     ///
     /// It updates the [`updated_at()`](Element::updated_at()) timestamp to
     /// reflect the time that the [`Element`] was last updated (this is part of
-    /// the metadata).
+    /// the metadata). The new timestamp is always later than the previous one,
+    /// even if it was issued by a node whose clock is ahead of this one.
     ///
     /// **IMPORTANT**: It does not update the actual data itself, as it has no
     /// way of accessing this. Therefore, this method should be called after
@@ -733,13 +837,14 @@ impl Element {
     ///
     pub fn update(&mut self) {
         self.is_dirty = true;
-        *self.metadata.updated_at = time_now();
+        env::hlc_observe(self.metadata.updated_at);
+        self.metadata.updated_at = env::hlc_now();
     }
 
     /// The timestamp when the [`Element`] was last updated.
     #[must_use]
-    pub fn updated_at(&self) -> u64 {
-        *self.metadata.updated_at
+    pub const fn updated_at(&self) -> HybridTimestamp {
+        self.metadata.updated_at
     }
 }
 
@@ -764,19 +869,32 @@ impl Display for Element {
 ///
 /// # Timestamps
 ///
-/// The timestamp fields, i.e. [`created_at()`](Element::created_at()) and
-/// [`updated_at()`](Element::updated_at()), are stored using [`u64`] integer
-/// values. This is because [Chrono](https://crates.io/crates/chrono) does not
-/// support [Borsh](https://crates.io/crates/borsh) serialisation, and also
-/// using a 64-bit integer is faster and more efficient (as Chrono uses 96 bits
-/// internally).
+/// The physical timestamps, i.e. [`created_at()`](Element::created_at()) and
+/// the physical part of [`updated_at()`](Element::updated_at()), are stored
+/// using [`u64`] integer values. This is because [Chrono](https://crates.io/crates/chrono)
+/// does not support [Borsh](https://crates.io/crates/borsh) serialisation, and
+/// also using a 64-bit integer is faster and more efficient (as Chrono uses 96
+/// bits internally).
 ///
 /// Using a [`u64`] timestamp allows for 585 years from the Unix epoch, at
 /// nanosecond precision. This is more than sufficient for our current needs.
 ///
-#[derive(
-    BorshDeserialize, BorshSerialize, Copy, Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd,
-)]
+/// The timestamps used to resolve conflicts, i.e. [`updated_at()`](Metadata::updated_at())
+/// and [`deleted_at()`](Metadata::deleted_at()), are [`HybridTimestamp`]s, so
+/// that they are not at the mercy of skewed clocks, and so that no two writes
+/// are considered to have happened at the same time.
+///
+/// # Tombstones
+///
+/// When an [`Element`] is deleted, its index entry is retained as a tombstone,
+/// with [`deleted_at`](Metadata::deleted_at()) recording when the deletion
+/// happened. This allows a deletion to be distinguished from a child that is
+/// simply missing, so that a node that never saw the deletion cannot re-add
+/// the [`Element`] during a comparison. Tombstones are excluded from Merkle
+/// hashes and from collection iteration, and are purged once they are older
+/// than the retention period.
+///
+#[derive(Copy, Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
 #[non_exhaustive]
 pub struct Metadata {
     /// When the [`Element`] was first created. Note that this is a global
@@ -788,36 +906,315 @@ pub struct Metadata {
     /// [`Element`] was last modified in any way, and is used to determine the
     /// freshness of the data. It is critical for the "last write wins" strategy
     /// that is used to resolve conflicts.
-    pub(crate) updated_at: UpdatedAt,
+    pub(crate) updated_at: HybridTimestamp,
+
+    /// When the [`Element`] was deleted, if it has been. A deleted [`Element`]
+    /// has no data, and is only kept as a tombstone until it gets purged. The
+    /// deletion competes with updates on a "last write wins" basis.
+    pub(crate) deleted_at: Option<HybridTimestamp>,
+
+    /// How conflicting versions of the [`Element`]'s data are resolved. This
+    /// is set from the [`Data`] type when the [`Element`] is saved.
+    pub(crate) merge_strategy: MergeStrategy,
+}
+
+impl Metadata {
+    /// Deserialises [`Metadata`], along with the version it was encoded with.
+    ///
+    /// Version `0` is the legacy encoding, which has only the creation and
+    /// update times, and is read with the remaining fields at their defaults.
+    /// The version allows types that contain [`Metadata`] to tell whether
+    /// fields that follow it were also written.
+    ///
+    /// # Errors
+    ///
+    /// If the data cannot be read, or was written with an unsupported version,
+    /// an error will be returned.
+    ///
+    pub(crate) fn deserialize_versioned<R: Read>(reader: &mut R) -> Result<(Self, u8), IoError> {
+        let marker = u64::deserialize_reader(reader)?;
+
+        if marker != METADATA_MARKER {
+            // Legacy metadata, where the marker is the creation time
+            let metadata = Self {
+                created_at: marker,
+                updated_at: HybridTimestamp::deserialize_reader(reader)?,
+                deleted_at: None,
+                merge_strategy: MergeStrategy::LastWriteWins,
+            };
+            return Ok((metadata, 0));
+        }
+
+        let version = u8::deserialize_reader(reader)?;
+
+        let metadata = match version {
+            // Version 1 wrote each timestamp with its own marker
+            1 => Self {
+                created_at: u64::deserialize_reader(reader)?,
+                updated_at: HybridTimestamp::deserialize_reader(reader)?,
+                deleted_at: Option::deserialize_reader(reader)?,
+                merge_strategy: MergeStrategy::deserialize_reader(reader)?,
+            },
+            METADATA_VERSION => Self {
+                created_at: u64::deserialize_reader(reader)?,
+                updated_at: HybridTimestamp::deserialize_unmarked(reader)?,
+                deleted_at: if bool::deserialize_reader(reader)? {
+                    Some(HybridTimestamp::deserialize_unmarked(reader)?)
+                } else {
+                    None
+                },
+                merge_strategy: MergeStrategy::deserialize_reader(reader)?,
+            },
+            _ => {
+                return Err(IoError::new(
+                    ErrorKind::InvalidData,
+                    format!("unsupported metadata version {version}"),
+                ));
+            }
+        };
+
+        Ok((metadata, version))
+    }
+
+    /// When the [`Element`] was first created.
+    #[must_use]
+    pub const fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    /// When the [`Element`] was last updated.
+    #[must_use]
+    pub const fn updated_at(&self) -> HybridTimestamp {
+        self.updated_at
+    }
+
+    /// When the [`Element`] was deleted, if it has been.
+    #[must_use]
+    pub const fn deleted_at(&self) -> Option<HybridTimestamp> {
+        self.deleted_at
+    }
+
+    /// Whether the [`Element`] has been deleted, i.e. is a tombstone.
+    #[must_use]
+    pub const fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// How conflicting versions of the [`Element`]'s data are resolved.
+    #[must_use]
+    pub const fn merge_strategy(&self) -> MergeStrategy {
+        self.merge_strategy
+    }
+}
+
+/// The strategy used to resolve conflicting versions of an [`Element`]'s data.
+///
+/// A conflict occurs when an update is received for an [`Element`] that has
+/// been updated locally since the incoming version was written.
+///
+#[derive(
+    BorshDeserialize, BorshSerialize, Copy, Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd,
+)]
+#[non_exhaustive]
+pub enum MergeStrategy {
+    /// The most recently updated version is kept, and the other discarded.
+    #[default]
+    LastWriteWins,
+
+    /// The versions are merged using the [`Mergeable`] implementation of the
+    /// type identified by the hash.
+    Custom([u8; 32]),
+}
+
+impl MergeStrategy {
+    /// The strategy of merging using the [`Mergeable`] implementation of the
+    /// type with the given identifier.
+    ///
+    /// The identifier is stored alongside each entity of the type, and so must
+    /// be stable across builds of the application, and unique within it. When
+    /// deriving [`AtomicUnit`], it defaults to the name of the type, and can be
+    /// overridden using `#[storage(merge_id = "...")]`.
+    ///
+    #[must_use]
+    pub fn custom(identifier: &str) -> Self {
+        Self::Custom(Sha256::digest(identifier).into())
+    }
+}
+
+/// The marker written before each versioned encoding of [`Metadata`].
+///
+/// Legacy [`Metadata`] starts with the creation time, which can never take
+/// this value, and so the two encodings can be told apart when read.
+///
+const METADATA_MARKER: u64 = u64::MAX;
+
+/// The current version of the [`Metadata`] encoding.
+///
+/// Version `0` is reserved for the legacy encoding, which has no marker.
+/// Version `1` wrote each [`HybridTimestamp`] with its own marker, which the
+/// metadata marker already makes redundant, and so version `2` omits them.
+///
+const METADATA_VERSION: u8 = 2;
+
+/// The marker written before each [`HybridTimestamp`].
+///
+/// Before hybrid logical clocks were introduced, timestamps were stored as a
+/// plain [`u64`] nanosecond value. The marker is not a plausible value for one
+/// of those, and so allows them to be told apart and migrated when read.
+///
+const HYBRID_TIMESTAMP_MARKER: u64 = u64::MAX;
+
+/// A timestamp issued by a hybrid logical clock.
+///
+/// This combines the physical time, as a nanosecond timestamp, with a logical
+/// counter and the identity of the node that issued it. Timestamps are ordered
+/// by physical time, then by counter, and then by node.
+///
+/// The counter orders events that fall within the same physical time, and also
+/// allows a node's clock to run ahead of its physical time once it has seen a
+/// timestamp from a node whose clock is ahead. This means that a write made
+/// after seeing another write will always be ordered after it, regardless of
+/// any skew between the clocks of the two nodes. The node identity then breaks
+/// any remaining ties between concurrent writes, so that all nodes agree on
+/// which one wins.
+///
+/// # Migration
+///
+/// Timestamps stored before hybrid logical clocks were introduced are read as
+/// having a counter of zero and an empty node identity, and so are ordered
+/// after any earlier timestamps and before any later ones, as before.
+///
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct HybridTimestamp {
+    /// The physical time, as a nanosecond timestamp.
+    physical: u64,
+
+    /// The logical counter, which orders timestamps with the same physical
+    /// time.
+    logical: u32,
+
+    /// The identity of the node that issued the timestamp.
+    node: [u8; 32],
+}
+
+impl HybridTimestamp {
+    /// Creates a new [`HybridTimestamp`].
+    ///
+    /// # Parameters
+    ///
+    /// * `physical` - The physical time, as a nanosecond timestamp.
+    /// * `logical`  - The logical counter.
+    /// * `node`     - The identity of the node issuing the timestamp.
+    ///
+    #[must_use]
+    pub const fn new(physical: u64, logical: u32, node: [u8; 32]) -> Self {
+        Self {
+            physical,
+            logical,
+            node,
+        }
+    }
+
+    /// The physical time, as a nanosecond timestamp.
+    #[must_use]
+    pub const fn physical(&self) -> u64 {
+        self.physical
+    }
+
+    /// The logical counter.
+    #[must_use]
+    pub const fn logical(&self) -> u32 {
+        self.logical
+    }
+
+    /// The identity of the node that issued the timestamp.
+    #[must_use]
+    pub const fn node(&self) -> [u8; 32] {
+        self.node
+    }
+
+    /// The next timestamp to issue after this one.
+    ///
+    /// If the physical time has moved on then it is used as-is, otherwise the
+    /// counter is advanced, so that the result is always later than `self`.
+    ///
+    /// # Parameters
+    ///
+    /// * `physical` - The current physical time, as a nanosecond timestamp.
+    /// * `node`     - The identity of the node issuing the timestamp.
+    ///
+    #[must_use]
+    pub const fn next(&self, physical: u64, node: [u8; 32]) -> Self {
+        if physical > self.physical {
+            Self::new(physical, 0, node)
+        } else if let Some(logical) = self.logical.checked_add(1) {
+            Self::new(self.physical, logical, node)
+        } else {
+            Self::new(self.physical.saturating_add(1), 0, node)
+        }
+    }
+
+    /// Deserialises a [`HybridTimestamp`] written without its marker, where
+    /// the surrounding encoding already rules out a legacy timestamp.
+    fn deserialize_unmarked<R: Read>(reader: &mut R) -> Result<Self, IoError> {
+        Ok(Self {
+            physical: u64::deserialize_reader(reader)?,
+            logical: u32::deserialize_reader(reader)?,
+            node: <[u8; 32]>::deserialize_reader(reader)?,
+        })
+    }
+
+    /// Serialises the [`HybridTimestamp`] without its marker.
+    fn serialize_unmarked<W: Write>(&self, writer: &mut W) -> Result<(), IoError> {
+        BorshSerialize::serialize(&self.physical, writer)?;
+        BorshSerialize::serialize(&self.logical, writer)?;
+        BorshSerialize::serialize(&self.node, writer)
+    }
 }
 
-/// The timestamp when the [`Element`] was last updated.
-#[derive(BorshDeserialize, BorshSerialize, Copy, Clone, Debug, Default, Eq, Ord, PartialOrd)]
-pub struct UpdatedAt(u64);
+impl BorshDeserialize for Metadata {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self, IoError> {
+        Self::deserialize_versioned(reader).map(|(metadata, _)| metadata)
+    }
+}
 
-impl PartialEq for UpdatedAt {
-    fn eq(&self, _other: &Self) -> bool {
-        // we don't care
-        true
+impl BorshSerialize for Metadata {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), IoError> {
+        BorshSerialize::serialize(&METADATA_MARKER, writer)?;
+        BorshSerialize::serialize(&METADATA_VERSION, writer)?;
+        BorshSerialize::serialize(&self.created_at, writer)?;
+        self.updated_at.serialize_unmarked(writer)?;
+        BorshSerialize::serialize(&self.deleted_at.is_some(), writer)?;
+        if let Some(deleted_at) = &self.deleted_at {
+            deleted_at.serialize_unmarked(writer)?;
+        }
+        BorshSerialize::serialize(&self.merge_strategy, writer)
     }
 }
 
-impl Deref for UpdatedAt {
-    type Target = u64;
+impl BorshDeserialize for HybridTimestamp {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self, IoError> {
+        let marker = u64::deserialize_reader(reader)?;
+
+        if marker != HYBRID_TIMESTAMP_MARKER {
+            // A legacy timestamp, which holds the physical time only
+            return Ok(Self::new(marker, 0, [0; 32]));
+        }
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+        Self::deserialize_unmarked(reader)
     }
 }
 
-impl DerefMut for UpdatedAt {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+impl BorshSerialize for HybridTimestamp {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<(), IoError> {
+        BorshSerialize::serialize(&HYBRID_TIMESTAMP_MARKER, writer)?;
+        self.serialize_unmarked(writer)
     }
 }
 
-impl From<u64> for UpdatedAt {
-    fn from(value: u64) -> Self {
-        Self(value)
+impl Display for HybridTimestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.physical, self.logical)
     }
 }
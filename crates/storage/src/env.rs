@@ -1,12 +1,20 @@
 //! Environment bindings for the storage crate.
 
+use core::cell::Cell;
+
 #[cfg(target_arch = "wasm32")]
 use calimero_vm as imp;
 #[cfg(not(target_arch = "wasm32"))]
 use mocked as imp;
 
+use crate::entities::HybridTimestamp;
 use crate::store::Key;
 
+thread_local! {
+    /// The latest timestamp issued or observed by the hybrid logical clock.
+    static CLOCK: Cell<HybridTimestamp> = const { Cell::new(HybridTimestamp::new(0, 0, [0; 32])) };
+}
+
 /// Commits the root hash to the runtime.
 ///
 #[expect(clippy::missing_const_for_fn, reason = "Cannot be const here")]
@@ -64,6 +72,35 @@ pub fn time_now() -> u64 {
     imp::time_now()
 }
 
+/// Get a new timestamp from the hybrid logical clock.
+///
+/// The timestamp is issued by the current executor, and is later than both the
+/// current time and every timestamp previously issued or observed by the
+/// clock.
+///
+#[must_use]
+pub fn hlc_now() -> HybridTimestamp {
+    CLOCK.with(|clock| {
+        let timestamp = clock.get().next(time_now(), executor_id());
+        clock.set(timestamp);
+        timestamp
+    })
+}
+
+/// Observe a timestamp with the hybrid logical clock.
+///
+/// This is used for timestamps received from elsewhere, such as from other
+/// nodes, so that all timestamps subsequently issued by the clock will be
+/// later than the observed one.
+///
+/// # Parameters
+///
+/// * `timestamp` - The timestamp to observe.
+///
+pub fn hlc_observe(timestamp: HybridTimestamp) {
+    CLOCK.with(|clock| clock.set(clock.get().max(timestamp)));
+}
+
 /// Return the context id.
 #[must_use]
 #[expect(clippy::missing_const_for_fn, reason = "Cannot be const here")]
@@ -71,6 +108,18 @@ pub fn context_id() -> [u8; 32] {
     imp::context_id()
 }
 
+/// Return the identity of the executor.
+#[must_use]
+pub fn executor_id() -> [u8; 32] {
+    imp::executor_id()
+}
+
+/// Set the identity of the executor, for simulating multiple nodes in tests.
+#[cfg(all(test, not(target_arch = "wasm32")))]
+pub(crate) fn set_executor_id(executor_id: [u8; 32]) {
+    imp::set_executor_id(executor_id);
+}
+
 #[cfg(target_arch = "wasm32")]
 mod calimero_vm {
     use calimero_sdk::env;
@@ -107,6 +156,11 @@ mod calimero_vm {
         env::context_id()
     }
 
+    /// Return the identity of the executor.
+    pub(super) fn executor_id() -> [u8; 32] {
+        env::executor_id()
+    }
+
     /// Gets the current time.
     ///
     /// This function obtains the current time as a nanosecond timestamp.
@@ -118,7 +172,7 @@ mod calimero_vm {
 
 #[cfg(not(target_arch = "wasm32"))]
 mod mocked {
-    use std::cell::RefCell;
+    use std::cell::{Cell, RefCell};
     use std::time::{SystemTime, UNIX_EPOCH};
 
     use rand::RngCore;
@@ -127,6 +181,7 @@ mod mocked {
 
     thread_local! {
         static ROOT_HASH: RefCell<Option<[u8; 32]>> = const { RefCell::new(None) };
+        static EXECUTOR_ID: Cell<[u8; 32]> = const { Cell::new([0; 32]) };
     }
 
     /// The default storage system.
@@ -166,6 +221,17 @@ mod mocked {
         [236; 32]
     }
 
+    /// Return the identity of the executor.
+    pub(super) fn executor_id() -> [u8; 32] {
+        EXECUTOR_ID.get()
+    }
+
+    /// Set the identity of the executor.
+    #[cfg(test)]
+    pub(super) fn set_executor_id(executor_id: [u8; 32]) {
+        EXECUTOR_ID.set(executor_id);
+    }
+
     /// Gets the current time.
     ///
     /// This function obtains the current time as a nanosecond timestamp.
@@ -6,17 +6,18 @@ mod tests;
 
 use core::marker::PhantomData;
 use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Error as IoError, Read};
 
 use borsh::{to_vec, BorshDeserialize, BorshSerialize};
 use sha2::{Digest, Sha256};
 
-use crate::address::Id;
-use crate::entities::{ChildInfo, Metadata, UpdatedAt};
+use crate::address::{Id, Path};
+use crate::entities::{ChildInfo, Metadata};
 use crate::interface::StorageError;
 use crate::store::{Key, StorageAdaptor};
 
 /// Stored index information for an entity in the storage system.
-#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[derive(BorshSerialize, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 struct EntityIndex {
     /// Unique identifier of the entity.
     id: Id,
@@ -37,6 +38,56 @@ struct EntityIndex {
 
     /// Metadata about the entity.
     metadata: Metadata,
+
+    /// The path of the entity in the hierarchy, if known. This is recorded so
+    /// that the entity can be removed from the path index when needed.
+    path: Option<Path>,
+}
+
+impl BorshDeserialize for EntityIndex {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self, IoError> {
+        let id = Id::deserialize_reader(reader)?;
+        let parent_id = Option::deserialize_reader(reader)?;
+        let children = BTreeMap::deserialize_reader(reader)?;
+        let full_hash = <[u8; 32]>::deserialize_reader(reader)?;
+        let own_hash = <[u8; 32]>::deserialize_reader(reader)?;
+        let (metadata, version) = Metadata::deserialize_versioned(reader)?;
+
+        // Indexes written with legacy metadata predate the recorded path, which
+        // is only backfilled once the entity is next saved
+        let path = if version == 0 {
+            None
+        } else {
+            Option::deserialize_reader(reader)?
+        };
+
+        Ok(Self {
+            id,
+            parent_id,
+            children,
+            full_hash,
+            own_hash,
+            metadata,
+            path,
+        })
+    }
+}
+
+/// Stored index information for a path in the storage system.
+///
+/// Each path that has entities at or below it has an entry, which records the
+/// entities located at that exact path, along with the names of the segments
+/// directly below it. This allows a path and all of its descendants to be
+/// found without walking the entire hierarchy. Both sets are ordered, which
+/// ensures that lookups are deterministic.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, Eq, PartialEq)]
+struct PathIndex {
+    /// The entities located at the path.
+    ids: BTreeSet<Id>,
+
+    /// The names of the segments directly below the path that have entities at
+    /// or below them.
+    segments: BTreeSet<String>,
 }
 
 /// Manages the indexing system for efficient tree navigation.
@@ -64,7 +115,7 @@ impl<S: StorageAdaptor> Index<S> {
     /// # See also
     ///
     /// * [`add_root()`](Index::add_root())
-    /// * [`remove_child_from()`](Index::remove_child_from())
+    /// * [`mark_deleted()`](Index::mark_deleted())
     ///
     pub(crate) fn add_child_to(
         parent_id: Id,
@@ -81,9 +132,11 @@ impl<S: StorageAdaptor> Index<S> {
             full_hash: [0; 32],
             own_hash: [0; 32],
             metadata: child.metadata,
+            path: None,
         });
         child_index.parent_id = Some(parent_id);
         child_index.own_hash = child.merkle_hash();
+        child_index.metadata.deleted_at = None;
         Self::save_index(&child_index)?;
         child_index.full_hash = Self::calculate_full_merkle_hash_for(child.id(), false)?;
         Self::save_index(&child_index)?;
@@ -93,6 +146,10 @@ impl<S: StorageAdaptor> Index<S> {
             .entry(collection.to_owned())
             .or_insert_with(Vec::new);
 
+        // A tombstone for the same child may be present with a different
+        // creation time, in which case it would not be replaced below.
+        children.retain(|existing| existing.id() != child.id());
+
         let mut ordered = children.drain(..).collect::<BTreeSet<_>>();
 
         let _ignored = ordered.replace(ChildInfo::new(
@@ -137,6 +194,7 @@ impl<S: StorageAdaptor> Index<S> {
             full_hash: [0; 32],
             own_hash: [0; 32],
             metadata: root.metadata,
+            path: None,
         });
         index.own_hash = root.merkle_hash();
         Self::save_index(&index)?;
@@ -253,6 +311,10 @@ impl<S: StorageAdaptor> Index<S> {
 
     /// Retrieves the children of a given entity.
     ///
+    /// Deleted children, i.e. tombstones, are not included. See
+    /// [`get_children_with_tombstones_of()`](Index::get_children_with_tombstones_of())
+    /// for a version that includes them.
+    ///
     /// # Parameters
     ///
     /// * `parent_id`  - The [`Id`] of the entity whose children are to be
@@ -268,6 +330,32 @@ impl<S: StorageAdaptor> Index<S> {
     pub(crate) fn get_children_of(
         parent_id: Id,
         collection: &str,
+    ) -> Result<Vec<ChildInfo>, StorageError> {
+        let mut children = Self::get_children_with_tombstones_of(parent_id, collection)?;
+        children.retain(|child| !child.is_deleted());
+        Ok(children)
+    }
+
+    /// Retrieves the children of a given entity, including tombstones.
+    ///
+    /// This is needed when comparing trees, as a deleted child has to be
+    /// distinguished from one that is missing.
+    ///
+    /// # Parameters
+    ///
+    /// * `parent_id`  - The [`Id`] of the entity whose children are to be
+    ///                  retrieved.
+    /// * `collection` - The name of the collection from which to retrieve the
+    ///                  children.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or deserialising the index information,
+    /// an error will be returned.
+    ///
+    pub(crate) fn get_children_with_tombstones_of(
+        parent_id: Id,
+        collection: &str,
     ) -> Result<Vec<ChildInfo>, StorageError> {
         Ok(Self::get_index(parent_id)?
             .ok_or(StorageError::IndexNotFound(parent_id))?
@@ -317,6 +405,69 @@ impl<S: StorageAdaptor> Index<S> {
         Ok(Self::get_index(id)?.map(|index| (index.full_hash, index.own_hash)))
     }
 
+    /// Retrieves the IDs of the entities located at a given path.
+    ///
+    /// Deleted entities, i.e. tombstones, are not included. The IDs are
+    /// returned in a deterministic order.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The [`Path`] at which to look for entities.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or deserialising the index information,
+    /// an error will be returned.
+    ///
+    pub(crate) fn get_ids_at_path(path: &Path) -> Result<Vec<Id>, StorageError> {
+        let Some(path_index) = Self::get_path_index(path)? else {
+            return Ok(vec![]);
+        };
+
+        let mut ids = Vec::with_capacity(path_index.ids.len());
+
+        for id in path_index.ids {
+            if Self::get_metadata(id)?.is_some_and(|metadata| !metadata.is_deleted()) {
+                ids.push(id);
+            }
+        }
+
+        Ok(ids)
+    }
+
+    /// Retrieves the IDs of the entities located below a given path.
+    ///
+    /// This includes the entities at any depth below the path, but not those
+    /// at the path itself. The hierarchy is walked depth-first, visiting the
+    /// segments at each level in lexicographical order, so the order of the
+    /// IDs returned is deterministic. Deleted entities are not included.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The [`Path`] below which to look for entities.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or deserialising the index information,
+    /// an error will be returned.
+    ///
+    pub(crate) fn get_ids_below_path(path: &Path) -> Result<Vec<Id>, StorageError> {
+        let mut ids = vec![];
+
+        let Some(path_index) = Self::get_path_index(path)? else {
+            return Ok(ids);
+        };
+
+        for segment in &path_index.segments {
+            let child_path = path.join(&Path::new(format!("::{segment}"))?)?;
+
+            ids.extend(Self::get_ids_at_path(&child_path)?);
+            ids.extend(Self::get_ids_below_path(&child_path)?);
+        }
+
+        Ok(ids)
+    }
+
     /// Retrieves the index information for an entity.
     ///
     /// # Parameters
@@ -353,6 +504,41 @@ impl<S: StorageAdaptor> Index<S> {
         Ok(Self::get_index(child_id)?.and_then(|index| index.parent_id))
     }
 
+    /// Retrieves the path of a given entity.
+    ///
+    /// # Parameters
+    ///
+    /// * `id` - The [`Id`] of the entity whose path is to be retrieved.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or deserialising the index information,
+    /// an error will be returned.
+    ///
+    pub(crate) fn get_path(id: Id) -> Result<Option<Path>, StorageError> {
+        Ok(Self::get_index(id)?.and_then(|index| index.path))
+    }
+
+    /// Retrieves the path index information for a given path.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The [`Path`] whose index information is to be retrieved.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or deserialising the index information,
+    /// an error will be returned.
+    ///
+    fn get_path_index(path: &Path) -> Result<Option<PathIndex>, StorageError> {
+        match S::storage_read(Self::path_key(path)) {
+            Some(data) => Ok(Some(
+                PathIndex::try_from_slice(&data).map_err(StorageError::DeserializationError)?,
+            )),
+            None => Ok(None),
+        }
+    }
+
     /// Whether the collection has children.
     ///
     /// # Parameters
@@ -373,7 +559,201 @@ impl<S: StorageAdaptor> Index<S> {
         Ok(parent_index
             .children
             .get(collection)
-            .map_or(false, |children| !children.is_empty()))
+            .map_or(false, |children| {
+                children.iter().any(|child| !child.is_deleted())
+            }))
+    }
+
+    /// Marks an entity as deleted, leaving a tombstone in the index.
+    ///
+    /// The index entry of the entity is retained, with the deletion time set in
+    /// its metadata, and the same is reflected in the parent's list of
+    /// children. Tombstones do not contribute to Merkle hashes, so the hashes
+    /// of the ancestors are recalculated as if the child had been removed.
+    ///
+    /// If the entity is not known locally, a tombstone is still recorded, so
+    /// that the entity cannot be re-added by a peer that did not see the
+    /// deletion. In this case it is placed in the specified collection of the
+    /// parent, if the parent is known.
+    ///
+    /// # Parameters
+    ///
+    /// * `parent_id`  - The [`Id`] of the parent entity, if known. This is
+    ///                  only used if the entity is not already indexed.
+    /// * `collection` - The name of the collection to record the tombstone in,
+    ///                  if the child is not already present in the parent.
+    /// * `id`         - The [`Id`] of the entity to be marked as deleted.
+    /// * `metadata`   - The metadata of the deleted entity, including the
+    ///                  deletion time.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or saving the index information, an error
+    /// will be returned.
+    ///
+    /// # See also
+    ///
+    /// * [`purge_tombstones()`](Index::purge_tombstones())
+    ///
+    pub(crate) fn mark_deleted(
+        parent_id: Option<Id>,
+        collection: &str,
+        id: Id,
+        metadata: Metadata,
+    ) -> Result<(), StorageError> {
+        let mut index = Self::get_index(id)?.unwrap_or_else(|| EntityIndex {
+            id,
+            parent_id,
+            children: BTreeMap::new(),
+            full_hash: [0; 32],
+            own_hash: [0; 32],
+            metadata,
+            path: None,
+        });
+        index.metadata.deleted_at = metadata.deleted_at;
+        Self::save_index(&index)?;
+
+        let Some(parent_id) = index.parent_id else {
+            return Ok(());
+        };
+
+        let Some(mut parent_index) = Self::get_index(parent_id)? else {
+            return Ok(());
+        };
+
+        let existing = parent_index
+            .children
+            .values_mut()
+            .flat_map(|children| children.iter_mut())
+            .find(|child| child.id() == id);
+
+        if let Some(child) = existing {
+            child.metadata.deleted_at = metadata.deleted_at;
+        } else {
+            let children = parent_index
+                .children
+                .entry(collection.to_owned())
+                .or_insert_with(Vec::new);
+            children.push(ChildInfo::new(id, index.full_hash, index.metadata));
+            children.sort();
+        }
+
+        Self::save_index(&parent_index)?;
+        parent_index.full_hash = Self::calculate_full_merkle_hash_for(parent_id, false)?;
+        Self::save_index(&parent_index)?;
+
+        Self::recalculate_ancestor_hashes_for(parent_id)?;
+        Ok(())
+    }
+
+    /// Generates the storage key for the index information of a path.
+    fn path_key(path: &Path) -> Key {
+        Key::Path(Id::new(Sha256::digest(path.to_string()).into()))
+    }
+
+    /// Purges expired tombstones from the index.
+    ///
+    /// This walks the hierarchy below the specified entity, and permanently
+    /// removes any tombstones that were deleted at or before the cut-off time,
+    /// along with anything that remains indexed below them. As tombstones do
+    /// not contribute to Merkle hashes, no hashes are affected by this.
+    ///
+    /// # Parameters
+    ///
+    /// * `id`     - The [`Id`] of the entity to start from.
+    /// * `cutoff` - The timestamp at or before which tombstones are purged.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or saving the index information, an error
+    /// will be returned.
+    ///
+    pub(crate) fn purge_tombstones(id: Id, cutoff: u64) -> Result<usize, StorageError> {
+        let Some(mut index) = Self::get_index(id)? else {
+            return Ok(0);
+        };
+
+        let mut purged = Self::purge_expired_children_of(&mut index, cutoff)?;
+
+        if purged > 0 {
+            Self::save_index(&index)?;
+        }
+
+        for child in index.children.values().flatten() {
+            purged = purged.saturating_add(Self::purge_tombstones(child.id(), cutoff)?);
+        }
+
+        Ok(purged)
+    }
+
+    /// Purges the expired tombstones among the children of an index entry.
+    ///
+    /// The index entry passed in is updated, but not saved, as this is left to
+    /// the caller.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or removing the index information, an
+    /// error will be returned.
+    ///
+    fn purge_expired_children_of(
+        index: &mut EntityIndex,
+        cutoff: u64,
+    ) -> Result<usize, StorageError> {
+        let mut expired = Vec::new();
+
+        for children in index.children.values_mut() {
+            children.retain(|child| match child.deleted_at() {
+                Some(deleted_at) if deleted_at.physical() <= cutoff => {
+                    expired.push(child.id());
+                    false
+                }
+                _ => true,
+            });
+        }
+
+        for id in &expired {
+            Self::remove_subtree(*id)?;
+        }
+
+        Ok(expired.len())
+    }
+
+    /// Adds an entity to the path index.
+    ///
+    /// The entity is recorded against its path, and the path is linked to each
+    /// of its ancestors in turn, stopping at the first one that already knows
+    /// about it.
+    ///
+    /// # Parameters
+    ///
+    /// * `id`   - The [`Id`] of the entity to be added.
+    /// * `path` - The [`Path`] of the entity.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or saving the index information, an error
+    /// will be returned.
+    ///
+    fn register_path(id: Id, path: &Path) -> Result<(), StorageError> {
+        let mut path_index = Self::get_path_index(path)?.unwrap_or_default();
+        let _ignored = path_index.ids.insert(id);
+        Self::save_path_index(path, &path_index)?;
+
+        let mut current = path.clone();
+
+        while let Some(parent) = current.parent() {
+            let mut parent_index = Self::get_path_index(&parent)?.unwrap_or_default();
+
+            if !parent_index.segments.insert(current.last().to_owned()) {
+                break;
+            }
+
+            Self::save_path_index(&parent, &parent_index)?;
+            current = parent;
+        }
+
+        Ok(())
     }
 
     /// Recalculates the Merkle hashes of the ancestors of the entity.
@@ -416,77 +796,175 @@ impl<S: StorageAdaptor> Index<S> {
         Ok(())
     }
 
-    /// Removes a child from a collection in the index.
-    ///
-    /// Note that removing a child from the index also deletes the child. To
-    /// move a child to a different parent, just add it to the new parent.
+    /// Removes the index information for an entity.
     ///
     /// # Parameters
     ///
-    /// * `parent_id`  - The [`Id`] of the parent entity.
-    /// * `collection` - The name of the collection from which the child is to
-    ///                  be removed.
-    /// * `child_id`   - The [`Id`] of the child entity to be removed.
+    /// * `index` - The [`EntityIndex`] to be saved.
     ///
-    /// # Errors
+    fn remove_index(id: Id) {
+        _ = S::storage_remove(Key::Index(id));
+    }
+
+    /// Removes an entity and all of its descendants from the storage system.
     ///
-    /// If there's an issue retrieving or saving the index information, an error
-    /// will be returned.
+    /// This removes the index information, including from the path index, and
+    /// the data, but does not update the parent, as this is left to the caller.
     ///
-    /// # See also
+    /// # Parameters
     ///
-    /// * [`add_child_to()`](Index::add_child_to())
+    /// * `id` - The [`Id`] of the entity to be removed.
     ///
-    pub(crate) fn remove_child_from(
-        parent_id: Id,
-        collection: &str,
-        child_id: Id,
-    ) -> Result<(), StorageError> {
-        let mut parent_index =
-            Self::get_index(parent_id)?.ok_or(StorageError::IndexNotFound(parent_id))?;
+    /// # Errors
+    ///
+    /// If there's an issue retrieving the index information, an error will be
+    /// returned.
+    ///
+    fn remove_subtree(id: Id) -> Result<(), StorageError> {
+        if let Some(index) = Self::get_index(id)? {
+            for child in index.children.values().flatten() {
+                Self::remove_subtree(child.id())?;
+            }
 
-        if let Some(children) = parent_index.children.get_mut(collection) {
-            children.retain(|child| child.id() != child_id);
+            if let Some(path) = index.path.as_ref() {
+                Self::unregister_path(id, path)?;
+            }
         }
 
-        Self::save_index(&parent_index)?;
-        parent_index.full_hash = Self::calculate_full_merkle_hash_for(parent_id, false)?;
-        Self::save_index(&parent_index)?;
-
-        Self::remove_index(child_id);
+        Self::remove_index(id);
+        _ = S::storage_remove(Key::Entry(id));
 
-        Self::recalculate_ancestor_hashes_for(parent_id)?;
         Ok(())
     }
 
-    /// Removes the index information for an entity.
+    /// Saves the index information for an entity.
     ///
     /// # Parameters
     ///
     /// * `index` - The [`EntityIndex`] to be saved.
     ///
-    fn remove_index(id: Id) {
-        _ = S::storage_remove(Key::Index(id));
+    /// # Errors
+    ///
+    /// If there's an issue with serialisation, an error will be returned.
+    ///
+    fn save_index(index: &EntityIndex) -> Result<(), StorageError> {
+        _ = S::storage_write(
+            Key::Index(index.id),
+            &to_vec(index).map_err(StorageError::SerializationError)?,
+        );
+        Ok(())
     }
 
-    /// Saves the index information for an entity.
+    /// Saves the index information for a path.
+    ///
+    /// If the path no longer has any entities at or below it, the index
+    /// information is removed instead.
     ///
     /// # Parameters
     ///
-    /// * `index` - The [`EntityIndex`] to be saved.
+    /// * `path`       - The [`Path`] the index information is for.
+    /// * `path_index` - The [`PathIndex`] to be saved.
     ///
     /// # Errors
     ///
     /// If there's an issue with serialisation, an error will be returned.
     ///
-    fn save_index(index: &EntityIndex) -> Result<(), StorageError> {
+    fn save_path_index(path: &Path, path_index: &PathIndex) -> Result<(), StorageError> {
+        if path_index.ids.is_empty() && path_index.segments.is_empty() {
+            _ = S::storage_remove(Self::path_key(path));
+            return Ok(());
+        }
+
         _ = S::storage_write(
-            Key::Index(index.id),
-            &to_vec(index).map_err(StorageError::SerializationError)?,
+            Self::path_key(path),
+            &to_vec(path_index).map_err(StorageError::SerializationError)?,
         );
         Ok(())
     }
 
+    /// Sets the path of an indexed entity.
+    ///
+    /// This records the path against the entity, and updates the path index
+    /// accordingly, so that the entity can be found by its path. If the entity
+    /// was previously recorded at a different path, it is moved.
+    ///
+    /// # Parameters
+    ///
+    /// * `id`   - The [`Id`] of the entity.
+    /// * `path` - The [`Path`] of the entity.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or saving the index information, or the
+    /// entity is not indexed, an error will be returned.
+    ///
+    /// # See also
+    ///
+    /// * [`get_ids_at_path()`](Index::get_ids_at_path())
+    /// * [`get_ids_below_path()`](Index::get_ids_below_path())
+    ///
+    pub(crate) fn set_path(id: Id, path: &Path) -> Result<(), StorageError> {
+        let mut index = Self::get_index(id)?.ok_or(StorageError::IndexNotFound(id))?;
+
+        if index.path.as_ref() == Some(path) {
+            return Ok(());
+        }
+
+        if let Some(old_path) = index.path.as_ref() {
+            Self::unregister_path(id, old_path)?;
+        }
+
+        Self::register_path(id, path)?;
+
+        index.path = Some(path.clone());
+        Self::save_index(&index)
+    }
+
+    /// Removes an entity from the path index.
+    ///
+    /// Any paths that are left with no entities at or below them are removed
+    /// as well, working upwards through the ancestors.
+    ///
+    /// # Parameters
+    ///
+    /// * `id`   - The [`Id`] of the entity to be removed.
+    /// * `path` - The [`Path`] the entity was recorded at.
+    ///
+    /// # Errors
+    ///
+    /// If there's an issue retrieving or saving the index information, an error
+    /// will be returned.
+    ///
+    fn unregister_path(id: Id, path: &Path) -> Result<(), StorageError> {
+        let Some(mut path_index) = Self::get_path_index(path)? else {
+            return Ok(());
+        };
+
+        _ = path_index.ids.remove(&id);
+        Self::save_path_index(path, &path_index)?;
+
+        let mut current = path.clone();
+        let mut is_empty = path_index.ids.is_empty() && path_index.segments.is_empty();
+
+        while is_empty {
+            let Some(parent) = current.parent() else {
+                break;
+            };
+
+            let Some(mut parent_index) = Self::get_path_index(&parent)? else {
+                break;
+            };
+
+            _ = parent_index.segments.remove(current.last());
+            Self::save_path_index(&parent, &parent_index)?;
+
+            is_empty = parent_index.ids.is_empty() && parent_index.segments.is_empty();
+            current = parent;
+        }
+
+        Ok(())
+    }
+
     /// Updates the Merkle hash for an indexed entity.
     ///
     /// This accepts the Merkle hash for the entity's "own" hash only, i.e. not
@@ -497,6 +975,8 @@ impl<S: StorageAdaptor> Index<S> {
     ///
     /// * `id`          - The [`Id`] of the entity being updated.
     /// * `merkle_hash` - The new Merkle hash for the entity.
+    /// * `metadata`    - The new metadata for the entity, if its data has been
+    ///                   updated.
     ///
     /// # Errors
     ///
@@ -506,14 +986,16 @@ impl<S: StorageAdaptor> Index<S> {
     pub(crate) fn update_hash_for(
         id: Id,
         merkle_hash: [u8; 32],
-        updated_at: Option<UpdatedAt>,
+        metadata: Option<Metadata>,
     ) -> Result<[u8; 32], StorageError> {
         let mut index = Self::get_index(id)?.ok_or(StorageError::IndexNotFound(id))?;
         index.own_hash = merkle_hash;
         Self::save_index(&index)?;
         index.full_hash = Self::calculate_full_merkle_hash_for(id, false)?;
-        if let Some(updated_at) = updated_at {
-            index.metadata.updated_at = updated_at;
+        if let Some(updated) = metadata {
+            index.metadata.updated_at = updated.updated_at;
+            index.metadata.merge_strategy = updated.merge_strategy;
+            index.metadata.deleted_at = None;
         }
         Self::save_index(&index)?;
         <Index<S>>::recalculate_ancestor_hashes_for(id)?;
@@ -184,17 +184,56 @@
 //!      the effect on the wider system of implementing that constraint, but
 //!      also in the need for garbage collection to remove the deleted entities.
 //!      This would likely be better conducted the next time the parent entity
-//!      is updated, but there are a number of factors to consider here.
+//!      is updated, but there are a number of factors to consider here. This
+//!      is the approach that has been implemented — see the section on
+//!      tombstones below.
 //!   4. Another way to potentially handle situations of this nature is to
 //!      combine multiple granular updates into an atomic group operation that
 //!      ensures that all updates are applied together. However, this remains to
 //!      be explored, as it may not fit with the wider system design.
 //!
-//! Due to the potential complexity, this edge case is not currently mitigated,
-//! but will be the focus of future development.
+//! The third option is used to mitigate the deletion side of this edge case.
+//! The case of a child being added locally whilst the parent is updated
+//! remotely remains reliant on the original add events being propagated.
 //!
-//! TODO: Assess the best approach for handling this edge case in a way that
-//! TODO: fits with the wider system design, and add extended tests for it.
+//! TODO: Assess whether the remaining part of this edge case needs handling,
+//! TODO: and add extended tests for it.
+//!
+//! ## Tombstones
+//!
+//! When an entity is deleted, its data is removed, but its index entry is kept
+//! as a tombstone, with the time of deletion recorded in its [`Metadata`]. The
+//! parent's list of children also retains the tombstone, so that comparisons
+//! can tell the difference between a child that has been deleted and one that
+//! has never been seen.
+//!
+//! Deletions compete with updates on a last-write-wins basis, with deletions
+//! winning ties. An incoming [`Add`](Action::Add) or [`Update`](Action::Update)
+//! that is older than a local tombstone is therefore ignored, and an incoming
+//! [`Delete`](Action::Delete) that is older than the local data is likewise
+//! ignored. An update that is newer than the deletion brings the entity back.
+//!
+//! Tombstones do not contribute to Merkle hashes, and are not returned when
+//! iterating over the children of a [`Collection`]. They are purged once they
+//! are older than the retention period, by a pass of
+//! [`purge_tombstones()`](Interface::purge_tombstones()), which the node runs
+//! periodically with the retention period it is configured with. Once purged,
+//! a node that was offline for longer than the retention period can bring the
+//! deleted entity back during a comparison, so the retention period bounds how
+//! long a node can be offline and still catch up safely.
+//!
+//! ## Merging
+//!
+//! Types that implement [`Mergeable`] can opt out of last-write-wins, so that
+//! when an incoming [`Update`](Action::Update) is older than the local data,
+//! the two versions are merged rather than the incoming one being discarded.
+//! The same applies when a comparison finds the local data to be newer. The
+//! merged result is saved as a new version, and is propagated to other nodes
+//! in the usual way, so that all nodes converge on it. Merge functions are
+//! looked up by the [`MergeStrategy`] recorded in the entity's [`Metadata`],
+//! and are registered through [`Data::register_merges()`] whenever a type is
+//! loaded or saved, so need no explicit setup when the type derives
+//! [`AtomicUnit`](crate::entities::AtomicUnit).
 //!
 //! The outcome of a comparison is that the calling code receives a list of
 //! actions, which can be [`Add`](Action::Add), [`Delete`](Action::Delete),
@@ -213,9 +252,11 @@
 #[path = "tests/interface.rs"]
 mod tests;
 
+use core::any::type_name;
+use core::cell::RefCell;
 use core::fmt::Debug;
 use core::marker::PhantomData;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::Error as IoError;
 
 use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
@@ -225,8 +266,9 @@ use serde::Serialize;
 use sha2::{Digest, Sha256};
 use thiserror::Error as ThisError;
 
-use crate::address::{Id, Path};
-use crate::entities::{ChildInfo, Collection, Data, Metadata};
+use crate::address::{Id, Path, PathError};
+use crate::entities::{ChildInfo, Collection, Data, MergeStrategy, Mergeable, Metadata};
+use crate::env::{self, time_now};
 use crate::index::Index;
 use crate::store::{Key, MainStorage, StorageAdaptor};
 use crate::sync;
@@ -234,6 +276,22 @@ use crate::sync;
 /// Convenient type alias for the main storage system.
 pub type MainInterface = Interface<MainStorage>;
 
+/// A function that merges an incoming version of an entity's serialised data
+/// into the local version, returning the merged data.
+type MergeFn = fn(&[u8], &[u8]) -> Result<Vec<u8>, StorageError>;
+
+thread_local! {
+    static MERGE_FUNCTIONS: RefCell<BTreeMap<MergeStrategy, MergeFn>> = const { RefCell::new(BTreeMap::new()) };
+    static MERGES_REGISTERED: RefCell<BTreeSet<&'static str>> = const { RefCell::new(BTreeSet::new()) };
+}
+
+/// The default period for which tombstones are retained, in nanoseconds.
+///
+/// This is seven days, which bounds how long a node can be offline and still
+/// have deletions correctly applied when it catches up. Nodes may be
+/// configured with a different period.
+pub const DEFAULT_TOMBSTONE_RETENTION: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
 /// Actions to be taken during synchronisation.
 ///
 /// The following variants represent the possible actions arising from either a
@@ -279,6 +337,9 @@ pub enum Action {
 
         /// The metadata of the entity.
         metadata: Metadata,
+
+        /// The path of the entity in the hierarchy, if known.
+        path: Option<Path>,
     },
 
     /// Compare the entity with the given ID and type. Note that this results in
@@ -298,6 +359,9 @@ pub enum Action {
 
         /// Details of the ancestors of the entity.
         ancestors: Vec<ChildInfo>,
+
+        /// The metadata of the entity, including the time of deletion.
+        metadata: Metadata,
     },
 
     /// Update the entity with the given ID and type to have the supplied data.
@@ -313,6 +377,9 @@ pub enum Action {
 
         /// The metadata of the entity.
         metadata: Metadata,
+
+        /// The path of the entity in the hierarchy, if known.
+        path: Option<Path>,
     },
 }
 
@@ -339,6 +406,9 @@ pub struct ComparisonData {
 
     /// The metadata of the entity.
     metadata: Metadata,
+
+    /// The path of the entity in the hierarchy, if known.
+    path: Option<Path>,
 }
 
 /// The primary interface for the storage system.
@@ -370,6 +440,9 @@ impl<S: StorageAdaptor> Interface<S> {
             return Ok(false);
         }
 
+        D::register_merges();
+        child.element_mut().metadata.merge_strategy = D::merge_strategy();
+
         let data = to_vec(child).map_err(|e| StorageError::SerializationError(e.into()))?;
 
         let own_hash = Sha256::digest(&data).into();
@@ -380,7 +453,13 @@ impl<S: StorageAdaptor> Interface<S> {
             ChildInfo::new(child.id(), own_hash, child.element().metadata),
         )?;
 
-        let Some(hash) = Self::save_raw(child.id(), data, child.element().metadata)? else {
+        let Some(hash) = Self::save_raw(
+            child.id(),
+            data,
+            &child.element().path(),
+            child.element().metadata,
+        )?
+        else {
             return Ok(false);
         };
 
@@ -426,13 +505,25 @@ impl<S: StorageAdaptor> Interface<S> {
                 // todo! we only need parent_id
                 ancestors,
                 metadata,
+                path,
             }
             | Action::Update {
                 id,
                 data,
                 ancestors,
                 metadata,
+                path,
             } => {
+                env::hlc_observe(metadata.updated_at);
+
+                if <Index<S>>::get_metadata(id)?
+                    .and_then(|local| local.deleted_at)
+                    .is_some_and(|deleted_at| deleted_at >= metadata.updated_at())
+                {
+                    // The entity was deleted after this version was written
+                    return Ok(());
+                }
+
                 if let Some(parent) = ancestors.first() {
                     let own_hash = Sha256::digest(&data).into();
 
@@ -443,8 +534,10 @@ impl<S: StorageAdaptor> Interface<S> {
                     )?;
                 }
 
-                if Self::save_internal(id, &data, metadata)?.is_none() {
-                    // we didn't save anything, so we skip updating the ancestors
+                if Self::save_internal(id, &data, path.as_ref(), metadata)?.is_none() {
+                    // we didn't save anything, so we skip updating the ancestors,
+                    // but the local version may be able to absorb the incoming one
+                    let _merged = Self::merge_into_local(id, &data)?;
                     return Ok(());
                 }
 
@@ -454,9 +547,30 @@ impl<S: StorageAdaptor> Interface<S> {
                 return Err(StorageError::ActionNotAllowed("Compare".to_owned()))
             }
             Action::Delete {
-                id, ancestors: _, ..
+                id,
+                ancestors,
+                metadata,
             } => {
-                // todo! remove_child_from here
+                let Some(deleted_at) = metadata.deleted_at else {
+                    return Err(StorageError::InvalidDataFound(id));
+                };
+
+                env::hlc_observe(deleted_at);
+
+                if let Some(local) = <Index<S>>::get_metadata(id)? {
+                    if local.is_deleted() || local.updated_at() > deleted_at {
+                        // Already deleted, or updated since the deletion
+                        return Ok(());
+                    }
+                }
+
+                <Index<S>>::mark_deleted(
+                    ancestors.first().map(ChildInfo::id),
+                    "no collection, remove this nonsense",
+                    id,
+                    metadata,
+                )?;
+
                 let _ignored = S::storage_remove(Key::Entry(id));
             }
         };
@@ -589,6 +703,61 @@ impl<S: StorageAdaptor> Interface<S> {
 
         let local_metadata = <Index<S>>::get_metadata(id)?;
 
+        // Deletions are resolved on a last-write-wins basis, with deletions
+        // winning ties. A deleted entity has no data or children to compare.
+        if let Some(foreign_deleted_at) = foreign_index_data.metadata.deleted_at {
+            match local_metadata {
+                Some(local) if local.is_deleted() => {}
+                Some(local) if local.updated_at() > foreign_deleted_at => {
+                    if let Some(local_entity) = Self::find_by_id_raw(id) {
+                        actions.1.push(Action::Add {
+                            id,
+                            data: local_entity,
+                            ancestors: <Index<S>>::get_ancestors_of(id)?,
+                            metadata: local,
+                            path: <Index<S>>::get_path(id)?,
+                        });
+                    }
+                }
+                _ => {
+                    actions.0.push(Action::Delete {
+                        id,
+                        ancestors: foreign_index_data.ancestors,
+                        metadata: foreign_index_data.metadata,
+                    });
+                }
+            }
+
+            return Ok(actions);
+        }
+
+        if let Some(local) = local_metadata.filter(Metadata::is_deleted) {
+            match foreign_entity_data {
+                Some(foreign_entity)
+                    if local.deleted_at.is_some_and(|deleted_at| {
+                        foreign_index_data.metadata.updated_at() > deleted_at
+                    }) =>
+                {
+                    actions.0.push(Action::Add {
+                        id,
+                        data: foreign_entity,
+                        ancestors: foreign_index_data.ancestors,
+                        metadata: foreign_index_data.metadata,
+                        path: foreign_index_data.path,
+                    });
+                }
+                _ => {
+                    actions.1.push(Action::Delete {
+                        id,
+                        ancestors: <Index<S>>::get_ancestors_of(id)?,
+                        metadata: local,
+                    });
+                }
+            }
+
+            return Ok(actions);
+        }
+
         let Some(local_entity) = Self::find_by_id_raw(id) else {
             if let Some(foreign_entity) = foreign_entity_data {
                 // Local entity doesn't exist, so we need to add it
@@ -597,6 +766,7 @@ impl<S: StorageAdaptor> Interface<S> {
                     data: foreign_entity,
                     ancestors: foreign_index_data.ancestors,
                     metadata: foreign_index_data.metadata,
+                    path: foreign_index_data.path,
                 });
             }
 
@@ -624,14 +794,30 @@ impl<S: StorageAdaptor> Interface<S> {
                         data: foreign_entity_data,
                         ancestors: foreign_index_data.ancestors,
                         metadata: foreign_index_data.metadata,
+                        path: foreign_index_data.path,
                     });
                 }
-                _ => {
+                older_foreign_data => {
+                    // The local version is newer, but may still need to absorb
+                    // the foreign one if the two can be merged
+                    if let Some(data) = older_foreign_data
+                        .filter(|_| local_metadata.merge_strategy != MergeStrategy::LastWriteWins)
+                    {
+                        actions.0.push(Action::Update {
+                            id,
+                            data,
+                            ancestors: foreign_index_data.ancestors.clone(),
+                            metadata: foreign_index_data.metadata,
+                            path: foreign_index_data.path.clone(),
+                        });
+                    }
+
                     actions.1.push(Action::Update {
                         id,
                         data: local_entity,
                         ancestors: <Index<S>>::get_ancestors_of(id)?,
                         metadata: local_metadata,
+                        path: <Index<S>>::get_path(id)?,
                     });
                 }
             }
@@ -645,7 +831,7 @@ impl<S: StorageAdaptor> Interface<S> {
         let local_collections = local_collection_names
             .into_iter()
             .map(|name| {
-                let children = <Index<S>>::get_children_of(id, &name)?;
+                let children = <Index<S>>::get_children_with_tombstones_of(id, &name)?;
                 Ok((name, children))
             })
             .collect::<Result<BTreeMap<_, _>, StorageError>>()?;
@@ -655,18 +841,32 @@ impl<S: StorageAdaptor> Interface<S> {
             if let Some(foreign_children) = foreign_index_data.children.get(local_coll_name) {
                 let local_child_map: IndexMap<_, _> = local_children
                     .iter()
-                    .map(|child| (child.id(), child.merkle_hash()))
+                    .map(|child| (child.id(), child))
                     .collect();
                 let foreign_child_map: IndexMap<_, _> = foreign_children
                     .iter()
-                    .map(|child| (child.id(), child.merkle_hash()))
+                    .map(|child| (child.id(), child))
                     .collect();
 
-                for (child_id, local_hash) in &local_child_map {
+                for (child_id, local_info) in &local_child_map {
                     match foreign_child_map.get(child_id) {
-                        Some(foreign_hash) if local_hash != foreign_hash => {
-                            actions.0.push(Action::Compare { id: *child_id });
-                            actions.1.push(Action::Compare { id: *child_id });
+                        Some(foreign_info) => {
+                            match (local_info.is_deleted(), foreign_info.is_deleted()) {
+                                // Deleted on both sides, no action needed
+                                (true, true) => {}
+                                // Hashes match, no action needed
+                                (false, false)
+                                    if local_info.merkle_hash() == foreign_info.merkle_hash() => {}
+                                // Either the data differs, or one side has deleted the child, in
+                                // which case the comparison will determine which side wins.
+                                _ => {
+                                    actions.0.push(Action::Compare { id: *child_id });
+                                    actions.1.push(Action::Compare { id: *child_id });
+                                }
+                            }
+                        }
+                        None if local_info.is_deleted() => {
+                            actions.1.push(Self::delete_action_for(*child_id)?);
                         }
                         None => {
                             if let Some(local_child) = Self::find_by_id_raw(*child_id) {
@@ -676,13 +876,12 @@ impl<S: StorageAdaptor> Interface<S> {
                                 actions.1.push(Action::Add {
                                     id: *child_id,
                                     data: local_child,
-                                    ancestors: <Index<S>>::get_ancestors_of(id)?,
+                                    ancestors: <Index<S>>::get_ancestors_of(*child_id)?,
                                     metadata,
+                                    path: <Index<S>>::get_path(*child_id)?,
                                 });
                             }
                         }
-                        // Hashes match, no action needed
-                        _ => {}
                     }
                 }
 
@@ -697,7 +896,9 @@ impl<S: StorageAdaptor> Interface<S> {
             } else {
                 // The entire collection is missing from the foreign entity
                 for child in local_children {
-                    if let Some(local_child) = Self::find_by_id_raw(child.id()) {
+                    if child.is_deleted() {
+                        actions.1.push(Self::delete_action_for(child.id())?);
+                    } else if let Some(local_child) = Self::find_by_id_raw(child.id()) {
                         let metadata = <Index<S>>::get_metadata(child.id())?
                             .ok_or(StorageError::IndexNotFound(child.id()))?;
 
@@ -706,6 +907,7 @@ impl<S: StorageAdaptor> Interface<S> {
                             data: local_child,
                             ancestors: <Index<S>>::get_ancestors_of(child.id())?,
                             metadata,
+                            path: <Index<S>>::get_path(child.id())?,
                         });
                     }
                 }
@@ -725,6 +927,21 @@ impl<S: StorageAdaptor> Interface<S> {
         Ok(actions)
     }
 
+    /// Generates a [`Delete`](Action::Delete) action for a local tombstone.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, or the
+    /// index entry cannot be found, an error will be returned.
+    ///
+    fn delete_action_for(id: Id) -> Result<Action, StorageError> {
+        Ok(Action::Delete {
+            id,
+            ancestors: <Index<S>>::get_ancestors_of(id)?,
+            metadata: <Index<S>>::get_metadata(id)?.ok_or(StorageError::IndexNotFound(id))?,
+        })
+    }
+
     /// Compares a foreign entity with a local one, and applies the resulting
     /// actions to bring the two entities into sync.
     ///
@@ -771,6 +988,8 @@ impl<S: StorageAdaptor> Interface<S> {
     /// will be returned.
     ///
     pub fn find_by_id<D: Data>(id: Id) -> Result<Option<D>, StorageError> {
+        D::register_merges();
+
         let value = S::storage_read(Key::Entry(id));
 
         let Some(slice) = value else {
@@ -817,6 +1036,16 @@ impl<S: StorageAdaptor> Interface<S> {
     /// multiple items if there are multiple [`Element`](crate::entities::Element)s
     /// at the same path.
     ///
+    /// The lookup is served by the path index, and so does not need to walk the
+    /// hierarchy. The order of the results is deterministic, but should not be
+    /// relied upon to have any particular meaning. Deleted entities are not
+    /// included.
+    ///
+    /// Entities last saved before paths were indexed are not found until they
+    /// are next saved, which is when their path is indexed. Their path can't
+    /// be backfilled as they are loaded, as that would make loading a write,
+    /// which read-only executions are not permitted.
+    ///
     /// # Parameters
     ///
     /// * `path` - The path to the [`Element`](crate::entities::Element)s to
@@ -825,10 +1054,64 @@ impl<S: StorageAdaptor> Interface<S> {
     /// # Errors
     ///
     /// If an error occurs when interacting with the storage system, an error
+    /// will be returned. This includes the case where any of the entities found
+    /// cannot be deserialised into the specified type.
+    ///
+    pub fn find_by_path<D: Data>(path: &Path) -> Result<Vec<D>, StorageError> {
+        Self::find_by_ids(<Index<S>>::get_ids_at_path(path)?)
+    }
+
+    /// Finds all [`Element`](crate::entities::Element)s below a path in the
+    /// hierarchy.
+    ///
+    /// This will retrieve all [`Element`](crate::entities::Element)s whose path
+    /// is a descendant of the specified path, at any depth. Those at the path
+    /// itself are not included, and can be obtained using
+    /// [`find_by_path()`](Interface::find_by_path()).
+    ///
+    /// The results are ordered depth-first, with the path segments at each
+    /// level visited in lexicographical order. So, for example, everything
+    /// under `::users::alice::notes` will be returned before anything under
+    /// `::users::alice::tasks`. Deleted entities are not included, and nor are
+    /// those last saved before paths were indexed, as with
+    /// [`find_by_path()`](Interface::find_by_path()).
+    ///
+    /// Note that all of the [`Element`](crate::entities::Element)s found must be
+    /// of the specified type, so this is best used on parts of the hierarchy
+    /// that hold one kind of data.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The path below which to find [`Element`](crate::entities::Element)s.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned. This includes the case where any of the entities found
+    /// cannot be deserialised into the specified type.
+    ///
+    pub fn find_descendants_by_path<D: Data>(path: &Path) -> Result<Vec<D>, StorageError> {
+        Self::find_by_ids(<Index<S>>::get_ids_below_path(path)?)
+    }
+
+    /// Finds the [`Element`](crate::entities::Element)s for a list of IDs,
+    /// preserving their order.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
     /// will be returned.
     ///
-    pub fn find_by_path<D: Data>(_path: &Path) -> Result<Vec<D>, StorageError> {
-        unimplemented!()
+    fn find_by_ids<D: Data>(ids: Vec<Id>) -> Result<Vec<D>, StorageError> {
+        let mut entities = Vec::with_capacity(ids.len());
+
+        for id in ids {
+            if let Some(entity) = Self::find_by_id(id)? {
+                entities.push(entity);
+            }
+        }
+
+        Ok(entities)
     }
 
     /// Finds the children of an [`Element`](crate::entities::Element) by its
@@ -894,7 +1177,7 @@ impl<S: StorageAdaptor> Interface<S> {
         let children = collection_names
             .into_iter()
             .map(|collection_name| {
-                <Index<S>>::get_children_of(id, &collection_name)
+                <Index<S>>::get_children_with_tombstones_of(id, &collection_name)
                     .map(|children| (collection_name.clone(), children))
             })
             .collect::<Result<BTreeMap<_, _>, _>>()?;
@@ -906,6 +1189,7 @@ impl<S: StorageAdaptor> Interface<S> {
             ancestors,
             children,
             metadata,
+            path: <Index<S>>::get_path(id)?,
         })
     }
 
@@ -949,8 +1233,36 @@ impl<S: StorageAdaptor> Interface<S> {
             .map_or_else(|| Ok(None), |parent_id| Self::find_by_id(parent_id))
     }
 
+    /// Purges tombstones that are older than the retention period.
+    ///
+    /// This walks the whole hierarchy from the root, and permanently removes
+    /// any deleted entities whose deletion happened longer ago than the
+    /// specified retention period. Tombstones are only ever purged by this
+    /// pass, so that the retention period is always the one configured.
+    ///
+    /// As tombstones do not contribute to Merkle hashes, purging them does not
+    /// affect the hashes, and generates no actions.
+    ///
+    /// # Parameters
+    ///
+    /// * `retention` - The period for which to retain tombstones, in
+    ///                 nanoseconds.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when interacting with the storage system, an error
+    /// will be returned.
+    ///
+    pub fn purge_tombstones(retention: u64) -> Result<usize, StorageError> {
+        <Index<S>>::purge_tombstones(Id::root(), time_now().saturating_sub(retention))
+    }
+
     /// Removes a child from a collection.
     ///
+    /// The child's data is removed, but a tombstone is retained in the index so
+    /// that the deletion can be recognised during synchronisation. See the
+    /// [module-level documentation](crate::interface) for more information.
+    ///
     /// # Parameters
     ///
     /// * `parent_id`  - The ID of the parent entity that owns the
@@ -975,20 +1287,29 @@ impl<S: StorageAdaptor> Interface<S> {
             return Ok(false);
         }
 
-        <Index<S>>::remove_child_from(parent_id, collection.name(), child_id)?;
+        let mut metadata =
+            <Index<S>>::get_metadata(child_id)?.ok_or(StorageError::IndexNotFound(child_id))?;
+        env::hlc_observe(metadata.updated_at);
+        metadata.deleted_at = Some(env::hlc_now());
+
+        <Index<S>>::mark_deleted(Some(parent_id), collection.name(), child_id, metadata)?;
 
         let (parent_full_hash, _) =
             <Index<S>>::get_hashes_for(parent_id)?.ok_or(StorageError::IndexNotFound(parent_id))?;
         let mut ancestors = <Index<S>>::get_ancestors_of(parent_id)?;
-        let metadata =
+        let parent_metadata =
             <Index<S>>::get_metadata(parent_id)?.ok_or(StorageError::IndexNotFound(parent_id))?;
-        ancestors.insert(0, ChildInfo::new(parent_id, parent_full_hash, metadata));
+        ancestors.insert(
+            0,
+            ChildInfo::new(parent_id, parent_full_hash, parent_metadata),
+        );
 
         _ = S::storage_remove(Key::Entry(child_id));
 
         sync::push_action(Action::Delete {
             id: child_id,
             ancestors,
+            metadata,
         });
 
         Ok(true)
@@ -1033,7 +1354,7 @@ impl<S: StorageAdaptor> Interface<S> {
 
             let data = to_vec(&root).map_err(|e| StorageError::SerializationError(e.into()))?;
 
-            Self::save_raw(id, data, root.element().metadata)?
+            Self::save_raw(id, data, &root.element().path(), root.element().metadata)?
         } else {
             <Index<S>>::get_hashes_for(id)?.map(|(full_hash, _)| full_hash)
         };
@@ -1084,6 +1405,10 @@ impl<S: StorageAdaptor> Interface<S> {
     /// associate the child with the parent. Thereafter, [`save()`](Interface::save())
     /// can be called to save updates to the child entity.
     ///
+    /// An entity that has been removed is left as a tombstone, and will not be
+    /// saved again. To restore it, it must be re-added using
+    /// [`add_child_to()`](Interface::add_child_to()).
+    ///
     /// # Merkle hash
     ///
     /// The Merkle hash of the [`Element`](crate::entities::Element) is
@@ -1112,9 +1437,22 @@ impl<S: StorageAdaptor> Interface<S> {
             return Ok(false);
         }
 
+        if <Index<S>>::get_metadata(entity.id())?.is_some_and(|metadata| metadata.is_deleted()) {
+            return Ok(false);
+        }
+
+        D::register_merges();
+        entity.element_mut().metadata.merge_strategy = D::merge_strategy();
+
         let data = to_vec(entity).map_err(|e| StorageError::SerializationError(e.into()))?;
 
-        let Some(hash) = Self::save_raw(entity.id(), data, entity.element().metadata)? else {
+        let Some(hash) = Self::save_raw(
+            entity.id(),
+            data,
+            &entity.element().path(),
+            entity.element().metadata,
+        )?
+        else {
             return Ok(false);
         };
 
@@ -1134,31 +1472,43 @@ impl<S: StorageAdaptor> Interface<S> {
     fn save_internal(
         id: Id,
         data: &[u8],
+        path: Option<&Path>,
         metadata: Metadata,
     ) -> Result<Option<(bool, [u8; 32])>, StorageError> {
         let last_metadata = <Index<S>>::get_metadata(id)?;
 
         if let Some(last_metadata) = &last_metadata {
-            if last_metadata.updated_at > metadata.updated_at {
+            if last_metadata.updated_at > metadata.updated_at
+                || last_metadata
+                    .deleted_at
+                    .is_some_and(|deleted_at| deleted_at >= metadata.updated_at())
+            {
                 return Ok(None);
             }
         } else if id.is_root() {
             <Index<S>>::add_root(ChildInfo::new(id, [0_u8; 32], metadata))?;
         }
 
-        let own_hash = Sha256::digest(data).into();
+        if let Some(entity_path) = path {
+            <Index<S>>::set_path(id, entity_path)?;
+        }
 
-        let full_hash = <Index<S>>::update_hash_for(id, own_hash, Some(metadata.updated_at))?;
+        let own_hash = Sha256::digest(data).into();
 
-        _ = S::storage_write(Key::Entry(id), data);
+        let full_hash = <Index<S>>::update_hash_for(id, own_hash, Some(metadata))?;
 
-        let is_new = metadata.created_at == *metadata.updated_at;
+        // The entity is new if there was no data for it, even if it has
+        // already been indexed, as happens when it is added to a collection
+        let is_new = !S::storage_write(Key::Entry(id), data);
 
         Ok(Some((is_new, full_hash)))
     }
 
     /// Saves raw data to the storage system.
     ///
+    /// The path is recorded in the index, so that the entity can be found using
+    /// [`find_by_path()`](Interface::find_by_path()).
+    ///
     /// # Errors
     ///
     /// If an error occurs when serialising data or interacting with the storage
@@ -1167,13 +1517,15 @@ impl<S: StorageAdaptor> Interface<S> {
     pub fn save_raw(
         id: Id,
         data: Vec<u8>,
+        path: &Path,
         metadata: Metadata,
     ) -> Result<Option<[u8; 32]>, StorageError> {
         if !id.is_root() && <Index<S>>::get_parent_id(id)?.is_none() {
             return Err(StorageError::CannotCreateOrphan(id));
         }
 
-        let Some((is_new, full_hash)) = Self::save_internal(id, &data, metadata)? else {
+        let Some((is_new, full_hash)) = Self::save_internal(id, &data, Some(path), metadata)?
+        else {
             return Ok(None);
         };
 
@@ -1185,6 +1537,7 @@ impl<S: StorageAdaptor> Interface<S> {
                 data,
                 ancestors,
                 metadata,
+                path: Some(path.clone()),
             }
         } else {
             Action::Update {
@@ -1192,6 +1545,7 @@ impl<S: StorageAdaptor> Interface<S> {
                 data,
                 ancestors,
                 metadata,
+                path: Some(path.clone()),
             }
         };
 
@@ -1200,6 +1554,70 @@ impl<S: StorageAdaptor> Interface<S> {
         Ok(Some(full_hash))
     }
 
+    /// Merges an incoming version of an entity's data into the local version.
+    ///
+    /// This is used when the local version is newer than the incoming one, and
+    /// so would otherwise win outright. If the entity's [`MergeStrategy`] has a
+    /// registered merge function, the two versions are merged and the result
+    /// saved as a new version, which will be propagated to other nodes.
+    ///
+    /// Returns whether a merged version was saved. Nothing is saved if there
+    /// is no merge function, or the merge makes no difference to the local
+    /// data.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs when merging the data or interacting with the storage
+    /// system, an error will be returned.
+    ///
+    fn merge_into_local(id: Id, incoming: &[u8]) -> Result<bool, StorageError> {
+        let Some(mut metadata) = <Index<S>>::get_metadata(id)? else {
+            return Ok(false);
+        };
+
+        if metadata.is_deleted() {
+            return Ok(false);
+        }
+
+        let Some(merge) = MERGE_FUNCTIONS
+            .with_borrow(|functions| functions.get(&metadata.merge_strategy).copied())
+        else {
+            return Ok(false);
+        };
+
+        let Some(local) = S::storage_read(Key::Entry(id)) else {
+            return Ok(false);
+        };
+
+        let merged = merge(&local, incoming)?;
+
+        if merged == local {
+            return Ok(false);
+        }
+
+        let path = <Index<S>>::get_path(id)?.ok_or(StorageError::NotFound(id))?;
+
+        env::hlc_observe(metadata.updated_at);
+        metadata.updated_at = env::hlc_now();
+
+        Ok(Self::save_raw(id, merged, &path, metadata)?.is_some())
+    }
+
+    /// Registers the merge function for a [`Mergeable`] type.
+    ///
+    /// Entities of the type, which record its [`MergeStrategy`], will then have
+    /// conflicting versions merged when actions are applied, rather than
+    /// resolved on a "last write wins" basis. Registration lasts for the
+    /// current thread. This is called by [`Data::register_merges()`], and so
+    /// only needs calling directly for types that implement [`Data`] by hand.
+    ///
+    pub fn register_merge<D: Mergeable>() {
+        if let MergeStrategy::Custom(_) = D::merge_strategy() {
+            let _ignored = MERGE_FUNCTIONS
+                .with_borrow_mut(|functions| functions.insert(D::merge_strategy(), merge_raw::<D>));
+        }
+    }
+
     /// Validates the stored state.
     ///
     /// This will validate the stored state of the storage system, i.e. the data
@@ -1217,6 +1635,39 @@ impl<S: StorageAdaptor> Interface<S> {
     }
 }
 
+/// Marks the merges of a [`Data`] type as registered.
+///
+/// Returns `true` the first time it is called for the type on the current
+/// thread, and `false` thereafter. This allows [`Data::register_merges()`] to
+/// descend through the types of an entity's children without repeating work,
+/// or looping when a type can be its own descendant. The type name is used
+/// only within the running process, and is never stored.
+///
+#[doc(hidden)]
+#[must_use]
+pub fn mark_merges_registered<D: Data>() -> bool {
+    MERGES_REGISTERED.with_borrow_mut(|registered| registered.insert(type_name::<D>()))
+}
+
+/// Merges serialised versions of a [`Mergeable`] type.
+///
+/// # Errors
+///
+/// If either version cannot be deserialised, or the result serialised, an
+/// error will be returned.
+///
+fn merge_raw<D: Mergeable>(
+    local_data: &[u8],
+    incoming_data: &[u8],
+) -> Result<Vec<u8>, StorageError> {
+    let mut local = from_slice::<D>(local_data).map_err(StorageError::DeserializationError)?;
+    let incoming = from_slice::<D>(incoming_data).map_err(StorageError::DeserializationError)?;
+
+    local.merge(incoming);
+
+    to_vec(&local).map_err(StorageError::SerializationError)
+}
+
 /// Errors that can occur when working with the storage system.
 #[derive(Debug, ThisError)]
 #[non_exhaustive]
@@ -1248,6 +1699,10 @@ pub enum StorageError {
     #[error("Invalid data was found for ID: {0}")]
     InvalidDataFound(Id),
 
+    /// The path is invalid.
+    #[error("Invalid path: {0}")]
+    InvalidPath(#[from] PathError),
+
     /// An index entry already exists for the specified entity. This would
     /// indicate a bug in the system.
     #[error("Index already exists for ID: {0}")]
@@ -1301,6 +1756,7 @@ impl Serialize for StorageError {
             | Self::InvalidDataFound(id)
             | Self::UnexpectedId(id)
             | Self::NotFound(id) => serializer.serialize_str(&id.to_string()),
+            Self::InvalidPath(ref err) => serializer.serialize_str(&err.to_string()),
             Self::StoreError(ref err) => serializer.serialize_str(&err.to_string()),
             Self::UnknownType(err) => serializer.serialize_u8(err),
         }
@@ -14,6 +14,13 @@ pub enum Key {
 
     /// An entry key.
     Entry(Id),
+
+    /// A path index key. The [`Id`] is derived from the path itself.
+    Path(Id),
+
+    /// A key order key, for collections which keep their entries in order.
+    /// The [`Id`] is that of the collection, or of a page of its key order.
+    Order(Id),
 }
 
 impl Key {
@@ -30,6 +37,14 @@ impl Key {
                 bytes[0] = 1;
                 bytes[1..33].copy_from_slice(id.as_bytes());
             }
+            Self::Path(id) => {
+                bytes[0] = 2;
+                bytes[1..33].copy_from_slice(id.as_bytes());
+            }
+            Self::Order(id) => {
+                bytes[0] = 3;
+                bytes[1..33].copy_from_slice(id.as_bytes());
+            }
         }
         Sha256::digest(bytes).into()
     }
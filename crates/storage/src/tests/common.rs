@@ -1,9 +1,12 @@
 use std::collections::BTreeMap;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use calimero_storage_macros::{AtomicUnit, Collection};
 use velcro::btree_map;
 
-use crate::entities::{AtomicUnit, ChildInfo, Collection, Data, Element};
+// fixme! macro expects `calimero_storage` to be in deps
+use crate as calimero_storage;
+use crate::entities::{AtomicUnit, ChildInfo, Collection, Data, Element, MergeStrategy, Mergeable};
 use crate::interface::MainInterface;
 
 /// For tests against empty data structs.
@@ -136,3 +139,83 @@ impl Data for Person {
         &mut self.storage
     }
 }
+
+/// A set of tags, which merges conflicting versions. No children.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq, PartialOrd)]
+pub struct Tags {
+    pub tags: Vec<String>,
+    pub storage: Element,
+}
+
+impl Tags {
+    /// Creates a new set of tags from an existing element.
+    pub fn new_from_element(tags: &[&str], element: Element) -> Self {
+        Self {
+            tags: tags.iter().map(|&tag| tag.to_owned()).collect(),
+            storage: element,
+        }
+    }
+}
+
+impl AtomicUnit for Tags {}
+
+impl Data for Tags {
+    fn collections(&self) -> BTreeMap<String, Vec<ChildInfo>> {
+        BTreeMap::new()
+    }
+
+    fn element(&self) -> &Element {
+        &self.storage
+    }
+
+    fn element_mut(&mut self) -> &mut Element {
+        &mut self.storage
+    }
+
+    fn merge_strategy() -> MergeStrategy {
+        MergeStrategy::custom("Tags")
+    }
+
+    fn register_merges() {
+        MainInterface::register_merge::<Self>();
+    }
+}
+
+impl Mergeable for Tags {
+    fn merge(&mut self, incoming: Self) {
+        for tag in incoming.tags {
+            if !self.tags.contains(&tag) {
+                self.tags.push(tag);
+            }
+        }
+    }
+}
+
+/// A ledger of tallies, for tests against derived merge registration.
+#[derive(AtomicUnit, BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct Ledger {
+    #[collection]
+    pub tallies: Tallies,
+    #[storage]
+    pub storage: Element,
+}
+
+/// A collection of tallies.
+#[derive(Collection, Clone, Copy, Debug, Eq, PartialEq)]
+#[children(Tally)]
+pub struct Tallies;
+
+/// A tally that keeps the highest count seen, when merged.
+#[derive(AtomicUnit, BorshDeserialize, BorshSerialize, Clone, Debug)]
+#[storage(merge = "Tally::keep_highest", merge_id = "tests::Tally")]
+pub struct Tally {
+    pub count: u64,
+    #[storage]
+    pub storage: Element,
+}
+
+impl Tally {
+    fn keep_highest(&mut self, incoming: Self) {
+        self.count = self.count.max(incoming.count);
+    }
+}
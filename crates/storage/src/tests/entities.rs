@@ -1,6 +1,7 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use claims::{assert_ge, assert_le};
+use borsh::{from_slice, to_vec};
+use claims::{assert_ge, assert_gt, assert_le};
 use sha2::{Digest, Sha256};
 use velcro::btree_map;
 
@@ -181,8 +182,8 @@ mod element__constructor {
         assert_eq!(element.path, path);
         assert_ge!(element.metadata.created_at, timestamp1);
         assert_le!(element.metadata.created_at, timestamp2);
-        assert_ge!(*element.metadata.updated_at, timestamp1);
-        assert_le!(*element.metadata.updated_at, timestamp2);
+        assert_ge!(element.metadata.updated_at.physical(), timestamp1);
+        assert_le!(element.metadata.updated_at.physical(), timestamp2);
         assert!(element.is_dirty);
     }
 }
@@ -257,7 +258,7 @@ mod element__public_methods {
 
         person.element_mut().update();
         assert!(person.element().is_dirty);
-        assert_ge!(person.element().metadata.updated_at, updated_at);
+        assert_gt!(person.element().metadata.updated_at, updated_at);
     }
 
     #[test]
@@ -276,16 +277,16 @@ mod element__public_methods {
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_nanos() as u64;
-        assert_ge!(person.element().updated_at(), timestamp1);
-        assert_le!(person.element().updated_at(), timestamp2);
+        assert_ge!(person.element().updated_at().physical(), timestamp1);
+        assert_le!(person.element().updated_at().physical(), timestamp2);
 
         person.element_mut().update();
         let timestamp3 = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_nanos() as u64;
-        assert_ge!(person.element().updated_at(), timestamp2);
-        assert_le!(person.element().updated_at(), timestamp3);
+        assert_ge!(person.element().updated_at().physical(), timestamp2);
+        assert_le!(person.element().updated_at().physical(), timestamp3);
     }
 }
 
@@ -308,6 +309,147 @@ mod element__traits {
     }
 }
 
+#[cfg(test)]
+mod hybrid_timestamp__public_methods {
+    use super::*;
+
+    #[test]
+    fn next__physical_time_advanced() {
+        let timestamp = HybridTimestamp::new(100, 5, [1; 32]);
+        assert_eq!(
+            timestamp.next(200, [2; 32]),
+            HybridTimestamp::new(200, 0, [2; 32])
+        );
+    }
+
+    #[test]
+    fn next__physical_time_behind() {
+        let timestamp = HybridTimestamp::new(100, 5, [1; 32]);
+        let next = timestamp.next(50, [2; 32]);
+        assert_eq!(next, HybridTimestamp::new(100, 6, [2; 32]));
+        assert_gt!(next, timestamp);
+    }
+
+    #[test]
+    fn next__counter_exhausted() {
+        let timestamp = HybridTimestamp::new(100, u32::MAX, [1; 32]);
+        assert_eq!(
+            timestamp.next(100, [1; 32]),
+            HybridTimestamp::new(101, 0, [1; 32])
+        );
+    }
+}
+
+#[cfg(test)]
+mod hybrid_timestamp__traits {
+    use super::*;
+
+    #[test]
+    fn borsh__roundtrip() {
+        let timestamp = HybridTimestamp::new(100, 5, [1; 32]);
+        let decoded = from_slice::<HybridTimestamp>(&to_vec(&timestamp).unwrap()).unwrap();
+        assert_eq!(decoded, timestamp);
+    }
+
+    #[test]
+    fn borsh__legacy_timestamp() {
+        let decoded = from_slice::<HybridTimestamp>(&to_vec(&100_u64).unwrap()).unwrap();
+        assert_eq!(decoded, HybridTimestamp::new(100, 0, [0; 32]));
+    }
+
+    #[test]
+    fn ord() {
+        let timestamp = HybridTimestamp::new(100, 5, [1; 32]);
+        assert_gt!(HybridTimestamp::new(101, 0, [0; 32]), timestamp);
+        assert_gt!(HybridTimestamp::new(100, 6, [0; 32]), timestamp);
+        assert_gt!(HybridTimestamp::new(100, 5, [2; 32]), timestamp);
+        assert_ne!(HybridTimestamp::new(100, 5, [2; 32]), timestamp);
+    }
+}
+
+#[cfg(test)]
+mod metadata__traits {
+    use super::*;
+
+    #[test]
+    fn borsh__roundtrip() {
+        let metadata = Metadata {
+            created_at: 100,
+            updated_at: HybridTimestamp::new(200, 1, [1; 32]),
+            deleted_at: Some(HybridTimestamp::new(300, 0, [2; 32])),
+            merge_strategy: MergeStrategy::custom("Tags"),
+        };
+        let decoded = from_slice::<Metadata>(&to_vec(&metadata).unwrap()).unwrap();
+        assert_eq!(decoded, metadata);
+    }
+
+    #[test]
+    fn borsh__legacy_metadata() {
+        // The baseline encoding of the creation and update times
+        let encoded = to_vec(&(100_u64, 200_u64)).unwrap();
+        let (decoded, version) = Metadata::deserialize_versioned(&mut encoded.as_slice()).unwrap();
+        assert_eq!(version, 0);
+        assert_eq!(decoded.created_at, 100);
+        assert_eq!(decoded.updated_at, HybridTimestamp::new(200, 0, [0; 32]));
+        assert_eq!(decoded.deleted_at, None);
+        assert_eq!(decoded.merge_strategy, MergeStrategy::LastWriteWins);
+    }
+
+    #[test]
+    fn borsh__timestamps_unmarked() {
+        let metadata = Metadata {
+            created_at: 100,
+            updated_at: HybridTimestamp::new(200, 1, [1; 32]),
+            deleted_at: Some(HybridTimestamp::new(300, 0, [2; 32])),
+            merge_strategy: MergeStrategy::LastWriteWins,
+        };
+        let encoded = to_vec(&metadata).unwrap();
+
+        // The metadata marker and version, the creation time, and each
+        // timestamp without a marker of its own
+        assert_eq!(encoded.len(), 8 + 1 + 8 + 44 + (1 + 44) + 1);
+    }
+
+    #[test]
+    fn borsh__version_1_metadata() {
+        // Version 1 wrote a marker before each timestamp
+        let updated_at = HybridTimestamp::new(200, 1, [1; 32]);
+        let deleted_at = HybridTimestamp::new(300, 0, [2; 32]);
+        let encoded = to_vec(&(
+            u64::MAX,
+            1_u8,
+            100_u64,
+            updated_at,
+            Some(deleted_at),
+            MergeStrategy::custom("Tags"),
+        ))
+        .unwrap();
+        let (decoded, version) = Metadata::deserialize_versioned(&mut encoded.as_slice()).unwrap();
+        assert_eq!(version, 1);
+        assert_eq!(decoded.created_at, 100);
+        assert_eq!(decoded.updated_at, updated_at);
+        assert_eq!(decoded.deleted_at, Some(deleted_at));
+        assert_eq!(decoded.merge_strategy, MergeStrategy::custom("Tags"));
+    }
+
+    #[test]
+    fn borsh__unsupported_version() {
+        let encoded = to_vec(&(u64::MAX, 3_u8, 100_u64)).unwrap();
+        assert!(from_slice::<Metadata>(&encoded).is_err());
+    }
+
+    #[test]
+    fn borsh__legacy_child_info() {
+        let id = Id::random();
+        let encoded = to_vec(&(id, [1_u8; 32], 100_u64, 200_u64)).unwrap();
+        let decoded = from_slice::<ChildInfo>(&encoded).unwrap();
+        assert_eq!(decoded.id(), id);
+        assert_eq!(decoded.merkle_hash(), [1; 32]);
+        assert_eq!(decoded.created_at(), 100);
+        assert_eq!(decoded.updated_at(), HybridTimestamp::new(200, 0, [0; 32]));
+    }
+}
+
 #[cfg(test)]
 mod metadata__constructor {
 
@@ -1,4 +1,6 @@
 use super::*;
+use crate::entities::HybridTimestamp;
+use crate::env::{hlc_now, time_now};
 use crate::store::MainStorage;
 
 mod index__public_methods {
@@ -346,6 +348,112 @@ mod index__public_methods {
         );
     }
 
+    #[test]
+    fn get_ids_at_path() {
+        let root_id = Id::random();
+        let child1_id = Id::random();
+        let child2_id = Id::random();
+        let child3_id = Id::random();
+        let path = Path::new("::root::books").unwrap();
+
+        assert!(<Index<MainStorage>>::add_root(ChildInfo::new(
+            root_id,
+            [1_u8; 32],
+            Metadata::default()
+        ),)
+        .is_ok());
+        for (id, hash) in [(child1_id, 2_u8), (child2_id, 3), (child3_id, 4)] {
+            assert!(<Index<MainStorage>>::add_child_to(
+                root_id,
+                "Books",
+                ChildInfo::new(id, [hash; 32], Metadata::default()),
+            )
+            .is_ok());
+        }
+        assert!(<Index<MainStorage>>::set_path(child1_id, &path).is_ok());
+        assert!(<Index<MainStorage>>::set_path(child2_id, &path).is_ok());
+        assert!(<Index<MainStorage>>::set_path(
+            child3_id,
+            &Path::new("::root::books::one").unwrap()
+        )
+        .is_ok());
+
+        let mut expected = vec![child1_id, child2_id];
+        expected.sort();
+        assert_eq!(
+            <Index<MainStorage>>::get_ids_at_path(&path).unwrap(),
+            expected
+        );
+        assert_eq!(
+            <Index<MainStorage>>::get_ids_at_path(&Path::new("::root::other").unwrap()).unwrap(),
+            vec![]
+        );
+
+        // Tombstones are excluded
+        assert!(<Index<MainStorage>>::mark_deleted(
+            Some(root_id),
+            "Books",
+            child1_id,
+            Metadata {
+                deleted_at: Some(hlc_now()),
+                ..Metadata::default()
+            }
+        )
+        .is_ok());
+        assert_eq!(
+            <Index<MainStorage>>::get_ids_at_path(&path).unwrap(),
+            vec![child2_id]
+        );
+    }
+
+    #[test]
+    fn get_ids_below_path() {
+        let root_id = Id::random();
+
+        assert!(<Index<MainStorage>>::add_root(ChildInfo::new(
+            root_id,
+            [1_u8; 32],
+            Metadata::default()
+        ),)
+        .is_ok());
+
+        let paths = [
+            "::users::bob",
+            "::users::alice::tasks",
+            "::users::alice",
+            "::users::alice::notes::first",
+            "::users::alice::notes",
+        ];
+        let mut ids = Vec::new();
+        for (i, path) in paths.iter().enumerate() {
+            let id = Id::random();
+            #[expect(clippy::cast_possible_truncation, reason = "Only a few items")]
+            let hash = [i as u8; 32];
+            assert!(<Index<MainStorage>>::add_child_to(
+                root_id,
+                "Users",
+                ChildInfo::new(id, hash, Metadata::default()),
+            )
+            .is_ok());
+            assert!(<Index<MainStorage>>::set_path(id, &Path::new(path).unwrap()).is_ok());
+            ids.push(id);
+        }
+
+        assert_eq!(
+            <Index<MainStorage>>::get_ids_below_path(&Path::new("::users::alice").unwrap())
+                .unwrap(),
+            vec![ids[4], ids[3], ids[1]]
+        );
+        assert_eq!(
+            <Index<MainStorage>>::get_ids_below_path(&Path::new("::users").unwrap()).unwrap(),
+            vec![ids[2], ids[4], ids[3], ids[1], ids[0]]
+        );
+        assert_eq!(
+            <Index<MainStorage>>::get_ids_below_path(&Path::new("::users::bob").unwrap()).unwrap(),
+            vec![]
+        );
+    }
+
     #[test]
     fn get_parent_id() {
         let root_id = Id::random();
@@ -409,7 +517,7 @@ mod index__public_methods {
     }
 
     #[test]
-    fn remove_child_from() {
+    fn mark_deleted() {
         let root_id = Id::random();
         let root_hash = [1_u8; 32];
 
@@ -420,12 +528,6 @@ mod index__public_methods {
         ),)
         .is_ok());
 
-        let root_index = <Index<MainStorage>>::get_index(root_id).unwrap().unwrap();
-        assert_eq!(root_index.id, root_id);
-        assert_eq!(root_index.own_hash, root_hash);
-        assert!(root_index.parent_id.is_none());
-        assert!(root_index.children.is_empty());
-
         let collection_name = "Books";
         let child_id = Id::random();
         let child_own_hash = [2_u8; 32];
@@ -436,13 +538,224 @@ mod index__public_methods {
             ChildInfo::new(child_id, child_own_hash, Metadata::default()),
         )
         .is_ok());
+        let (root_full_hash, _) = <Index<MainStorage>>::get_hashes_for(root_id)
+            .unwrap()
+            .unwrap();
+
+        let metadata = Metadata {
+            deleted_at: Some(hlc_now()),
+            ..Metadata::default()
+        };
+        assert!(<Index<MainStorage>>::mark_deleted(
+            Some(root_id),
+            collection_name,
+            child_id,
+            metadata
+        )
+        .is_ok());
+
         assert!(
-            <Index<MainStorage>>::remove_child_from(root_id, collection_name, child_id).is_ok()
+            <Index<MainStorage>>::get_children_of(root_id, collection_name)
+                .unwrap()
+                .is_empty()
         );
+        assert!(!<Index<MainStorage>>::has_children(root_id, collection_name).unwrap());
 
-        let root_index = <Index<MainStorage>>::get_index(root_id).unwrap().unwrap();
-        assert!(root_index.children[collection_name].is_empty());
-        assert!(<Index<MainStorage>>::get_index(child_id).unwrap().is_none());
+        let tombstones =
+            <Index<MainStorage>>::get_children_with_tombstones_of(root_id, collection_name)
+                .unwrap();
+        assert_eq!(tombstones.len(), 1);
+        assert_eq!(tombstones[0].id(), child_id);
+        assert_eq!(tombstones[0].deleted_at(), metadata.deleted_at);
+
+        let child_index = <Index<MainStorage>>::get_index(child_id).unwrap().unwrap();
+        assert_eq!(child_index.parent_id, Some(root_id));
+        assert!(child_index.metadata.is_deleted());
+
+        let (root_full_hash_after, _) = <Index<MainStorage>>::get_hashes_for(root_id)
+            .unwrap()
+            .unwrap();
+        assert_ne!(root_full_hash_after, root_full_hash);
+        assert_eq!(
+            root_full_hash_after,
+            <Index<MainStorage>>::calculate_full_merkle_hash_for(root_id, false).unwrap()
+        );
+    }
+
+    #[test]
+    fn mark_deleted__unknown_child() {
+        let root_id = Id::random();
+
+        assert!(<Index<MainStorage>>::add_root(ChildInfo::new(
+            root_id,
+            [1_u8; 32],
+            Metadata::default()
+        ),)
+        .is_ok());
+
+        let collection_name = "Books";
+        let child_id = Id::random();
+        let metadata = Metadata {
+            deleted_at: Some(hlc_now()),
+            ..Metadata::default()
+        };
+        assert!(<Index<MainStorage>>::mark_deleted(
+            Some(root_id),
+            collection_name,
+            child_id,
+            metadata
+        )
+        .is_ok());
+
+        let tombstones =
+            <Index<MainStorage>>::get_children_with_tombstones_of(root_id, collection_name)
+                .unwrap();
+        assert_eq!(tombstones.len(), 1);
+        assert!(tombstones[0].is_deleted());
+        assert_eq!(
+            <Index<MainStorage>>::get_metadata(child_id).unwrap(),
+            Some(metadata)
+        );
+    }
+
+    #[test]
+    fn purge_tombstones() {
+        let root_id = Id::random();
+
+        assert!(<Index<MainStorage>>::add_root(ChildInfo::new(
+            root_id,
+            [1_u8; 32],
+            Metadata::default()
+        ),)
+        .is_ok());
+
+        let collection_name = "Books";
+        let child1_id = Id::random();
+        let child2_id = Id::random();
+        let grandchild_id = Id::random();
+
+        assert!(<Index<MainStorage>>::add_child_to(
+            root_id,
+            collection_name,
+            ChildInfo::new(child1_id, [2_u8; 32], Metadata::default()),
+        )
+        .is_ok());
+        assert!(<Index<MainStorage>>::add_child_to(
+            root_id,
+            collection_name,
+            ChildInfo::new(child2_id, [3_u8; 32], Metadata::default()),
+        )
+        .is_ok());
+        assert!(<Index<MainStorage>>::add_child_to(
+            child1_id,
+            "Pages",
+            ChildInfo::new(grandchild_id, [4_u8; 32], Metadata::default()),
+        )
+        .is_ok());
+
+        let deleted_at = time_now();
+        let metadata = Metadata {
+            deleted_at: Some(HybridTimestamp::new(deleted_at, 0, [0; 32])),
+            ..Metadata::default()
+        };
+        assert!(<Index<MainStorage>>::mark_deleted(
+            Some(root_id),
+            collection_name,
+            child1_id,
+            metadata
+        )
+        .is_ok());
+        let (root_full_hash, _) = <Index<MainStorage>>::get_hashes_for(root_id)
+            .unwrap()
+            .unwrap();
+
+        // Not yet expired
+        assert_eq!(
+            <Index<MainStorage>>::purge_tombstones(root_id, deleted_at - 1).unwrap(),
+            0
+        );
+        assert!(<Index<MainStorage>>::get_index(child1_id)
+            .unwrap()
+            .is_some());
+
+        assert_eq!(
+            <Index<MainStorage>>::purge_tombstones(root_id, deleted_at).unwrap(),
+            1
+        );
+        assert!(<Index<MainStorage>>::get_index(child1_id)
+            .unwrap()
+            .is_none());
+        assert!(<Index<MainStorage>>::get_index(grandchild_id)
+            .unwrap()
+            .is_none());
+        assert!(<Index<MainStorage>>::get_index(child2_id)
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            <Index<MainStorage>>::get_children_with_tombstones_of(root_id, collection_name)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            <Index<MainStorage>>::get_hashes_for(root_id)
+                .unwrap()
+                .unwrap()
+                .0,
+            root_full_hash
+        );
+    }
+
+    #[test]
+    fn set_path() {
+        let root_id = Id::random();
+        let child_id = Id::random();
+        let old_path = Path::new("::root::drafts::note").unwrap();
+        let new_path = Path::new("::root::published::note").unwrap();
+
+        assert!(<Index<MainStorage>>::add_root(ChildInfo::new(
+            root_id,
+            [1_u8; 32],
+            Metadata::default()
+        ),)
+        .is_ok());
+        assert!(<Index<MainStorage>>::set_path(child_id, &old_path).is_err());
+        assert!(<Index<MainStorage>>::add_child_to(
+            root_id,
+            "Notes",
+            ChildInfo::new(child_id, [2_u8; 32], Metadata::default()),
+        )
+        .is_ok());
+
+        assert!(<Index<MainStorage>>::set_path(child_id, &old_path).is_ok());
+        assert_eq!(
+            <Index<MainStorage>>::get_path(child_id).unwrap(),
+            Some(old_path.clone())
+        );
+        assert_eq!(
+            <Index<MainStorage>>::get_ids_at_path(&old_path).unwrap(),
+            vec![child_id]
+        );
+
+        // Moving the entity also clears out the paths left empty
+        assert!(<Index<MainStorage>>::set_path(child_id, &new_path).is_ok());
+        assert_eq!(
+            <Index<MainStorage>>::get_path(child_id).unwrap(),
+            Some(new_path.clone())
+        );
+        assert_eq!(
+            <Index<MainStorage>>::get_ids_at_path(&old_path).unwrap(),
+            vec![]
+        );
+        assert_eq!(
+            <Index<MainStorage>>::get_ids_below_path(&Path::new("::root").unwrap()).unwrap(),
+            vec![child_id]
+        );
+        assert!(
+            <Index<MainStorage>>::get_path_index(&Path::new("::root::drafts").unwrap())
+                .unwrap()
+                .is_none()
+        );
     }
 }
 
@@ -463,12 +776,59 @@ mod index__private_methods {
             full_hash: hash1,
             own_hash: hash2,
             metadata: Metadata::default(),
+            path: None,
         };
         <Index<MainStorage>>::save_index(&index).unwrap();
 
         assert_eq!(<Index<MainStorage>>::get_index(id).unwrap().unwrap(), index);
     }
 
+    #[test]
+    fn get_index__legacy_encoding() {
+        let id = Id::random();
+        let parent_id = Id::random();
+        let child_id = Id::random();
+
+        // The baseline encoding, before tombstones, merge strategies, and paths
+        let legacy_child = (child_id, [3_u8; 32], 10_u64, 20_u64);
+        let legacy_index = (
+            id,
+            Some(parent_id),
+            BTreeMap::from([("Children".to_owned(), vec![legacy_child])]),
+            [1_u8; 32],
+            [2_u8; 32],
+            100_u64,
+            200_u64,
+        );
+        assert!(!MainStorage::storage_write(
+            Key::Index(id),
+            &to_vec(&legacy_index).unwrap()
+        ));
+
+        let index = <Index<MainStorage>>::get_index(id).unwrap().unwrap();
+        assert_eq!(index.id, id);
+        assert_eq!(index.parent_id, Some(parent_id));
+        assert_eq!(index.full_hash, [1; 32]);
+        assert_eq!(index.own_hash, [2; 32]);
+        assert_eq!(index.metadata.created_at(), 100);
+        assert_eq!(
+            index.metadata.updated_at(),
+            HybridTimestamp::new(200, 0, [0; 32])
+        );
+        assert_eq!(index.metadata.deleted_at(), None);
+        assert_eq!(index.path, None);
+
+        let children = &index.children["Children"];
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].id(), child_id);
+        assert_eq!(children[0].merkle_hash(), [3; 32]);
+        assert_eq!(children[0].created_at(), 10);
+
+        // Saving rewrites the index in the current encoding
+        <Index<MainStorage>>::save_index(&index).unwrap();
+        assert_eq!(<Index<MainStorage>>::get_index(id).unwrap().unwrap(), index);
+    }
+
     #[test]
     fn save_and_remove_index() {
         let id = Id::random();
@@ -483,6 +843,7 @@ mod index__private_methods {
             full_hash: hash1,
             own_hash: hash2,
             metadata: Metadata::default(),
+            path: None,
         };
         <Index<MainStorage>>::save_index(&index).unwrap();
         assert_eq!(<Index<MainStorage>>::get_index(id).unwrap().unwrap(), index);
@@ -1,12 +1,13 @@
-use std::thread::sleep;
+use std::thread::{sleep, spawn};
 use std::time::Duration;
 
-use claims::{assert_none, assert_ok};
+use claims::{assert_gt, assert_none, assert_ok};
 
 use super::*;
-use crate::entities::{Data, Element};
-use crate::store::MockedStorage;
-use crate::tests::common::{Page, Paragraph};
+use crate::entities::{Data, Element, HybridTimestamp};
+use crate::env::hlc_now;
+use crate::store::{MainStorage, MockedStorage};
+use crate::tests::common::{Ledger, Page, Paragraph, Tags, Tally};
 
 #[cfg(test)]
 mod interface__public_methods {
@@ -62,9 +63,130 @@ mod interface__public_methods {
     }
 
     #[test]
-    #[ignore]
     fn find_by_path() {
-        todo!()
+        let mut page = Page::new_from_element("Node", Element::root());
+        assert!(MainInterface::save(&mut page).unwrap());
+
+        let path = Path::new("::root::node::leaf").unwrap();
+        let mut para1 = Paragraph::new_from_element("Leaf1", Element::new(&path, None));
+        let mut para2 = Paragraph::new_from_element("Leaf2", Element::new(&path, None));
+        let mut para3 = Paragraph::new_from_element(
+            "Leaf3",
+            Element::new(&Path::new("::root::node::other").unwrap(), None),
+        );
+        assert!(MainInterface::add_child_to(page.id(), &page.paragraphs, &mut para1).unwrap());
+        assert!(MainInterface::add_child_to(page.id(), &page.paragraphs, &mut para2).unwrap());
+        assert!(MainInterface::add_child_to(page.id(), &page.paragraphs, &mut para3).unwrap());
+
+        let mut expected = vec![para1.clone(), para2.clone()];
+        expected.sort_by_key(Data::id);
+        assert_eq!(
+            MainInterface::find_by_path::<Paragraph>(&path).unwrap(),
+            expected
+        );
+        assert_eq!(
+            MainInterface::find_by_path::<Page>(&Path::new("::root").unwrap())
+                .unwrap()
+                .iter()
+                .map(Data::id)
+                .collect::<Vec<_>>(),
+            vec![page.id()]
+        );
+        assert_eq!(
+            MainInterface::find_by_path::<Paragraph>(&Path::new("::root::missing").unwrap())
+                .unwrap(),
+            vec![]
+        );
+
+        assert!(MainInterface::remove_child_from(page.id(), &page.paragraphs, para1.id()).unwrap());
+        assert_eq!(
+            MainInterface::find_by_path::<Paragraph>(&path).unwrap(),
+            vec![para2]
+        );
+    }
+
+    #[test]
+    fn find_by_path__legacy_index() {
+        let mut page = Page::new_from_element("Node", Element::root());
+        assert!(MainInterface::save(&mut page).unwrap());
+
+        let path = Path::new("::root::node::legacy").unwrap();
+        let mut para = Paragraph::new_from_element("Legacy", Element::new(&path, None));
+        let id = para.id();
+
+        // An entity last saved before paths were indexed
+        let legacy_index = (
+            id,
+            Some(page.id()),
+            BTreeMap::<String, Vec<(Id, [u8; 32], u64, u64)>>::new(),
+            [1_u8; 32],
+            [2_u8; 32],
+            100_u64,
+            200_u64,
+        );
+        assert!(!MainStorage::storage_write(
+            Key::Index(id),
+            &to_vec(&legacy_index).unwrap()
+        ));
+        assert!(!MainStorage::storage_write(
+            Key::Entry(id),
+            &to_vec(&para).unwrap()
+        ));
+
+        assert!(MainInterface::find_by_id::<Paragraph>(id)
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            MainInterface::find_by_path::<Paragraph>(&path).unwrap(),
+            vec![]
+        );
+
+        // Its path is indexed once it's next saved
+        para.element_mut().update();
+        assert!(MainInterface::save(&mut para).unwrap());
+
+        assert_eq!(
+            MainInterface::find_by_path::<Paragraph>(&path)
+                .unwrap()
+                .iter()
+                .map(Data::id)
+                .collect::<Vec<_>>(),
+            vec![id]
+        );
+    }
+
+    #[test]
+    fn find_descendants_by_path() {
+        let mut page = Page::new_from_element("Node", Element::root());
+        assert!(MainInterface::save(&mut page).unwrap());
+
+        let mut paras = Vec::new();
+        for path in [
+            "::root::node::b",
+            "::root::node::a::deep",
+            "::root::node",
+            "::root::node::a",
+        ] {
+            let mut para =
+                Paragraph::new_from_element(path, Element::new(&Path::new(path).unwrap(), None));
+            assert!(MainInterface::add_child_to(page.id(), &page.paragraphs, &mut para).unwrap());
+            paras.push(para);
+        }
+
+        assert_eq!(
+            MainInterface::find_descendants_by_path::<Paragraph>(
+                &Path::new("::root::node").unwrap()
+            )
+            .unwrap(),
+            vec![paras[3].clone(), paras[1].clone(), paras[0].clone()]
+        );
+        assert_eq!(
+            MainInterface::find_descendants_by_path::<Paragraph>(
+                &Path::new("::root::node::b").unwrap()
+            )
+            .unwrap(),
+            vec![]
+        );
     }
 
     #[test]
@@ -73,6 +195,59 @@ mod interface__public_methods {
         todo!()
     }
 
+    #[test]
+    fn purge_tombstones() {
+        let mut page = Page::new_from_element("Node", Element::root());
+        assert!(MainInterface::save(&mut page).unwrap());
+
+        let child = Element::new(&Path::new("::root::node::leaf").unwrap(), None);
+        let mut para = Paragraph::new_from_element("Leaf", child);
+        assert!(MainInterface::add_child_to(page.id(), &page.paragraphs, &mut para).unwrap());
+        assert!(MainInterface::remove_child_from(page.id(), &page.paragraphs, para.id()).unwrap());
+
+        assert_eq!(
+            MainInterface::purge_tombstones(DEFAULT_TOMBSTONE_RETENTION).unwrap(),
+            0
+        );
+        assert!(<Index<MainStorage>>::get_metadata(para.id())
+            .unwrap()
+            .is_some());
+
+        assert_eq!(MainInterface::purge_tombstones(0).unwrap(), 1);
+        assert!(<Index<MainStorage>>::get_metadata(para.id())
+            .unwrap()
+            .is_none());
+        assert!(
+            <Index<MainStorage>>::get_children_with_tombstones_of(page.id(), "Paragraphs")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn remove_child_from() {
+        let mut page = Page::new_from_element("Node", Element::root());
+        assert!(MainInterface::save(&mut page).unwrap());
+
+        let child = Element::new(&Path::new("::root::node::leaf").unwrap(), None);
+        let mut para = Paragraph::new_from_element("Leaf", child);
+        assert!(MainInterface::add_child_to(page.id(), &page.paragraphs, &mut para).unwrap());
+        assert!(MainInterface::remove_child_from(page.id(), &page.paragraphs, para.id()).unwrap());
+        assert!(!MainInterface::remove_child_from(page.id(), &page.paragraphs, para.id()).unwrap());
+
+        assert_eq!(
+            MainInterface::children_of(page.id(), &page.paragraphs).unwrap(),
+            vec![]
+        );
+        assert!(!MainInterface::has_children(page.id(), &page.paragraphs).unwrap());
+        assert_none!(MainInterface::find_by_id::<Paragraph>(para.id()).unwrap());
+
+        let tombstones =
+            <Index<MainStorage>>::get_children_with_tombstones_of(page.id(), "Paragraphs").unwrap();
+        assert_eq!(tombstones.len(), 1);
+        assert!(tombstones[0].is_deleted());
+    }
+
     #[test]
     fn save__basic() {
         let element = Element::root();
@@ -151,6 +326,8 @@ mod interface__public_methods {
 #[cfg(test)]
 mod interface__apply_actions {
     use super::*;
+    use crate::collections::Root;
+    use crate::sync::SyncArtifact;
 
     #[test]
     fn apply_action__add() {
@@ -161,6 +338,7 @@ mod interface__apply_actions {
             data: serialized,
             ancestors: vec![],
             metadata: page.element().metadata,
+            path: Some(page.element().path()),
         };
 
         assert!(MainInterface::apply_action(action).is_ok());
@@ -169,6 +347,11 @@ mod interface__apply_actions {
         let retrieved_page = MainInterface::find_by_id::<Page>(page.id()).unwrap();
         assert!(retrieved_page.is_some());
         assert_eq!(retrieved_page.unwrap().title, "Test Page");
+
+        // Verify the page can be found by its path
+        let found = MainInterface::find_by_path::<Page>(&page.element().path()).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id(), page.id());
     }
 
     #[test]
@@ -184,6 +367,7 @@ mod interface__apply_actions {
             data: serialized,
             ancestors: vec![],
             metadata: page.element().metadata,
+            path: Some(page.element().path()),
         };
 
         assert!(MainInterface::apply_action(action).is_ok());
@@ -203,6 +387,10 @@ mod interface__apply_actions {
         let action = Action::Delete {
             id: page.id(),
             ancestors: vec![],
+            metadata: Metadata {
+                deleted_at: Some(hlc_now()),
+                ..page.element().metadata
+            },
         };
 
         assert!(MainInterface::apply_action(action).is_ok());
@@ -210,6 +398,271 @@ mod interface__apply_actions {
         // Verify the page was deleted
         let retrieved_page = MainInterface::find_by_id::<Page>(page.id()).unwrap();
         assert!(retrieved_page.is_none());
+        assert!(<Index<MainStorage>>::get_metadata(page.id())
+            .unwrap()
+            .unwrap()
+            .is_deleted());
+    }
+
+    #[test]
+    fn apply_action__delete_without_deleted_at() {
+        let mut page = Page::new_from_element("Test Page", Element::root());
+        assert!(MainInterface::save(&mut page).unwrap());
+
+        let action = Action::Delete {
+            id: page.id(),
+            ancestors: vec![],
+            metadata: page.element().metadata,
+        };
+
+        assert!(MainInterface::apply_action(action).is_err());
+    }
+
+    #[test]
+    fn apply_action__delete_older_than_update() {
+        let mut page = Page::new_from_element("Test Page", Element::root());
+        let metadata = Metadata {
+            deleted_at: Some(page.element().updated_at()),
+            ..page.element().metadata
+        };
+        sleep(Duration::from_millis(2));
+        page.element_mut().update();
+        assert!(MainInterface::save(&mut page).unwrap());
+
+        let action = Action::Delete {
+            id: page.id(),
+            ancestors: vec![],
+            metadata,
+        };
+
+        assert!(MainInterface::apply_action(action).is_ok());
+        assert!(MainInterface::find_by_id::<Page>(page.id())
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn apply_action__add_older_than_delete() {
+        let mut page = Page::new_from_element("Test Page", Element::root());
+        assert!(MainInterface::save(&mut page).unwrap());
+        let serialized = to_vec(&page).unwrap();
+        let metadata = page.element().metadata;
+
+        sleep(Duration::from_millis(2));
+        let action = Action::Delete {
+            id: page.id(),
+            ancestors: vec![],
+            metadata: Metadata {
+                deleted_at: Some(hlc_now()),
+                ..metadata
+            },
+        };
+        assert!(MainInterface::apply_action(action).is_ok());
+
+        // A peer that never saw the deletion sends the old version again
+        let action = Action::Add {
+            id: page.id(),
+            data: serialized,
+            ancestors: vec![],
+            metadata,
+            path: Some(page.element().path()),
+        };
+        assert!(MainInterface::apply_action(action).is_ok());
+        assert!(MainInterface::find_by_id::<Page>(page.id())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn apply_action__update_newer_than_delete() {
+        let mut page = Page::new_from_element("Test Page", Element::root());
+        assert!(MainInterface::save(&mut page).unwrap());
+
+        let action = Action::Delete {
+            id: page.id(),
+            ancestors: vec![],
+            metadata: Metadata {
+                deleted_at: Some(hlc_now()),
+                ..page.element().metadata
+            },
+        };
+        assert!(MainInterface::apply_action(action).is_ok());
+
+        sleep(Duration::from_millis(2));
+        page.title = "Restored".to_owned();
+        page.element_mut().update();
+        let action = Action::Update {
+            id: page.id(),
+            data: to_vec(&page).unwrap(),
+            ancestors: vec![],
+            metadata: page.element().metadata,
+            path: Some(page.element().path()),
+        };
+        assert!(MainInterface::apply_action(action).is_ok());
+
+        let retrieved_page = MainInterface::find_by_id::<Page>(page.id())
+            .unwrap()
+            .unwrap();
+        assert_eq!(retrieved_page.title, "Restored");
+        assert!(!retrieved_page.element().metadata().is_deleted());
+    }
+
+    #[test]
+    fn apply_action__update_from_skewed_clock() {
+        let mut page = Page::new_from_element("Original", Element::root());
+        assert!(MainInterface::save(&mut page).unwrap());
+
+        // A peer whose clock is an hour ahead updates the page
+        let mut foreign = page.clone();
+        foreign.title = "Foreign".to_owned();
+        foreign.element_mut().metadata.updated_at =
+            HybridTimestamp::new(time_now() + 3_600_000_000_000, 0, [1; 32]);
+        let action = Action::Update {
+            id: foreign.id(),
+            data: to_vec(&foreign).unwrap(),
+            ancestors: vec![],
+            metadata: foreign.element().metadata,
+            path: Some(foreign.element().path()),
+        };
+        assert!(MainInterface::apply_action(action).is_ok());
+
+        // A subsequent local update still supersedes it
+        let mut local = MainInterface::find_by_id::<Page>(page.id())
+            .unwrap()
+            .unwrap();
+        local.title = "Local".to_owned();
+        local.element_mut().update();
+        assert!(MainInterface::save(&mut local).unwrap());
+        assert_gt!(local.element().updated_at(), foreign.element().updated_at());
+
+        let retrieved_page = MainInterface::find_by_id::<Page>(page.id())
+            .unwrap()
+            .unwrap();
+        assert_eq!(retrieved_page.title, "Local");
+    }
+
+    #[test]
+    fn apply_action__update_older_than_local_merged() {
+        let mut tags = Tags::new_from_element(&["common"], Element::root());
+        assert!(MainInterface::save(&mut tags).unwrap());
+
+        // A peer updates its copy before the local update is made
+        let mut foreign = tags.clone();
+        foreign.tags.push("foreign".to_owned());
+        foreign.element_mut().update();
+
+        sleep(Duration::from_millis(2));
+        tags.tags.push("local".to_owned());
+        tags.element_mut().update();
+        assert!(MainInterface::save(&mut tags).unwrap());
+
+        let action = Action::Update {
+            id: foreign.id(),
+            data: to_vec(&foreign).unwrap(),
+            ancestors: vec![],
+            metadata: foreign.element().metadata,
+            path: Some(foreign.element().path()),
+        };
+        assert!(MainInterface::apply_action(action).is_ok());
+
+        let retrieved_tags = MainInterface::find_by_id::<Tags>(tags.id())
+            .unwrap()
+            .unwrap();
+        assert_eq!(retrieved_tags.tags, vec!["common", "local", "foreign"]);
+        assert!(retrieved_tags.element().updated_at() > tags.element().updated_at());
+    }
+
+    #[test]
+    fn apply_action__update_older_than_local_unknown_strategy() {
+        let mut tags = Tags::new_from_element(&["common"], Element::root());
+        assert!(MainInterface::save(&mut tags).unwrap());
+
+        let mut foreign = tags.clone();
+        foreign.tags.push("foreign".to_owned());
+        foreign.element_mut().update();
+
+        sleep(Duration::from_millis(2));
+        tags.tags.push("local".to_owned());
+        tags.element_mut().update();
+        assert!(MainInterface::save(&mut tags).unwrap());
+
+        // A strategy with no merge function on this node falls back
+        MERGE_FUNCTIONS.with_borrow_mut(BTreeMap::clear);
+
+        let action = Action::Update {
+            id: foreign.id(),
+            data: to_vec(&foreign).unwrap(),
+            ancestors: vec![],
+            metadata: foreign.element().metadata,
+            path: Some(foreign.element().path()),
+        };
+        assert!(MainInterface::apply_action(action).is_ok());
+
+        // Without a registered merge function, last-write-wins applies
+        let retrieved_tags = MainInterface::find_by_id_raw(tags.id()).unwrap();
+        let retrieved_tags = from_slice::<Tags>(&retrieved_tags).unwrap();
+        assert_eq!(retrieved_tags.tags, vec!["common", "local"]);
+    }
+
+    #[test]
+    fn sync__registered_merges_in_fresh_thread() {
+        // Each execution runs in a fresh instance, with nothing registered
+        spawn(|| {
+            let mut tags = Tags::new_from_element(&["common"], Element::root());
+            assert!(MainInterface::save(&mut tags).unwrap());
+
+            let mut foreign = tags.clone();
+            foreign.tags.push("foreign".to_owned());
+            foreign.element_mut().update();
+
+            sleep(Duration::from_millis(2));
+            tags.tags.push("local".to_owned());
+            tags.element_mut().update();
+            assert!(MainInterface::save(&mut tags).unwrap());
+
+            MERGE_FUNCTIONS.with_borrow_mut(BTreeMap::clear);
+
+            let action = Action::Update {
+                id: foreign.id(),
+                data: to_vec(&foreign).unwrap(),
+                ancestors: vec![],
+                metadata: foreign.element().metadata,
+                path: Some(foreign.element().path()),
+            };
+            let args = to_vec(&SyncArtifact::Actions(vec![action])).unwrap();
+
+            // As the generated sync entrypoint does before syncing
+            Tags::register_merges();
+            Root::<Tags>::sync(&args).unwrap();
+
+            let retrieved_tags = MainInterface::find_by_id::<Tags>(tags.id())
+                .unwrap()
+                .unwrap();
+            assert_eq!(retrieved_tags.tags, vec!["common", "local", "foreign"]);
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn register_merges__descends_into_collections() {
+        let strategy = Tally::merge_strategy();
+        assert_eq!(strategy, MergeStrategy::custom("tests::Tally"));
+        assert!(!MERGE_FUNCTIONS.with_borrow(|functions| functions.contains_key(&strategy)));
+
+        // Loading the parent type registers the merge functions of its children
+        assert_none!(MainInterface::find_by_id::<Ledger>(Id::random()).unwrap());
+        assert!(MERGE_FUNCTIONS.with_borrow(|functions| functions.contains_key(&strategy)));
+    }
+
+    #[test]
+    fn merge_strategy__stable_identifier() {
+        assert_eq!(Tags::merge_strategy(), MergeStrategy::custom("Tags"));
+        assert_eq!(
+            MergeStrategy::custom("Tags"),
+            MergeStrategy::Custom(Sha256::digest("Tags").into())
+        );
+        assert_ne!(Tags::merge_strategy(), Tally::merge_strategy());
     }
 
     #[test]
@@ -230,6 +683,7 @@ mod interface__apply_actions {
             data: serialized,
             ancestors: vec![],
             metadata: page.element().metadata,
+            path: Some(page.element().path()),
         };
 
         // Updating a non-existent page should still succeed (it will be added)
@@ -309,6 +763,48 @@ mod interface__comparison {
                     data: to_vec(&local).unwrap(),
                     ancestors: vec![],
                     metadata: local.element().metadata,
+                    path: Some(local.element().path()),
+                }]
+            )
+        );
+    }
+
+    #[test]
+    fn compare_trees__local_newer_mergeable() {
+        let element = Element::root();
+        let mut local = Tags::new_from_element(&["local"], element.clone());
+        let mut foreign = Tags::new_from_element(&["foreign"], element);
+
+        assert!(ForeignInterface::save(&mut foreign).unwrap());
+
+        // Make local newer
+        sleep(Duration::from_millis(10));
+        local.element_mut().update();
+        assert!(MainInterface::save(&mut local).unwrap());
+
+        let result = compare_trees(
+            Some(&foreign),
+            ForeignInterface::generate_comparison_data(Some(foreign.id())).unwrap(),
+        )
+        .unwrap();
+
+        // The foreign version is also applied locally, so it can be merged
+        assert_eq!(
+            result,
+            (
+                vec![Action::Update {
+                    id: foreign.id(),
+                    data: to_vec(&foreign).unwrap(),
+                    ancestors: vec![],
+                    metadata: foreign.element().metadata,
+                    path: Some(foreign.element().path()),
+                }],
+                vec![Action::Update {
+                    id: local.id(),
+                    data: to_vec(&local).unwrap(),
+                    ancestors: vec![],
+                    metadata: local.element().metadata,
+                    path: Some(local.element().path()),
                 }]
             )
         );
@@ -340,6 +836,7 @@ mod interface__comparison {
                     data: to_vec(&foreign).unwrap(),
                     ancestors: vec![],
                     metadata: foreign.element().metadata,
+                    path: Some(foreign.element().path()),
                 }],
                 vec![]
             )
@@ -405,6 +902,7 @@ mod interface__comparison {
                     data: to_vec(&foreign_page).unwrap(),
                     ancestors: vec![],
                     metadata: foreign_page.element().metadata,
+                    path: Some(foreign_page.element().path()),
                 },
                 // Para1 needs comparison due to different hash
                 Action::Compare {
@@ -420,12 +918,13 @@ mod interface__comparison {
                 Action::Compare {
                     id: local_para1.id()
                 },
-                // Para2 needs to be added to foreign
+                // Para2 needs to be added to foreign, under the page
                 Action::Add {
                     id: local_para2.id(),
                     data: to_vec(&local_para2).unwrap(),
-                    ancestors: vec![],
+                    ancestors: <Index<MainStorage>>::get_ancestors_of(local_para2.id()).unwrap(),
                     metadata: local_para2.element().metadata,
+                    path: Some(local_para2.element().path()),
                 },
                 // Para3 needs to be added locally, but we don't have the data, so we compare
                 Action::Compare {
@@ -465,6 +964,7 @@ mod interface__comparison {
                     local_page.element().metadata
                 )],
                 metadata: foreign_para1.element().metadata,
+                path: Some(foreign_para1.element().path()),
             }]
         );
         assert_eq!(foreign_para1_actions, vec![]);
@@ -500,8 +1000,299 @@ mod interface__comparison {
                     foreign_page.element().metadata
                 )],
                 metadata: foreign_para3.element().metadata,
+                path: Some(foreign_para3.element().path()),
             }]
         );
         assert_eq!(foreign_para3_actions, vec![]);
     }
+
+    #[test]
+    fn compare_trees__local_deleted() {
+        let page_element = Element::root();
+        let para_element = Element::new(&Path::new("::root::node::leaf1").unwrap(), None);
+
+        let mut local_page = Page::new_from_element("Page", page_element.clone());
+        let mut local_para = Paragraph::new_from_element("Paragraph", para_element.clone());
+        let mut foreign_page = Page::new_from_element("Page", page_element);
+        let mut foreign_para = Paragraph::new_from_element("Paragraph", para_element);
+
+        assert!(MainInterface::save(&mut local_page).unwrap());
+        assert!(MainInterface::add_child_to(
+            local_page.id(),
+            &local_page.paragraphs,
+            &mut local_para
+        )
+        .unwrap());
+        assert!(ForeignInterface::save(&mut foreign_page).unwrap());
+        assert!(ForeignInterface::add_child_to(
+            foreign_page.id(),
+            &foreign_page.paragraphs,
+            &mut foreign_para
+        )
+        .unwrap());
+
+        sleep(Duration::from_millis(2));
+        assert!(MainInterface::remove_child_from(
+            local_page.id(),
+            &local_page.paragraphs,
+            local_para.id()
+        )
+        .unwrap());
+
+        // The deletion is detected from the parent, rather than re-adding
+        let (local_actions, foreign_actions) = compare_trees(
+            Some(&foreign_page),
+            ForeignInterface::generate_comparison_data(Some(foreign_page.id())).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            local_actions,
+            vec![Action::Compare {
+                id: local_para.id()
+            }]
+        );
+        assert_eq!(
+            foreign_actions,
+            vec![Action::Compare {
+                id: local_para.id()
+            }]
+        );
+
+        // The comparison of the child itself resolves to a deletion
+        let (local_actions, foreign_actions) = compare_trees(
+            Some(&foreign_para),
+            ForeignInterface::generate_comparison_data(Some(foreign_para.id())).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(local_actions, vec![]);
+        let [Action::Delete { id, metadata, .. }] = foreign_actions.as_slice() else {
+            panic!("Expected a single delete action");
+        };
+        assert_eq!(*id, local_para.id());
+        assert!(metadata.is_deleted());
+
+        ForeignInterface::apply_action(foreign_actions[0].clone()).unwrap();
+        assert_none!(ForeignInterface::find_by_id::<Paragraph>(foreign_para.id()).unwrap());
+        assert_eq!(
+            <Index<MainStorage>>::get_hashes_for(local_page.id()).unwrap(),
+            <Index<MockedStorage<0>>>::get_hashes_for(foreign_page.id()).unwrap()
+        );
+    }
+
+    #[test]
+    fn compare_trees__foreign_deleted() {
+        let page_element = Element::root();
+        let para_element = Element::new(&Path::new("::root::node::leaf1").unwrap(), None);
+
+        let mut local_page = Page::new_from_element("Page", page_element.clone());
+        let mut local_para = Paragraph::new_from_element("Paragraph", para_element.clone());
+        let mut foreign_page = Page::new_from_element("Page", page_element);
+        let mut foreign_para = Paragraph::new_from_element("Paragraph", para_element);
+
+        assert!(MainInterface::save(&mut local_page).unwrap());
+        assert!(MainInterface::add_child_to(
+            local_page.id(),
+            &local_page.paragraphs,
+            &mut local_para
+        )
+        .unwrap());
+        assert!(ForeignInterface::save(&mut foreign_page).unwrap());
+        assert!(ForeignInterface::add_child_to(
+            foreign_page.id(),
+            &foreign_page.paragraphs,
+            &mut foreign_para
+        )
+        .unwrap());
+
+        sleep(Duration::from_millis(2));
+        assert!(ForeignInterface::remove_child_from(
+            foreign_page.id(),
+            &foreign_page.paragraphs,
+            foreign_para.id()
+        )
+        .unwrap());
+
+        let (local_actions, _) = compare_trees(
+            None::<&Paragraph>,
+            ForeignInterface::generate_comparison_data(Some(foreign_para.id())).unwrap(),
+        )
+        .unwrap();
+        let [Action::Delete { id, .. }] = local_actions.as_slice() else {
+            panic!("Expected a single delete action");
+        };
+        assert_eq!(*id, local_para.id());
+
+        MainInterface::apply_action(local_actions[0].clone()).unwrap();
+        assert_none!(MainInterface::find_by_id::<Paragraph>(local_para.id()).unwrap());
+        assert_eq!(
+            MainInterface::children_of(local_page.id(), &local_page.paragraphs).unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn compare_trees__updated_after_delete() {
+        let element = Element::root();
+        let mut local = Page::new_from_element("Test Page", element.clone());
+        let mut foreign = Page::new_from_element("Test Page", element);
+
+        assert!(MainInterface::save(&mut local).unwrap());
+        assert!(ForeignInterface::save(&mut foreign).unwrap());
+
+        let action = Action::Delete {
+            id: local.id(),
+            ancestors: vec![],
+            metadata: Metadata {
+                deleted_at: Some(hlc_now()),
+                ..local.element().metadata
+            },
+        };
+        MainInterface::apply_action(action).unwrap();
+
+        sleep(Duration::from_millis(2));
+        foreign.title = "Updated".to_owned();
+        foreign.element_mut().update();
+        assert!(ForeignInterface::save(&mut foreign).unwrap());
+
+        let (local_actions, foreign_actions) = compare_trees(
+            Some(&foreign),
+            ForeignInterface::generate_comparison_data(Some(foreign.id())).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            local_actions,
+            vec![Action::Add {
+                id: foreign.id(),
+                data: to_vec(&foreign).unwrap(),
+                ancestors: vec![],
+                metadata: foreign.element().metadata,
+                path: Some(foreign.element().path()),
+            }]
+        );
+        assert_eq!(foreign_actions, vec![]);
+    }
+}
+
+#[cfg(test)]
+mod interface__convergence {
+    use super::*;
+    use crate::collections::{Counter, LwwRegister, MultiValueRegister, Root};
+
+    type ForeignStorage = MockedStorage<0>;
+    type ForeignInterface = Interface<ForeignStorage>;
+
+    /// Sync the whole tree between the local and foreign storage, in both
+    /// directions, as a full sync between two nodes would.
+    fn sync() {
+        let mut pending = vec![Id::root()];
+        let mut compared = BTreeSet::new();
+
+        while let Some(id) = pending.pop() {
+            if !compared.insert(id) {
+                continue;
+            }
+
+            let (local_actions, foreign_actions) = MainInterface::compare_trees(
+                ForeignInterface::find_by_id_raw(id),
+                ForeignInterface::generate_comparison_data(Some(id)).unwrap(),
+            )
+            .unwrap();
+
+            for action in local_actions {
+                match action {
+                    Action::Compare { id } => pending.push(id),
+                    action => MainInterface::apply_action(action).unwrap(),
+                }
+            }
+
+            for action in foreign_actions {
+                match action {
+                    Action::Compare { id } => pending.push(id),
+                    action => ForeignInterface::apply_action(action).unwrap(),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn counter__concurrent_updates() {
+        env::set_executor_id([1; 32]);
+        let mut local = Root::new(|| Counter::new());
+        sync();
+        let mut foreign = Root::<Counter<ForeignStorage>, ForeignStorage>::fetch().unwrap();
+
+        assert_eq!(local.increment_by(2).unwrap(), 2);
+
+        env::set_executor_id([2; 32]);
+        assert_eq!(foreign.increment_by(3).unwrap(), 3);
+        assert_eq!(foreign.decrement().unwrap(), 2);
+
+        sync();
+
+        let local = Root::<Counter>::fetch().unwrap();
+        let foreign = Root::<Counter<ForeignStorage>, ForeignStorage>::fetch().unwrap();
+        assert_eq!(local.value().unwrap(), 4);
+        assert_eq!(foreign.value().unwrap(), 4);
+    }
+
+    #[test]
+    fn lww_register__concurrent_writes() {
+        env::set_executor_id([1; 32]);
+        let mut local = Root::new(|| LwwRegister::new());
+        sync();
+        let mut foreign =
+            Root::<LwwRegister<String, ForeignStorage>, ForeignStorage>::fetch().unwrap();
+
+        local.set("local".to_owned()).unwrap();
+
+        env::set_executor_id([2; 32]);
+        foreign.set("foreign".to_owned()).unwrap();
+
+        assert_eq!(local.get().unwrap().as_deref(), Some("local"));
+        assert_eq!(foreign.get().unwrap().as_deref(), Some("foreign"));
+
+        sync();
+
+        // The foreign write was made later, and so wins on both nodes
+        let local = Root::<LwwRegister<String>>::fetch().unwrap();
+        let foreign = Root::<LwwRegister<String, ForeignStorage>, ForeignStorage>::fetch().unwrap();
+        assert_eq!(local.get().unwrap().as_deref(), Some("foreign"));
+        assert_eq!(foreign.get().unwrap().as_deref(), Some("foreign"));
+    }
+
+    #[test]
+    fn multi_value_register__concurrent_writes() {
+        env::set_executor_id([1; 32]);
+        let mut local = Root::new(|| MultiValueRegister::new());
+        sync();
+        let mut foreign =
+            Root::<MultiValueRegister<String, ForeignStorage>, ForeignStorage>::fetch().unwrap();
+
+        local.set("local".to_owned()).unwrap();
+
+        env::set_executor_id([2; 32]);
+        foreign.set("foreign".to_owned()).unwrap();
+
+        sync();
+
+        // Neither write knew of the other, so both are kept on both nodes
+        let mut local = Root::<MultiValueRegister<String>>::fetch().unwrap();
+        let foreign =
+            Root::<MultiValueRegister<String, ForeignStorage>, ForeignStorage>::fetch().unwrap();
+        assert_eq!(local.values().unwrap(), vec!["local", "foreign"]);
+        assert_eq!(foreign.values().unwrap(), vec!["local", "foreign"]);
+
+        // A write made after syncing supersedes both
+        env::set_executor_id([1; 32]);
+        local.set("resolved".to_owned()).unwrap();
+
+        sync();
+
+        let local = Root::<MultiValueRegister<String>>::fetch().unwrap();
+        let foreign =
+            Root::<MultiValueRegister<String, ForeignStorage>, ForeignStorage>::fetch().unwrap();
+        assert_eq!(local.values().unwrap(), vec!["resolved"]);
+        assert_eq!(foreign.values().unwrap(), vec!["resolved"]);
+        assert!(!foreign.is_conflicted().unwrap());
+    }
 }
@@ -1,10 +1,13 @@
 use core::fmt::{self, Debug, Formatter};
+use core::mem;
 use core::pin::{pin, Pin};
 use core::task::{Context, Poll};
-use std::io::ErrorKind as IoErrorKind;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{ErrorKind as IoErrorKind, SeekFrom};
+use std::sync::Arc;
 
 use async_stream::try_stream;
-use calimero_primitives::blobs::BlobId;
+use calimero_primitives::blobs::{BlobId, BlobInfo};
 use calimero_primitives::hash::Hash;
 use calimero_store::key::BlobMeta as BlobMetaKey;
 use calimero_store::types::BlobMeta as BlobMetaValue;
@@ -15,10 +18,19 @@ use futures_util::io::BufReader;
 use futures_util::{AsyncRead, AsyncReadExt, Stream, StreamExt, TryStreamExt};
 use sha2::{Digest, Sha256};
 use thiserror::Error as ThisError;
-use tokio::fs::{create_dir_all, read as async_read, try_exists, write as async_write};
+use tokio::fs::{
+    create_dir_all, read as async_read, read_dir, remove_file, try_exists, write as async_write,
+    File,
+};
+use tokio::io::{AsyncReadExt as _, AsyncSeekExt as _};
+use tokio::sync::RwLock;
 
 pub mod config;
 
+#[cfg(test)]
+#[path = "tests/manager.rs"]
+mod tests;
+
 use config::BlobStoreConfig;
 
 pub const CHUNK_SIZE: usize = 1 << 20; // 1MiB
@@ -32,6 +44,9 @@ const _: [(); { (usize::BITS - CHUNK_SIZE.leading_zeros()) > 32 } as usize] = [
 pub struct BlobManager {
     data_store: DataStore,
     blob_store: FileSystem, // Arc<dyn BlobRepository>
+    // held for writing while blobs are removed, so that chunks are not removed
+    // from under a concurrent `put`, which holds it for reading
+    gc_lock: Arc<RwLock<()>>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -80,10 +95,11 @@ impl Size {
 
 impl BlobManager {
     #[must_use]
-    pub const fn new(data_store: DataStore, blob_store: FileSystem) -> Self {
+    pub fn new(data_store: DataStore, blob_store: FileSystem) -> Self {
         Self {
             data_store,
             blob_store,
+            gc_lock: Arc::default(),
         }
     }
 
@@ -91,9 +107,44 @@ impl BlobManager {
         Ok(self.data_store.handle().has(&BlobMetaKey::new(id))?)
     }
 
+    pub fn info(&self, id: BlobId) -> EyreResult<Option<BlobInfo>> {
+        let Some(meta) = self.data_store.handle().get(&BlobMetaKey::new(id))? else {
+            return Ok(None);
+        };
+
+        Ok(Some(BlobInfo::new(id, meta.size, meta.hash.into())))
+    }
+
+    /// Lists all blobs, excluding the chunks they are made up of.
+    pub fn list(&self) -> EyreResult<Vec<BlobInfo>> {
+        let handle = self.data_store.handle();
+
+        let mut iter = handle.iter::<BlobMetaKey>()?;
+
+        let mut blobs = vec![];
+
+        for (key, meta) in iter.entries() {
+            let (key, meta) = (key?, meta?);
+
+            if is_chunk(&meta) {
+                continue;
+            }
+
+            blobs.push(BlobInfo::new(key.blob_id(), meta.size, meta.hash.into()));
+        }
+
+        Ok(blobs)
+    }
+
     // return a concrete type that resolves to the content of the file
     pub fn get(&self, id: BlobId) -> EyreResult<Option<Blob>> {
-        Blob::new(id, self.clone())
+        Blob::new(id, self.clone(), 0, u64::MAX)
+    }
+
+    /// Get `len` bytes of the blob, starting `offset` bytes in, or as many as
+    /// there are. Only the chunks that overlap the range are read.
+    pub fn get_range(&self, id: BlobId, offset: u64, len: u64) -> EyreResult<Option<Blob>> {
+        Blob::new(id, self.clone(), offset, len)
     }
 
     pub async fn put<T>(&self, stream: T) -> EyreResult<(BlobId, Hash, u64)>
@@ -111,6 +162,8 @@ impl BlobManager {
     where
         T: AsyncRead,
     {
+        let _guard = self.gc_lock.read().await;
+
         let mut stream = pin!(BufReader::new(stream));
 
         let blobs = try_stream!({
@@ -205,7 +258,7 @@ impl BlobManager {
         let (hash, size) = match blobs.try_next().await? {
             Some(Value::Full { hash, size }) => (hash, size),
             Some(Value::Overflow { found, expected }) => {
-                eyre::bail!("expected {} bytes in the stream, found {}", expected, found)
+                return Err(BlobError::SizeMismatch { expected, found }.into());
             }
             _ => {
                 unreachable!("the root should always be emitted");
@@ -214,13 +267,239 @@ impl BlobManager {
 
         let id = BlobId::from(*(AsRef::<[u8; 32]>::as_ref(&digest.finalize())));
 
-        self.data_store.handle().put(
-            &BlobMetaKey::new(id),
-            &BlobMetaValue::new(size, *hash, links.into_boxed_slice()),
-        )?;
+        let key = BlobMetaKey::new(id);
+
+        let mut meta = BlobMetaValue::new(size, *hash, links.into_boxed_slice());
+
+        // putting the same blob again must not discard its existing holders
+        if let Some(existing) = self.data_store.handle().get(&key)? {
+            meta.refs = existing.refs;
+        }
+
+        self.data_store.handle().put(&key, &meta)?;
 
         Ok((id, hash, size)) // todo!: Ok(Blob { id, size, hash }::{fn stream()})
     }
+
+    /// Records a new holder of the blob, such as an application, so that it
+    /// is kept until every holder has released it.
+    ///
+    /// Blobs stored before holders were counted are left uncounted, and so
+    /// are kept, until [`backfill_refs`](Self::backfill_refs) is called.
+    ///
+    /// Returns `false` if the blob does not exist.
+    pub fn retain(&self, id: BlobId) -> EyreResult<bool> {
+        let key = BlobMetaKey::new(id);
+
+        let Some(mut meta) = self.data_store.handle().get(&key)? else {
+            return Ok(false);
+        };
+
+        meta.refs = meta.refs.map(|refs| refs.saturating_add(1));
+
+        self.data_store.handle().put(&key, &meta)?;
+
+        Ok(true)
+    }
+
+    /// Releases a holder of the blob, deleting it once there are none left.
+    ///
+    /// Blobs whose holders have not been counted are never deleted here.
+    ///
+    /// Returns `true` if the blob was deleted.
+    pub async fn release(&self, id: BlobId) -> EyreResult<bool> {
+        let key = BlobMetaKey::new(id);
+
+        let Some(mut meta) = self.data_store.handle().get(&key)? else {
+            return Ok(false);
+        };
+
+        let Some(refs) = meta.refs else {
+            return Ok(false);
+        };
+
+        let refs = refs.saturating_sub(1);
+
+        if refs > 0 {
+            meta.refs = Some(refs);
+
+            self.data_store.handle().put(&key, &meta)?;
+
+            return Ok(false);
+        }
+
+        self.delete(id).await
+    }
+
+    /// Deletes the blob if nothing holds it, as for a blob that was stored
+    /// only to be rejected, such as an upload that failed verification.
+    /// Unlike chunks, such blobs are never collected as garbage.
+    ///
+    /// Returns `true` if the blob was deleted.
+    pub async fn discard(&self, id: BlobId) -> EyreResult<bool> {
+        let Some(meta) = self.data_store.handle().get(&BlobMetaKey::new(id))? else {
+            return Ok(false);
+        };
+
+        if meta.refs != Some(0) {
+            return Ok(false);
+        }
+
+        self.delete(id).await
+    }
+
+    /// Counts the holders of blobs stored before holders were counted, using
+    /// the number of times each blob appears in `holders`. Blobs which do not
+    /// appear are recorded as having none. Blobs already counted are left
+    /// as they are, so this only has an effect the first time it is called.
+    ///
+    /// Returns the number of blobs that were counted.
+    pub fn backfill_refs<I>(&self, holders: I) -> EyreResult<usize>
+    where
+        I: IntoIterator<Item = BlobId>,
+    {
+        let mut counts = BTreeMap::<BlobId, u64>::new();
+
+        for id in holders {
+            let count = counts.entry(id).or_default();
+            *count = count.saturating_add(1);
+        }
+
+        let mut uncounted = vec![];
+
+        {
+            let handle = self.data_store.handle();
+
+            let mut iter = handle.iter::<BlobMetaKey>()?;
+
+            for (key, meta) in iter.entries() {
+                let (key, meta) = (key?, meta?);
+
+                if meta.refs.is_none() {
+                    uncounted.push((key, meta));
+                }
+            }
+        }
+
+        let mut handle = self.data_store.handle();
+
+        for (key, meta) in &mut uncounted {
+            meta.refs = Some(counts.get(&key.blob_id()).copied().unwrap_or_default());
+
+            handle.put(key, meta)?;
+        }
+
+        Ok(uncounted.len())
+    }
+
+    /// Deletes the blob, regardless of whether it is still held, along with
+    /// any of its chunks that are not shared with other blobs.
+    ///
+    /// Returns `false` if the blob does not exist.
+    pub async fn delete(&self, id: BlobId) -> EyreResult<bool> {
+        let _guard = self.gc_lock.write().await;
+
+        let key = BlobMetaKey::new(id);
+
+        let Some(meta) = self.data_store.handle().get(&key)? else {
+            return Ok(false);
+        };
+
+        self.data_store.handle().delete(&key)?;
+
+        let linked = self.linked()?;
+
+        let mut removed = BTreeSet::from([id]);
+
+        for link in &meta.links {
+            if linked.contains(&link.blob_id()) {
+                continue;
+            }
+
+            self.data_store.handle().delete(link)?;
+
+            let _ignored = removed.insert(link.blob_id());
+        }
+
+        for id in removed {
+            let _ignored = self.blob_store.delete(id).await?;
+        }
+
+        Ok(true)
+    }
+
+    /// Removes chunks which are not linked to by any blob, and files in the
+    /// repository which have no metadata, as can be left behind by an
+    /// interrupted `put` or `delete`.
+    ///
+    /// Returns the number of files removed.
+    pub async fn collect_garbage(&self) -> EyreResult<usize> {
+        let _guard = self.gc_lock.write().await;
+
+        let mut known = BTreeSet::new();
+        let mut chunks = BTreeSet::new();
+        let mut linked = BTreeSet::new();
+
+        {
+            let handle = self.data_store.handle();
+
+            let mut iter = handle.iter::<BlobMetaKey>()?;
+
+            for (key, meta) in iter.entries() {
+                let (key, meta) = (key?, meta?);
+
+                let _ignored = known.insert(key.blob_id());
+
+                if is_chunk(&meta) {
+                    let _ignored = chunks.insert(key.blob_id());
+                } else {
+                    linked.extend(meta.links.iter().map(BlobMetaKey::blob_id));
+                }
+            }
+        }
+
+        let unreferenced = chunks.difference(&linked).copied().collect::<BTreeSet<_>>();
+
+        for id in &unreferenced {
+            self.data_store.handle().delete(&BlobMetaKey::new(*id))?;
+        }
+
+        let mut removed = 0_usize;
+
+        for id in self.blob_store.list().await? {
+            if known.contains(&id) && !unreferenced.contains(&id) {
+                continue;
+            }
+
+            if self.blob_store.delete(id).await? {
+                removed = removed.saturating_add(1);
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// The IDs of all blobs linked to by other blobs.
+    fn linked(&self) -> EyreResult<BTreeSet<BlobId>> {
+        let handle = self.data_store.handle();
+
+        let mut iter = handle.iter::<BlobMetaKey>()?;
+
+        let mut linked = BTreeSet::new();
+
+        for meta in iter.entries().map(|(_, meta)| meta) {
+            linked.extend(meta?.links.iter().map(BlobMetaKey::blob_id));
+        }
+
+        Ok(linked)
+    }
+}
+
+/// Whether the blob is a chunk of another blob, rather than a blob in its own
+/// right. Chunks hold data directly, whereas other blobs only link to chunks,
+/// unless they are empty.
+const fn is_chunk(meta: &BlobMetaValue) -> bool {
+    meta.links.is_empty() && meta.size != 0
 }
 
 fn typed_stream<T>(s: impl Stream<Item = T>) -> impl Stream<Item = T> {
@@ -233,34 +512,63 @@ pub struct Blob {
 
     // blob_mgr: BlobManager,
     #[expect(clippy::type_complexity, reason = "Acceptable here")]
-    stream: Pin<Box<dyn Stream<Item = Result<Box<[u8]>, BlobError>>>>,
+    stream: Pin<Box<dyn Stream<Item = Result<Box<[u8]>, BlobError>> + Send>>,
 }
 
 impl Blob {
-    fn new(id: BlobId, blob_mgr: BlobManager) -> EyreResult<Option<Self>> {
+    fn new(id: BlobId, blob_mgr: BlobManager, offset: u64, len: u64) -> EyreResult<Option<Self>> {
         let Some(blob_meta) = blob_mgr.data_store.handle().get(&BlobMetaKey::new(id))? else {
             return Ok(None);
         };
 
-        #[expect(
-            clippy::semicolon_if_nothing_returned,
-            reason = "False positive; not possible with macro"
-        )]
         let stream = Box::pin(try_stream!({
             if blob_meta.links.is_empty() {
-                let maybe_blob = blob_mgr.blob_store.get(id).await;
+                if len == 0 || offset >= blob_meta.size {
+                    return;
+                }
+
+                let maybe_blob = if offset == 0 && len >= blob_meta.size {
+                    blob_mgr.blob_store.get(id).await
+                } else {
+                    blob_mgr.blob_store.get_range(id, offset, len).await
+                };
                 let maybe_blob = maybe_blob.map_err(BlobError::RepoError)?;
                 let blob = maybe_blob.ok_or_else(|| BlobError::DanglingBlob { id })?;
                 return yield blob;
             }
 
+            let mut skip = offset;
+            let mut remaining = len;
+
             for link in blob_meta.links {
-                let maybe_link = Self::new(link.blob_id(), blob_mgr.clone());
+                if remaining == 0 {
+                    break;
+                }
+
+                // chunks entirely before the range are skipped without being read
+                let maybe_meta = blob_mgr
+                    .data_store
+                    .handle()
+                    .get(&link)
+                    .map_err(|err| BlobError::RepoError(err.into()));
+                let link_meta = maybe_meta?.ok_or_else(|| BlobError::DanglingBlob { id })?;
+
+                if skip >= link_meta.size {
+                    skip = skip.saturating_sub(link_meta.size);
+                    continue;
+                }
+
+                let part = remaining.min(link_meta.size.saturating_sub(skip));
+
+                let maybe_link = Self::new(link.blob_id(), blob_mgr.clone(), skip, part);
                 let maybe_link = maybe_link.map_err(BlobError::RepoError)?;
                 let link = maybe_link.ok_or_else(|| BlobError::DanglingBlob { id })?;
                 for await blob in link {
                     yield blob?;
                 }
+
+                skip = 0;
+                remaining = remaining.saturating_sub(part);
             }
         }));
 
@@ -276,13 +584,16 @@ impl Debug for Blob {
 }
 
 #[derive(Debug, ThisError)]
-#[expect(variant_size_differences, reason = "Doesn't matter here")]
 #[non_exhaustive]
 pub enum BlobError {
     #[error("encountered a dangling Blob ID: `{id}`, the blob store may be corrupt")]
     DanglingBlob { id: BlobId },
     #[error(transparent)]
     RepoError(Report),
+    #[error("expected {expected} bytes in the stream, found {found}")]
+    SizeMismatch { expected: u64, found: u64 },
+    #[error("expected a blob with hash `{expected}`, found `{found}`")]
+    HashMismatch { expected: Hash, found: Hash },
 }
 
 impl Stream for Blob {
@@ -297,7 +608,10 @@ trait BlobRepository {
     #[expect(dead_code, reason = "Will be used in future")]
     async fn has(&self, id: BlobId) -> EyreResult<bool>;
     async fn get(&self, id: BlobId) -> EyreResult<Option<Box<[u8]>>>;
+    async fn get_range(&self, id: BlobId, offset: u64, len: u64) -> EyreResult<Option<Box<[u8]>>>;
     async fn put(&self, id: BlobId, data: &[u8]) -> EyreResult<()>;
+    async fn delete(&self, id: BlobId) -> EyreResult<bool>;
+    async fn list(&self) -> EyreResult<Vec<BlobId>>;
 }
 
 #[derive(Clone, Debug)]
@@ -337,9 +651,95 @@ impl BlobRepository for FileSystem {
         }
     }
 
+    async fn get_range(&self, id: BlobId, offset: u64, len: u64) -> EyreResult<Option<Box<[u8]>>> {
+        let mut file = match File::open(self.path(id)).await {
+            Ok(file) => file,
+            Err(err) if err.kind() == IoErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        let _ignored = file.seek(SeekFrom::Start(offset)).await?;
+
+        let mut buf = Vec::new();
+
+        let _ignored = file.take(len).read_to_end(&mut buf).await?;
+
+        Ok(Some(buf.into_boxed_slice()))
+    }
+
     async fn put(&self, id: BlobId, data: &[u8]) -> EyreResult<()> {
         async_write(self.path(id), data).await.map_err(Into::into)
     }
+
+    async fn delete(&self, id: BlobId) -> EyreResult<bool> {
+        match remove_file(self.path(id)).await {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == IoErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn list(&self) -> EyreResult<Vec<BlobId>> {
+        let mut entries = read_dir(&self.root).await?;
+
+        let mut ids = vec![];
+
+        while let Some(entry) = entries.next_entry().await? {
+            // anything not named after a blob is not ours to manage
+            if let Some(id) = entry.file_name().to_str().and_then(|s| s.parse().ok()) {
+                ids.push(id);
+            }
+        }
+
+        Ok(ids)
+    }
+}
+
+/// Computes the id a blob would be stored under, without storing it.
+#[derive(Clone, Debug, Default)]
+pub struct BlobHasher {
+    chunk: State,
+    digest: Sha256,
+}
+
+impl BlobHasher {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let len = CHUNK_SIZE.saturating_sub(self.chunk.size).min(data.len());
+
+            let (chunk, rest) = data.split_at(len);
+
+            self.chunk.digest.update(chunk);
+            self.chunk.size = self.chunk.size.saturating_add(len);
+
+            data = rest;
+
+            if self.chunk.size == CHUNK_SIZE {
+                self.finish_chunk();
+            }
+        }
+    }
+
+    fn finish_chunk(&mut self) {
+        let chunk = mem::take(&mut self.chunk);
+
+        self.digest.update(chunk.digest.finalize());
+    }
+
+    #[must_use]
+    pub fn finalize(mut self) -> BlobId {
+        // like `put`, which stores no chunk for an empty blob
+        if self.chunk.size > 0 {
+            self.finish_chunk();
+        }
+
+        BlobId::from(*AsRef::<[u8; 32]>::as_ref(&self.digest.finalize()))
+    }
 }
 
 #[cfg(test)]
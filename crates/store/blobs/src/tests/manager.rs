@@ -0,0 +1,307 @@
+use calimero_store::db::InMemoryDB;
+use camino::Utf8Path;
+use tempfile::{tempdir, TempDir};
+
+use super::*;
+
+async fn manager() -> (BlobManager, TempDir) {
+    let dir = tempdir().unwrap();
+
+    let path = Utf8Path::from_path(dir.path()).unwrap().to_owned();
+
+    let blob_store = FileSystem::new(&BlobStoreConfig::new(path)).await.unwrap();
+
+    let data_store = DataStore::new(Arc::new(InMemoryDB::owned()));
+
+    (BlobManager::new(data_store, blob_store), dir)
+}
+
+fn chunk(byte: u8) -> Vec<u8> {
+    vec![byte; CHUNK_SIZE]
+}
+
+async fn read(manager: &BlobManager, id: BlobId) -> Option<Vec<u8>> {
+    let blob = manager.get(id).unwrap()?;
+
+    let chunks = blob.try_collect::<Vec<_>>().await.unwrap();
+
+    Some(chunks.concat())
+}
+
+async fn read_range(manager: &BlobManager, id: BlobId, offset: u64, len: u64) -> Vec<u8> {
+    let blob = manager.get_range(id, offset, len).unwrap().unwrap();
+
+    let chunks = blob.try_collect::<Vec<_>>().await.unwrap();
+
+    chunks.concat()
+}
+
+/// Data spanning several chunks, where every byte depends on its position.
+fn patterned(len: usize) -> Vec<u8> {
+    #[expect(clippy::cast_possible_truncation, reason = "truncation is intended")]
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+fn refs(manager: &BlobManager, id: BlobId) -> Option<u64> {
+    let meta = manager.data_store.handle().get(&BlobMetaKey::new(id));
+
+    meta.unwrap().unwrap().refs
+}
+
+fn file_exists(manager: &BlobManager, id: BlobId) -> bool {
+    manager.blob_store.path(id).exists()
+}
+
+#[tokio::test]
+async fn test_retain_and_release() {
+    let (manager, _dir) = manager().await;
+
+    let (id, _, _) = manager.put(&b"hello"[..]).await.unwrap();
+
+    assert_eq!(refs(&manager, id), Some(0));
+
+    assert!(manager.retain(id).unwrap());
+    assert!(manager.retain(id).unwrap());
+    assert_eq!(refs(&manager, id), Some(2));
+
+    assert!(!manager.release(id).await.unwrap());
+    assert_eq!(refs(&manager, id), Some(1));
+    assert_eq!(read(&manager, id).await.unwrap(), b"hello");
+
+    assert!(manager.release(id).await.unwrap());
+    assert!(!manager.has(id).unwrap());
+    assert!(!file_exists(&manager, id));
+
+    assert!(!manager.retain(id).unwrap());
+    assert!(!manager.release(id).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_put_again_keeps_holders() {
+    let (manager, _dir) = manager().await;
+
+    let (id, _, _) = manager.put(&b"hello"[..]).await.unwrap();
+
+    assert!(manager.retain(id).unwrap());
+
+    let (again, _, _) = manager.put(&b"hello"[..]).await.unwrap();
+
+    assert_eq!(again, id);
+    assert_eq!(refs(&manager, id), Some(1));
+}
+
+#[tokio::test]
+async fn test_discard_only_unheld() {
+    let (manager, _dir) = manager().await;
+
+    let (held, _, _) = manager.put(&b"held"[..]).await.unwrap();
+    let (unheld, _, _) = manager.put(&b"unheld"[..]).await.unwrap();
+
+    assert!(manager.retain(held).unwrap());
+
+    assert!(!manager.discard(held).await.unwrap());
+    assert_eq!(read(&manager, held).await.unwrap(), b"held");
+
+    assert!(manager.discard(unheld).await.unwrap());
+    assert!(!manager.has(unheld).unwrap());
+    assert!(!file_exists(&manager, unheld));
+
+    assert!(!manager.discard(unheld).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_uncounted_blobs_are_kept_until_backfilled() {
+    let (manager, _dir) = manager().await;
+
+    let (held, _, _) = manager.put(&b"held"[..]).await.unwrap();
+    let (unheld, _, _) = manager.put(&b"unheld"[..]).await.unwrap();
+
+    // as stored before holders were counted
+    for id in [held, unheld] {
+        let key = BlobMetaKey::new(id);
+
+        let mut meta = manager.data_store.handle().get(&key).unwrap().unwrap();
+
+        meta.refs = None;
+
+        manager.data_store.handle().put(&key, &meta).unwrap();
+    }
+
+    assert!(manager.retain(held).unwrap());
+    assert!(!manager.release(held).await.unwrap());
+    assert_eq!(refs(&manager, held), None);
+
+    let counted = manager.backfill_refs([held, held]).unwrap();
+
+    // the chunks of the two blobs were counted too, when they were put
+    assert_eq!(counted, 2);
+    assert_eq!(refs(&manager, held), Some(2));
+    assert_eq!(refs(&manager, unheld), Some(0));
+
+    assert_eq!(manager.backfill_refs([held]).unwrap(), 0);
+    assert_eq!(refs(&manager, held), Some(2));
+}
+
+#[tokio::test]
+async fn test_delete_keeps_shared_chunks() {
+    let (manager, _dir) = manager().await;
+
+    let first = [chunk(1), chunk(2)].concat();
+    let second = [chunk(1), chunk(3)].concat();
+
+    let (first_id, _, _) = manager.put(first.as_slice()).await.unwrap();
+    let (second_id, _, _) = manager.put(second.as_slice()).await.unwrap();
+
+    assert!(manager.retain(first_id).unwrap());
+
+    assert!(manager.delete(first_id).await.unwrap());
+    assert!(!manager.delete(first_id).await.unwrap());
+
+    assert!(!manager.has(first_id).unwrap());
+    assert_eq!(read(&manager, first_id).await, None);
+    assert_eq!(read(&manager, second_id).await.unwrap(), second);
+
+    let chunks = manager.list().unwrap();
+
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].id, second_id);
+}
+
+#[tokio::test]
+async fn test_collect_garbage() {
+    let (manager, _dir) = manager().await;
+
+    let data = [chunk(1), chunk(2)].concat();
+
+    let (id, _, _) = manager.put(data.as_slice()).await.unwrap();
+
+    assert_eq!(manager.collect_garbage().await.unwrap(), 0);
+
+    // a chunk left behind by an interrupted put, which nothing links to
+    let (orphan_chunk, _, _) = manager.put(chunk(3).as_slice()).await.unwrap();
+
+    let orphan_meta = manager
+        .data_store
+        .handle()
+        .get(&BlobMetaKey::new(orphan_chunk))
+        .unwrap()
+        .unwrap();
+
+    let [link] = &*orphan_meta.links else {
+        panic!("expected a single chunk");
+    };
+
+    manager
+        .data_store
+        .handle()
+        .delete(&BlobMetaKey::new(orphan_chunk))
+        .unwrap();
+
+    // a file with no metadata at all
+    let stray = BlobId::from([7; 32]);
+
+    manager.blob_store.put(stray, b"stray").await.unwrap();
+
+    assert_eq!(manager.collect_garbage().await.unwrap(), 2);
+
+    assert!(!manager.has(link.blob_id()).unwrap());
+    assert!(!file_exists(&manager, link.blob_id()));
+    assert!(!file_exists(&manager, stray));
+
+    assert_eq!(read(&manager, id).await.unwrap(), data);
+}
+
+#[tokio::test]
+async fn test_get_range_within_single_chunk() {
+    let (manager, _dir) = manager().await;
+
+    let data = patterned(100);
+
+    let (id, _, _) = manager.put(data.as_slice()).await.unwrap();
+
+    assert_eq!(read_range(&manager, id, 10, 20).await, &data[10..30]);
+    assert_eq!(read_range(&manager, id, 90, 50).await, &data[90..]);
+    assert_eq!(read_range(&manager, id, 0, 100).await, data);
+    assert!(read_range(&manager, id, 100, 10).await.is_empty());
+    assert!(read_range(&manager, id, 10, 0).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_get_range_across_chunks() {
+    let (manager, _dir) = manager().await;
+
+    let data = patterned(CHUNK_SIZE * 2 + 100);
+
+    let (id, _, size) = manager.put(data.as_slice()).await.unwrap();
+
+    assert_eq!(size, data.len() as u64);
+
+    let chunk_size = CHUNK_SIZE as u64;
+
+    // spanning the boundary between the first and second chunks
+    assert_eq!(
+        read_range(&manager, id, chunk_size - 10, 20).await,
+        &data[CHUNK_SIZE - 10..CHUNK_SIZE + 10]
+    );
+
+    // starting exactly on a boundary
+    assert_eq!(
+        read_range(&manager, id, chunk_size, 10).await,
+        &data[CHUNK_SIZE..CHUNK_SIZE + 10]
+    );
+
+    // ending exactly on a boundary
+    assert_eq!(
+        read_range(&manager, id, chunk_size - 10, 10).await,
+        &data[CHUNK_SIZE - 10..CHUNK_SIZE]
+    );
+
+    // spanning every chunk, and running past the end
+    assert_eq!(read_range(&manager, id, 5, u64::MAX).await, &data[5..]);
+
+    // within the last, partial chunk
+    assert_eq!(
+        read_range(&manager, id, chunk_size * 2 + 50, 10).await,
+        &data[CHUNK_SIZE * 2 + 50..CHUNK_SIZE * 2 + 60]
+    );
+
+    assert!(read_range(&manager, id, size, 10).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_put_sized_overflow_is_size_mismatch() {
+    let (manager, _dir) = manager().await;
+
+    let err = manager
+        .put_sized(Some(Size::Exact(4)), &b"hello"[..])
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<BlobError>(),
+        Some(BlobError::SizeMismatch {
+            expected: 4,
+            found: 5
+        })
+    ));
+}
+
+#[tokio::test]
+async fn test_hasher_matches_put() {
+    let (manager, _dir) = manager().await;
+
+    for len in [0, 1, CHUNK_SIZE, CHUNK_SIZE * 2 + 7] {
+        let data = patterned(len);
+
+        let (id, _, _) = manager.put(&*data).await.unwrap();
+
+        let mut hasher = BlobHasher::new();
+
+        // split unevenly, so that writes straddle chunk boundaries
+        for part in data.chunks(CHUNK_SIZE.div_ceil(3)) {
+            hasher.update(part);
+        }
+
+        assert_eq!(hasher.finalize(), id, "len {len}");
+    }
+}
@@ -23,6 +23,14 @@ pub enum Column {
     Application,
     Alias,
     Generic,
+    /// Storage used by each context. Kept out of `Meta`, where every key is a
+    /// context id of the same length as `ContextMeta`'s, so contexts can be
+    /// iterated without running into other records.
+    Usage,
+    /// When each context last synced, kept out of `Meta` for the same reason.
+    Sync,
+    /// The blobs held by each context, keyed by context then blob.
+    Holds,
 }
 
 pub trait Database<'a>: Debug + Send + Sync + 'static {
@@ -23,7 +23,10 @@ pub use alias::{Alias, Aliasable, StoreScopeCompat};
 pub use application::ApplicationMeta;
 pub use blobs::BlobMeta;
 use component::KeyComponents;
-pub use context::{ContextConfig, ContextIdentity, ContextMeta, ContextState};
+pub use context::{
+    ContextBlob, ContextConfig, ContextIdentity, ContextMeta, ContextState, ContextSync,
+    ContextUsage,
+};
 pub use generic::Generic;
 
 pub struct Key<T: KeyComponents>(GenericArray<u8, T::LEN>);
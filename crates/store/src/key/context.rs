@@ -3,6 +3,7 @@ use core::fmt::{self, Debug, Formatter};
 
 #[cfg(feature = "borsh")]
 use borsh::{BorshDeserialize, BorshSerialize};
+use calimero_primitives::blobs::BlobId as PrimitiveBlobId;
 use calimero_primitives::context::ContextId as PrimitiveContextId;
 use calimero_primitives::identity::PublicKey as PrimitivePublicKey;
 use generic_array::sequence::Concat;
@@ -10,6 +11,7 @@ use generic_array::typenum::U32;
 use generic_array::GenericArray;
 
 use crate::db::Column;
+use crate::key::blobs::BlobId;
 use crate::key::component::KeyComponent;
 use crate::key::{AsKeyParts, FromKeyParts, Key};
 
@@ -64,6 +66,94 @@ impl Debug for ContextMeta {
     }
 }
 
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+pub struct ContextUsage(Key<ContextId>);
+
+impl ContextUsage {
+    #[must_use]
+    pub fn new(context_id: PrimitiveContextId) -> Self {
+        Self(Key((*context_id).into()))
+    }
+
+    #[must_use]
+    pub fn context_id(&self) -> PrimitiveContextId {
+        (*AsRef::<[_; 32]>::as_ref(&self.0)).into()
+    }
+}
+
+impl AsKeyParts for ContextUsage {
+    type Components = (ContextId,);
+
+    fn column() -> Column {
+        Column::Usage
+    }
+
+    fn as_key(&self) -> &Key<Self::Components> {
+        (&self.0).into()
+    }
+}
+
+impl FromKeyParts for ContextUsage {
+    type Error = Infallible;
+
+    fn try_from_parts(parts: Key<Self::Components>) -> Result<Self, Self::Error> {
+        Ok(Self(*<&_>::from(&parts)))
+    }
+}
+
+impl Debug for ContextUsage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextUsage")
+            .field("id", &self.context_id())
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+pub struct ContextSync(Key<ContextId>);
+
+impl ContextSync {
+    #[must_use]
+    pub fn new(context_id: PrimitiveContextId) -> Self {
+        Self(Key((*context_id).into()))
+    }
+
+    #[must_use]
+    pub fn context_id(&self) -> PrimitiveContextId {
+        (*AsRef::<[_; 32]>::as_ref(&self.0)).into()
+    }
+}
+
+impl AsKeyParts for ContextSync {
+    type Components = (ContextId,);
+
+    fn column() -> Column {
+        Column::Sync
+    }
+
+    fn as_key(&self) -> &Key<Self::Components> {
+        (&self.0).into()
+    }
+}
+
+impl FromKeyParts for ContextSync {
+    type Error = Infallible;
+
+    fn try_from_parts(parts: Key<Self::Components>) -> Result<Self, Self::Error> {
+        Ok(Self(*<&_>::from(&parts)))
+    }
+}
+
+impl Debug for ContextSync {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextSync")
+            .field("id", &self.context_id())
+            .finish()
+    }
+}
+
 #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
 pub struct ContextConfig(Key<ContextId>);
@@ -239,3 +329,64 @@ impl Debug for ContextState {
             .finish()
     }
 }
+
+/// A blob held by a context, having been written by its application.
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "borsh", derive(BorshSerialize, BorshDeserialize))]
+pub struct ContextBlob(Key<(ContextId, BlobId)>);
+
+impl ContextBlob {
+    #[must_use]
+    pub fn new(context_id: PrimitiveContextId, blob_id: PrimitiveBlobId) -> Self {
+        Self(Key(
+            GenericArray::from(*context_id).concat(GenericArray::from(*blob_id))
+        ))
+    }
+
+    #[must_use]
+    pub fn context_id(&self) -> PrimitiveContextId {
+        let mut context_id = [0; 32];
+
+        context_id.copy_from_slice(&AsRef::<[_; 64]>::as_ref(&self.0)[..32]);
+
+        context_id.into()
+    }
+
+    #[must_use]
+    pub fn blob_id(&self) -> PrimitiveBlobId {
+        let mut blob_id = [0; 32];
+
+        blob_id.copy_from_slice(&AsRef::<[_; 64]>::as_ref(&self.0)[32..]);
+
+        blob_id.into()
+    }
+}
+
+impl AsKeyParts for ContextBlob {
+    type Components = (ContextId, BlobId);
+
+    fn column() -> Column {
+        Column::Holds
+    }
+
+    fn as_key(&self) -> &Key<Self::Components> {
+        &self.0
+    }
+}
+
+impl FromKeyParts for ContextBlob {
+    type Error = Infallible;
+
+    fn try_from_parts(parts: Key<Self::Components>) -> Result<Self, Self::Error> {
+        Ok(Self(parts))
+    }
+}
+
+impl Debug for ContextBlob {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextBlob")
+            .field("context_id", &self.context_id())
+            .field("blob_id", &self.blob_id())
+            .finish()
+    }
+}
@@ -0,0 +1,31 @@
+use borsh::{from_slice, to_vec};
+
+use super::*;
+
+#[test]
+fn test_blob_meta_roundtrip() {
+    let mut meta = BlobMeta::new(5, [1; 32], Box::default());
+
+    assert_eq!(meta.refs, Some(0));
+
+    meta.refs = Some(3);
+
+    assert_eq!(
+        from_slice::<BlobMeta>(&to_vec(&meta).unwrap()).unwrap(),
+        meta
+    );
+}
+
+#[test]
+fn test_blob_meta_legacy() {
+    let links: Box<[BlobMetaKey]> = Box::default();
+
+    // the encoding used before holders were counted
+    let legacy = to_vec(&(5_u64, [1_u8; 32], links)).unwrap();
+
+    let meta = from_slice::<BlobMeta>(&legacy).unwrap();
+
+    assert_eq!(meta.size, 5);
+    assert_eq!(meta.hash, [1; 32]);
+    assert_eq!(meta.refs, None);
+}
@@ -0,0 +1,33 @@
+use borsh::{from_slice, to_vec};
+use calimero_primitives::application::ApplicationId;
+use calimero_primitives::blobs::BlobId;
+
+use super::*;
+
+#[test]
+fn test_context_meta_roundtrip() {
+    let meta = ContextMeta::new(
+        ApplicationMetaKey::new(ApplicationId::from([1; 32])),
+        [2; 32],
+        Some(BlobMetaKey::new(BlobId::from([3; 32]))),
+    );
+
+    assert_eq!(
+        from_slice::<ContextMeta>(&to_vec(&meta).unwrap()).unwrap(),
+        meta
+    );
+}
+
+#[test]
+fn test_context_meta_legacy() {
+    let application = ApplicationMetaKey::new(ApplicationId::from([1; 32]));
+
+    // the encoding used before the bytecode hold was recorded
+    let legacy = to_vec(&(application, [2_u8; 32])).unwrap();
+
+    let meta = from_slice::<ContextMeta>(&legacy).unwrap();
+
+    assert_eq!(meta.application, application);
+    assert_eq!(meta.root_hash, [2; 32]);
+    assert_eq!(meta.bytecode, None);
+}
@@ -9,7 +9,10 @@ mod generic;
 
 pub use application::ApplicationMeta;
 pub use blobs::BlobMeta;
-pub use context::{ContextConfig, ContextIdentity, ContextMeta, ContextState};
+pub use context::{
+    ContextConfig, ContextIdentity, ContextMeta, ContextState, ContextSync, ContextSyncStatus,
+    ContextUsage,
+};
 pub use generic::GenericData;
 
 pub trait PredefinedEntry: AsKeyParts {
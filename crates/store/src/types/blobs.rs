@@ -1,23 +1,62 @@
-use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(test)]
+#[path = "../tests/types/blobs.rs"]
+mod tests;
+
+use std::io::{Read, Result as IoResult};
+
+use borsh::{from_slice, BorshDeserialize, BorshSerialize};
 
 use crate::entry::Borsh;
 use crate::key::BlobMeta as BlobMetaKey;
 use crate::types::PredefinedEntry;
 
-#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+#[derive(BorshSerialize, Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub struct BlobMeta {
-    // todo! impl proper entry reference count
-    // pub refs: usize,
     pub size: u64,
     pub hash: [u8; 32],
     pub links: Box<[BlobMetaKey]>,
+    /// The number of holders of the blob, such as applications, which keep it
+    /// from being deleted when released by others. This is `None` for blobs
+    /// stored before holders were counted, until the count is backfilled.
+    pub refs: Option<u64>,
 }
 
 impl BlobMeta {
     #[must_use]
     pub const fn new(size: u64, hash: [u8; 32], links: Box<[BlobMetaKey]>) -> Self {
-        Self { size, hash, links }
+        Self {
+            size,
+            hash,
+            links,
+            refs: Some(0),
+        }
+    }
+}
+
+impl BorshDeserialize for BlobMeta {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> IoResult<Self> {
+        let size = u64::deserialize_reader(reader)?;
+        let hash = <[u8; 32]>::deserialize_reader(reader)?;
+        let links = Box::<[BlobMetaKey]>::deserialize_reader(reader)?;
+
+        // entries written before reference counting have no count
+        let mut rest = vec![];
+
+        let _ignored = reader.read_to_end(&mut rest)?;
+
+        let refs = if rest.is_empty() {
+            None
+        } else {
+            from_slice(&rest)?
+        };
+
+        Ok(Self {
+            size,
+            hash,
+            links,
+            refs,
+        })
     }
 }
 
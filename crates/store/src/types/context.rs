@@ -1,38 +1,166 @@
-use borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(test)]
+#[path = "../tests/types/context.rs"]
+mod tests;
+
+use std::io::{Read, Result as IoResult};
+
+use borsh::{from_slice, BorshDeserialize, BorshSerialize};
 
 use crate::entry::{Borsh, Identity};
 use crate::key::{
-    ApplicationMeta as ApplicationMetaKey, ContextConfig as ContextConfigKey,
-    ContextIdentity as ContextIdentityKey, ContextMeta as ContextMetaKey,
-    ContextState as ContextStateKey,
+    ApplicationMeta as ApplicationMetaKey, BlobMeta as BlobMetaKey, ContextBlob as ContextBlobKey,
+    ContextConfig as ContextConfigKey, ContextIdentity as ContextIdentityKey,
+    ContextMeta as ContextMetaKey, ContextState as ContextStateKey, ContextSync as ContextSyncKey,
+    ContextUsage as ContextUsageKey,
 };
 use crate::slice::Slice;
 use crate::types::PredefinedEntry;
 
 pub type Hash = [u8; 32];
 
-#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(BorshSerialize, Clone, Copy, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub struct ContextMeta {
     pub application: ApplicationMetaKey,
     pub root_hash: Hash,
+    /// The application bytecode held by the context, so that the hold can be
+    /// released even after the application is uninstalled. This is `None` for
+    /// contexts stored before their hold was recorded.
+    pub bytecode: Option<BlobMetaKey>,
 }
 
 impl ContextMeta {
     #[must_use]
-    pub const fn new(application: ApplicationMetaKey, root_hash: Hash) -> Self {
+    pub const fn new(
+        application: ApplicationMetaKey,
+        root_hash: Hash,
+        bytecode: Option<BlobMetaKey>,
+    ) -> Self {
         Self {
             application,
             root_hash,
+            bytecode,
         }
     }
 }
 
+impl BorshDeserialize for ContextMeta {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> IoResult<Self> {
+        let application = ApplicationMetaKey::deserialize_reader(reader)?;
+        let root_hash = Hash::deserialize_reader(reader)?;
+
+        // entries written before the hold was recorded have no bytecode
+        let mut rest = vec![];
+
+        let _ignored = reader.read_to_end(&mut rest)?;
+
+        let bytecode = if rest.is_empty() {
+            None
+        } else {
+            from_slice(&rest)?
+        };
+
+        Ok(Self {
+            application,
+            root_hash,
+            bytecode,
+        })
+    }
+}
+
 impl PredefinedEntry for ContextMetaKey {
     type Codec = Borsh;
     type DataType<'a> = ContextMeta;
 }
 
+impl PredefinedEntry for ContextBlobKey {
+    type Codec = Borsh;
+    type DataType<'a> = ();
+}
+
+/// The storage held by a context's state.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ContextUsage {
+    /// Bytes of keys and values.
+    pub storage_bytes: u64,
+}
+
+impl ContextUsage {
+    #[must_use]
+    pub const fn new(storage_bytes: u64) -> Self {
+        Self { storage_bytes }
+    }
+
+    /// Returns the bytes held by a state entry with a value of the given
+    /// length, its key counting for the 32 bytes it is stored in.
+    #[must_use]
+    pub const fn entry_size(value_len: u64) -> u64 {
+        value_len.saturating_add(32)
+    }
+}
+
+impl PredefinedEntry for ContextUsageKey {
+    type Codec = Borsh;
+    type DataType<'a> = ContextUsage;
+}
+
+/// How the last attempt to sync a context with its peers went.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+pub enum ContextSyncStatus {
+    Succeeded,
+    Failed { reason: Box<str> },
+    TimedOut,
+}
+
+/// A context's history of syncing with its peers.
+///
+/// Timestamps are in milliseconds since the unix epoch.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ContextSync {
+    pub last_attempt: u64,
+    pub last_success: Option<u64>,
+    /// The peer synced with, if the last attempt got as far as choosing one.
+    pub peer_id: Option<Box<str>>,
+    pub status: ContextSyncStatus,
+    pub bytes_transferred: u64,
+    pub root_hash_before: Hash,
+    pub root_hash_after: Hash,
+    pub consecutive_failures: u32,
+}
+
+impl ContextSync {
+    #[must_use]
+    #[expect(clippy::too_many_arguments, reason = "Acceptable here")]
+    pub const fn new(
+        last_attempt: u64,
+        last_success: Option<u64>,
+        peer_id: Option<Box<str>>,
+        status: ContextSyncStatus,
+        bytes_transferred: u64,
+        root_hash_before: Hash,
+        root_hash_after: Hash,
+        consecutive_failures: u32,
+    ) -> Self {
+        Self {
+            last_attempt,
+            last_success,
+            peer_id,
+            status,
+            bytes_transferred,
+            root_hash_before,
+            root_hash_after,
+            consecutive_failures,
+        }
+    }
+}
+
+impl PredefinedEntry for ContextSyncKey {
+    type Codec = Borsh;
+    type DataType<'a> = ContextSync;
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub struct ContextConfig {